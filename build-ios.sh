@@ -73,17 +73,35 @@ int init_freelist(const char* db_path);
 int init_freelist_memory(void);
 
 // Task operations
+// due_date: RFC 3339 or natural language ("tomorrow 5pm", "next friday")
 int64_t add_task(const char* title, const char* tag, const char* due_date);
+// Quick-add text, e.g. "Buy milk #groceries !high due:tomorrow ~15m ^12"
+int64_t add_task_quick(const char* input);
 char* get_tasks_json(const char* filter);
 char* get_tasks_by_tag_json(const char* tag);
 int mark_task_done(int64_t id, int done);
 int delete_task(int64_t id);
 
+// Generic JSON dispatcher: {"version": 1, "method": "...", "params": {...}}
+char* freelist_call(const char* json_request);
+
+// Change notifications: callback receives a JSON ChangeSet
+typedef void (*freelist_change_callback)(void* ctx, const char* change_set_json);
+int64_t freelist_subscribe(freelist_change_callback callback, void* ctx);
+int freelist_unsubscribe(int64_t subscription_id);
+int freelist_poll_changes(void);
+
 // Utility operations
 char* get_all_tags_json(void);
 int clear_all_tasks(void);
 void free_string(char* ptr);
 
+// Error reporting (codes: 0 ok, 1 not initialised, 2 invalid UTF-8,
+// 3 not found, 4 storage, 5 null argument, 6 serialization, 7 panic,
+// 8 invalid request, 9 unsupported version, 10 invalid date, 11 invalid input)
+int freelist_last_error_code(void);
+char* freelist_last_error_message(void);
+
 #endif /* FREELIST_CORE_H */
 EOF
 
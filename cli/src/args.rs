@@ -0,0 +1,633 @@
+// cli/src/args.rs
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use crate::output::Format;
+use freelist_core::fitness::MAX_SCHEDULE_WEEKS;
+use freelist_core::{
+    Amount, ExerciseKind, FoodDump, Ingredient, Meal, PlanSession, Priority, Quantity, SetMeasures, SourceFormat,
+};
+use std::path::PathBuf;
+
+/// Most days a journal command looks back over, about ten years
+const MAX_JOURNAL_DAYS: i64 = 3660;
+/// Most weeks a workout listing or volume report looks back over, about ten years
+const MAX_WORKOUT_WEEKS: i64 = 520;
+
+#[derive(Debug, Parser)]
+#[command(name = "freelist", version, about = "FreeList CLI - Task Management")]
+pub struct Cli {
+    /// Path to the SQLite database (overrides $FREELIST_DB and the config file)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub db: Option<PathBuf>,
+
+    /// Named profile from the config file, e.g. work or home (overrides $FREELIST_PROFILE)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Add a new task, e.g. "Buy milk #groceries !high due:tomorrow ~15m"
+    Add {
+        /// Task text; #tag, !priority, due:<date>, ~estimate and ^parent are parsed out unless --raw
+        title: String,
+        tag: Option<String>,
+        /// Use the title exactly as typed instead of parsing quick-add syntax
+        #[arg(long)]
+        raw: bool,
+        /// low, medium, high or urgent
+        #[arg(long)]
+        priority: Option<Priority>,
+        /// Due date, e.g. "tomorrow 5pm", "next friday", "in 3 days", "2026-11-01"
+        #[arg(long)]
+        due: Option<String>,
+        /// Longer description
+        #[arg(long)]
+        details: Option<String>,
+        /// Make this a subtask of the task with this id
+        #[arg(long, value_name = "ID")]
+        parent: Option<i64>,
+        /// Estimated duration in minutes
+        #[arg(long, value_name = "MINUTES")]
+        estimate: Option<i32>,
+        /// Put the task in this list (name or id)
+        #[arg(long, value_name = "LIST")]
+        list: Option<String>,
+    },
+    /// List tasks (all, todo, done, overdue, #tag, @list)
+    List {
+        #[arg(default_value = "all")]
+        filter: String,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Mark task as done
+    Done { id: i64 },
+    /// Mark task as not done
+    Undone { id: i64 },
+    /// Delete a task and its subtasks
+    Delete { id: i64 },
+    /// Reorder a task, e.g. `freelist move 7 --below 3` puts task 7 right under task 3
+    #[command(group(clap::ArgGroup::new("neighbour").required(true).multiple(true).args(["below", "above"])))]
+    Move {
+        id: i64,
+        /// The task that should come right before it
+        #[arg(long, value_name = "ID")]
+        below: Option<i64>,
+        /// The task that should come right after it
+        #[arg(long, value_name = "ID")]
+        above: Option<i64>,
+    },
+    /// List all tags
+    Tags {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Back up every task and its history
+    Export {
+        /// File (json) or directory (csv) to write; JSON goes to stdout when omitted
+        path: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = ArchiveFormat::Json)]
+        format: ArchiveFormat,
+    },
+    /// Restore a backup made by `freelist export`, or import tasks from another tool;
+    /// tasks already present are skipped
+    Import {
+        /// File to read ("-" for stdin); a FreeList backup may also be a directory of CSV files
+        path: PathBuf,
+        #[arg(long, value_enum, default_value_t = ImportSource::Freelist)]
+        from: ImportSource,
+        /// Tag for imported tasks that have none; Todoist backups default to the file name
+        #[arg(long)]
+        tag: Option<String>,
+        /// Show what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Share tasks with calendar apps as iCalendar (.ics) to-dos
+    Ics {
+        #[command(subcommand)]
+        action: IcsAction,
+    },
+    /// Serve tasks to CalDAV clients, one to-do calendar per tag (no authentication)
+    Caldav {
+        /// Address to listen on; use 0.0.0.0:5232 to accept other machines on the network
+        #[arg(long, default_value = "127.0.0.1:5232")]
+        bind: String,
+    },
+    /// Log what you eat and see daily nutrition totals
+    Food {
+        #[command(subcommand)]
+        action: FoodAction,
+    },
+    /// Keep track of what is in the kitchen
+    #[command(args_conflicts_with_subcommands = true)]
+    Pantry {
+        #[command(subcommand)]
+        action: Option<PantryAction>,
+        /// Format of the pantry shown when no subcommand is given
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Save recipes and take their ingredients out of the pantry when cooking
+    Recipe {
+        #[command(subcommand)]
+        action: RecipeAction,
+    },
+    /// Log workouts and see personal records and weekly training volume
+    #[command(args_conflicts_with_subcommands = true)]
+    Workout {
+        #[command(subcommand)]
+        action: Option<WorkoutAction>,
+        /// Format of the workout shown when no subcommand is given
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Group tasks into lists such as Groceries, Work or Home
+    #[command(args_conflicts_with_subcommands = true)]
+    Lists {
+        #[command(subcommand)]
+        action: Option<ListsAction>,
+        /// Format of the lists shown when no subcommand is given
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Record mood, energy and sleep, and see how they line up with tasks and meals
+    #[command(args_conflicts_with_subcommands = true)]
+    Journal {
+        #[command(subcommand)]
+        action: Option<JournalAction>,
+        /// Format of the entries shown when no subcommand is given
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Review what got done, what slipped and how habits held up; the last 7 days by default
+    Report {
+        /// Review just today
+        #[arg(long, conflicts_with_all = ["week", "from"])]
+        day: bool,
+        /// Review this calendar week, from Monday to today
+        #[arg(long, conflicts_with = "from")]
+        week: bool,
+        /// First day to review, e.g. 2026-10-01 or "2 weeks ago"
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day to review; today when omitted
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Interactive task browser with tags, search, inline editing and timers
+    Tui,
+    /// Delete all tasks
+    Clear {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Print a shell completion script
+    Completions { shell: Shell },
+    /// Inspect and edit settings in config.toml
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveFormat {
+    /// One versioned JSON file
+    Json,
+    /// tasks.csv, tags.csv, history.csv and lists.csv in a directory
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    /// A backup made by `freelist export`
+    Freelist,
+    /// A todo.txt file
+    Todotxt,
+    /// The JSON from `task export`
+    Taskwarrior,
+    /// A Todoist project backup (CSV)
+    Todoist,
+    /// To-dos and events from an iCalendar (.ics) file
+    Ics,
+}
+
+impl ImportSource {
+    /// The converter for another tool's format, or None for a FreeList backup.
+    pub fn converter(self) -> Option<SourceFormat> {
+        match self {
+            ImportSource::Freelist => None,
+            ImportSource::Todotxt => Some(SourceFormat::TodoTxt),
+            ImportSource::Taskwarrior => Some(SourceFormat::Taskwarrior),
+            ImportSource::Todoist => Some(SourceFormat::Todoist),
+            ImportSource::Ics => Some(SourceFormat::Ics),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum IcsAction {
+    /// Write tasks as VTODOs
+    Export {
+        /// File to write; goes to stdout when omitted
+        path: Option<PathBuf>,
+        /// Only tasks that have a due date
+        #[arg(long)]
+        due_only: bool,
+    },
+    /// Import VTODOs and VEVENTs; an event's start becomes the due date
+    Import {
+        /// File to read ("-" for stdin)
+        path: PathBuf,
+        /// Tag for imported tasks without categories
+        #[arg(long)]
+        tag: Option<String>,
+        /// Show what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FoodSource {
+    /// Open Food Facts product export
+    Openfoodfacts,
+    /// USDA FoodData Central
+    Usda,
+}
+
+impl From<FoodSource> for FoodDump {
+    fn from(source: FoodSource) -> Self {
+        match source {
+            FoodSource::Openfoodfacts => FoodDump::OpenFoodFacts,
+            FoodSource::Usda => FoodDump::Usda,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum FoodAction {
+    /// Add a food; nutrients are per 100 g
+    Add {
+        name: String,
+        #[arg(long)]
+        brand: Option<String>,
+        /// Grams in one serving, so the food can be logged as "2x"
+        #[arg(long, value_name = "GRAMS")]
+        serving: Option<f64>,
+        #[arg(long)]
+        kcal: Option<f64>,
+        /// Protein in grams
+        #[arg(long)]
+        protein: Option<f64>,
+        /// Carbohydrates in grams
+        #[arg(long)]
+        carbs: Option<f64>,
+        /// Fat in grams
+        #[arg(long)]
+        fat: Option<f64>,
+        /// Any other nutrient, e.g. --set fiber=4.2 --set sodium_mg=380
+        #[arg(long = "set", value_name = "NUTRIENT=AMOUNT")]
+        nutrients: Vec<String>,
+        /// EAN or UPC barcode
+        #[arg(long)]
+        barcode: Option<String>,
+    },
+    /// Import foods from an Open Food Facts or USDA FoodData Central download
+    Import {
+        /// An Open Food Facts CSV or JSONL file, or a FoodData Central CSV directory
+        path: PathBuf,
+        /// Guessed from the path when omitted: directories are FoodData Central
+        #[arg(long, value_enum)]
+        from: Option<FoodSource>,
+    },
+    /// Find foods by name or brand
+    Search {
+        query: String,
+        /// Show at most this many foods
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Find a food by its EAN or UPC barcode
+    Barcode {
+        code: String,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Record eating a food
+    Log {
+        /// Food id or name
+        food: String,
+        /// 150g, 0.2kg, 5oz or servings such as 2x; one serving (or 100 g) when omitted
+        quantity: Option<Quantity>,
+        /// breakfast, lunch, dinner or snack; guessed from the time when omitted
+        #[arg(long)]
+        meal: Option<Meal>,
+        /// When it was eaten, e.g. "yesterday 8am"; now when omitted
+        #[arg(long)]
+        at: Option<String>,
+        /// Also take it out of the pantry
+        #[arg(long)]
+        pantry: bool,
+    },
+    /// Show what was eaten today, with totals against targets
+    Today {
+        /// Another day, e.g. yesterday or 2026-10-01
+        #[arg(long)]
+        date: Option<String>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Show daily targets, or set one, e.g. `freelist food target protein 120`
+    Target {
+        /// kcal, protein, carbs, fat, fiber, sodium, ...
+        nutrient: Option<String>,
+        amount: Option<f64>,
+        /// Remove the target for this nutrient
+        #[arg(long, conflicts_with = "amount")]
+        clear: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PantryAction {
+    /// Show what is in the pantry (default)
+    List {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Add an item, e.g. `freelist pantry add Milk 2l`
+    Add {
+        name: String,
+        /// 500g, 1.5l, 2 cups, or a count such as 6
+        amount: Amount,
+        /// The food (id or name) this is, so eating it under that name uses it up
+        #[arg(long)]
+        food: Option<String>,
+    },
+    /// Set how much of an item is left
+    Set { id: i64, amount: Amount },
+    /// Remove an item
+    Remove { id: i64 },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RecipeAction {
+    /// Save a recipe, e.g. `freelist recipe add Porridge "100g oats" "2 cups milk"`
+    Add {
+        name: String,
+        /// Ingredients for one batch: an amount then a name
+        #[arg(required = true)]
+        ingredients: Vec<Ingredient>,
+        /// Servings one batch makes
+        #[arg(long, default_value_t = 1.0)]
+        serves: f64,
+    },
+    /// List recipes and their ingredients
+    List {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Take a recipe's ingredients out of the pantry
+    Cook {
+        /// Recipe id or name
+        recipe: String,
+        #[arg(long, default_value_t = 1.0)]
+        batches: f64,
+    },
+    /// Delete a recipe
+    Remove {
+        /// Recipe id or name
+        recipe: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WorkoutAction {
+    /// Show the workout in progress, or another one by id (default)
+    Show {
+        id: Option<i64>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Start a workout; sets are logged to it until it is finished
+    Start {
+        title: Option<String>,
+        /// When it started, e.g. "today 7am"; now when omitted
+        #[arg(long)]
+        at: Option<String>,
+        /// The training plan task this workout does; it is marked done
+        #[arg(long, value_name = "ID")]
+        task: Option<i64>,
+    },
+    /// Log sets of an exercise, e.g. `freelist workout log Squat 5x100kg 5x100kg "3 @ 110kg"`
+    Log {
+        /// Exercise name; new exercises are added
+        exercise: String,
+        /// 5x100kg, 8 @ 135lb, 12 reps, 5km in 25:30, 90s, ...
+        #[arg(required = true)]
+        sets: Vec<SetMeasures>,
+        /// strength or cardio, for a new exercise; guessed from its first set when omitted
+        #[arg(long)]
+        kind: Option<ExerciseKind>,
+        /// When the sets were done, e.g. "yesterday 6pm"; now when omitted
+        #[arg(long)]
+        at: Option<String>,
+    },
+    /// Finish the workout in progress
+    Finish,
+    /// List recent workouts
+    List {
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=MAX_WORKOUT_WEEKS))]
+        weeks: u32,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Show the exercises and what kind they are
+    Exercises {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Show personal records, for every exercise or one
+    Prs {
+        /// Exercise name
+        exercise: Option<String>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Show training volume for each week, Monday to Sunday
+    Volume {
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=MAX_WORKOUT_WEEKS))]
+        weeks: u32,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Delete a workout and its sets
+    Delete { id: i64 },
+    /// Training plans that schedule sessions as tasks
+    #[command(args_conflicts_with_subcommands = true)]
+    Plan {
+        #[command(subcommand)]
+        action: Option<PlanAction>,
+        /// Format of the plans shown when no subcommand is given
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PlanAction {
+    /// Show plans and their sessions (default)
+    List {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Add a plan and schedule its sessions for the next 4 weeks,
+    /// e.g. `freelist workout plan add Base --session "mon,wed,fri 7am Strength" --session "sun Long run"`
+    Add {
+        name: String,
+        /// Days, an optional time and a title; days are mon..sun, weekdays, weekends or daily
+        #[arg(long = "session", required = true)]
+        sessions: Vec<PlanSession>,
+        /// Tag for the scheduled tasks
+        #[arg(long, default_value = freelist_core::fitness::PLAN_TAG)]
+        tag: String,
+        /// First day of the plan, e.g. monday; today when omitted
+        #[arg(long)]
+        from: Option<String>,
+        /// Last day of the plan; it goes on until removed when omitted
+        #[arg(long)]
+        until: Option<String>,
+    },
+    /// Add tasks for sessions that are not scheduled yet
+    Schedule {
+        /// Plan id or name; every plan when omitted
+        plan: Option<String>,
+        /// How far ahead to schedule
+        #[arg(
+            long,
+            default_value_t = 4,
+            value_parser = clap::value_parser!(u32).range(1..=i64::from(MAX_SCHEDULE_WEEKS))
+        )]
+        weeks: u32,
+    },
+    /// Delete a plan and its tasks that are not done
+    Remove {
+        /// Plan id or name
+        plan: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum JournalAction {
+    /// Show recent entries (default)
+    List {
+        #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(u32).range(1..=MAX_JOURNAL_DAYS))]
+        days: u32,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Add an entry, e.g. `freelist journal add --mood 4 --slept 7.5 "Long walk at lunch"`
+    Add {
+        /// Free-text notes
+        notes: Vec<String>,
+        /// Ratings from 1 (worst) to 5 (best)
+        #[arg(long)]
+        mood: Option<u8>,
+        #[arg(long)]
+        energy: Option<u8>,
+        /// How well you slept
+        #[arg(long)]
+        sleep: Option<u8>,
+        /// Hours slept
+        #[arg(long, value_name = "HOURS")]
+        slept: Option<f64>,
+        /// When, e.g. "yesterday 9pm"; now when omitted
+        #[arg(long)]
+        at: Option<String>,
+    },
+    /// Show each day's ratings next to tasks completed and meals logged
+    Days {
+        #[arg(long, default_value_t = 14, value_parser = clap::value_parser!(u32).range(1..=MAX_JOURNAL_DAYS))]
+        days: u32,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Show how ratings move with tasks completed and meals logged
+    Correlate {
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=MAX_JOURNAL_DAYS))]
+        days: u32,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Delete an entry
+    Delete { id: i64 },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ListsAction {
+    /// Show lists with how many tasks are left in each (default)
+    Show {
+        /// Include archived lists
+        #[arg(long)]
+        all: bool,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Add a list at the end, e.g. `freelist lists add Groceries --colour "#2e8b57" --icon 🛒`
+    Add {
+        name: String,
+        /// #rgb or #rrggbb
+        #[arg(long)]
+        colour: Option<String>,
+        /// An emoji or icon name
+        #[arg(long)]
+        icon: Option<String>,
+    },
+    /// Rename, recolour or reorder a list; "" clears the colour or icon
+    Edit {
+        /// Name or id
+        list: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        colour: Option<String>,
+        #[arg(long)]
+        icon: Option<String>,
+        /// Lists are shown lowest first
+        #[arg(long, allow_negative_numbers = true)]
+        position: Option<i64>,
+    },
+    /// Hide a list from `freelist lists` without touching its tasks
+    Archive { list: String },
+    /// Show an archived list again
+    Unarchive { list: String },
+    /// Delete a list; its tasks are kept outside any list
+    Remove { list: String },
+    /// Put a task in a list, or take it out of its list when LIST is left out
+    Move { task: i64, list: Option<String> },
+    /// Make a list for each tag and move the tag's tasks that are in no list into it
+    FromTags,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Show the config file, active profile and database location (default)
+    Show,
+    /// Print a setting (profile, db, profiles.<name>.db)
+    Get { key: String },
+    /// Change a setting; relative database paths are relative to config.toml
+    Set { key: String, value: String },
+    /// Remove a setting
+    Unset { key: String },
+}
@@ -0,0 +1,426 @@
+// cli/src/caldav/handler.rs
+// Maps CalDAV requests onto a TaskList. Kept apart from the HTTP server so it only deals in strings.
+use super::xml::{self, Multistatus, PropName, PropRequest, Report, CALDAV, CALENDARSERVER, DAV};
+use chrono::{DateTime, Local, Utc};
+use freelist_core::{ics, Task, TaskList};
+
+type HandlerResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// The calendar home; each tag is a calendar below it.
+pub const HOME: &str = "/calendars/";
+/// The calendar holding tasks without a tag.
+pub const UNTAGGED: &str = "untagged";
+
+const ALLOW: &str = "OPTIONS, GET, HEAD, PUT, DELETE, PROPFIND, REPORT";
+
+pub struct Request<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub depth: Option<&'a str>,
+    pub if_match: Option<&'a str>,
+    pub if_none_match: Option<&'a str>,
+    pub body: &'a str,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl Response {
+    fn new(status: u16) -> Self {
+        Response { status, headers: Vec::new(), body: String::new() }
+    }
+
+    fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    fn with_body(self, content_type: &str, body: String) -> Self {
+        Response { body, ..self.with_header("Content-Type", content_type) }
+    }
+
+    fn multistatus(body: String) -> Self {
+        Response::new(207).with_body("application/xml; charset=utf-8", body)
+    }
+
+    fn bad_request(message: String) -> Self {
+        Response::new(400).with_body("text/plain; charset=utf-8", message)
+    }
+}
+
+/// What a path refers to. Calendars are named by tag; `None` is the untagged calendar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Principal,
+    Home,
+    Calendar(Option<String>),
+    Task(Option<String>, String),
+}
+
+pub fn handle(task_list: &mut TaskList, request: &Request, now: DateTime<Utc>) -> Response {
+    route(task_list, request, now)
+        .unwrap_or_else(|err| Response::new(500).with_body("text/plain; charset=utf-8", err.to_string()))
+}
+
+fn route(task_list: &mut TaskList, request: &Request, now: DateTime<Utc>) -> HandlerResult<Response> {
+    if request.path.trim_end_matches('/') == "/.well-known/caldav" {
+        return Ok(Response::new(301).with_header("Location", "/"));
+    }
+    let Some(target) = parse_path(request.path) else {
+        return Ok(Response::new(404));
+    };
+
+    match (request.method, target) {
+        ("OPTIONS", _) => Ok(Response::new(200).with_header("DAV", "1, 3, calendar-access").with_header("Allow", ALLOW)),
+        ("PROPFIND", target) => propfind(task_list, request, target, now),
+        ("REPORT", Target::Calendar(tag)) => report(task_list, request, &tag, now),
+        ("GET" | "HEAD", Target::Calendar(tag)) => {
+            let calendar = task_list.export_ics(&calendar_tasks(task_list, &tag)?, now)?;
+            Ok(Response::new(200).with_body("text/calendar; charset=utf-8", calendar))
+        }
+        ("GET" | "HEAD", Target::Task(tag, uid)) => match find_task(task_list, &tag, &uid)? {
+            Some(task) => Ok(Response::new(200)
+                .with_header("ETag", etag(&task))
+                .with_body("text/calendar; charset=utf-8", task_list.export_ics(&[task], now)?)),
+            None => Ok(Response::new(404)),
+        },
+        ("PUT", Target::Task(tag, uid)) => put(task_list, request, tag, &uid),
+        ("DELETE", Target::Task(tag, uid)) => delete(task_list, request, &tag, &uid),
+        // Calendars exist for as long as some task has their tag
+        ("PUT" | "DELETE" | "MKCOL" | "MKCALENDAR", _) => Ok(Response::new(403)),
+        _ => Ok(Response::new(405).with_header("Allow", ALLOW)),
+    }
+}
+
+fn propfind(task_list: &TaskList, request: &Request, target: Target, now: DateTime<Utc>) -> HandlerResult<Response> {
+    let props = match xml::parse_propfind(request.body) {
+        Ok(props) => props,
+        Err(err) => return Ok(Response::bad_request(err)),
+    };
+    // Depth defaults to infinity, which is one level here
+    let children = request.depth.is_none_or(|depth| depth.trim() != "0");
+    let mut multistatus = Multistatus::new();
+
+    match target {
+        Target::Principal => respond(&mut multistatus, "/", principal_props(), &props),
+        Target::Home => {
+            respond(&mut multistatus, HOME, home_props(), &props);
+            if children {
+                for tag in calendars(task_list)? {
+                    let tasks = calendar_tasks(task_list, &tag)?;
+                    respond(&mut multistatus, &calendar_href(&tag), calendar_props(&tag, &tasks), &props);
+                }
+            }
+        }
+        Target::Calendar(tag) => {
+            if !calendars(task_list)?.contains(&tag) {
+                return Ok(Response::new(404));
+            }
+            let tasks = calendar_tasks(task_list, &tag)?;
+            respond(&mut multistatus, &calendar_href(&tag), calendar_props(&tag, &tasks), &props);
+            if children {
+                for task in tasks {
+                    let href = task_href(task_list, &tag, &task)?;
+                    respond(&mut multistatus, &href, task_props(task_list, task, &props, now)?, &props);
+                }
+            }
+        }
+        Target::Task(tag, uid) => match find_task(task_list, &tag, &uid)? {
+            Some(task) => {
+                let href = task_href(task_list, &tag, &task)?;
+                respond(&mut multistatus, &href, task_props(task_list, task, &props, now)?, &props);
+            }
+            None => return Ok(Response::new(404)),
+        },
+    }
+    Ok(Response::multistatus(multistatus.finish()))
+}
+
+fn report(task_list: &TaskList, request: &Request, tag: &Option<String>, now: DateTime<Utc>) -> HandlerResult<Response> {
+    let mut multistatus = Multistatus::new();
+    match xml::parse_report(request.body) {
+        Err(err) => return Ok(Response::bad_request(err)),
+        Ok(Report::Query { props, components }) => {
+            // Only to-dos are served, so a query for events matches nothing
+            if components.iter().all(|name| name == "VCALENDAR" || name == "VTODO") {
+                for task in calendar_tasks(task_list, tag)? {
+                    let href = task_href(task_list, tag, &task)?;
+                    respond(&mut multistatus, &href, task_props(task_list, task, &props, now)?, &props);
+                }
+            }
+        }
+        Ok(Report::Multiget { props, hrefs }) => {
+            for href in hrefs {
+                let task = match parse_path(&href) {
+                    Some(Target::Task(tag, uid)) => find_task(task_list, &tag, &uid)?,
+                    _ => None,
+                };
+                match task {
+                    Some(task) => respond(&mut multistatus, &href, task_props(task_list, task, &props, now)?, &props),
+                    None => multistatus.not_found(&href),
+                }
+            }
+        }
+        Ok(Report::Unsupported(_)) => {
+            let error = format!("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<d:error xmlns:d=\"{}\"><d:supported-report/></d:error>\n", DAV);
+            return Ok(Response::new(403).with_body("application/xml; charset=utf-8", error));
+        }
+    }
+    Ok(Response::multistatus(multistatus.finish()))
+}
+
+/// Creates or replaces a task. The calendar sets the tag, and the resource
+/// name (not the UID inside the body) identifies the task from then on.
+fn put(task_list: &mut TaskList, request: &Request, tag: Option<String>, uid: &str) -> HandlerResult<Response> {
+    let existing = task_list.find_by_uid(uid)?;
+    if !preconditions_hold(request, existing.as_ref()) {
+        return Ok(Response::new(412));
+    }
+
+    let (items, _) = match ics::parse_calendar(request.body, &Local::now()) {
+        Ok(parsed) => parsed,
+        Err(err) => return Ok(Response::bad_request(err.to_string())),
+    };
+    let [item] = items.as_slice() else {
+        return Ok(Response::bad_request(format!("expected one VTODO, found {}", items.len())));
+    };
+
+    let mut task = item.task.clone();
+    task.tag = tag;
+    task.parent_id = match &item.parent_uid {
+        Some(parent_uid) => task_list.find_by_uid(parent_uid)?.and_then(|parent| parent.id),
+        None => None,
+    };
+
+    let (id, status) = match existing {
+        Some(existing) => {
+            let id = existing.id.ok_or("stored task has no id")?;
+            // Fields iCalendar cannot carry are kept
+            task.id = Some(id);
+            task.created_at = existing.created_at;
+            task.estimated_duration = existing.estimated_duration;
+            task.last_duration = existing.last_duration;
+            task.list_id = existing.list_id;
+            if let Some(parent_id) = task.parent_id
+                && is_descendant(task_list, parent_id, id)?
+            {
+                task.parent_id = existing.parent_id;
+            }
+            task_list.update(&task)?;
+            (id, 204)
+        }
+        None => {
+            let id = task_list.add(task)?;
+            task_list.set_uid(id, uid)?;
+            (id, 201)
+        }
+    };
+
+    let stored = task_list.get_by_id(id)?.ok_or("task was not saved")?;
+    Ok(Response::new(status).with_header("ETag", etag(&stored)))
+}
+
+fn delete(task_list: &mut TaskList, request: &Request, tag: &Option<String>, uid: &str) -> HandlerResult<Response> {
+    let Some(task) = find_task(task_list, tag, uid)? else {
+        return Ok(Response::new(404));
+    };
+    if !preconditions_hold(request, Some(&task)) {
+        return Ok(Response::new(412));
+    }
+    task_list.delete(task.id.ok_or("stored task has no id")?)?;
+    Ok(Response::new(204))
+}
+
+/// If-Match and If-None-Match against the current version of a resource.
+fn preconditions_hold(request: &Request, current: Option<&Task>) -> bool {
+    let matches = |header: &str| {
+        current.is_some_and(|task| {
+            let current = etag(task);
+            header.split(',').map(|tag| tag.trim().trim_start_matches("W/")).any(|tag| tag == "*" || tag == current)
+        })
+    };
+    request.if_match.is_none_or(matches) && request.if_none_match.is_none_or(|header| !matches(header))
+}
+
+/// Whether `id` is `ancestor` or below it, so `ancestor` cannot become its child.
+fn is_descendant(task_list: &TaskList, id: i64, ancestor: i64) -> HandlerResult<bool> {
+    let mut current = Some(id);
+    while let Some(current_id) = current {
+        if current_id == ancestor {
+            return Ok(true);
+        }
+        current = task_list.get_by_id(current_id)?.and_then(|task| task.parent_id);
+    }
+    Ok(false)
+}
+
+/// Changes whenever a task in the calendar is added, edited or removed. An
+/// FNV-1a hash rather than `DefaultHasher`, which may change between Rust
+/// releases and make clients refetch every calendar after an upgrade.
+fn ctag(tasks: &[Task]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for task in tasks {
+        let fields = [task.id.unwrap_or(0), task.updated_at.timestamp_micros()];
+        for byte in fields.iter().flat_map(|field| field.to_le_bytes()) {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:x}", hash)
+}
+
+fn etag(task: &Task) -> String {
+    format!("\"{}\"", task.updated_at.timestamp_micros())
+}
+
+fn calendars(task_list: &TaskList) -> HandlerResult<Vec<Option<String>>> {
+    let tags = task_list.get_all_tags()?.into_iter().filter(|tag| tag != UNTAGGED).map(Some);
+    Ok(tags.chain(std::iter::once(None)).collect())
+}
+
+fn in_calendar(task: &Task, tag: &Option<String>) -> bool {
+    task.tag.as_deref().filter(|task_tag| *task_tag != UNTAGGED) == tag.as_deref()
+}
+
+fn calendar_tasks(task_list: &TaskList, tag: &Option<String>) -> HandlerResult<Vec<Task>> {
+    Ok(task_list.all()?.into_iter().filter(|task| in_calendar(task, tag)).collect())
+}
+
+fn find_task(task_list: &TaskList, tag: &Option<String>, uid: &str) -> HandlerResult<Option<Task>> {
+    Ok(task_list.find_by_uid(uid)?.filter(|task| in_calendar(task, tag)))
+}
+
+fn calendar_href(tag: &Option<String>) -> String {
+    format!("{}{}/", HOME, encode(tag.as_deref().unwrap_or(UNTAGGED)))
+}
+
+fn task_href(task_list: &TaskList, tag: &Option<String>, task: &Task) -> HandlerResult<String> {
+    let uid = task_list.uid(task.id.ok_or("stored task has no id")?)?;
+    Ok(format!("{}{}.ics", calendar_href(tag), encode(&uid)))
+}
+
+/// Accepts absolute paths or full URLs, as multiget hrefs may be either.
+fn parse_path(path: &str) -> Option<Target> {
+    let path = path.split(['?', '#']).next()?;
+    let path = match path.split_once("://") {
+        Some((_, rest)) => &rest[rest.find('/').unwrap_or(rest.len())..],
+        None => path,
+    };
+    let segments: Vec<String> = path.split('/').filter(|s| !s.is_empty()).map(decode).collect::<Option<_>>()?;
+    let calendar = |name: &str| Some(name.to_string()).filter(|name| name != UNTAGGED);
+    match segments.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] => Some(Target::Principal),
+        ["calendars"] => Some(Target::Home),
+        ["calendars", name] => Some(Target::Calendar(calendar(name))),
+        ["calendars", name, resource] => {
+            let uid = resource.strip_suffix(".ics")?;
+            Some(Target::Task(calendar(name), uid.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn encode(segment: &str) -> String {
+    let mut encoded = String::new();
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Adds a response with the requested properties, or all of them for allprop.
+fn respond(multistatus: &mut Multistatus, href: &str, available: Vec<(PropName, String)>, request: &PropRequest) {
+    match request {
+        PropRequest::All => multistatus.response(href, &available, &[]),
+        PropRequest::Props(requested) => {
+            let mut found = Vec::new();
+            let mut missing = Vec::new();
+            for prop in requested {
+                match available.iter().find(|(name, _)| name == prop) {
+                    Some(entry) => found.push(entry.clone()),
+                    None => missing.push(prop.clone()),
+                }
+            }
+            multistatus.response(href, &found, &missing);
+        }
+    }
+}
+
+fn principal_href() -> String {
+    "<d:href>/</d:href>".to_string()
+}
+
+fn principal_props() -> Vec<(PropName, String)> {
+    vec![
+        (PropName::new(DAV, "resourcetype"), "<d:collection/><d:principal/>".to_string()),
+        (PropName::new(DAV, "displayname"), "FreeList".to_string()),
+        (PropName::new(DAV, "current-user-principal"), principal_href()),
+        (PropName::new(DAV, "principal-URL"), principal_href()),
+        (PropName::new(CALDAV, "calendar-home-set"), format!("<d:href>{}</d:href>", HOME)),
+    ]
+}
+
+fn home_props() -> Vec<(PropName, String)> {
+    vec![
+        (PropName::new(DAV, "resourcetype"), "<d:collection/>".to_string()),
+        (PropName::new(DAV, "displayname"), "FreeList".to_string()),
+        (PropName::new(DAV, "current-user-principal"), principal_href()),
+    ]
+}
+
+fn calendar_props(tag: &Option<String>, tasks: &[Task]) -> Vec<(PropName, String)> {
+    let reports = ["calendar-query", "calendar-multiget"]
+        .map(|report| format!("<d:supported-report><d:report><c:{}/></d:report></d:supported-report>", report))
+        .concat();
+    let privileges = ["read", "write", "write-content", "bind", "unbind"]
+        .map(|privilege| format!("<d:privilege><d:{}/></d:privilege>", privilege))
+        .concat();
+    vec![
+        (PropName::new(DAV, "resourcetype"), "<d:collection/><c:calendar/>".to_string()),
+        (PropName::new(DAV, "displayname"), xml::escape(tag.as_deref().unwrap_or("Untagged"))),
+        (PropName::new(DAV, "current-user-principal"), principal_href()),
+        (PropName::new(DAV, "owner"), principal_href()),
+        (PropName::new(DAV, "supported-report-set"), reports),
+        (PropName::new(DAV, "current-user-privilege-set"), privileges),
+        (PropName::new(CALDAV, "supported-calendar-component-set"), "<c:comp name=\"VTODO\"/>".to_string()),
+        (PropName::new(CALENDARSERVER, "getctag"), ctag(tasks)),
+    ]
+}
+
+/// calendar-data is only rendered when asked for, as allprop leaves it out.
+fn task_props(task_list: &TaskList, task: Task, request: &PropRequest, now: DateTime<Utc>) -> HandlerResult<Vec<(PropName, String)>> {
+    let mut props = vec![
+        (PropName::new(DAV, "resourcetype"), String::new()),
+        (PropName::new(DAV, "getetag"), xml::escape(&etag(&task))),
+        (PropName::new(DAV, "getcontenttype"), "text/calendar; charset=utf-8; component=VTODO".to_string()),
+        (PropName::new(DAV, "getlastmodified"), task.updated_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()),
+    ];
+    if request.wants(CALDAV, "calendar-data") {
+        let calendar = task_list.export_ics(&[task], now)?;
+        props.push((PropName::new(CALDAV, "calendar-data"), xml::escape(&calendar)));
+    }
+    Ok(props)
+}
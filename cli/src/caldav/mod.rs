@@ -0,0 +1,62 @@
+// cli/src/caldav/mod.rs
+// `freelist caldav`: serves each tag as a CalDAV calendar of to-dos.
+mod handler;
+mod xml;
+
+use chrono::Utc;
+use freelist_core::TaskList;
+use handler::Request;
+use std::io::{Cursor, Write};
+
+pub fn serve(mut task_list: TaskList, bind: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let server = tiny_http::Server::http(bind).map_err(|e| format!("cannot listen on {}: {}", bind, e))?;
+    let address = server.server_addr().to_ip().ok_or("not listening on an IP address")?;
+    println!("Serving CalDAV at http://{}/ without authentication; press Ctrl-C to stop", address);
+    std::io::stdout().flush()?;
+
+    for mut request in server.incoming_requests() {
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv(name))
+                .map(|header| header.value.as_str().to_string())
+        };
+        let (depth, if_match, if_none_match) = (header("Depth"), header("If-Match"), header("If-None-Match"));
+        let method = request.method().as_str().to_string();
+        let path = request.url().to_string();
+
+        let mut body = String::new();
+        let response = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => handler::handle(
+                &mut task_list,
+                &Request {
+                    method: &method,
+                    path: &path,
+                    depth: depth.as_deref(),
+                    if_match: if_match.as_deref(),
+                    if_none_match: if_none_match.as_deref(),
+                    body: &body,
+                },
+                Utc::now(),
+            ),
+            Err(err) => {
+                eprintln!("{} {}: cannot read body: {}", method, path, err);
+                continue;
+            }
+        };
+        eprintln!("{} {} {}", method, path, response.status);
+
+        let headers = response
+            .headers
+            .iter()
+            .filter_map(|(name, value)| tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).ok())
+            .collect();
+        let length = response.body.len();
+        let reply = tiny_http::Response::new(response.status.into(), headers, Cursor::new(response.body), Some(length), None);
+        if let Err(err) = request.respond(reply) {
+            eprintln!("{} {}: {}", method, path, err);
+        }
+    }
+    Ok(())
+}
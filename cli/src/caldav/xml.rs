@@ -0,0 +1,180 @@
+// cli/src/caldav/xml.rs
+// Reading PROPFIND/REPORT bodies and writing 207 Multi-Status responses.
+use roxmltree::{Document, Node};
+
+pub const DAV: &str = "DAV:";
+pub const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+pub const CALENDARSERVER: &str = "http://calendarserver.org/ns/";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropName {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl PropName {
+    pub fn new(namespace: &str, name: &str) -> Self {
+        PropName { namespace: namespace.to_string(), name: name.to_string() }
+    }
+
+    pub fn is(&self, namespace: &str, name: &str) -> bool {
+        self.namespace == namespace && self.name == name
+    }
+}
+
+/// The properties a client asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropRequest {
+    /// `<allprop/>`, `<propname/>` or an empty body
+    All,
+    Props(Vec<PropName>),
+}
+
+impl PropRequest {
+    pub fn wants(&self, namespace: &str, name: &str) -> bool {
+        match self {
+            PropRequest::All => false,
+            PropRequest::Props(props) => props.iter().any(|prop| prop.is(namespace, name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Report {
+    /// calendar-query, with the component names its filter asks for
+    Query { props: PropRequest, components: Vec<String> },
+    Multiget { props: PropRequest, hrefs: Vec<String> },
+    /// Any other report, by element name
+    Unsupported(String),
+}
+
+pub fn parse_propfind(body: &str) -> Result<PropRequest, String> {
+    if body.trim().is_empty() {
+        return Ok(PropRequest::All);
+    }
+    let document = Document::parse(body).map_err(|e| e.to_string())?;
+    Ok(requested_props(document.root_element()))
+}
+
+pub fn parse_report(body: &str) -> Result<Report, String> {
+    let document = Document::parse(body).map_err(|e| e.to_string())?;
+    let root = document.root_element();
+    let props = requested_props(root);
+    match (root.tag_name().namespace(), root.tag_name().name()) {
+        (Some(CALDAV), "calendar-query") => {
+            let components = root
+                .descendants()
+                .filter(|node| is(node, CALDAV, "comp-filter"))
+                .filter_map(|node| node.attribute("name"))
+                .map(str::to_uppercase)
+                .collect();
+            Ok(Report::Query { props, components })
+        }
+        (Some(CALDAV), "calendar-multiget") => {
+            let hrefs = root
+                .children()
+                .filter(|node| is(node, DAV, "href"))
+                .filter_map(|node| node.text())
+                .map(|href| href.trim().to_string())
+                .collect();
+            Ok(Report::Multiget { props, hrefs })
+        }
+        (_, name) => Ok(Report::Unsupported(name.to_string())),
+    }
+}
+
+fn is(node: &Node, namespace: &str, name: &str) -> bool {
+    node.is_element() && node.tag_name().namespace() == Some(namespace) && node.tag_name().name() == name
+}
+
+fn requested_props(root: Node) -> PropRequest {
+    match root.children().find(|node| is(node, DAV, "prop")) {
+        Some(prop) => PropRequest::Props(
+            prop.children()
+                .filter(Node::is_element)
+                .map(|node| PropName::new(node.tag_name().namespace().unwrap_or(""), node.tag_name().name()))
+                .collect(),
+        ),
+        None => PropRequest::All,
+    }
+}
+
+/// Escapes text content; carriage returns are kept as references so the
+/// CRLF line endings of calendar data survive XML parsing.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\r', "&#13;")
+}
+
+fn prefix(namespace: &str) -> Option<&'static str> {
+    match namespace {
+        DAV => Some("d"),
+        CALDAV => Some("c"),
+        CALENDARSERVER => Some("cs"),
+        _ => None,
+    }
+}
+
+fn element(prop: &PropName, value: &str) -> String {
+    let (open, close) = match prefix(&prop.namespace) {
+        Some(prefix) => (format!("{}:{}", prefix, prop.name), format!("{}:{}", prefix, prop.name)),
+        None => (format!("x:{} xmlns:x=\"{}\"", prop.name, escape(&prop.namespace)), format!("x:{}", prop.name)),
+    };
+    if value.is_empty() {
+        format!("<{}/>", open)
+    } else {
+        format!("<{}>{}</{}>", open, value, close)
+    }
+}
+
+/// A 207 Multi-Status body, built one `<d:response>` at a time.
+pub struct Multistatus {
+    out: String,
+}
+
+impl Multistatus {
+    pub fn new() -> Self {
+        Multistatus {
+            out: format!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<d:multistatus xmlns:d=\"{}\" xmlns:c=\"{}\" xmlns:cs=\"{}\">\n",
+                DAV, CALDAV, CALENDARSERVER
+            ),
+        }
+    }
+
+    /// A resource's properties. Values are XML fragments; `missing` are
+    /// requested properties this resource does not have.
+    pub fn response(&mut self, href: &str, found: &[(PropName, String)], missing: &[PropName]) {
+        self.out.push_str(&format!("<d:response><d:href>{}</d:href>", escape(href)));
+        if !found.is_empty() {
+            self.propstat(found.iter().map(|(prop, value)| element(prop, value)), "200 OK");
+        }
+        if !missing.is_empty() {
+            self.propstat(missing.iter().map(|prop| element(prop, "")), "404 Not Found");
+        }
+        self.out.push_str("</d:response>\n");
+    }
+
+    fn propstat(&mut self, elements: impl Iterator<Item = String>, status: &str) {
+        self.out.push_str("<d:propstat><d:prop>");
+        for element in elements {
+            self.out.push_str(&element);
+        }
+        self.out.push_str(&format!("</d:prop><d:status>HTTP/1.1 {}</d:status></d:propstat>", status));
+    }
+
+    pub fn not_found(&mut self, href: &str) {
+        self.out.push_str(&format!(
+            "<d:response><d:href>{}</d:href><d:status>HTTP/1.1 404 Not Found</d:status></d:response>\n",
+            escape(href)
+        ));
+    }
+
+    pub fn finish(mut self) -> String {
+        self.out.push_str("</d:multistatus>\n");
+        self.out
+    }
+}
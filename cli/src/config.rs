@@ -0,0 +1,321 @@
+// cli/src/config.rs
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings stored in `$XDG_CONFIG_HOME/freelist/config.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Profile used when neither `--profile` nor `$FREELIST_PROFILE` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Database for the default (unnamed) profile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db: Option<PathBuf>,
+}
+
+/// Where the database path came from, for `freelist config show`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DbSource {
+    Flag,
+    Env,
+    Profile(String),
+    Config,
+    Default,
+}
+
+impl std::fmt::Display for DbSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DbSource::Flag => write!(f, "--db flag"),
+            DbSource::Env => write!(f, "$FREELIST_DB"),
+            DbSource::Profile(name) => write!(f, "profile '{}' in config file", name),
+            DbSource::Config => write!(f, "config file"),
+            DbSource::Default => write!(f, "default data directory"),
+        }
+    }
+}
+
+/// Environment lookups, injectable so resolution can be tested without touching the process env.
+pub trait Env {
+    fn var(&self, key: &str) -> Option<String>;
+}
+
+pub struct ProcessEnv;
+
+impl Env for ProcessEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok().filter(|value| !value.is_empty())
+    }
+}
+
+fn base_dir(env: &dyn Env, xdg_var: &str, home_fallback: &str) -> Result<PathBuf, String> {
+    if let Some(dir) = env.var(xdg_var) {
+        return Ok(PathBuf::from(dir));
+    }
+    env.var("HOME")
+        .map(|home| Path::new(&home).join(home_fallback))
+        .ok_or_else(|| format!("Neither ${} nor $HOME is set", xdg_var))
+}
+
+pub fn config_path(env: &dyn Env) -> Result<PathBuf, String> {
+    Ok(config_dir(env)?.join("config.toml"))
+}
+
+fn config_dir(env: &dyn Env) -> Result<PathBuf, String> {
+    Ok(base_dir(env, "XDG_CONFIG_HOME", ".config")?.join("freelist"))
+}
+
+pub fn data_dir(env: &dyn Env) -> Result<PathBuf, String> {
+    Ok(base_dir(env, "XDG_DATA_HOME", ".local/share")?.join("freelist"))
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(format!("Cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+        }
+        let text = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    /// Picks the profile: `--profile`, then `$FREELIST_PROFILE`, then the config file.
+    pub fn active_profile(&self, flag: Option<&str>, env: &dyn Env) -> Option<String> {
+        flag.map(str::to_string)
+            .or_else(|| env.var("FREELIST_PROFILE"))
+            .or_else(|| self.profile.clone())
+    }
+
+    /// Resolves the database path: `--db`, then `$FREELIST_DB`, then the active
+    /// profile or top-level `db` in the config file, then the XDG data directory.
+    /// Relative paths in the config file are relative to the file's directory.
+    pub fn resolve_db(
+        &self,
+        flag: Option<&Path>,
+        profile: Option<&str>,
+        env: &dyn Env,
+    ) -> Result<(PathBuf, DbSource), String> {
+        if let Some(path) = flag {
+            return Ok((path.to_path_buf(), DbSource::Flag));
+        }
+        if let Some(path) = env.var("FREELIST_DB") {
+            return Ok((PathBuf::from(path), DbSource::Env));
+        }
+        match profile {
+            Some(name) => {
+                check_profile_name(name)?;
+                if let Some(path) = self.profiles.get(name).and_then(|p| p.db.as_ref()) {
+                    return Ok((config_dir(env)?.join(path), DbSource::Profile(name.to_string())));
+                }
+                // A profile without a configured path only exists once its database does,
+                // so a mistyped name is not silently a new, empty profile
+                let path = data_dir(env)?.join(format!("{}.db", name));
+                if !path.exists() {
+                    return Err(format!(
+                        "No profile called '{}'. Create it with `freelist config set profiles.{}.db <path>`",
+                        name, name
+                    ));
+                }
+                Ok((path, DbSource::Default))
+            }
+            None => match &self.db {
+                Some(path) => Ok((config_dir(env)?.join(path), DbSource::Config)),
+                None => Ok((data_dir(env)?.join("freelist.db"), DbSource::Default)),
+            },
+        }
+    }
+
+    /// Reads a setting: `profile`, `db` or `profiles.<name>.db`.
+    pub fn get(&self, key: &str) -> Result<Option<String>, String> {
+        let display = |path: &Option<PathBuf>| path.as_ref().map(|p| p.display().to_string());
+        match parse_key(key)? {
+            Key::Profile => Ok(self.profile.clone()),
+            Key::Db => Ok(display(&self.db)),
+            Key::ProfileDb(name) => Ok(self.profiles.get(name).and_then(|p| display(&p.db))),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match parse_key(key)? {
+            Key::Profile => {
+                check_profile_name(value)?;
+                self.profile = Some(value.to_string());
+            }
+            Key::Db => self.db = Some(PathBuf::from(value)),
+            Key::ProfileDb(name) => {
+                check_profile_name(name)?;
+                self.profiles.entry(name.to_string()).or_default().db = Some(PathBuf::from(value));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn unset(&mut self, key: &str) -> Result<(), String> {
+        match parse_key(key)? {
+            Key::Profile => self.profile = None,
+            Key::Db => self.db = None,
+            Key::ProfileDb(name) => {
+                self.profiles.remove(name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Profile names become file names in the data directory, so they cannot hold a path.
+fn check_profile_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(format!("'{}' cannot be a profile name", name));
+    }
+    Ok(())
+}
+
+enum Key<'a> {
+    Profile,
+    Db,
+    ProfileDb(&'a str),
+}
+
+fn parse_key(key: &str) -> Result<Key<'_>, String> {
+    match key {
+        "profile" => Ok(Key::Profile),
+        "db" => Ok(Key::Db),
+        _ => key
+            .strip_prefix("profiles.")
+            .and_then(|rest| rest.strip_suffix(".db"))
+            .filter(|name| !name.is_empty())
+            .map(Key::ProfileDb)
+            .ok_or_else(|| format!("Unknown setting '{}'. Use profile, db or profiles.<name>.db", key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct FakeEnv(HashMap<&'static str, String>);
+
+    impl Env for FakeEnv {
+        fn var(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+    }
+
+    fn env(vars: &[(&'static str, &str)]) -> FakeEnv {
+        FakeEnv(vars.iter().map(|(key, value)| (*key, value.to_string())).collect())
+    }
+
+    #[test]
+    fn test_default_locations_follow_xdg() {
+        let home_only = env(&[("HOME", "/home/ada")]);
+        let config = Config::default();
+        assert_eq!(
+            config_path(&home_only).unwrap(),
+            PathBuf::from("/home/ada/.config/freelist/config.toml")
+        );
+        assert_eq!(
+            config.resolve_db(None, None, &home_only).unwrap(),
+            (PathBuf::from("/home/ada/.local/share/freelist/freelist.db"), DbSource::Default)
+        );
+
+        let data = tempfile::TempDir::new().unwrap();
+        let xdg = env(&[("HOME", "/home/ada"), ("XDG_DATA_HOME", data.path().to_str().unwrap())]);
+        let error = config.resolve_db(None, Some("work"), &xdg).unwrap_err();
+        assert_eq!(error, "No profile called 'work'. Create it with `freelist config set profiles.work.db <path>`");
+        std::fs::create_dir(data.path().join("freelist")).unwrap();
+        std::fs::write(data.path().join("freelist/work.db"), "").unwrap();
+        assert_eq!(
+            config.resolve_db(None, Some("work"), &xdg).unwrap(),
+            (data.path().join("freelist/work.db"), DbSource::Default)
+        );
+    }
+
+    #[test]
+    fn test_profile_names_are_not_paths() {
+        let mut config = Config::default();
+        let home = env(&[("HOME", "/h")]);
+        for name in ["../x", "a/b", "a\\b", ".hidden", ""] {
+            let error = config.resolve_db(None, Some(name), &home).unwrap_err();
+            assert!(error.contains("cannot be a profile name"), "{}", name);
+            assert!(config.set("profile", name).is_err(), "{}", name);
+        }
+        assert!(config.set("profiles.../x.db", "/x.db").is_err());
+    }
+
+    #[test]
+    fn test_resolution_precedence() {
+        let mut config = Config::default();
+        config.set("db", "/config/home.db").unwrap();
+        config.set("profiles.work.db", "/config/work.db").unwrap();
+        let plain = env(&[("HOME", "/h")]);
+        let with_env = env(&[("HOME", "/h"), ("FREELIST_DB", "/env.db")]);
+
+        assert_eq!(config.resolve_db(None, None, &plain).unwrap().1, DbSource::Config);
+        assert_eq!(
+            config.resolve_db(None, Some("work"), &plain).unwrap(),
+            (PathBuf::from("/config/work.db"), DbSource::Profile("work".to_string()))
+        );
+        assert_eq!(config.resolve_db(None, Some("work"), &with_env).unwrap().1, DbSource::Env);
+        assert_eq!(
+            config.resolve_db(Some(Path::new("/flag.db")), None, &with_env).unwrap().1,
+            DbSource::Flag
+        );
+    }
+
+    #[test]
+    fn test_relative_paths_follow_the_config_file() {
+        let mut config = Config::default();
+        config.set("db", "tasks.db").unwrap();
+        config.set("profiles.work.db", "../work/tasks.db").unwrap();
+        let xdg = env(&[("HOME", "/h"), ("XDG_CONFIG_HOME", "/config")]);
+        assert_eq!(config.resolve_db(None, None, &xdg).unwrap().0, PathBuf::from("/config/freelist/tasks.db"));
+        assert_eq!(
+            config.resolve_db(None, Some("work"), &xdg).unwrap().0,
+            PathBuf::from("/config/freelist/../work/tasks.db")
+        );
+    }
+
+    #[test]
+    fn test_active_profile_precedence() {
+        let mut config = Config::default();
+        assert_eq!(config.active_profile(None, &env(&[])), None);
+        config.set("profile", "home").unwrap();
+        assert_eq!(config.active_profile(None, &env(&[])).as_deref(), Some("home"));
+        let with_env = env(&[("FREELIST_PROFILE", "work")]);
+        assert_eq!(config.active_profile(None, &with_env).as_deref(), Some("work"));
+        assert_eq!(config.active_profile(Some("gym"), &with_env).as_deref(), Some("gym"));
+    }
+
+    #[test]
+    fn test_settings_round_trip_through_toml() {
+        let mut config = Config::default();
+        config.set("profile", "work").unwrap();
+        config.set("profiles.work.db", "/w.db").unwrap();
+        assert!(config.set("colour", "blue").is_err());
+
+        let parsed: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.get("profiles.work.db").unwrap().as_deref(), Some("/w.db"));
+        assert_eq!(parsed.get("db").unwrap(), None);
+
+        config.unset("profiles.work.db").unwrap();
+        assert!(config.profiles.is_empty());
+    }
+}
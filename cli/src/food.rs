@@ -0,0 +1,238 @@
+// cli/src/food.rs
+// `freelist food`: the food catalogue and its imports, the meal log and daily totals.
+use crate::args::FoodAction;
+use crate::output::{self, Format};
+use chrono::{Local, Utc};
+use freelist_core::nutrition::{self, NUTRIENTS};
+use freelist_core::{dates, DayNutrition, Food, FoodDump, Meal, MealEntry, Nutrients, TaskList};
+use std::io::{self, Write};
+
+type FoodResult = Result<(), Box<dyn std::error::Error>>;
+
+pub fn run(task_list: &mut TaskList, action: FoodAction) -> FoodResult {
+    match action {
+        FoodAction::Add { name, brand, serving, kcal, protein, carbs, fat, nutrients, barcode } => {
+            let mut per_100g = Nutrients {
+                energy_kcal: kcal.unwrap_or_default(),
+                protein_g: protein.unwrap_or_default(),
+                carbs_g: carbs.unwrap_or_default(),
+                fat_g: fat.unwrap_or_default(),
+                ..Default::default()
+            };
+            for setting in &nutrients {
+                let (nutrient, amount) = setting
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected NUTRIENT=AMOUNT, got '{}'", setting))?;
+                let amount: f64 = amount.trim().parse().map_err(|_| format!("'{}' is not a number", amount))?;
+                per_100g.set(nutrition::nutrient_name(nutrient)?, amount)?;
+            }
+
+            let mut food = Food::new(&name, per_100g);
+            food.brand = brand;
+            food.serving_g = serving;
+            if let Some(code) = barcode {
+                let barcode = nutrition::normalise_barcode(&code);
+                food.barcode = Some(barcode.ok_or_else(|| format!("'{}' is not an EAN or UPC barcode", code))?);
+            }
+            let id = task_list.add_food(&food)?;
+            println!("Added food with ID: {}", id);
+        }
+
+        FoodAction::Import { path, from } => {
+            let dump = from.map(FoodDump::from).unwrap_or_else(|| FoodDump::detect(&path));
+            let summary = task_list.import_food_dump(dump, &path)?;
+            println!(
+                "Imported {} foods ({} new, {} updated); skipped {} without a name or nutrition facts",
+                summary.foods_added + summary.foods_updated,
+                summary.foods_added,
+                summary.foods_updated,
+                summary.records_skipped
+            );
+        }
+
+        FoodAction::Search { query, limit, format } => {
+            let foods = task_list.search_foods(&query, limit)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if foods.is_empty() => writeln!(out, "No food matches '{}'", query)?,
+                Format::Table => {
+                    for food in &foods {
+                        writeln!(out, "{}", food_line(food))?;
+                    }
+                }
+                Format::Json => output::write_json(&foods, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&foods, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("food search", format)),
+            }
+        }
+
+        FoodAction::Barcode { code, format } => {
+            let food = task_list.lookup_barcode(&code)?.ok_or_else(|| format!("No food has barcode {}", code))?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table => writeln!(out, "{}", food_line(&food))?,
+                Format::Json => output::write_json(&food, &mut out)?,
+                Format::Ndjson => output::write_ndjson(std::slice::from_ref(&food), &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("food barcode", format)),
+            }
+        }
+
+        FoodAction::Log { food, quantity, meal, at, pantry } => {
+            let food = resolve_food(task_list, &food)?;
+            let grams = match quantity {
+                Some(quantity) => food.grams(quantity)?,
+                None => food.serving_g.unwrap_or(100.0),
+            };
+            let eaten_at = match at {
+                Some(at) => dates::parse_past_local(&at)?,
+                None => Utc::now(),
+            };
+            let meal = meal.unwrap_or_else(|| Meal::at(&eaten_at.with_timezone(&Local)));
+            let entry = MealEntry::new(food.id.unwrap_or_default(), grams, meal, eaten_at);
+            let report = if pantry {
+                Some(task_list.log_meal_from_pantry(&entry)?)
+            } else {
+                task_list.log_meal(&entry)?;
+                None
+            };
+            println!(
+                "Logged {} of {} ({:.0} kcal) for {}",
+                grams_text(grams),
+                food.name,
+                food.per_100g.for_grams(grams).energy_kcal,
+                meal.as_str()
+            );
+            if let Some(report) = report {
+                crate::pantry::write_report(&report, &mut io::stdout().lock())?;
+            }
+        }
+
+        FoodAction::Today { date, format } => {
+            let date = match date {
+                Some(date) => dates::parse_past_local(&date)?.with_timezone(&Local).date_naive(),
+                None => Local::now().date_naive(),
+            };
+            let day = task_list.day_nutrition(date, &Local)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table => write_day(&day, &mut out)?,
+                Format::Json => output::write_json(&day, &mut out)?,
+                Format::Ndjson => output::write_ndjson(std::slice::from_ref(&day), &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("food today", format)),
+            }
+        }
+
+        FoodAction::Target { nutrient: None, .. } => {
+            let targets = task_list.nutrition_targets()?;
+            if targets.is_empty() {
+                println!("No targets set. Set one with `freelist food target <nutrient> <amount>`.");
+            }
+            for (nutrient, amount) in targets {
+                println!("{:<16} {}", nutrient, amount);
+            }
+        }
+
+        FoodAction::Target { nutrient: Some(nutrient), amount, clear } => {
+            let nutrient = nutrition::nutrient_name(&nutrient)?;
+            match (amount, clear) {
+                (_, true) => {
+                    task_list.set_nutrition_target(nutrient, None)?;
+                    println!("Cleared the {} target", nutrient);
+                }
+                (Some(amount), false) => {
+                    task_list.set_nutrition_target(nutrient, Some(amount))?;
+                    println!("Daily {} target set to {}", nutrient, amount);
+                }
+                (None, false) => match task_list.nutrition_targets()?.get(nutrient) {
+                    Some(amount) => println!("{}", amount),
+                    None => println!("No {} target set", nutrient),
+                },
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Finds a food by barcode, id, exact name, or a part of its name that only one food has.
+pub fn resolve_food(task_list: &TaskList, query: &str) -> Result<Food, Box<dyn std::error::Error>> {
+    if query.len() >= 8
+        && query.chars().all(|c| c.is_ascii_digit())
+        && let Some(food) = task_list.lookup_barcode(query)?
+    {
+        return Ok(food);
+    }
+    if let Ok(id) = query.parse::<i64>() {
+        return task_list.get_food(id)?.ok_or_else(|| format!("Food {} not found", id).into());
+    }
+    if let Some(food) = task_list.find_food(query)? {
+        return Ok(food);
+    }
+
+    let needle = query.to_lowercase();
+    let mut matches: Vec<Food> =
+        task_list.foods()?.into_iter().filter(|food| food.name.to_lowercase().contains(&needle)).collect();
+    match matches.len() {
+        0 => Err(format!("No food matches '{}'. Add it with `freelist food add`", query).into()),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let names: Vec<String> =
+                matches.iter().map(|food| format!("{} ({})", food.name, food.id.unwrap_or(0))).collect();
+            Err(format!("'{}' matches several foods: {}. Use the id or the full name", query, names.join(", ")).into())
+        }
+    }
+}
+
+/// Id, name, brand and energy per 100 g.
+fn food_line(food: &Food) -> String {
+    let name = match &food.brand {
+        Some(brand) => format!("{} ({})", food.name, brand),
+        None => food.name.clone(),
+    };
+    format!("{:>6}  {:<48} {:>5.0} kcal/100 g", food.id.unwrap_or_default(), name, food.per_100g.energy_kcal)
+}
+
+fn grams_text(grams: f64) -> String {
+    format!("{} g", (grams * 10.0).round() / 10.0)
+}
+
+fn macros(nutrients: &Nutrients) -> String {
+    format!(
+        "{:>5.0} kcal  P {:.1}  C {:.1}  F {:.1}",
+        nutrients.energy_kcal, nutrients.protein_g, nutrients.carbs_g, nutrients.fat_g
+    )
+}
+
+/// Entries grouped by meal, then totals and progress towards each target.
+fn write_day(day: &DayNutrition, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "{}", day.date.format("%A %-d %B %Y"))?;
+    if day.entries.is_empty() {
+        writeln!(out, "Nothing logged.")?;
+    }
+    for meal in [Meal::Breakfast, Meal::Lunch, Meal::Dinner, Meal::Snack] {
+        let entries: Vec<_> = day.entries.iter().filter(|logged| logged.entry.meal == meal).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        let mut title = meal.as_str().to_string();
+        title[..1].make_ascii_uppercase();
+        writeln!(out, "\n{}", title)?;
+        for logged in entries {
+            let time = logged.entry.eaten_at.with_timezone(&Local).format("%H:%M");
+            let name = format!("{} {}", logged.food, grams_text(logged.entry.grams));
+            writeln!(out, "  {} {:<32} {}", time, name, macros(&logged.nutrients))?;
+        }
+    }
+
+    writeln!(out, "\n{:<40} {}", "Total", macros(&day.totals))?;
+    if !day.targets.is_empty() {
+        writeln!(out, "\nTargets")?;
+        for nutrient in NUTRIENTS {
+            let Some(target) = day.targets.get(nutrient) else { continue };
+            let eaten = day.totals.get(nutrient).unwrap_or(0.0);
+            let left = day.remaining.get(nutrient).copied().unwrap_or(0.0);
+            let status = if left >= 0.0 { format!("{:.0} left", left) } else { format!("{:.0} over", -left) };
+            writeln!(out, "  {:<16} {:>6.0} / {:<6.0} {}", nutrient, eaten, target, status)?;
+        }
+    }
+    Ok(())
+}
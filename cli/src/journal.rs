@@ -0,0 +1,180 @@
+// cli/src/journal.rs
+// `freelist journal`: mood, energy and sleep entries and how they line up with each day.
+use crate::args::JournalAction;
+use crate::output::{self, Format};
+use chrono::{Days, Duration, Local, NaiveDate, NaiveTime, Utc};
+use freelist_core::{dates, Correlation, JournalDay, JournalEntry, TaskList};
+use std::io::{self, Write};
+
+type JournalResult = Result<(), Box<dyn std::error::Error>>;
+
+/// CSV columns, the serde field names of `JournalEntry`.
+const ENTRY_FIELDS: [&str; 7] = ["id", "recorded_at", "mood", "energy", "sleep", "sleep_hours", "notes"];
+
+pub fn run(task_list: &mut TaskList, action: JournalAction) -> JournalResult {
+    match action {
+        JournalAction::List { days, format } => {
+            let (first, _) = recent_days(days)?;
+            let start = first
+                .and_time(NaiveTime::MIN)
+                .and_local_timezone(Local)
+                .earliest()
+                .ok_or("--days reaches too far back")?;
+            // A day's leeway covers a clock that has moved back
+            let entries = task_list.journal_between(start.with_timezone(&Utc), Utc::now() + Duration::days(1))?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if entries.is_empty() => writeln!(
+                    out,
+                    "No journal entries in the last {} days. Add one with `freelist journal add --mood 4`.",
+                    days
+                )?,
+                Format::Table => {
+                    for entry in &entries {
+                        writeln!(out, "{}", entry_text(entry))?;
+                    }
+                }
+                Format::Json => output::write_json(&entries, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&entries, &mut out)?,
+                Format::Csv => output::write_csv(&ENTRY_FIELDS, &entries, &mut out)?,
+                Format::Markdown => return Err(output::unsupported("journal list", format)),
+            }
+        }
+
+        JournalAction::Add { notes, mood, energy, sleep, slept, at } => {
+            let recorded_at = match at {
+                Some(at) => dates::parse_past_local(&at)?,
+                None => Utc::now(),
+            };
+            let mut entry = JournalEntry::new(recorded_at).with_notes(&notes.join(" "));
+            entry.mood = mood;
+            entry.energy = energy;
+            entry.sleep = sleep;
+            entry.sleep_hours = slept;
+            entry.validate().map_err(|err| err.to_string())?;
+            let id = task_list.add_journal_entry(&entry)?;
+            println!("Added journal entry {}", id);
+        }
+
+        JournalAction::Days { days, format } => {
+            let (first, last) = recent_days(days)?;
+            let journal_days = task_list.journal_days(first, last, &Local)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table => write_days(&journal_days, &mut out)?,
+                Format::Json => output::write_json(&journal_days, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&journal_days, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("journal days", format)),
+            }
+        }
+
+        JournalAction::Correlate { days, format } => {
+            let (first, last) = recent_days(days)?;
+            let correlations = task_list.journal_correlations(first, last, &Local)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table => write_correlations(&correlations, days, &mut out)?,
+                Format::Json => output::write_json(&correlations, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&correlations, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("journal correlate", format)),
+            }
+        }
+
+        JournalAction::Delete { id } => {
+            if !task_list.delete_journal_entry(id)? {
+                return Err(format!("Journal entry {} not found", id).into());
+            }
+            println!("Deleted journal entry {}", id);
+        }
+    }
+    Ok(())
+}
+
+// The last `days` local days, ending today
+fn recent_days(days: u32) -> Result<(NaiveDate, NaiveDate), Box<dyn std::error::Error>> {
+    if days == 0 {
+        return Err("--days must be at least 1".into());
+    }
+    let today = Local::now().date_naive();
+    let first = today.checked_sub_days(Days::new(u64::from(days) - 1)).ok_or("--days reaches too far back")?;
+    Ok((first, today))
+}
+
+fn entry_text(entry: &JournalEntry) -> String {
+    let mut parts = Vec::new();
+    for (name, rating) in [("mood", entry.mood), ("energy", entry.energy), ("sleep", entry.sleep)] {
+        if let Some(rating) = rating {
+            parts.push(format!("{} {}/5", name, rating));
+        }
+    }
+    if let Some(hours) = entry.sleep_hours {
+        parts.push(format!("slept {} h", hours));
+    }
+    if let Some(notes) = &entry.notes {
+        parts.push(notes.clone());
+    }
+    format!(
+        "{:>4}  {}  {}",
+        entry.id.unwrap_or_default(),
+        entry.recorded_at.with_timezone(&Local).format("%a %e %b %H:%M"),
+        parts.join("  ")
+    )
+}
+
+fn average(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| format!("{:.1}", value))
+}
+
+fn write_days(days: &[JournalDay], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "{:<10}  mood  energy  sleep  hours  tasks  meals    kcal", "")?;
+    for day in days {
+        writeln!(
+            out,
+            "{:<10}  {:>4}  {:>6}  {:>5}  {:>5}  {:>5}  {:>5}  {:>6.0}",
+            day.date.format("%a %e %b").to_string(),
+            average(day.mood),
+            average(day.energy),
+            average(day.sleep),
+            average(day.sleep_hours),
+            day.tasks_completed,
+            day.meals_logged,
+            day.energy_kcal
+        )?;
+    }
+    Ok(())
+}
+
+// Rough reading of Pearson's r, for people rather than statisticians
+fn strength(coefficient: f64) -> &'static str {
+    match coefficient.abs() {
+        r if r >= 0.7 => "strong",
+        r if r >= 0.4 => "moderate",
+        r if r >= 0.2 => "weak",
+        _ => "no",
+    }
+}
+
+fn write_correlations(correlations: &[Correlation], days: u32, out: &mut impl Write) -> io::Result<()> {
+    if correlations.iter().all(|correlation| correlation.days == 0) {
+        return writeln!(out, "No journal ratings in the last {} days", days);
+    }
+    for correlation in correlations {
+        let reading = match correlation.coefficient {
+            Some(r) => match strength(r) {
+                "no" => format!("{:+.2}  no link", r),
+                strength if r > 0.0 => format!("{:+.2}  {}, rise together", r, strength),
+                strength => format!("{:+.2}  {}, move opposite ways", r, strength),
+            },
+            None => "  -    not enough to tell".to_string(),
+        };
+        writeln!(
+            out,
+            "{:<12} vs {:<16} {}  ({} days)",
+            correlation.wellbeing.as_str(),
+            correlation.activity.as_str(),
+            reading,
+            correlation.days
+        )?;
+    }
+    Ok(())
+}
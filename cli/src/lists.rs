@@ -0,0 +1,145 @@
+// cli/src/lists.rs
+// `freelist lists`: containers such as Groceries, Work or Home that tasks live in.
+use crate::args::ListsAction;
+use crate::output::{self, Format};
+use freelist_core::{List, TaskList};
+use std::io::{self, Write};
+
+type ListsResult = Result<(), Box<dyn std::error::Error>>;
+
+/// CSV columns, the serde field names of `List`.
+const LIST_FIELDS: [&str; 7] = ["id", "name", "colour", "icon", "sort_order", "archived", "created_at"];
+
+pub fn run(task_list: &mut TaskList, action: ListsAction) -> ListsResult {
+    match action {
+        ListsAction::Show { all, format } => {
+            let lists = task_list.lists(all)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if lists.is_empty() => {
+                    writeln!(out, "No lists yet. Add one with `freelist lists add Groceries`.")?
+                }
+                Format::Table => {
+                    for list in lists {
+                        let id = list.id.ok_or("stored list has no id")?;
+                        let open = task_list.get_by_list(id)?.iter().filter(|task| !task.done).count();
+                        writeln!(out, "{}", list_text(&list, open))?;
+                    }
+                }
+                Format::Json => output::write_json(&lists, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&lists, &mut out)?,
+                Format::Csv => output::write_csv(&LIST_FIELDS, &lists, &mut out)?,
+                Format::Markdown => return Err(output::unsupported("lists", format)),
+            }
+        }
+
+        ListsAction::Add { name, colour, icon } => {
+            let mut list = List::new(&name);
+            if let Some(colour) = colour {
+                list = list.with_colour(&colour);
+            }
+            if let Some(icon) = icon {
+                list = list.with_icon(&icon);
+            }
+            list.validate().map_err(|err| err.to_string())?;
+            let id = task_list.add_list(&list).map_err(|err| err.to_string())?;
+            println!("Added list {} ({})", list.name, id);
+        }
+
+        ListsAction::Edit { list, name, colour, icon, position } => {
+            let mut list = find(task_list, &list)?;
+            if let Some(name) = name {
+                list.name = name.trim().to_string();
+            }
+            match colour.as_deref().map(str::trim) {
+                Some("") => list.colour = None,
+                Some(colour) => list = list.with_colour(colour),
+                None => {}
+            }
+            match icon.as_deref().map(str::trim) {
+                Some("") => list.icon = None,
+                Some(icon) => list = list.with_icon(icon),
+                None => {}
+            }
+            list.sort_order = position.unwrap_or(list.sort_order);
+            list.validate().map_err(|err| err.to_string())?;
+            task_list.update_list(&list).map_err(|err| err.to_string())?;
+            println!("Updated list {}", list.name);
+        }
+
+        ListsAction::Archive { list } => set_archived(task_list, &list, true)?,
+
+        ListsAction::Unarchive { list } => set_archived(task_list, &list, false)?,
+
+        ListsAction::Remove { list } => {
+            let list = find(task_list, &list)?;
+            task_list.delete_list(list.id.ok_or("stored list has no id")?)?;
+            println!("Removed list {}", list.name);
+        }
+
+        ListsAction::Move { task, list } => {
+            if task_list.get_by_id(task)?.is_none() {
+                return Err(format!("Task {} not found", task).into());
+            }
+            match list {
+                Some(list) => {
+                    let list = find(task_list, &list)?;
+                    task_list.set_task_list(task, list.id)?;
+                    println!("Moved task {} to {}", task, list.name);
+                }
+                None => {
+                    task_list.set_task_list(task, None)?;
+                    println!("Took task {} out of its list", task);
+                }
+            }
+        }
+
+        ListsAction::FromTags => {
+            let migration = task_list.lists_from_tags()?;
+            println!(
+                "Added {} lists and moved {} tasks into lists named after their tags",
+                migration.lists_created, migration.tasks_moved
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The list called `name` (ignoring case), or with `name` as its id.
+pub fn find(task_list: &TaskList, name: &str) -> Result<List, Box<dyn std::error::Error>> {
+    if let Some(list) = task_list.find_list(name)? {
+        return Ok(list);
+    }
+    if let Ok(id) = name.trim().parse::<i64>()
+        && let Some(list) = task_list.get_list(id)?
+    {
+        return Ok(list);
+    }
+    Err(format!("No list called {}; see `freelist lists`", name).into())
+}
+
+fn set_archived(task_list: &mut TaskList, name: &str, archived: bool) -> ListsResult {
+    let mut list = find(task_list, name)?;
+    list.archived = archived;
+    task_list.update_list(&list)?;
+    println!("{} list {}", if archived { "Archived" } else { "Unarchived" }, list.name);
+    Ok(())
+}
+
+fn list_text(list: &List, open: usize) -> String {
+    let name = match &list.icon {
+        Some(icon) => format!("{} {}", icon, list.name),
+        None => list.name.clone(),
+    };
+    let mut text = format!(
+        "{:>4}  {:<20}  {:<7}  {} open",
+        list.id.unwrap_or_default(),
+        name,
+        list.colour.as_deref().unwrap_or(""),
+        open
+    );
+    if list.archived {
+        text.push_str("  (archived)");
+    }
+    text
+}
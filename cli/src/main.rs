@@ -1,168 +1,343 @@
 // cli/src/main.rs
-use core::{Task, TaskList};
-use std::env;
+mod args;
+mod caldav;
+mod config;
+mod food;
+mod journal;
+mod lists;
+mod output;
+mod pantry;
+mod tui;
+mod workout;
+
+use args::{
+    ArchiveFormat, Cli, Command, ConfigAction, IcsAction, ImportSource, JournalAction, ListsAction, PantryAction,
+    WorkoutAction,
+};
+use config::{Config, DbSource, ProcessEnv};
+use output::Format;
+use clap::{CommandFactory, Parser};
+use chrono::{Datelike, Duration, Local, NaiveDate, Utc};
+use freelist_core::{dates, ics, importers, Archive, SourceFormat, Task, TaskList};
 use std::io::{self, Write};
+use std::path::Path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 2 {
-        print_help();
+    let cli = Cli::parse();
+
+    if let Command::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "freelist", &mut io::stdout());
         return Ok(());
     }
 
-    // Use a local database file
-    let db_path = "freelist.db";
-    let mut task_list = TaskList::new(db_path)?;
+    let config_path = config::config_path(&ProcessEnv)?;
+    let mut config = Config::load(&config_path)?;
+    let profile = config.active_profile(cli.profile.as_deref(), &ProcessEnv);
+    // Config commands still run when the database cannot be found, so a bad profile can be fixed
+    let resolved = config.resolve_db(cli.db.as_deref(), profile.as_deref(), &ProcessEnv);
 
-    match args[1].as_str() {
-        "add" => {
-            if args.len() < 3 {
-                eprintln!("Usage: {} add <task_title> [tag]", args[0]);
-                return Ok(());
+    if let Command::Config { action } = cli.command {
+        match action.unwrap_or(ConfigAction::Show) {
+            ConfigAction::Show => {
+                println!("Config file: {}", config_path.display());
+                println!("Profile:     {}", profile.as_deref().unwrap_or("(default)"));
+                match &resolved {
+                    Ok((db_path, db_source)) => println!("Database:    {} ({})", db_path.display(), db_source),
+                    Err(err) => println!("Database:    {}", err),
+                }
+                let settings = toml::to_string_pretty(&config)?;
+                if !settings.trim().is_empty() {
+                    println!();
+                    print!("{}", settings);
+                }
+            }
+            ConfigAction::Get { key } => match config.get(&key)? {
+                Some(value) => println!("{}", value),
+                None => return Err(format!("'{}' is not set", key).into()),
+            },
+            ConfigAction::Set { key, value } => {
+                config.set(&key, &value)?;
+                config.save(&config_path)?;
             }
-            
-            let title = &args[2];
-            let mut task = Task::new(title);
-            
-            if args.len() > 3 {
-                task = task.with_tag(&args[3]);
+            ConfigAction::Unset { key } => {
+                config.unset(&key)?;
+                config.save(&config_path)?;
             }
-            
+        }
+        return Ok(());
+    }
+
+    let (db_path, db_source) = resolved?;
+    if db_source == DbSource::Default && let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut task_list = TaskList::new(&db_path)?;
+
+    match cli.command {
+        Command::Add { title, tag, raw, priority, due, details, parent, estimate, list } => {
+            // Flags win over anything parsed from the title
+            let mut task = if raw { Task::new(&title) } else { Task::parse_quick_add(&title)? };
+
+            if let Some(tag) = tag {
+                task = task.with_tag(&tag);
+            }
+            if let Some(priority) = priority {
+                task = task.with_priority(priority);
+            }
+            if let Some(due) = due {
+                task = task.with_due_date(dates::parse_due_date_local(&due)?);
+            }
+            if let Some(details) = details {
+                task = task.with_details(&details);
+            }
+            if let Some(parent) = parent {
+                task = task.with_parent(parent);
+            }
+            if let Some(parent) = task.parent_id {
+                ensure_exists(&task_list, parent)?;
+            }
+            if let Some(minutes) = estimate {
+                task.set_estimated_duration(minutes);
+            }
+            if let Some(list) = list {
+                task.list_id = lists::find(&task_list, &list)?.id;
+            }
+
             let id = task_list.add(task)?;
             println!("Added task with ID: {}", id);
         }
-        
-        "list" => {
-            let filter = args.get(2).map(|s| s.as_str()).unwrap_or("all");
-            
-            let tasks = match filter {
+
+        Command::List { filter, format } => {
+            let tasks = match filter.as_str() {
                 "todo" => task_list.get_todo()?,
                 "done" => task_list.get_completed()?,
+                "overdue" => task_list.get_overdue()?,
                 "all" => task_list.all()?,
                 tag if tag.starts_with('#') => {
                     let tag_name = &tag[1..];
                     task_list.get_by_tag(tag_name)?
                 }
+                list if list.starts_with('@') => {
+                    let list = lists::find(&task_list, &list[1..])?;
+                    task_list.get_by_list(list.id.ok_or("stored list has no id")?)?
+                }
                 _ => {
-                    eprintln!("Invalid filter. Use: all, todo, done, or #tag");
-                    return Ok(());
+                    return Err("Invalid filter. Use: all, todo, done, overdue, #tag or @list".into());
                 }
             };
-            
-            if tasks.is_empty() {
-                println!("No tasks found.");
-            } else {
-                for task in tasks {
-                    let status = if task.done { "✓" } else { "○" };
-                    let tag = task.tag.map(|t| format!(" #{}", t)).unwrap_or_default();
-                    let due = task.due_date
-                        .map(|d| format!(" (due: {})", d.format("%Y-%m-%d")))
-                        .unwrap_or_default();
-                    
-                    println!("{} [{}] {}{}{}", 
-                        status, 
-                        task.id.unwrap_or(0), 
-                        task.title,
-                        tag,
-                        due
-                    );
-                }
-            }
+            output::print_tasks(format, &tasks)?;
         }
-        
-        "done" => {
-            if args.len() < 3 {
-                eprintln!("Usage: {} done <task_id>", args[0]);
-                return Ok(());
-            }
-            
-            let id: i64 = args[2].parse()?;
-            task_list.mark_done(id)?;
+
+        Command::Done { id } => {
+            ensure_exists(&task_list, id)?;
+            let planned = task_list.complete_planned_task(id, &Local::now())?;
             println!("Marked task {} as done", id);
-        }
-        
-        "undone" => {
-            if args.len() < 3 {
-                eprintln!("Usage: {} undone <task_id>", args[0]);
-                return Ok(());
+            if let Some(planned) = planned {
+                workout::write_planned(&planned, &mut io::stdout().lock())?;
             }
-            
-            let id: i64 = args[2].parse()?;
+        }
+
+        Command::Undone { id } => {
+            ensure_exists(&task_list, id)?;
             task_list.mark_undone(id)?;
             println!("Marked task {} as not done", id);
         }
-        
-        "delete" => {
-            if args.len() < 3 {
-                eprintln!("Usage: {} delete <task_id>", args[0]);
-                return Ok(());
-            }
-            
-            let id: i64 = args[2].parse()?;
+
+        Command::Delete { id } => {
+            ensure_exists(&task_list, id)?;
             task_list.delete(id)?;
             println!("Deleted task {}", id);
         }
-        
-        "tags" => {
-            let tags = task_list.get_all_tags()?;
-            if tags.is_empty() {
-                println!("No tags found.");
-            } else {
-                println!("Available tags:");
-                for tag in tags {
-                    println!("  #{}", tag);
+
+        Command::Move { id, below, above } => {
+            ensure_exists(&task_list, id)?;
+            task_list.move_task(id, below, above).map_err(|err| err.to_string())?;
+            println!("Moved task {}", id);
+        }
+
+        Command::Tags { format } => output::print_tags(format, &task_list.get_all_tags()?)?,
+
+        Command::Export { path, format } => {
+            let archive = task_list.export_archive()?;
+            match (format, path) {
+                (ArchiveFormat::Json, Some(path)) => {
+                    std::fs::write(&path, archive.to_json()?)?;
+                    eprintln!("Exported {} tasks to {}", archive.tasks.len(), path.display());
+                }
+                (ArchiveFormat::Json, None) => println!("{}", archive.to_json()?),
+                (ArchiveFormat::Csv, Some(dir)) => {
+                    archive.write_csv(&dir)?;
+                    eprintln!("Exported {} tasks to {}", archive.tasks.len(), dir.display());
                 }
+                (ArchiveFormat::Csv, None) => return Err("CSV export needs a directory to write to".into()),
             }
         }
-        
-        "clear" => {
-            print!("Are you sure you want to delete all tasks? (y/N): ");
-            io::stdout().flush()?;
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            
-            if input.trim().to_lowercase() == "y" {
+
+        Command::Import { path, from, tag, dry_run } => import(&mut task_list, &path, from, tag, dry_run)?,
+
+        Command::Ics { action: IcsAction::Export { path, due_only } } => {
+            let mut tasks = task_list.all()?;
+            if due_only {
+                tasks.retain(|task| task.due_date.is_some());
+            }
+            let calendar = ics::to_ics(&tasks, Utc::now());
+            match path {
+                Some(path) => {
+                    std::fs::write(&path, calendar)?;
+                    eprintln!("Exported {} tasks to {}", tasks.len(), path.display());
+                }
+                None => print!("{}", calendar),
+            }
+        }
+
+        Command::Ics { action: IcsAction::Import { path, tag, dry_run } } => {
+            import(&mut task_list, &path, ImportSource::Ics, tag, dry_run)?
+        }
+
+        Command::Caldav { bind } => caldav::serve(task_list, &bind)?,
+
+        Command::Food { action } => food::run(&mut task_list, action)?,
+
+        Command::Pantry { action, format } => {
+            pantry::run(&mut task_list, action.unwrap_or(PantryAction::List { format }))?
+        }
+
+        Command::Recipe { action } => pantry::run_recipe(&mut task_list, action)?,
+
+        Command::Workout { action, format } => {
+            workout::run(&mut task_list, action.unwrap_or(WorkoutAction::Show { id: None, format }))?
+        }
+
+        Command::Lists { action, format } => {
+            lists::run(&mut task_list, action.unwrap_or(ListsAction::Show { all: false, format }))?
+        }
+
+        Command::Journal { action, format } => {
+            journal::run(&mut task_list, action.unwrap_or(JournalAction::List { days: 7, format }))?
+        }
+
+        Command::Report { day, week, from, to, format } => {
+            let (first, last) = review_days(day, week, from.as_deref(), to.as_deref())?;
+            let review = task_list.review(first, last, &Local)?;
+            match format {
+                Format::Table => print!("{}", review.to_text()),
+                Format::Markdown => print!("{}", review.to_markdown()),
+                Format::Json => output::write_json(&review, &mut io::stdout().lock())?,
+                Format::Ndjson => output::write_ndjson(std::slice::from_ref(&review), &mut io::stdout().lock())?,
+                Format::Csv => return Err(output::unsupported("report", format)),
+            }
+        }
+
+        Command::Tui => tui::run(task_list)?,
+
+        Command::Clear { yes } => {
+            let confirmed = yes || {
+                print!("Are you sure you want to delete all tasks? (y/N): ");
+                io::stdout().flush()?;
+
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                input.trim().to_lowercase() == "y"
+            };
+
+            if confirmed {
                 task_list.clear_all()?;
                 println!("All tasks deleted.");
             } else {
                 println!("Cancelled.");
             }
         }
-        
-        "help" | "--help" | "-h" => {
-            print_help();
-        }
-        
-        _ => {
-            eprintln!("Unknown command: {}", args[1]);
-            print_help();
+
+        Command::Completions { .. } | Command::Config { .. } => {
+            unreachable!("handled before opening the database")
         }
     }
 
     Ok(())
 }
 
-fn print_help() {
-    println!("FreeList CLI - Task Management");
-    println!();
-    println!("USAGE:");
-    println!("    freelist <COMMAND> [OPTIONS]");
-    println!();
-    println!("COMMANDS:");
-    println!("    add <title> [tag]    Add a new task");
-    println!("    list [filter]        List tasks (all, todo, done, #tag)");
-    println!("    done <id>            Mark task as done");
-    println!("    undone <id>          Mark task as not done");
-    println!("    delete <id>          Delete a task");
-    println!("    tags                 List all tags");
-    println!("    clear                Delete all tasks");
-    println!("    help                 Show this help message");
-    println!();
-    println!("EXAMPLES:");
-    println!("    freelist add \"Buy groceries\" shopping");
-    println!("    freelist list todo");
-    println!("    freelist list #shopping");
-    println!("    freelist done 1");
+// The local days `freelist report` covers: today, a given range, or the last 7 days
+fn review_days(
+    day: bool,
+    week: bool,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<(NaiveDate, NaiveDate), Box<dyn std::error::Error>> {
+    let local_day = |text: &str| -> Result<NaiveDate, Box<dyn std::error::Error>> {
+        Ok(dates::parse_past_local(text)?.with_timezone(&Local).date_naive())
+    };
+    let today = Local::now().date_naive();
+    let last = match to {
+        Some(to) => local_day(to)?,
+        None => today,
+    };
+    let first = match from {
+        Some(from) => local_day(from)?,
+        None if day => today,
+        None if week => today - Duration::days(today.weekday().num_days_from_monday().into()),
+        None => today - Duration::days(6),
+    };
+    if last < first {
+        return Err(format!("--to {} is before --from {}", last, first).into());
+    }
+    Ok((first, last))
+}
+
+fn ensure_exists(task_list: &TaskList, id: i64) -> Result<(), Box<dyn std::error::Error>> {
+    match task_list.get_by_id(id)? {
+        Some(_) => Ok(()),
+        None => Err(format!("Task {} not found", id).into()),
+    }
+}
+
+fn import(
+    task_list: &mut TaskList,
+    path: &Path,
+    from: ImportSource,
+    tag: Option<String>,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let archive = match from.converter() {
+        None if path.is_dir() => Archive::read_csv(path)?,
+        None => Archive::from_json(&read_input(path)?)?,
+        Some(format) => {
+            // A Todoist backup is one project, named only by its file
+            let tag = tag.or_else(|| match format {
+                SourceFormat::Todoist if path.as_os_str() != "-" => {
+                    path.file_stem().map(|stem| stem.to_string_lossy().into_owned())
+                }
+                _ => None,
+            });
+            let conversion = importers::convert(format, &read_input(path)?, tag.as_deref(), &Local::now())?;
+            for warning in &conversion.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            conversion.archive
+        }
+    };
+
+    if dry_run {
+        output::write_task_tree(&archive.tasks, &mut io::stdout().lock())?;
+        let summary = task_list.preview_import(&archive)?;
+        println!(
+            "Would import {} tasks ({} already present), {} history entries",
+            summary.tasks_added, summary.tasks_skipped, summary.history_added
+        );
+    } else {
+        let summary = task_list.import_archive(&archive)?;
+        println!(
+            "Imported {} tasks ({} already present), {} history entries",
+            summary.tasks_added, summary.tasks_skipped, summary.history_added
+        );
+    }
+    Ok(())
+}
+
+/// Reads a whole file, or stdin for "-".
+fn read_input(path: &Path) -> io::Result<String> {
+    if path.as_os_str() == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    }
 }
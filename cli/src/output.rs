@@ -0,0 +1,226 @@
+// cli/src/output.rs
+use chrono::Local;
+use clap::ValueEnum;
+use freelist_core::Task;
+use std::io::{self, Write};
+
+type OutputResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Output of read commands.
+///
+/// The machine-readable formats use the serde field names of `Task`, listed in
+/// `TASK_FIELDS`. Dates are RFC 3339 in UTC, durations are minutes, `priority`
+/// is low/medium/high/urgent and missing values are null (empty in CSV).
+/// Other read commands print the serde form of what they show; CSV is only
+/// offered where that is one flat record per line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human-readable list (default)
+    Table,
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+    /// A Markdown checklist, or a document for `report`
+    Markdown,
+}
+
+/// Field names (and CSV column order) for tasks in every machine-readable format.
+pub const TASK_FIELDS: [&str; 15] = [
+    "id",
+    "title",
+    "details",
+    "done",
+    "due_date",
+    "is_recurring",
+    "estimated_duration",
+    "last_duration",
+    "tag",
+    "priority",
+    "parent_id",
+    "created_at",
+    "updated_at",
+    "list_id",
+    "position",
+];
+
+pub fn print_tasks(format: Format, tasks: &[Task]) -> OutputResult {
+    write_tasks(format, tasks, &mut io::stdout().lock())
+}
+
+pub fn print_tags(format: Format, tags: &[String]) -> OutputResult {
+    write_tags(format, tags, &mut io::stdout().lock())
+}
+
+pub fn write_tasks(format: Format, tasks: &[Task], out: &mut impl Write) -> OutputResult {
+    match format {
+        Format::Table if tasks.is_empty() => writeln!(out, "No tasks found.")?,
+        Format::Table => {
+            for task in tasks {
+                writeln!(out, "{} [{}] {}", status(task), task.id.unwrap_or(0), describe(task))?;
+            }
+        }
+        Format::Json => write_json(tasks, out)?,
+        Format::Ndjson => write_ndjson(tasks, out)?,
+        Format::Csv => write_csv(&TASK_FIELDS, tasks, out)?,
+        Format::Markdown => {
+            for task in tasks {
+                let check = if task.done { "x" } else { " " };
+                writeln!(out, "- [{}] {}", check, describe(task))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Tasks indented under their parents, without ids. Used to preview tasks that
+/// are not in the database yet, so `parent_id` refers to ids within `tasks`.
+pub fn write_task_tree(tasks: &[Task], out: &mut impl Write) -> OutputResult {
+    fn write_level(tasks: &[Task], parent: Option<i64>, depth: usize, out: &mut impl Write) -> OutputResult {
+        for task in tasks.iter().filter(|task| task.parent_id == parent) {
+            writeln!(out, "{}{} {}", "  ".repeat(depth), status(task), describe(task))?;
+            if task.id.is_some() {
+                write_level(tasks, task.id, depth + 1, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Tasks whose parent is missing are shown at the top level
+    let ids: Vec<Option<i64>> = tasks.iter().map(|task| task.id).collect();
+    let roots: Vec<Task> = tasks
+        .iter()
+        .map(|task| match task.parent_id {
+            Some(_) if !ids.contains(&task.parent_id) => Task { parent_id: None, ..task.clone() },
+            _ => task.clone(),
+        })
+        .collect();
+    write_level(&roots, None, 0, out)
+}
+
+fn status(task: &Task) -> &'static str {
+    if task.done { "✓" } else { "○" }
+}
+
+/// Title, tag, priority and due date as shown in the table format.
+fn describe(task: &Task) -> String {
+    let tag = task.tag.as_ref().map(|t| format!(" #{}", t)).unwrap_or_default();
+    let priority = task.priority.map(|p| format!(" !{}", p)).unwrap_or_default();
+    let due = task.due_date
+        .map(|d| format!(" (due: {})", d.with_timezone(&Local).format("%Y-%m-%d")))
+        .unwrap_or_default();
+    format!("{}{}{}{}", task.title, tag, priority, due)
+}
+
+pub fn write_tags(format: Format, tags: &[String], out: &mut impl Write) -> OutputResult {
+    match format {
+        Format::Table if tags.is_empty() => writeln!(out, "No tags found.")?,
+        Format::Table => {
+            writeln!(out, "Available tags:")?;
+            for tag in tags {
+                writeln!(out, "  #{}", tag)?;
+            }
+        }
+        Format::Json => write_json(tags, out)?,
+        Format::Ndjson => write_ndjson(tags, out)?,
+        Format::Csv => write_csv(&["tag"], tags, out)?,
+        Format::Markdown => {
+            for tag in tags {
+                writeln!(out, "- #{}", tag)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Pretty JSON of a list, or of one result such as a day's nutrition.
+pub fn write_json<T: serde::Serialize + ?Sized>(value: &T, out: &mut impl Write) -> OutputResult {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+pub fn write_ndjson<T: serde::Serialize>(items: &[T], out: &mut impl Write) -> OutputResult {
+    for item in items {
+        serde_json::to_writer(&mut *out, item)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// CSV of flat records with a header row, which is written even when there are none.
+pub fn write_csv<T: serde::Serialize>(header: &[&str], records: &[T], out: &mut impl Write) -> OutputResult {
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(out);
+    writer.write_record(header)?;
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The error for a format a command has no layout for, such as CSV of nested totals.
+pub fn unsupported(command: &str, format: Format) -> Box<dyn std::error::Error> {
+    let name = format.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default();
+    format!("`freelist {}` cannot print --format {}", command, name).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use freelist_core::Priority;
+
+    fn sample() -> Task {
+        let mut task = Task::new("Buy milk, oat").with_tag("groceries").with_priority(Priority::High);
+        task.id = Some(3);
+        task
+    }
+
+    #[test]
+    fn test_task_fields_match_serde_names() {
+        let value = serde_json::to_value(sample()).unwrap();
+        let mut keys: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        let mut expected = TASK_FIELDS.to_vec();
+        keys.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn test_csv_round_trips() {
+        let task = sample();
+        let mut out = Vec::new();
+        write_tasks(Format::Csv, std::slice::from_ref(&task), &mut out).unwrap();
+
+        let mut reader = csv::Reader::from_reader(out.as_slice());
+        assert_eq!(reader.headers().unwrap(), TASK_FIELDS.as_slice());
+        let parsed: Vec<Task> = reader.deserialize().collect::<Result<_, _>>().unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].title, "Buy milk, oat");
+        assert_eq!(parsed[0].priority, Some(Priority::High));
+        assert_eq!(parsed[0].due_date, None);
+        assert_eq!(parsed[0].created_at, task.created_at);
+    }
+
+    #[test]
+    fn test_empty_results() {
+        let mut out = Vec::new();
+        write_tasks(Format::Csv, &[], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", TASK_FIELDS.join(",")));
+
+        let mut out = Vec::new();
+        write_tags(Format::Json, &[], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+    }
+
+    #[test]
+    fn test_markdown_checklist() {
+        let mut done = Task::new("Call bank");
+        done.done = true;
+        let mut out = Vec::new();
+        write_tasks(Format::Markdown, &[sample(), done], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "- [ ] Buy milk, oat #groceries !high\n- [x] Call bank\n");
+    }
+}
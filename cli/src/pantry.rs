@@ -0,0 +1,149 @@
+// cli/src/pantry.rs
+// `freelist pantry` and `freelist recipe`: what is in the kitchen and what cooking uses up.
+use crate::args::{PantryAction, RecipeAction};
+use crate::food::resolve_food;
+use crate::output::{self, Format};
+use freelist_core::nutrition::UnmatchedReason;
+use freelist_core::{PantryItem, PantryReport, Recipe, TaskList};
+use std::io::{self, Write};
+
+type PantryResult = Result<(), Box<dyn std::error::Error>>;
+
+/// CSV columns, the serde field names of `PantryItem`.
+const PANTRY_FIELDS: [&str; 6] = ["id", "name", "food_id", "quantity", "unit", "updated_at"];
+
+pub fn run(task_list: &mut TaskList, action: PantryAction) -> PantryResult {
+    match action {
+        PantryAction::List { format } => {
+            let items = task_list.pantry()?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if items.is_empty() => {
+                    writeln!(out, "The pantry is empty. Add something with `freelist pantry add <name> <amount>`.")?
+                }
+                Format::Table => {
+                    for item in &items {
+                        let amount =
+                            if item.amount.quantity > 0.0 { item.amount.to_string() } else { "out".to_string() };
+                        writeln!(out, "{:>4}  {:<32} {}", item.id.unwrap_or_default(), item.name, amount)?;
+                    }
+                }
+                Format::Json => output::write_json(&items, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&items, &mut out)?,
+                Format::Csv => {
+                    // The amount is flattened into quantity and unit, as in JSON
+                    let rows: Vec<_> = items
+                        .iter()
+                        .map(|item| {
+                            (item.id, &item.name, item.food_id, item.amount.quantity, item.amount.unit, item.updated_at)
+                        })
+                        .collect();
+                    output::write_csv(&PANTRY_FIELDS, &rows, &mut out)?;
+                }
+                Format::Markdown => return Err(output::unsupported("pantry list", format)),
+            }
+        }
+
+        PantryAction::Add { name, amount, food } => {
+            let mut item = PantryItem::new(&name, amount);
+            if let Some(food) = food {
+                item.food_id = resolve_food(task_list, &food)?.id;
+            }
+            let id = task_list.add_pantry_item(&item)?;
+            println!("Added {} of {} to the pantry with ID: {}", amount, item.name, id);
+        }
+
+        PantryAction::Set { id, amount } => {
+            if !task_list.set_pantry_amount(id, amount)? {
+                return Err(format!("Pantry item {} not found", id).into());
+            }
+            println!("Pantry item {} now has {}", id, amount);
+        }
+
+        PantryAction::Remove { id } => {
+            if !task_list.delete_pantry_item(id)? {
+                return Err(format!("Pantry item {} not found", id).into());
+            }
+            println!("Removed pantry item {}", id);
+        }
+    }
+    Ok(())
+}
+
+pub fn run_recipe(task_list: &mut TaskList, action: RecipeAction) -> PantryResult {
+    match action {
+        RecipeAction::Add { name, ingredients, serves } => {
+            if !serves.is_finite() || serves <= 0.0 {
+                return Err("--serves must be more than 0".into());
+            }
+            let id = task_list.add_recipe(&Recipe::new(&name, serves, ingredients))?;
+            println!("Added recipe with ID: {}", id);
+        }
+
+        RecipeAction::List { format } => {
+            let recipes = task_list.recipes()?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if recipes.is_empty() => {
+                    writeln!(out, "No recipes yet. Add one with `freelist recipe add <name> <ingredients>...`.")?
+                }
+                Format::Table => {
+                    for recipe in &recipes {
+                        let id = recipe.id.unwrap_or_default();
+                        writeln!(out, "{:>4}  {} (serves {})", id, recipe.name, recipe.servings)?;
+                        for ingredient in &recipe.ingredients {
+                            writeln!(out, "        {} {}", ingredient.amount, ingredient.name)?;
+                        }
+                    }
+                }
+                Format::Json => output::write_json(&recipes, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&recipes, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("recipe list", format)),
+            }
+        }
+
+        RecipeAction::Cook { recipe, batches } => {
+            let recipe = resolve_recipe(task_list, &recipe)?;
+            let report = task_list.cook_recipe(recipe.id.unwrap_or_default(), batches)?;
+            println!("Cooked {}", recipe.name);
+            write_report(&report, &mut io::stdout().lock())?;
+        }
+
+        RecipeAction::Remove { recipe } => {
+            let recipe = resolve_recipe(task_list, &recipe)?;
+            task_list.delete_recipe(recipe.id.unwrap_or_default())?;
+            println!("Deleted recipe {}", recipe.name);
+        }
+    }
+    Ok(())
+}
+
+fn resolve_recipe(task_list: &TaskList, query: &str) -> Result<Recipe, Box<dyn std::error::Error>> {
+    if let Ok(id) = query.parse::<i64>() {
+        return task_list.get_recipe(id)?.ok_or_else(|| format!("Recipe {} not found", id).into());
+    }
+    task_list.find_recipe(query)?.ok_or_else(|| format!("No recipe is called '{}'", query).into())
+}
+
+/// What was taken from the pantry, then anything that was not there.
+pub fn write_report(report: &PantryReport, out: &mut impl Write) -> io::Result<()> {
+    if !report.used.is_empty() {
+        writeln!(out, "Used from the pantry:")?;
+        for used in &report.used {
+            writeln!(out, "  {:<32} {:<10} {} left", used.name, used.taken.to_string(), used.remaining)?;
+        }
+    }
+    if !report.unmatched.is_empty() {
+        writeln!(out, "Not taken from the pantry:")?;
+        for unmatched in &report.unmatched {
+            let reason = match unmatched.reason {
+                UnmatchedReason::NotInPantry => "not in the pantry",
+                UnmatchedReason::NotEnough => "ran out",
+                UnmatchedReason::IncompatibleUnits => "kept in units that do not convert",
+            };
+            writeln!(out, "  {:<32} {:<10} {}", unmatched.name, unmatched.missing.to_string(), reason)?;
+        }
+    }
+    Ok(())
+}
+
@@ -0,0 +1,554 @@
+// cli/src/tui/app.rs
+use chrono::{DateTime, Utc};
+use freelist_core::{ChangeSet, Task, TaskList};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+type AppResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Tags,
+    Tasks,
+    Completed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    Search,
+    Add,
+    Edit(i64),
+    ConfirmDelete(i64),
+}
+
+/// A todo task and how deeply it is nested under its parents.
+pub struct Row {
+    pub task: Task,
+    pub depth: usize,
+}
+
+pub struct App {
+    task_list: TaskList,
+    // Set by the change observer, including for writes from other processes
+    dirty: Arc<AtomicBool>,
+    /// Sidebar entries; index 0 is "All"
+    pub tags: Vec<String>,
+    pub todo: Vec<Row>,
+    pub completed: Vec<Task>,
+    pub focus: Focus,
+    pub mode: Mode,
+    pub tag_index: usize,
+    pub task_index: usize,
+    pub completed_index: usize,
+    pub search: String,
+    /// Text being typed in add and edit mode
+    pub input: String,
+    /// Recently completed task ids, most recent last
+    pub undo: Vec<i64>,
+    /// Start time of each running timer by task id
+    pub timers: HashMap<i64, DateTime<Utc>>,
+    pub status: Option<String>,
+    pub now: DateTime<Utc>,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(mut task_list: TaskList, now: DateTime<Utc>) -> AppResult<Self> {
+        let dirty = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&dirty);
+        task_list.subscribe(move |_: &ChangeSet| flag.store(true, Ordering::Relaxed));
+
+        let mut app = App {
+            task_list,
+            dirty,
+            tags: Vec::new(),
+            todo: Vec::new(),
+            completed: Vec::new(),
+            focus: Focus::Tasks,
+            mode: Mode::Normal,
+            tag_index: 0,
+            task_index: 0,
+            completed_index: 0,
+            search: String::new(),
+            input: String::new(),
+            undo: Vec::new(),
+            timers: HashMap::new(),
+            status: None,
+            now,
+            quit: false,
+        };
+        app.refresh()?;
+        Ok(app)
+    }
+
+    /// Advances the clock and reloads if the database changed, e.g. from `freelist add` in another shell.
+    pub fn tick(&mut self, now: DateTime<Utc>) -> AppResult<()> {
+        self.now = now;
+        self.task_list.poll_changes()?;
+        if self.dirty.swap(false, Ordering::Relaxed) {
+            self.refresh()?;
+        }
+        Ok(())
+    }
+
+    /// Reloads tags and tasks, applying the selected tag and the search text.
+    pub fn refresh(&mut self) -> AppResult<()> {
+        self.dirty.store(false, Ordering::Relaxed);
+        let selected_tag = self.selected_tag().map(str::to_string);
+        self.tags = self.task_list.get_all_tags()?;
+        self.tag_index = selected_tag
+            .and_then(|tag| self.tags.iter().position(|t| *t == tag))
+            .map_or(0, |index| index + 1);
+
+        // In the list's own order (see `TaskList::all`), so manual moves show up here
+        let tasks: Vec<Task> = self
+            .task_list
+            .all()?
+            .into_iter()
+            .filter(|task| self.matches(task))
+            .collect();
+        let (completed, todo): (Vec<Task>, Vec<Task>) = tasks.into_iter().partition(|task| task.done);
+
+        self.todo = nest(todo);
+        self.completed = completed;
+        self.task_index = self.task_index.min(self.todo.len().saturating_sub(1));
+        self.completed_index = self.completed_index.min(self.completed.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn selected_tag(&self) -> Option<&str> {
+        self.tag_index.checked_sub(1).and_then(|index| self.tags.get(index)).map(String::as_str)
+    }
+
+    fn matches(&self, task: &Task) -> bool {
+        let tag_matches = match self.selected_tag() {
+            Some(tag) => task.tag.as_deref() == Some(tag),
+            None => true,
+        };
+        let query = self.search.to_lowercase();
+        tag_matches
+            && (query.is_empty()
+                || task.title.to_lowercase().contains(&query)
+                || task.tag.as_ref().is_some_and(|tag| tag.to_lowercase().contains(&query)))
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        match self.focus {
+            Focus::Tasks => self.todo.get(self.task_index).map(|row| &row.task),
+            Focus::Completed => self.completed.get(self.completed_index),
+            Focus::Tags => None,
+        }
+    }
+
+    fn selected_id(&self) -> Option<i64> {
+        self.selected_task().and_then(|task| task.id)
+    }
+
+    /// Handles a key press; failures are shown in the status line instead of ending the session.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let result = match self.mode.clone() {
+            Mode::Normal => self.handle_normal_key(key),
+            Mode::Search => self.handle_search_key(key),
+            Mode::Add | Mode::Edit(_) => self.handle_input_key(key),
+            Mode::ConfirmDelete(id) => self.handle_confirm_key(key, id),
+        };
+        if let Err(err) = result {
+            self.status = Some(format!("Error: {}", err));
+        }
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent) -> AppResult<()> {
+        self.status = None;
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return self.quit();
+        }
+        match key.code {
+            KeyCode::Char('q') => self.quit()?,
+            KeyCode::Esc if !self.search.is_empty() => {
+                self.search.clear();
+                self.refresh()?;
+            }
+            KeyCode::Esc => self.quit()?,
+            KeyCode::Tab => self.cycle_focus(true),
+            KeyCode::BackTab => self.cycle_focus(false),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1)?,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1)?,
+            KeyCode::Char(' ') | KeyCode::Char('x') => self.toggle_done()?,
+            KeyCode::Char('u') => self.undo_completion()?,
+            KeyCode::Char('t') => self.toggle_timer()?,
+            KeyCode::Char('/') => self.mode = Mode::Search,
+            KeyCode::Char('a') => {
+                self.input.clear();
+                self.mode = Mode::Add;
+            }
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some((id, title)) = self.selected_task().and_then(|t| Some((t.id?, t.title.clone()))) {
+                    self.input = title;
+                    self.mode = Mode::Edit(id);
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(id) = self.selected_id() {
+                    self.mode = Mode::ConfirmDelete(id);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) -> AppResult<()> {
+        match key.code {
+            KeyCode::Enter => self.mode = Mode::Normal,
+            KeyCode::Esc => {
+                self.search.clear();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) => self.search.push(c),
+            _ => return Ok(()),
+        }
+        self.task_index = 0;
+        self.completed_index = 0;
+        self.refresh()
+    }
+
+    fn handle_input_key(&mut self, key: KeyEvent) -> AppResult<()> {
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter => {
+                let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+                let input = std::mem::take(&mut self.input);
+                match mode {
+                    Mode::Add => self.add(&input)?,
+                    Mode::Edit(id) => self.rename(id, &input)?,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_confirm_key(&mut self, key: KeyEvent, id: i64) -> AppResult<()> {
+        self.mode = Mode::Normal;
+        if let KeyCode::Char('y') | KeyCode::Char('Y') = key.code {
+            self.timers.remove(&id);
+            self.undo.retain(|undo_id| *undo_id != id);
+            self.task_list.delete(id)?;
+            self.status = Some(format!("Deleted task {}", id));
+            self.refresh()?;
+        }
+        Ok(())
+    }
+
+    fn quit(&mut self) -> AppResult<()> {
+        // Keep the time tracked so far rather than dropping running timers
+        let running: Vec<i64> = self.timers.keys().copied().collect();
+        for id in running {
+            self.stop_timer(id)?;
+        }
+        self.quit = true;
+        Ok(())
+    }
+
+    fn cycle_focus(&mut self, forward: bool) {
+        self.focus = match (self.focus, forward) {
+            (Focus::Tags, true) | (Focus::Completed, false) => Focus::Tasks,
+            (Focus::Tasks, true) | (Focus::Tags, false) => Focus::Completed,
+            (Focus::Completed, true) | (Focus::Tasks, false) => Focus::Tags,
+        };
+    }
+
+    fn move_selection(&mut self, delta: isize) -> AppResult<()> {
+        let step = |index: usize, len: usize| index.saturating_add_signed(delta).min(len.saturating_sub(1));
+        match self.focus {
+            Focus::Tags => {
+                self.tag_index = step(self.tag_index, self.tags.len() + 1);
+                self.task_index = 0;
+                self.completed_index = 0;
+                self.refresh()?;
+            }
+            Focus::Tasks => self.task_index = step(self.task_index, self.todo.len()),
+            Focus::Completed => self.completed_index = step(self.completed_index, self.completed.len()),
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, input: &str) -> AppResult<()> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+        let mut task = Task::parse_quick_add(input)?;
+        if task.tag.is_none()
+            && let Some(tag) = self.selected_tag()
+        {
+            task = task.with_tag(tag);
+        }
+        if let Some(parent_id) = task.parent_id
+            && self.task_list.get_by_id(parent_id)?.is_none()
+        {
+            return Err(format!("Task {} not found", parent_id).into());
+        }
+        let id = self.task_list.add(task)?;
+        self.status = Some(format!("Added task {}", id));
+        self.refresh()?;
+        self.focus = Focus::Tasks;
+        if let Some(index) = self.todo.iter().position(|row| row.task.id == Some(id)) {
+            self.task_index = index;
+        }
+        Ok(())
+    }
+
+    fn rename(&mut self, id: i64, title: &str) -> AppResult<()> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err("title cannot be empty".into());
+        }
+        if let Some(mut task) = self.task_list.get_by_id(id)? {
+            task.title = title.to_string();
+            self.task_list.update(&task)?;
+            self.refresh()?;
+        }
+        Ok(())
+    }
+
+    fn toggle_done(&mut self) -> AppResult<()> {
+        let Some(id) = self.selected_id() else { return Ok(()) };
+        match self.focus {
+            Focus::Tasks => {
+                if self.timers.contains_key(&id) {
+                    self.stop_timer(id)?;
+                }
+                self.task_list.mark_done(id)?;
+                self.undo.push(id);
+                self.status = Some(format!("Completed task {} (u to undo)", id));
+            }
+            Focus::Completed => {
+                self.task_list.mark_undone(id)?;
+                self.undo.retain(|undo_id| *undo_id != id);
+            }
+            Focus::Tags => {}
+        }
+        self.refresh()
+    }
+
+    fn undo_completion(&mut self) -> AppResult<()> {
+        match self.undo.pop() {
+            Some(id) => {
+                self.task_list.mark_undone(id)?;
+                self.status = Some(format!("Restored task {}", id));
+                self.refresh()
+            }
+            None => {
+                self.status = Some("Nothing to undo".to_string());
+                Ok(())
+            }
+        }
+    }
+
+    fn toggle_timer(&mut self) -> AppResult<()> {
+        if self.focus != Focus::Tasks {
+            return Ok(());
+        }
+        let Some(id) = self.selected_id() else { return Ok(()) };
+        if self.timers.contains_key(&id) {
+            self.stop_timer(id)
+        } else {
+            self.timers.insert(id, self.now);
+            self.status = Some(format!("Timer started for task {}", id));
+            Ok(())
+        }
+    }
+
+    /// Stops a timer and records its length, rounded up to whole minutes, as the task's last duration.
+    fn stop_timer(&mut self, id: i64) -> AppResult<()> {
+        let Some(started) = self.timers.remove(&id) else { return Ok(()) };
+        let seconds = (self.now - started).num_seconds().max(1);
+        let minutes = i32::try_from((seconds + 59) / 60).unwrap_or(i32::MAX);
+        if let Some(mut task) = self.task_list.get_by_id(id)? {
+            task.record_duration(minutes);
+            self.task_list.update(&task)?;
+        }
+        self.status = Some(format!("Recorded {} min on task {}", minutes, id));
+        self.refresh()
+    }
+
+    /// Elapsed time of a running timer as "m:ss" or "h:mm:ss".
+    pub fn timer_label(&self, id: i64) -> Option<String> {
+        let started = self.timers.get(&id)?;
+        let seconds = (self.now - *started).num_seconds().max(0);
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        Some(if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        })
+    }
+
+    #[cfg(test)]
+    pub fn task_list(&self) -> &TaskList {
+        &self.task_list
+    }
+}
+
+/// Orders tasks so each subtask follows its parent; orphans whose parent is filtered out stay top level.
+fn nest(tasks: Vec<Task>) -> Vec<Row> {
+    let ids: Vec<Option<i64>> = tasks.iter().map(|task| task.id).collect();
+    let mut children: HashMap<Option<i64>, Vec<Task>> = HashMap::new();
+    for task in tasks {
+        let parent = task.parent_id.filter(|parent| ids.contains(&Some(*parent)));
+        children.entry(parent).or_default().push(task);
+    }
+
+    fn visit(parent: Option<i64>, depth: usize, children: &mut HashMap<Option<i64>, Vec<Task>>, rows: &mut Vec<Row>) {
+        for task in children.remove(&parent).unwrap_or_default() {
+            let id = task.id;
+            rows.push(Row { task, depth });
+            visit(id, depth + 1, children, rows);
+        }
+    }
+
+    let mut rows = Vec::new();
+    visit(None, 0, &mut children, &mut rows);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::KeyEvent;
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    fn key(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::from(code));
+    }
+
+    fn titles(app: &App) -> Vec<String> {
+        app.todo.iter().map(|row| row.task.title.clone()).collect()
+    }
+
+    // New tasks go to the top, so these list as Plan trip, Book flights, Buy milk
+    fn app() -> App {
+        let mut task_list = TaskList::new_in_memory().unwrap();
+        task_list.add(Task::new("Buy milk").with_tag("groceries")).unwrap();
+        let trip = task_list.add(Task::new("Plan trip").with_tag("travel")).unwrap();
+        task_list.add(Task::new("Book flights").with_tag("travel").with_parent(trip)).unwrap();
+        App::new(task_list, Utc::now()).unwrap()
+    }
+
+    #[test]
+    fn test_subtasks_follow_their_parent() {
+        let app = app();
+        let depths: Vec<(String, usize)> =
+            app.todo.iter().map(|row| (row.task.title.clone(), row.depth)).collect();
+        assert_eq!(
+            depths,
+            vec![
+                ("Plan trip".to_string(), 0),
+                ("Book flights".to_string(), 1),
+                ("Buy milk".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manual_order_is_kept() {
+        let mut app = app();
+        app.task_list.move_task(1, None, Some(2)).unwrap();
+        app.refresh().unwrap();
+        assert_eq!(titles(&app), vec!["Buy milk", "Plan trip", "Book flights"]);
+    }
+
+    #[test]
+    fn test_complete_and_undo() {
+        let mut app = app();
+        press(&mut app, "jx");
+        assert_eq!(titles(&app), vec!["Plan trip", "Buy milk"]);
+        assert_eq!(app.completed[0].title, "Book flights");
+
+        press(&mut app, "u");
+        assert_eq!(titles(&app), vec!["Plan trip", "Book flights", "Buy milk"]);
+        assert!(app.completed.is_empty());
+
+        // Space in the completed pane also restores
+        press(&mut app, "x");
+        key(&mut app, KeyCode::Tab);
+        press(&mut app, " ");
+        assert!(app.completed.is_empty());
+    }
+
+    #[test]
+    fn test_tag_sidebar_and_search_filter() {
+        let mut app = app();
+        key(&mut app, KeyCode::BackTab);
+        assert_eq!(app.focus, Focus::Tags);
+        press(&mut app, "j");
+        assert_eq!(app.selected_tag(), Some("groceries"));
+        assert_eq!(titles(&app), vec!["Buy milk"]);
+
+        press(&mut app, "k/flig");
+        assert_eq!(titles(&app), vec!["Book flights"]);
+        key(&mut app, KeyCode::Esc);
+        assert_eq!(titles(&app).len(), 3);
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn test_add_edit_and_delete() {
+        let mut app = app();
+        press(&mut app, "aCall bank !high ~10m");
+        key(&mut app, KeyCode::Enter);
+        let added = &app.todo[app.task_index].task;
+        assert_eq!((added.title.as_str(), added.estimated_duration), ("Call bank", Some(10)));
+
+        press(&mut app, "e");
+        key(&mut app, KeyCode::Backspace);
+        press(&mut app, "k manager");
+        key(&mut app, KeyCode::Enter);
+        assert_eq!(app.todo[app.task_index].task.title, "Call bank manager");
+
+        press(&mut app, "dn");
+        assert_eq!(app.todo.len(), 4);
+        press(&mut app, "dy");
+        assert_eq!(app.todo.len(), 3);
+
+        press(&mut app, "a^99 orphan");
+        key(&mut app, KeyCode::Enter);
+        assert_eq!(app.status.as_deref(), Some("Error: Task 99 not found"));
+    }
+
+    #[test]
+    fn test_timer_records_duration() {
+        let mut app = app();
+        let start = app.now;
+        press(&mut app, "t");
+        app.tick(start + chrono::Duration::seconds(75)).unwrap();
+        assert_eq!(app.timer_label(2).as_deref(), Some("1:15"));
+
+        press(&mut app, "t");
+        assert!(app.timers.is_empty());
+        assert_eq!(app.task_list().get_by_id(2).unwrap().unwrap().last_duration, Some(2));
+
+        // Quitting stops running timers too
+        press(&mut app, "jt");
+        app.tick(start + chrono::Duration::seconds(200)).unwrap();
+        press(&mut app, "q");
+        assert!(app.quit);
+        assert_eq!(app.task_list().get_by_id(3).unwrap().unwrap().last_duration, Some(3));
+    }
+}
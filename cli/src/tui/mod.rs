@@ -0,0 +1,36 @@
+// cli/src/tui/mod.rs
+// Interactive mode started by `freelist tui`.
+mod app;
+mod ui;
+
+use app::App;
+use chrono::Utc;
+use freelist_core::TaskList;
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::time::Duration;
+
+// Redraw at least this often so running timers tick and external writes show up
+const TICK: Duration = Duration::from_millis(500);
+
+pub fn run(task_list: TaskList) -> Result<(), Box<dyn std::error::Error>> {
+    let app = App::new(task_list, Utc::now())?;
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> Result<(), Box<dyn std::error::Error>> {
+    while !app.quit {
+        terminal.draw(|frame| ui::render(frame, &app))?;
+        if event::poll(TICK)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.handle_key(key);
+        }
+        app.tick(Utc::now())?;
+    }
+    Ok(())
+}
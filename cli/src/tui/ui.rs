@@ -0,0 +1,177 @@
+// cli/src/tui/ui.rs
+use super::app::{App, Focus, Mode};
+use chrono::Local;
+use freelist_core::Task;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, HighlightSpacing, List, ListItem, ListState, Paragraph};
+
+const HELP: &str = "a add  e edit  space done  u undo  t timer  / search  d delete  tab pane  q quit";
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let [main, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [sidebar, panes] = Layout::horizontal([Constraint::Length(18), Constraint::Min(0)]).areas(main);
+    let [tasks, completed] = Layout::vertical([Constraint::Percentage(65), Constraint::Percentage(35)]).areas(panes);
+
+    render_tags(frame, app, sidebar);
+    render_tasks(frame, app, tasks);
+    render_completed(frame, app, completed);
+    render_footer(frame, app, footer);
+}
+
+fn pane(title: String, focused: bool) -> Block<'static> {
+    let style = if focused { Style::new().fg(Color::Cyan) } else { Style::new() };
+    Block::bordered().title(title).border_style(style)
+}
+
+fn render_list(frame: &mut Frame, area: Rect, block: Block, items: Vec<ListItem>, selected: usize, focused: bool) {
+    let list = List::new(items)
+        .block(block)
+        .highlight_symbol("> ")
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(focused.then_some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_tags(frame: &mut Frame, app: &App, area: Rect) {
+    let items = std::iter::once(ListItem::new("All"))
+        .chain(app.tags.iter().map(|tag| ListItem::new(format!("#{}", tag))))
+        .collect();
+    let focused = app.focus == Focus::Tags;
+    render_list(frame, area, pane("Tags".to_string(), focused), items, app.tag_index, focused);
+}
+
+fn render_tasks(frame: &mut Frame, app: &App, area: Rect) {
+    let mut title = format!("Tasks ({})", app.todo.len());
+    if !app.search.is_empty() {
+        title.push_str(&format!(" matching \"{}\"", app.search));
+    }
+    let items = app
+        .todo
+        .iter()
+        .map(|row| {
+            let mut spans = vec![Span::raw("  ".repeat(row.depth)), Span::raw("○ ")];
+            spans.extend(task_spans(&row.task));
+            if let Some(elapsed) = row.task.id.and_then(|id| app.timer_label(id)) {
+                spans.push(Span::styled(format!(" ⏱ {}", elapsed), Style::new().fg(Color::Green)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let focused = app.focus == Focus::Tasks;
+    render_list(frame, area, pane(title, focused), items, app.task_index, focused);
+}
+
+fn render_completed(frame: &mut Frame, app: &App, area: Rect) {
+    let items = app
+        .completed
+        .iter()
+        .map(|task| {
+            let mut spans = vec![Span::raw("✓ ")];
+            spans.extend(task_spans(task));
+            ListItem::new(Line::from(spans)).style(Style::new().fg(Color::DarkGray))
+        })
+        .collect();
+    let focused = app.focus == Focus::Completed;
+    let title = format!("Completed ({})", app.completed.len());
+    render_list(frame, area, pane(title, focused), items, app.completed_index, focused);
+}
+
+fn task_spans(task: &Task) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::raw(format!("[{}] {}", task.id.unwrap_or(0), task.title))];
+    if let Some(tag) = &task.tag {
+        spans.push(Span::styled(format!(" #{}", tag), Style::new().fg(Color::Blue)));
+    }
+    if let Some(priority) = task.priority {
+        spans.push(Span::styled(format!(" !{}", priority), Style::new().fg(Color::Red)));
+    }
+    if let Some(due) = task.due_date {
+        let style = if task.is_overdue() { Style::new().fg(Color::Red) } else { Style::new() };
+        spans.push(Span::styled(format!(" due {}", due.with_timezone(&Local).format("%Y-%m-%d")), style));
+    }
+    spans
+}
+
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.mode {
+        Mode::Search => Line::from(format!("/{}▏", app.search)),
+        Mode::Add => Line::from(format!("Add: {}▏", app.input)),
+        Mode::Edit(id) => Line::from(format!("Edit [{}]: {}▏", id, app.input)),
+        Mode::ConfirmDelete(id) => Line::from(format!("Delete task {} and its subtasks? (y/n)", id)),
+        Mode::Normal => match &app.status {
+            Some(status) => Line::from(status.as_str()),
+            None => Line::styled(HELP, Style::new().fg(Color::DarkGray)),
+        },
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, Utc};
+    use freelist_core::{Priority, TaskList};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-18T17:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    fn app() -> App {
+        let mut task_list = TaskList::new_in_memory().unwrap();
+        // Midday UTC so the rendered date is the same in every test machine's timezone
+        let due = DateTime::parse_from_rfc3339("2030-05-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let trip = task_list.add(Task::new("Plan trip").with_tag("travel").with_due_date(due)).unwrap();
+        task_list.add(Task::new("Book flights").with_tag("travel").with_parent(trip)).unwrap();
+        task_list.add(Task::new("Buy milk").with_tag("groceries").with_priority(Priority::High)).unwrap();
+        let done = task_list.add(Task::new("Renew passport").with_tag("travel")).unwrap();
+        task_list.mark_done(done).unwrap();
+        App::new(task_list, now()).unwrap()
+    }
+
+    fn draw(app: &App) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(80, 16)).unwrap();
+        terminal.draw(|frame| render(frame, app)).unwrap();
+        terminal
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_initial_screen() {
+        insta::assert_snapshot!(draw(&app()).backend());
+    }
+
+    #[test]
+    fn test_running_timer_and_completed_selection() {
+        let mut app = app();
+        press(&mut app, "jt");
+        app.tick(now() + Duration::seconds(754)).unwrap();
+        app.handle_key(KeyEvent::from(KeyCode::Tab));
+        insta::assert_snapshot!(draw(&app).backend());
+    }
+
+    #[test]
+    fn test_search_and_tag_filter() {
+        let mut app = app();
+        app.handle_key(KeyEvent::from(KeyCode::BackTab));
+        press(&mut app, "jj/fli");
+        insta::assert_snapshot!(draw(&app).backend());
+    }
+
+    #[test]
+    fn test_inline_add() {
+        let mut app = app();
+        press(&mut app, "aWater plants #home ~5m");
+        insta::assert_snapshot!(draw(&app).backend());
+    }
+}
@@ -0,0 +1,460 @@
+// cli/src/workout.rs
+// `freelist workout`: logging sets, personal records, weekly training volume and training plans.
+use crate::args::{PlanAction, WorkoutAction};
+use crate::output::{self, Format};
+use chrono::{DateTime, Days, Duration, Local, NaiveDate, Utc};
+use freelist_core::{
+    dates, Exercise, ExerciseKind, PersonalRecord, PlannedTask, RecordKind, SetMeasures, TaskList, TrainingPlan, Volume,
+    WeeklyVolume, Workout, WorkoutSet,
+};
+use std::io::{self, Write};
+
+type WorkoutResult = Result<(), Box<dyn std::error::Error>>;
+
+// How far ahead a new training plan is scheduled
+const SCHEDULE_WEEKS: i64 = 4;
+
+/// CSV columns, the serde field names of `Exercise` and `PersonalRecord`.
+const EXERCISE_FIELDS: [&str; 4] = ["id", "name", "kind", "created_at"];
+const RECORD_FIELDS: [&str; 7] = ["exercise_id", "exercise", "kind", "value", "previous", "set_id", "achieved_at"];
+
+pub fn run(task_list: &mut TaskList, action: WorkoutAction) -> WorkoutResult {
+    match action {
+        WorkoutAction::Show { id, format } => {
+            let workout = match id {
+                Some(id) => Some(task_list.get_workout(id)?.ok_or_else(|| format!("Workout {} not found", id))?),
+                None => task_list.current_workout()?,
+            };
+            let mut out = io::stdout().lock();
+            match (format, workout) {
+                (Format::Table, Some(workout)) => write_workout(&workout, &mut out)?,
+                (Format::Table, None) => {
+                    writeln!(out, "No workout in progress. Start one with `freelist workout start`.")?
+                }
+                // null when no workout is in progress
+                (Format::Json, workout) => output::write_json(&workout, &mut out)?,
+                (Format::Ndjson, workout) => output::write_ndjson(workout.as_slice(), &mut out)?,
+                (Format::Csv | Format::Markdown, _) => return Err(output::unsupported("workout show", format)),
+            }
+        }
+
+        WorkoutAction::Start { title, at, task } => {
+            if let Some(current) = task_list.current_workout()? {
+                return Err(format!(
+                    "Workout {} is still in progress; finish it with `freelist workout finish`",
+                    current.id.unwrap_or_default()
+                )
+                .into());
+            }
+            let mut workout = Workout::new(started_at(at.as_deref())?);
+            workout.title = title;
+            if let Some(task_id) = task {
+                if task_list.planned_task(task_id)?.is_none() {
+                    return Err(format!("Task {} was not scheduled by a training plan", task_id).into());
+                }
+                // Named after the session unless given a title
+                let session = task_list.get_by_id(task_id)?.map(|task| task.title);
+                workout.title = workout.title.or(session);
+            }
+            let id = task_list.start_workout(&workout)?;
+            println!("Started workout {}", id);
+            if let Some(task_id) = task {
+                task_list.link_workout(task_id, id)?;
+                println!("Marked task {} as done", task_id);
+            }
+        }
+
+        WorkoutAction::Log { exercise, sets, kind, at } => {
+            let performed_at = started_at(at.as_deref())?;
+            let exercise = match task_list.find_exercise(&exercise)? {
+                Some(exercise) => exercise,
+                None => {
+                    let kind = kind.unwrap_or_else(|| guess_kind(&sets[0]));
+                    let mut new_exercise = Exercise::new(&exercise, kind);
+                    new_exercise.id = Some(task_list.add_exercise(&new_exercise)?);
+                    println!("Added {} exercise {}", kind.as_str(), new_exercise.name);
+                    new_exercise
+                }
+            };
+            let workout_id = match task_list.current_workout()? {
+                Some(workout) => workout.id.unwrap_or_default(),
+                None => {
+                    let id = task_list.start_workout(&Workout::new(performed_at))?;
+                    println!("Started workout {}", id);
+                    id
+                }
+            };
+
+            for measures in sets {
+                let set = WorkoutSet::new(workout_id, exercise.id.unwrap_or_default(), measures, performed_at);
+                let (_, records) = task_list.log_set(&set)?;
+                println!("Logged {} {}", exercise.name, set_text(&measures));
+                for record in records {
+                    let previous = record.previous.map(|previous| record_value(record.kind, previous));
+                    println!(
+                        "  New personal record: {} {} (was {})",
+                        record.kind.as_str(),
+                        record_value(record.kind, record.value),
+                        previous.unwrap_or_default()
+                    );
+                }
+            }
+        }
+
+        WorkoutAction::Finish => {
+            let workout = task_list.current_workout()?.ok_or("No workout in progress")?;
+            let id = workout.id.unwrap_or_default();
+            let ended_at = Utc::now();
+            task_list.finish_workout(id, ended_at)?;
+            println!(
+                "Finished workout {}: {} sets in {}",
+                id,
+                workout.sets.len(),
+                clock((ended_at - workout.started_at).num_seconds().max(0) as u32)
+            );
+        }
+
+        WorkoutAction::List { weeks, format } => {
+            // Start times are never in the future, but a day's leeway covers a clock that has moved back
+            let now = Utc::now();
+            let since = now.checked_sub_days(Days::new(7 * u64::from(weeks))).ok_or("--weeks reaches too far back")?;
+            let workouts = task_list.workouts_between(since, now + Duration::days(1))?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if workouts.is_empty() => writeln!(out, "No workouts in the last {} weeks", weeks)?,
+                Format::Table => {
+                    for workout in &workouts {
+                        let length = match workout.ended_at {
+                            Some(ended_at) => clock((ended_at - workout.started_at).num_seconds().max(0) as u32),
+                            None => "in progress".to_string(),
+                        };
+                        writeln!(
+                            out,
+                            "{:>4}  {}  {:<24} {:>3} sets  {}",
+                            workout.id.unwrap_or_default(),
+                            workout.started_at.with_timezone(&Local).format("%a %e %b %H:%M"),
+                            workout.title.as_deref().unwrap_or("Workout"),
+                            workout.sets.len(),
+                            length
+                        )?;
+                    }
+                }
+                Format::Json => output::write_json(&workouts, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&workouts, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("workout list", format)),
+            }
+        }
+
+        WorkoutAction::Exercises { format } => {
+            let exercises = task_list.exercises()?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if exercises.is_empty() => {
+                    writeln!(out, "No exercises yet. They are added the first time you log one.")?
+                }
+                Format::Table => {
+                    for exercise in &exercises {
+                        let id = exercise.id.unwrap_or_default();
+                        writeln!(out, "{:>4}  {:<32} {}", id, exercise.name, exercise.kind.as_str())?;
+                    }
+                }
+                Format::Json => output::write_json(&exercises, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&exercises, &mut out)?,
+                Format::Csv => output::write_csv(&EXERCISE_FIELDS, &exercises, &mut out)?,
+                Format::Markdown => return Err(output::unsupported("workout exercises", format)),
+            }
+        }
+
+        WorkoutAction::Prs { exercise, format } => {
+            let exercise_id = match exercise {
+                Some(name) => {
+                    let exercise = task_list.find_exercise(&name)?;
+                    exercise.ok_or_else(|| format!("No exercise is called '{}'", name))?.id
+                }
+                None => None,
+            };
+            let records = task_list.personal_records(exercise_id)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if records.is_empty() => {
+                    writeln!(out, "No personal records yet. Log some sets with `freelist workout log`.")?
+                }
+                Format::Table => write_records(&records, &mut out)?,
+                Format::Json => output::write_json(&records, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&records, &mut out)?,
+                Format::Csv => output::write_csv(&RECORD_FIELDS, &records, &mut out)?,
+                Format::Markdown => return Err(output::unsupported("workout prs", format)),
+            }
+        }
+
+        WorkoutAction::Volume { weeks, format } => {
+            let today = Local::now().date_naive();
+            let first = today
+                .checked_sub_days(Days::new(7 * (u64::from(weeks) - 1)))
+                .ok_or("--weeks reaches too far back")?;
+            let volume = task_list.weekly_volume(first, today, &Local)?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table => write_volume(&volume, &mut out)?,
+                Format::Json => output::write_json(&volume, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&volume, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("workout volume", format)),
+            }
+        }
+
+        WorkoutAction::Delete { id } => {
+            if !task_list.delete_workout(id)? {
+                return Err(format!("Workout {} not found", id).into());
+            }
+            println!("Deleted workout {}", id);
+        }
+
+        WorkoutAction::Plan { action, format } => run_plan(task_list, action.unwrap_or(PlanAction::List { format }))?,
+    }
+    Ok(())
+}
+
+fn run_plan(task_list: &mut TaskList, action: PlanAction) -> WorkoutResult {
+    match action {
+        PlanAction::List { format } => {
+            let plans = task_list.plans()?;
+            let mut out = io::stdout().lock();
+            match format {
+                Format::Table if plans.is_empty() => writeln!(
+                    out,
+                    "No training plans. Add one with `freelist workout plan add <name> --session <session>`."
+                )?,
+                Format::Table => {
+                    for plan in &plans {
+                        let until = plan.ends_on.map(|day| format!(" until {}", day)).unwrap_or_default();
+                        writeln!(
+                            out,
+                            "{:>4}  {} (#{}, from {}{})",
+                            plan.id.unwrap_or_default(),
+                            plan.name,
+                            plan.tag,
+                            plan.starts_on,
+                            until
+                        )?;
+                        for session in &plan.sessions {
+                            writeln!(out, "        {}", session)?;
+                        }
+                    }
+                }
+                Format::Json => output::write_json(&plans, &mut out)?,
+                Format::Ndjson => output::write_ndjson(&plans, &mut out)?,
+                Format::Csv | Format::Markdown => return Err(output::unsupported("workout plan list", format)),
+            }
+        }
+
+        PlanAction::Add { name, sessions, tag, from, until } => {
+            let now = Local::now();
+            let starts_on = from.as_deref().map(local_day).transpose()?.unwrap_or(now.date_naive());
+            let mut plan = TrainingPlan::new(&name, starts_on, sessions).with_tag(&tag);
+            plan.ends_on = until.as_deref().map(local_day).transpose()?;
+            let id = task_list.add_plan(&plan)?;
+            let through = now.date_naive() + Duration::weeks(SCHEDULE_WEEKS) - Duration::days(1);
+            let scheduled = task_list.schedule_plan(id, through, &now)?;
+            println!("Added training plan with ID: {}", id);
+            println!(
+                "Scheduled {} sessions in the next {} weeks as #{} tasks",
+                scheduled.len(),
+                SCHEDULE_WEEKS,
+                plan.tag
+            );
+        }
+
+        PlanAction::Schedule { plan, weeks } => {
+            let plans = match plan {
+                Some(plan) => vec![resolve_plan(task_list, &plan)?],
+                None => task_list.plans()?,
+            };
+            let now = Local::now();
+            let through = now
+                .date_naive()
+                .checked_add_days(Days::new(7 * u64::from(weeks) - 1))
+                .ok_or("--weeks reaches too far ahead")?;
+            for plan in plans {
+                let scheduled = task_list.schedule_plan(plan.id.unwrap_or_default(), through, &now)?;
+                println!("Scheduled {} sessions of {}", scheduled.len(), plan.name);
+            }
+        }
+
+        PlanAction::Remove { plan } => {
+            let plan = resolve_plan(task_list, &plan)?;
+            task_list.delete_plan(plan.id.unwrap_or_default())?;
+            println!("Deleted training plan {} and its open tasks", plan.name);
+        }
+    }
+    Ok(())
+}
+
+fn resolve_plan(task_list: &TaskList, query: &str) -> Result<TrainingPlan, Box<dyn std::error::Error>> {
+    if let Ok(id) = query.parse::<i64>() {
+        return task_list.get_plan(id)?.ok_or_else(|| format!("Training plan {} not found", id).into());
+    }
+    task_list.find_plan(query)?.ok_or_else(|| format!("No training plan is called '{}'", query).into())
+}
+
+fn local_day(input: &str) -> Result<NaiveDate, dates::DateParseError> {
+    Ok(dates::parse_due_date_local(input)?.with_timezone(&Local).date_naive())
+}
+
+/// What `freelist done` adds for a training plan task: the workout it was
+/// linked to, or how to log one.
+pub fn write_planned(planned: &PlannedTask, out: &mut impl Write) -> io::Result<()> {
+    match planned.workout_id {
+        Some(workout_id) => writeln!(out, "Linked to workout {}", workout_id),
+        None => writeln!(
+            out,
+            "Log the workout with `freelist workout start --task {}`, then `freelist workout log`",
+            planned.task_id.unwrap_or_default()
+        ),
+    }
+}
+
+fn started_at(at: Option<&str>) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    Ok(match at {
+        Some(at) => dates::parse_past_local(at)?,
+        None => Utc::now(),
+    })
+}
+
+// Runs and rides are measured in distance or time; everything else in reps
+fn guess_kind(measures: &SetMeasures) -> ExerciseKind {
+    if measures.distance_m.is_some() || (measures.duration_s.is_some() && measures.reps.is_none()) {
+        ExerciseKind::Cardio
+    } else {
+        ExerciseKind::Strength
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn kg(weight: f64) -> String {
+    format!("{} kg", round1(weight))
+}
+
+fn distance(metres: f64) -> String {
+    if metres >= 1000.0 {
+        format!("{:.2} km", metres / 1000.0)
+    } else {
+        format!("{} m", round1(metres))
+    }
+}
+
+/// 1:02:03, or 25:30 under an hour.
+fn clock(seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+fn set_text(measures: &SetMeasures) -> String {
+    let mut parts = Vec::new();
+    match (measures.reps, measures.weight_kg) {
+        (Some(reps), Some(weight)) => parts.push(format!("{} × {}", reps, kg(weight))),
+        (Some(reps), None) => parts.push(format!("{} reps", reps)),
+        (None, Some(weight)) => parts.push(kg(weight)),
+        (None, None) => {}
+    }
+    if let Some(metres) = measures.distance_m {
+        parts.push(distance(metres));
+    }
+    if let Some(seconds) = measures.duration_s {
+        parts.push(clock(seconds));
+    }
+    parts.join(" in ")
+}
+
+fn record_value(kind: RecordKind, value: f64) -> String {
+    match kind {
+        RecordKind::MaxWeight | RecordKind::EstimatedOneRepMax | RecordKind::MaxVolume => kg(value),
+        RecordKind::MaxReps => format!("{} reps", value),
+        RecordKind::LongestDistance => distance(value),
+        RecordKind::LongestDuration => clock(value.round() as u32),
+        RecordKind::FastestPace => format!("{}/km", clock(value.round() as u32)),
+    }
+}
+
+fn volume_text(volume: &Volume) -> String {
+    let mut parts = vec![format!("{} sets", volume.sets)];
+    if volume.reps > 0 {
+        parts.push(format!("{} reps", volume.reps));
+    }
+    if volume.volume_kg > 0.0 {
+        parts.push(format!("{} lifted", kg(volume.volume_kg)));
+    }
+    if volume.distance_m > 0.0 {
+        parts.push(distance(volume.distance_m));
+    }
+    if volume.duration_s > 0 {
+        parts.push(clock(volume.duration_s));
+    }
+    parts.join(", ")
+}
+
+/// The workout's heading, then each exercise with its sets in the order they were done.
+fn write_workout(workout: &Workout, out: &mut impl Write) -> io::Result<()> {
+    let status = match workout.ended_at {
+        Some(ended_at) => clock((ended_at - workout.started_at).num_seconds().max(0) as u32),
+        None => "in progress".to_string(),
+    };
+    writeln!(
+        out,
+        "Workout {}: {} ({}, {})",
+        workout.id.unwrap_or_default(),
+        workout.title.as_deref().unwrap_or("untitled"),
+        workout.started_at.with_timezone(&Local).format("%a %e %b %H:%M"),
+        status
+    )?;
+    if workout.sets.is_empty() {
+        writeln!(out, "  No sets yet. Log one with `freelist workout log <exercise> <set>`.")?;
+    }
+    let mut exercises: Vec<(&str, Vec<String>)> = Vec::new();
+    for logged in &workout.sets {
+        let text = set_text(&logged.set.measures);
+        match exercises.iter_mut().find(|(name, _)| *name == logged.exercise) {
+            Some((_, sets)) => sets.push(text),
+            None => exercises.push((&logged.exercise, vec![text])),
+        }
+    }
+    for (name, sets) in exercises {
+        writeln!(out, "  {:<24} {}", name, sets.join(", "))?;
+    }
+    Ok(())
+}
+
+fn write_records(records: &[PersonalRecord], out: &mut impl Write) -> io::Result<()> {
+    for record in records {
+        writeln!(
+            out,
+            "{:<24} {:<18} {:<12} {}",
+            record.exercise,
+            record.kind.as_str(),
+            record_value(record.kind, record.value),
+            record.achieved_at.with_timezone(&Local).format("%a %e %b %Y")
+        )?;
+    }
+    Ok(())
+}
+
+fn write_volume(weeks: &[WeeklyVolume], out: &mut impl Write) -> io::Result<()> {
+    for week in weeks {
+        if week.workouts == 0 {
+            writeln!(out, "Week of {}: no workouts", week.week_start)?;
+            continue;
+        }
+        let workouts = if week.workouts == 1 { "workout" } else { "workouts" };
+        writeln!(out, "Week of {}: {} {}, {}", week.week_start, week.workouts, workouts, volume_text(&week.total))?;
+        for (exercise, volume) in &week.by_exercise {
+            writeln!(out, "  {:<24} {}", exercise, volume_text(volume))?;
+        }
+    }
+    Ok(())
+}
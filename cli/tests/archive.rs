@@ -0,0 +1,146 @@
+// cli/tests/archive.rs
+mod common;
+
+use common::{freelist, stdout};
+use freelist_core::TaskList;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+fn titles(db: &Path) -> Vec<(String, Option<i64>)> {
+    let mut tasks: Vec<_> = TaskList::new(db)
+        .unwrap()
+        .all()
+        .unwrap()
+        .into_iter()
+        .map(|task| (task.title, task.parent_id))
+        .collect();
+    tasks.sort();
+    tasks
+}
+
+#[test]
+fn test_json_export_import_is_idempotent() {
+    let dir = TempDir::new().unwrap();
+    let source = dir.path().join("source.db");
+    let target = dir.path().join("target.db");
+    stdout(freelist(&source, &["add", "Plan trip #travel"]));
+    stdout(freelist(&source, &["add", "Book flights ^1"]));
+    stdout(freelist(&source, &["done", "2"]));
+    stdout(freelist(&target, &["add", "Already here"]));
+
+    let backup = dir.path().join("backup.json");
+    stdout(freelist(&source, &["export", backup.to_str().unwrap()]));
+
+    let out = stdout(freelist(&target, &["import", backup.to_str().unwrap()]));
+    assert_eq!(out.trim(), "Imported 2 tasks (0 already present), 1 history entries");
+    let out = stdout(freelist(&target, &["import", backup.to_str().unwrap()]));
+    assert_eq!(out.trim(), "Imported 0 tasks (2 already present), 0 history entries");
+
+    // The subtask points at the renumbered parent
+    assert_eq!(
+        titles(&target),
+        vec![
+            ("Already here".to_string(), None),
+            ("Book flights".to_string(), Some(2)),
+            ("Plan trip".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn test_csv_export_and_stdin_import() {
+    let dir = TempDir::new().unwrap();
+    let source = dir.path().join("source.db");
+    stdout(freelist(&source, &["add", "Water plants #home"]));
+
+    let csv_dir = dir.path().join("backup");
+    stdout(freelist(&source, &["export", csv_dir.to_str().unwrap(), "--format", "csv"]));
+    for file in ["tasks.csv", "tags.csv", "history.csv"] {
+        assert!(csv_dir.join(file).exists(), "missing {}", file);
+    }
+    let from_csv = dir.path().join("from_csv.db");
+    stdout(freelist(&from_csv, &["import", csv_dir.to_str().unwrap()]));
+    assert_eq!(titles(&from_csv), titles(&source));
+
+    let json = stdout(freelist(&source, &["export"]));
+    let from_stdin = dir.path().join("from_stdin.db");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_freelist"))
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .arg("--db")
+        .arg(&from_stdin)
+        .args(["import", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(json.as_bytes()).unwrap();
+    assert!(child.wait_with_output().unwrap().status.success());
+    assert_eq!(titles(&from_stdin), titles(&source));
+
+    assert!(!freelist(&source, &["export", "--format", "csv"]).status.success());
+}
+
+fn fixture(name: &str) -> String {
+    format!("{}/../core/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn test_import_from_other_tools() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+
+    // A dry run reports the tasks and warnings but writes nothing
+    let output = freelist(&db, &["import", "--from", "todotxt", "--dry-run", &fixture("todo.txt")]);
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    let out = stdout(output);
+    assert!(out.contains("○ Plan the garden #Home\n  ○ Buy seeds #shop !medium\n"), "{}", out);
+    assert!(out.contains("✓ File taxes #Finance !high\n"), "{}", out);
+    assert!(out.ends_with("Would import 7 tasks (0 already present), 1 history entries\n"), "{}", out);
+    assert!(stderr.contains("warning: line 8: parent 'p:missing' not found"), "{}", stderr);
+    assert!(titles(&db).is_empty());
+
+    let out = stdout(freelist(&db, &["import", "--from", "taskwarrior", &fixture("taskwarrior.json")]));
+    assert_eq!(out.trim(), "Imported 4 tasks (0 already present), 1 history entries");
+    let out = stdout(freelist(&db, &["import", "--from", "taskwarrior", "--dry-run", &fixture("taskwarrior.json")]));
+    assert!(out.ends_with("Would import 0 tasks (4 already present), 0 history entries\n"), "{}", out);
+
+    // Todoist tasks without a label are tagged with the backup's file name
+    let backup = dir.path().join("Offsite.csv");
+    std::fs::copy(fixture("todoist.csv"), &backup).unwrap();
+    stdout(freelist(&db, &["import", "--from", "todoist", backup.to_str().unwrap()]));
+    let out = stdout(freelist(&db, &["list", "--format", "csv"]));
+    assert!(out.contains(",Book venue,"), "{}", out);
+    let tags = stdout(freelist(&db, &["tags"]));
+    assert!(tags.contains("#Offsite") && tags.contains("#work"), "{}", tags);
+}
+
+#[test]
+fn test_ics_export_and_import() {
+    let dir = TempDir::new().unwrap();
+    let source = dir.path().join("source.db");
+    let target = dir.path().join("target.db");
+    stdout(freelist(&source, &["add", "Plan trip #travel due:2030-05-01"]));
+    stdout(freelist(&source, &["add", "Book flights ^1 due:2030-04-01"]));
+    stdout(freelist(&source, &["add", "Someday maybe"]));
+
+    let out = stdout(freelist(&source, &["ics", "export", "--due-only"]));
+    assert!(out.starts_with("BEGIN:VCALENDAR\r\n"), "{}", out);
+    assert_eq!(out.matches("BEGIN:VTODO").count(), 2);
+    assert!(out.contains("RELATED-TO;RELTYPE=PARENT:freelist-task-1"));
+
+    let calendar = dir.path().join("tasks.ics");
+    stdout(freelist(&source, &["ics", "export", calendar.to_str().unwrap()]));
+    let out = stdout(freelist(&target, &["ics", "import", calendar.to_str().unwrap()]));
+    assert_eq!(out.trim(), "Imported 3 tasks (0 already present), 0 history entries");
+    let tasks = TaskList::new(&target).unwrap().all().unwrap();
+    let trip = tasks.iter().find(|task| task.title == "Plan trip").unwrap();
+    let flights = tasks.iter().find(|task| task.title == "Book flights").unwrap();
+    assert_eq!(flights.parent_id, trip.id);
+    assert_eq!(tasks.len(), 3);
+
+    let out = stdout(freelist(&target, &["import", "--from", "ics", "--dry-run", &fixture("calendar.ics")]));
+    assert!(out.contains("○ Team standup"), "{}", out);
+    assert!(out.ends_with("Would import 3 tasks (0 already present), 1 history entries\n"), "{}", out);
+}
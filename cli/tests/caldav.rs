@@ -0,0 +1,206 @@
+// cli/tests/caldav.rs
+// Runs `freelist caldav` on a free port and talks to it the way calendar clients do.
+use freelist_core::TaskList;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use tempfile::TempDir;
+
+struct Server {
+    child: Child,
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl Server {
+    fn start(db: &Path) -> Server {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_freelist"))
+            .env("XDG_CONFIG_HOME", db.parent().unwrap().join("config"))
+            .arg("--db")
+            .arg(db)
+            .args(["caldav", "--bind", "127.0.0.1:0"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start freelist caldav");
+
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+        let start = line.find("http://").expect("server did not print its address");
+        let url = line[start..].split_whitespace().next().unwrap().trim_end_matches('/').to_string();
+        let agent = ureq::AgentBuilder::new().redirects(0).build();
+        Server { child, url, agent }
+    }
+
+    /// Status, ETag and body; error statuses are returned rather than failing.
+    fn send(&self, method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> (u16, Option<String>, String) {
+        let mut request = self.agent.request(method, &format!("{}{}", self.url, path));
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        let response = match request.send_string(body) {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(err) => panic!("{} {} failed: {}", method, path, err),
+        };
+        let status = response.status();
+        let etag = response.header("ETag").map(str::to_string);
+        (status, etag, response.into_string().unwrap())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn add(db: &Path, text: &str) {
+    let status = Command::new(env!("CARGO_BIN_EXE_freelist"))
+        .env("XDG_CONFIG_HOME", db.parent().unwrap().join("config"))
+        .arg("--db")
+        .arg(db)
+        .args(["add", text])
+        .stdout(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn setup() -> (TempDir, PathBuf, Server) {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    add(&db, "Plan trip #travel");
+    add(&db, "Buy milk #groceries");
+    add(&db, "Call mom");
+    let server = Server::start(&db);
+    (dir, db, server)
+}
+
+fn ctag(server: &Server, path: &str) -> String {
+    let body = r#"<d:propfind xmlns:d="DAV:" xmlns:cs="http://calendarserver.org/ns/"><d:prop><cs:getctag/></d:prop></d:propfind>"#;
+    let (status, _, response) = server.send("PROPFIND", path, &[("Depth", "0")], body);
+    assert_eq!(status, 207);
+    let start = response.find("<cs:getctag>").unwrap() + "<cs:getctag>".len();
+    response[start..start + response[start..].find('<').unwrap()].to_string()
+}
+
+fn vtodo(uid: &str, extra: &str) -> String {
+    format!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//EN\r\nBEGIN:VTODO\r\nUID:{}\r\nDTSTAMP:20261018T120000Z\r\n\
+         SUMMARY:Pack bags\r\nDUE:20261101T090000Z\r\n{}END:VTODO\r\nEND:VCALENDAR\r\n",
+        uid, extra
+    )
+}
+
+#[test]
+fn test_discovery() {
+    let (_dir, _db, server) = setup();
+
+    let (status, _, _) = server.send("GET", "/.well-known/caldav", &[], "");
+    assert_eq!(status, 301);
+
+    let body = r#"<?xml version="1.0"?>
+        <d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+          <d:prop><d:current-user-principal/><c:calendar-home-set/><d:unknown-thing/></d:prop>
+        </d:propfind>"#;
+    let (status, _, response) = server.send("PROPFIND", "/", &[("Depth", "0")], body);
+    assert_eq!(status, 207);
+    assert!(response.contains("<c:calendar-home-set><d:href>/calendars/</d:href></c:calendar-home-set>"), "{}", response);
+    assert!(response.contains("<d:unknown-thing/></d:prop><d:status>HTTP/1.1 404 Not Found"), "{}", response);
+
+    let (status, _, response) = server.send("PROPFIND", "/calendars/", &[("Depth", "1")], "");
+    assert_eq!(status, 207);
+    for calendar in ["/calendars/travel/", "/calendars/groceries/", "/calendars/untagged/"] {
+        assert!(response.contains(&format!("<d:href>{}</d:href>", calendar)), "{}", response);
+    }
+    assert!(response.contains("<c:comp name=\"VTODO\"/>"), "{}", response);
+
+    let body = r#"<d:propfind xmlns:d="DAV:"><d:prop><d:getetag/></d:prop></d:propfind>"#;
+    let (_, _, response) = server.send("PROPFIND", "/calendars/untagged/", &[("Depth", "1")], body);
+    assert!(response.contains("<d:href>/calendars/untagged/freelist-task-3.ics</d:href>"), "{}", response);
+    assert!(!response.contains("freelist-task-1"), "{}", response);
+
+    let (status, _, _) = server.send("PROPFIND", "/calendars/nope/", &[("Depth", "0")], "");
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn test_reports() {
+    let (_dir, _db, server) = setup();
+
+    let query = r#"<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+          <d:prop><d:getetag/><c:calendar-data/></d:prop>
+          <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter>
+        </c:calendar-query>"#;
+    let (status, _, response) = server.send("REPORT", "/calendars/travel/", &[("Depth", "1")], query);
+    assert_eq!(status, 207);
+    assert!(response.contains("SUMMARY:Plan trip&#13;\n"), "{}", response);
+    assert!(!response.contains("Buy milk"), "{}", response);
+
+    let events = query.replace("\"VTODO\"", "\"VEVENT\"");
+    let (_, _, response) = server.send("REPORT", "/calendars/travel/", &[], &events);
+    assert!(!response.contains("<d:response>"), "{}", response);
+
+    let multiget = r#"<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+          <d:prop><d:getetag/><c:calendar-data/></d:prop>
+          <d:href>/calendars/groceries/freelist-task-2.ics</d:href>
+          <d:href>/calendars/groceries/missing.ics</d:href>
+        </c:calendar-multiget>"#;
+    let (status, _, response) = server.send("REPORT", "/calendars/groceries/", &[], multiget);
+    assert_eq!(status, 207);
+    assert!(response.contains("UID:freelist-task-2"), "{}", response);
+    assert!(response.contains("<d:href>/calendars/groceries/missing.ics</d:href><d:status>HTTP/1.1 404 Not Found"), "{}", response);
+
+    let sync = r#"<d:sync-collection xmlns:d="DAV:"><d:sync-token/><d:prop><d:getetag/></d:prop></d:sync-collection>"#;
+    let (status, _, _) = server.send("REPORT", "/calendars/travel/", &[], sync);
+    assert_eq!(status, 403);
+}
+
+#[test]
+fn test_put_and_delete_with_etags() {
+    let (_dir, db, server) = setup();
+    let path = "/calendars/travel/3F2A9C1E-client.ics";
+    let before = ctag(&server, "/calendars/travel/");
+
+    // Create, as a subtask of a task FreeList exported
+    let body = vtodo("3F2A9C1E-client", "RELATED-TO:freelist-task-1\r\nPRIORITY:1\r\n");
+    let (status, created, _) = server.send("PUT", path, &[("If-None-Match", "*")], &body);
+    assert_eq!(status, 201);
+    let created = created.expect("no ETag on create");
+    let after_create = ctag(&server, "/calendars/travel/");
+    assert_ne!(after_create, before);
+    let (status, _, _) = server.send("PUT", path, &[("If-None-Match", "*")], &body);
+    assert_eq!(status, 412);
+    assert_eq!(ctag(&server, "/calendars/travel/"), after_create);
+
+    let task = TaskList::new(&db).unwrap().all().unwrap().into_iter().find(|t| t.title == "Pack bags").unwrap();
+    assert_eq!((task.tag.as_deref(), task.parent_id, task.done), (Some("travel"), Some(1), false));
+
+    let (status, etag, response) = server.send("GET", path, &[], "");
+    assert_eq!((status, etag.as_deref()), (200, Some(created.as_str())));
+    assert!(response.contains("UID:3F2A9C1E-client\r\n"), "{}", response);
+
+    // Update, only against the current version
+    let done = vtodo("3F2A9C1E-client", "STATUS:COMPLETED\r\nCOMPLETED:20261019T080000Z\r\n");
+    let (status, _, _) = server.send("PUT", path, &[("If-Match", "\"1\"")], &done);
+    assert_eq!(status, 412);
+    let (status, updated, _) = server.send("PUT", path, &[("If-Match", &created)], &done);
+    assert_eq!(status, 204);
+    let updated = updated.expect("no ETag on update");
+    assert_ne!(updated, created);
+    let task = TaskList::new(&db).unwrap().get_by_id(task.id.unwrap()).unwrap().unwrap();
+    assert!(task.done);
+    assert_eq!(task.parent_id, None);
+
+    let (status, _, _) = server.send("PUT", path, &[], "not a calendar");
+    assert_eq!(status, 400);
+
+    let (status, _, _) = server.send("DELETE", path, &[("If-Match", &created)], "");
+    assert_eq!(status, 412);
+    let (status, _, _) = server.send("DELETE", path, &[("If-Match", &updated)], "");
+    assert_eq!(status, 204);
+    let (status, _, _) = server.send("GET", path, &[], "");
+    assert_eq!(status, 404);
+    assert_eq!(TaskList::new(&db).unwrap().all().unwrap().len(), 3);
+}
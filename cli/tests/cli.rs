@@ -0,0 +1,141 @@
+// cli/tests/cli.rs
+mod common;
+
+use common::{freelist, stdout};
+use freelist_core::{Priority, TaskList};
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn test_add_with_flags() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+
+    let out = stdout(freelist(&db, &["add", "Plan trip", "travel"]));
+    assert!(out.contains("Added task with ID: 1"));
+    stdout(freelist(
+        &db,
+        &[
+            "add", "Book flights", "--parent", "1", "--due", "2030-05-01T09:00:00Z",
+            "--details", "Window seat", "--estimate", "45",
+        ],
+    ));
+
+    let task_list = TaskList::new(&db).unwrap();
+    let task = task_list.get_by_id(2).unwrap().unwrap();
+    assert_eq!(task.parent_id, Some(1));
+    assert_eq!(task.details.as_deref(), Some("Window seat"));
+    assert_eq!(task.estimated_duration, Some(45));
+    assert_eq!(task.due_date.unwrap().to_rfc3339(), "2030-05-01T09:00:00+00:00");
+
+    let listed = stdout(freelist(&db, &["list", "#travel"]));
+    assert!(listed.contains("[1] Plan trip #travel"));
+    assert!(!listed.contains("Book flights"));
+}
+
+#[test]
+fn test_add_parses_quick_add_syntax() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+
+    stdout(freelist(&db, &["add", "Buy milk #groceries !high due:2030-05-01 ~15m"]));
+    stdout(freelist(&db, &["add", "Oat milk ^1 !low", "--priority", "urgent"]));
+    stdout(freelist(&db, &["add", "Tweet #hashtag !wow", "--raw"]));
+    assert!(!freelist(&db, &["add", "Orphan ^99"]).status.success());
+    assert!(!freelist(&db, &["add", "#just-a-tag"]).status.success());
+
+    let task_list = TaskList::new(&db).unwrap();
+    let milk = task_list.get_by_id(1).unwrap().unwrap();
+    assert_eq!(milk.title, "Buy milk");
+    assert_eq!(milk.tag.as_deref(), Some("groceries"));
+    assert_eq!(milk.estimated_duration, Some(15));
+    assert!(milk.due_date.is_some());
+    let oat = task_list.get_by_id(2).unwrap().unwrap();
+    assert_eq!((oat.parent_id, oat.priority), (Some(1), Some(Priority::Urgent)));
+    assert_eq!(task_list.get_by_id(3).unwrap().unwrap().title, "Tweet #hashtag !wow");
+
+    let listed = stdout(freelist(&db, &["list", "#groceries"]));
+    assert!(listed.contains("[1] Buy milk #groceries !high (due: 2030-0"));
+}
+
+#[test]
+fn test_done_undone_delete() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    stdout(freelist(&db, &["add", "Water plants"]));
+
+    assert!(stdout(freelist(&db, &["done", "1"])).contains("Marked task 1 as done"));
+    assert!(stdout(freelist(&db, &["list", "done"])).contains("✓ [1] Water plants"));
+    stdout(freelist(&db, &["undone", "1"]));
+    assert!(stdout(freelist(&db, &["list", "todo"])).contains("○ [1] Water plants"));
+    stdout(freelist(&db, &["delete", "1"]));
+    assert!(stdout(freelist(&db, &["list"])).contains("No tasks found."));
+}
+
+#[test]
+fn test_move_reorders_tasks() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    for title in ["Wash", "Dry", "Fold"] {
+        stdout(freelist(&db, &["add", title]));
+    }
+    let ids = |db: &Path| -> Vec<i64> {
+        let tasks: Vec<serde_json::Value> =
+            serde_json::from_str(&stdout(freelist(db, &["list", "--format", "json"]))).unwrap();
+        tasks.iter().map(|task| task["id"].as_i64().unwrap()).collect()
+    };
+    assert_eq!(ids(&db), [3, 2, 1]);
+
+    assert_eq!(stdout(freelist(&db, &["move", "1", "--above", "3"])).trim(), "Moved task 1");
+    assert_eq!(ids(&db), [1, 3, 2]);
+    stdout(freelist(&db, &["move", "3", "--below", "2"]));
+    assert_eq!(ids(&db), [1, 2, 3]);
+    stdout(freelist(&db, &["move", "3", "--below", "1", "--above", "2"]));
+    assert_eq!(ids(&db), [1, 3, 2]);
+
+    assert!(!freelist(&db, &["move", "3"]).status.success());
+    let output = freelist(&db, &["move", "1", "--below", "2", "--above", "3"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("task 2 does not come before task 3"));
+    assert!(!freelist(&db, &["move", "9", "--below", "1"]).status.success());
+}
+
+#[test]
+fn test_invalid_input_fails() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+
+    let output = freelist(&db, &["done", "7"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Task 7 not found"));
+
+    assert!(!freelist(&db, &["add", "Orphan", "--parent", "99"]).status.success());
+    assert!(!freelist(&db, &["add", "Someday", "--due", "whenever"]).status.success());
+    assert!(!freelist(&db, &["list", "bogus"]).status.success());
+
+    let output = freelist(&db, &["add", "Soon", "--due", "in 3 days"]);
+    assert!(output.status.success());
+    assert!(!freelist(&db, &["frobnicate"]).status.success());
+}
+
+#[test]
+fn test_clear_and_tags() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    stdout(freelist(&db, &["add", "Milk", "groceries"]));
+    stdout(freelist(&db, &["add", "Email", "work"]));
+
+    let tags = stdout(freelist(&db, &["tags"]));
+    assert!(tags.contains("#groceries") && tags.contains("#work"));
+
+    assert!(stdout(freelist(&db, &["clear", "--yes"])).contains("All tasks deleted."));
+    assert!(stdout(freelist(&db, &["tags"])).contains("No tags found."));
+}
+
+#[test]
+fn test_completions() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let script = stdout(freelist(&db, &["completions", "bash"]));
+    assert!(script.contains("freelist"));
+    assert!(!db.exists(), "completions should not open the database");
+}
@@ -0,0 +1,28 @@
+// cli/tests/common/mod.rs
+// Helpers shared by the integration tests that run the freelist binary.
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Runs freelist against `db`, keeping the user's own config file out of the way.
+// tests/config.rs only shares `stdout`, as it sets up its own environment
+#[allow(dead_code)]
+pub fn freelist(db: &Path, args: &[&str]) -> Output {
+    let config_home = db.parent().unwrap().join("config");
+    Command::new(env!("CARGO_BIN_EXE_freelist"))
+        .env("XDG_CONFIG_HOME", config_home)
+        .arg("--db")
+        .arg(db)
+        .args(args)
+        .output()
+        .expect("failed to run freelist")
+}
+
+/// The output of a run that must have succeeded.
+pub fn stdout(output: Output) -> String {
+    assert!(
+        output.status.success(),
+        "freelist failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
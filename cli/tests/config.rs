@@ -0,0 +1,127 @@
+// cli/tests/config.rs
+mod common;
+
+use common::stdout;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Runs freelist with HOME and the XDG directories pointed into `home`.
+fn freelist(home: &Path, envs: &[(&str, &Path)], args: &[&str]) -> Output {
+    freelist_in(home, home, envs, args)
+}
+
+fn freelist_in(home: &Path, cwd: &Path, envs: &[(&str, &Path)], args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_freelist"));
+    command
+        .env_remove("FREELIST_DB")
+        .env_remove("FREELIST_PROFILE")
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .current_dir(cwd);
+    for (key, value) in envs {
+        command.env(key, value);
+    }
+    command.args(args).output().expect("failed to run freelist")
+}
+
+#[test]
+fn test_default_database_lives_in_xdg_data_home() {
+    let home = TempDir::new().unwrap();
+    stdout(freelist(home.path(), &[], &["add", "Anywhere"]));
+
+    assert!(home.path().join("data/freelist/freelist.db").exists());
+    assert!(!home.path().join("freelist.db").exists());
+
+    let elsewhere = home.path().join("projects");
+    std::fs::create_dir(&elsewhere).unwrap();
+    let listed = stdout(freelist_in(home.path(), &elsewhere, &[], &["list"]));
+    assert!(listed.contains("Anywhere"));
+}
+
+#[test]
+fn test_freelist_db_env_overrides_config() {
+    let home = TempDir::new().unwrap();
+    let env_db = home.path().join("env.db");
+    stdout(freelist(home.path(), &[], &["config", "set", "db", "/nonexistent/config.db"]));
+
+    stdout(freelist(home.path(), &[("FREELIST_DB", &env_db)], &["add", "From env"]));
+    assert!(env_db.exists());
+
+    let shown = stdout(freelist(home.path(), &[("FREELIST_DB", &env_db)], &["config"]));
+    assert!(shown.contains("$FREELIST_DB"));
+}
+
+#[test]
+fn test_profiles_keep_separate_databases() {
+    let home = TempDir::new().unwrap();
+    let work_db = home.path().join("work.db");
+    stdout(freelist(
+        home.path(),
+        &[],
+        &["config", "set", "profiles.work.db", work_db.to_str().unwrap()],
+    ));
+
+    stdout(freelist(home.path(), &[], &["--profile", "work", "add", "Quarterly report"]));
+    let home_db = home.path().join("home.db");
+    stdout(freelist(home.path(), &[], &["config", "set", "profiles.home.db", home_db.to_str().unwrap()]));
+    stdout(freelist(home.path(), &[], &["--profile", "home", "add", "Mow lawn"]));
+
+    assert!(work_db.exists());
+    assert!(home_db.exists());
+    let work = stdout(freelist(home.path(), &[], &["list", "--profile", "work"]));
+    assert!(work.contains("Quarterly report") && !work.contains("Mow lawn"));
+
+    stdout(freelist(home.path(), &[], &["config", "set", "profile", "work"]));
+    assert!(stdout(freelist(home.path(), &[], &["list"])).contains("Quarterly report"));
+}
+
+#[test]
+fn test_unknown_profiles_are_errors() {
+    let home = TempDir::new().unwrap();
+    stdout(freelist(home.path(), &[], &["add", "Default task"]));
+
+    let failed = freelist(home.path(), &[], &["--profile", "wrok", "list"]);
+    assert!(!failed.status.success());
+    let error = String::from_utf8_lossy(&failed.stderr);
+    assert!(error.contains("No profile called 'wrok'"), "{}", error);
+    assert!(!home.path().join("data/freelist/wrok.db").exists());
+    let failed = freelist(home.path(), &[], &["--profile", "../escape", "list"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("'../escape' cannot be a profile name"));
+
+    // A mistyped default profile can still be shown and cleared
+    stdout(freelist(home.path(), &[], &["config", "set", "profile", "wrok"]));
+    assert!(stdout(freelist(home.path(), &[], &["config"])).contains("No profile called 'wrok'"));
+    stdout(freelist(home.path(), &[], &["config", "unset", "profile"]));
+    assert!(stdout(freelist(home.path(), &[], &["list"])).contains("Default task"));
+}
+
+#[test]
+fn test_relative_config_paths_do_not_depend_on_cwd() {
+    let home = TempDir::new().unwrap();
+    let (first, second) = (home.path().join("first"), home.path().join("second"));
+    std::fs::create_dir(&first).unwrap();
+    std::fs::create_dir(&second).unwrap();
+    stdout(freelist(home.path(), &[], &["config", "set", "db", "tasks.db"]));
+
+    stdout(freelist_in(home.path(), &first, &[], &["add", "Written from first"]));
+    let listed = stdout(freelist_in(home.path(), &second, &[], &["list"]));
+    assert!(listed.contains("Written from first"), "{}", listed);
+    assert!(home.path().join("config/freelist/tasks.db").exists());
+    assert!(!first.join("tasks.db").exists() && !second.join("tasks.db").exists());
+}
+
+#[test]
+fn test_config_get_set_unset() {
+    let home = TempDir::new().unwrap();
+    stdout(freelist(home.path(), &[], &["config", "set", "profile", "home"]));
+    assert_eq!(stdout(freelist(home.path(), &[], &["config", "get", "profile"])).trim(), "home");
+
+    let file = std::fs::read_to_string(home.path().join("config/freelist/config.toml")).unwrap();
+    assert!(file.contains("profile = \"home\""));
+
+    stdout(freelist(home.path(), &[], &["config", "unset", "profile"]));
+    assert!(!freelist(home.path(), &[], &["config", "get", "profile"]).status.success());
+    assert!(!freelist(home.path(), &[], &["config", "set", "colour", "blue"]).status.success());
+}
@@ -0,0 +1,89 @@
+// cli/tests/food.rs
+mod common;
+
+use common::{freelist, stdout};
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn test_log_food_and_show_today() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let oats = ["food", "add", "Rolled oats", "--kcal", "379", "--protein", "13.2", "--serving", "40"];
+    assert_eq!(stdout(freelist(&db, &oats)).trim(), "Added food with ID: 1");
+    stdout(freelist(&db, &["food", "add", "Oat milk", "--kcal", "46", "--set", "calcium=120"]));
+    stdout(freelist(&db, &["food", "add", "Apple", "--kcal", "52", "--set", "fibre=2.4"]));
+
+    let out = stdout(freelist(&db, &["food", "log", "rolled oats", "2x", "--meal", "breakfast"]));
+    assert_eq!(out.trim(), "Logged 80 g of Rolled oats (303 kcal) for breakfast");
+    stdout(freelist(&db, &["food", "log", "Oat milk", "250g", "--meal", "breakfast"]));
+    stdout(freelist(&db, &["food", "log", "3", "--meal", "snack"]));
+
+    let ambiguous = freelist(&db, &["food", "log", "oat"]);
+    assert!(!ambiguous.status.success());
+    assert!(String::from_utf8_lossy(&ambiguous.stderr).contains("matches several foods"));
+    assert!(!freelist(&db, &["food", "log", "Apple", "2x"]).status.success());
+
+    stdout(freelist(&db, &["food", "target", "calories", "2000"]));
+    let out = stdout(freelist(&db, &["food", "today", "--format", "json"]));
+    let day: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(day["entries"].as_array().unwrap().len(), 3);
+    assert_eq!(day["totals"]["energy_kcal"], 303.2 + 115.0 + 52.0);
+    assert_eq!(day["totals"]["calcium_mg"], 300.0);
+    assert_eq!(day["targets"]["energy_kcal"], 2000.0);
+
+    let table = stdout(freelist(&db, &["food", "today"]));
+    assert!(table.contains("\nBreakfast\n"), "{}", table);
+    assert!(table.contains("\nSnack\n"), "{}", table);
+    assert!(table.contains("energy_kcal         470 / 2000   1530 left"), "{}", table);
+
+    let out = stdout(freelist(&db, &["food", "today", "--format", "ndjson"]));
+    assert_eq!(out.lines().count(), 1);
+    let failed = freelist(&db, &["food", "today", "--format", "csv"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("`freelist food today` cannot print --format csv"));
+
+    let yesterday = stdout(freelist(&db, &["food", "today", "--date", "yesterday"]));
+    assert!(yesterday.contains("Nothing logged."), "{}", yesterday);
+}
+
+#[test]
+fn test_import_search_and_barcodes() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../core/tests/fixtures");
+
+    let dump = fixtures.join("openfoodfacts.tsv");
+    let out = stdout(freelist(&db, &["food", "import", dump.to_str().unwrap()]));
+    assert_eq!(out.trim(), "Imported 3 foods (3 new, 0 updated); skipped 2 without a name or nutrition facts");
+    let usda = fixtures.join("usda");
+    let out = stdout(freelist(&db, &["food", "import", usda.to_str().unwrap(), "--from", "usda"]));
+    assert!(out.starts_with("Imported 3 foods (3 new, 0 updated)"), "{}", out);
+
+    let found = stdout(freelist(&db, &["food", "search", "oats"]));
+    assert_eq!(found.lines().count(), 2, "{}", found);
+    assert!(found.contains("OLD FASHIONED OATS (Quaker)"), "{}", found);
+    assert!(stdout(freelist(&db, &["food", "search", "kale"])).contains("No food matches 'kale'"));
+
+    let out = stdout(freelist(&db, &["food", "barcode", "3017620422003"]));
+    assert!(out.contains("Nutella (Ferrero)"), "{}", out);
+    assert!(out.contains("539 kcal/100 g"), "{}", out);
+    assert!(!freelist(&db, &["food", "barcode", "12345678"]).status.success());
+
+    let out = stdout(freelist(&db, &["food", "log", "3017620422003", "--meal", "snack"]));
+    assert_eq!(out.trim(), "Logged 15 g of Nutella (81 kcal) for snack");
+
+    let out = stdout(freelist(&db, &["food", "search", "oats", "--format", "json"]));
+    let foods: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!(foods.len(), 2);
+    assert!(foods.iter().any(|food| food["brand"] == "Quaker"), "{}", out);
+    assert!(foods.iter().all(|food| food["per_100g"]["energy_kcal"].as_f64().unwrap() > 0.0), "{}", out);
+    let out = stdout(freelist(&db, &["food", "barcode", "3017620422003", "--format", "json"]));
+    let food: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!((food["name"].as_str(), food["per_100g"]["energy_kcal"].as_f64()), (Some("Nutella"), Some(539.0)));
+    let failed = freelist(&db, &["food", "search", "oats", "--format", "csv"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("`freelist food search` cannot print --format csv"));
+
+    stdout(freelist(&db, &["food", "add", "Sourdough", "--kcal", "250", "--barcode", "4006381333931"]));
+    assert!(stdout(freelist(&db, &["food", "barcode", "4006381333931"])).contains("Sourdough"));
+    assert!(!freelist(&db, &["food", "add", "Bagel", "--barcode", "bagel"]).status.success());
+}
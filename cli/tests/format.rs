@@ -0,0 +1,83 @@
+// cli/tests/format.rs
+mod common;
+
+use common::{freelist, stdout};
+use freelist_core::{Priority, Task, TaskList};
+use tempfile::TempDir;
+
+/// A database with a subtask, a completed task and one using every optional field.
+fn seeded() -> (TempDir, std::path::PathBuf, Vec<Task>) {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let mut task_list = TaskList::new(&db).unwrap();
+    let parent = task_list
+        .add(Task::new("Plan trip, \"Lisbon\"").with_tag("travel").with_priority(Priority::Urgent))
+        .unwrap();
+    let mut child = Task::new("Book flights")
+        .with_parent(parent)
+        .with_details("Window seat\nno red-eye")
+        .with_due_date("2030-05-01T09:00:00Z".parse().unwrap());
+    child.set_estimated_duration(45);
+    child.record_duration(50);
+    task_list.add(child).unwrap();
+    let done = task_list.add(Task::new("Renew passport")).unwrap();
+    task_list.mark_done(done).unwrap();
+    let tasks = task_list.all().unwrap();
+    (dir, db, tasks)
+}
+
+fn assert_same(parsed: &[Task], expected: &[Task]) {
+    assert_eq!(parsed.len(), expected.len());
+    for (parsed, expected) in parsed.iter().zip(expected) {
+        assert_eq!(serde_json::to_value(parsed).unwrap(), serde_json::to_value(expected).unwrap());
+    }
+}
+
+#[test]
+fn test_json_round_trips_into_tasks() {
+    let (_dir, db, expected) = seeded();
+    let out = stdout(freelist(&db, &["list", "--format", "json"]));
+    let parsed: Vec<Task> = serde_json::from_str(&out).unwrap();
+    assert_same(&parsed, &expected);
+
+    let todo: Vec<Task> = serde_json::from_str(&stdout(freelist(&db, &["list", "todo", "--format", "json"]))).unwrap();
+    assert_eq!(todo.len(), 2);
+    assert!(todo.iter().all(|task| !task.done));
+}
+
+#[test]
+fn test_ndjson_has_one_task_per_line() {
+    let (_dir, db, expected) = seeded();
+    let out = stdout(freelist(&db, &["list", "--format", "ndjson"]));
+    let parsed: Vec<Task> = out.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_same(&parsed, &expected);
+
+    assert_eq!(stdout(freelist(&db, &["list", "#nothing", "--format", "ndjson"])), "");
+}
+
+#[test]
+fn test_csv_round_trips_into_tasks() {
+    let (_dir, db, expected) = seeded();
+    let out = stdout(freelist(&db, &["list", "--format", "csv"]));
+    let mut reader = csv::Reader::from_reader(out.as_bytes());
+    assert_eq!(
+        reader.headers().unwrap().iter().collect::<Vec<_>>(),
+        [
+            "id", "title", "details", "done", "due_date", "is_recurring", "estimated_duration",
+            "last_duration", "tag", "priority", "parent_id", "created_at", "updated_at", "list_id", "position",
+        ]
+    );
+    let parsed: Vec<Task> = reader.deserialize().collect::<Result<_, _>>().unwrap();
+    assert_same(&parsed, &expected);
+}
+
+#[test]
+fn test_tags_formats() {
+    let (_dir, db, _) = seeded();
+    let tags: Vec<String> = serde_json::from_str(&stdout(freelist(&db, &["tags", "--format", "json"]))).unwrap();
+    assert_eq!(tags, ["travel"]);
+    assert_eq!(stdout(freelist(&db, &["tags", "--format", "csv"])), "tag\ntravel\n");
+    assert_eq!(stdout(freelist(&db, &["tags", "--format", "ndjson"])), "\"travel\"\n");
+    assert!(stdout(freelist(&db, &["tags"])).contains("  #travel"));
+    assert!(!freelist(&db, &["tags", "--format", "yaml"]).status.success());
+}
@@ -0,0 +1,71 @@
+// cli/tests/journal.rs
+mod common;
+
+use common::{freelist, stdout};
+use tempfile::TempDir;
+
+#[test]
+fn test_journal_entries_days_and_correlations() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let out = stdout(freelist(&db, &["journal"]));
+    assert!(out.starts_with("No journal entries in the last 7 days"), "{}", out);
+    let out = stdout(freelist(&db, &["journal", "correlate"]));
+    assert_eq!(out.trim(), "No journal ratings in the last 30 days");
+
+    for (at, mood) in [("3 days ago", "2"), ("2 days ago", "3"), ("yesterday", "5")] {
+        stdout(freelist(&db, &["journal", "add", "--mood", mood, "--slept", "7.5", "--at", at]));
+    }
+    let out = stdout(freelist(&db, &["journal", "add", "--energy", "4", "Long", "walk", "at", "lunch"]));
+    assert_eq!(out.trim(), "Added journal entry 4");
+    let failed = freelist(&db, &["journal", "add", "--mood", "7"]);
+    assert!(!failed.status.success());
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("mood must be from 1 to 5, not 7"));
+    for days in ["0", "4000000000"] {
+        let failed = freelist(&db, &["journal", "list", "--days", days]);
+        assert!(String::from_utf8_lossy(&failed.stderr).contains("is not in 1..=3660"), "--days {}", days);
+    }
+    let failed = freelist(&db, &["journal", "add"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("a journal entry needs a rating or a note"));
+
+    let out = stdout(freelist(&db, &["journal", "list"]));
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 4, "{}", out);
+    assert!(lines[0].ends_with("mood 2/5  slept 7.5 h"), "{}", out);
+    assert!(lines[3].ends_with("energy 4/5  Long walk at lunch"), "{}", out);
+
+    let out = stdout(freelist(&db, &["journal", "--format", "json"]));
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!(entries.len(), 4);
+    assert_eq!((entries[0]["mood"].as_u64(), entries[0]["sleep_hours"].as_f64()), (Some(2), Some(7.5)));
+    assert_eq!((entries[3]["id"].as_i64(), entries[3]["notes"].as_str()), (Some(4), Some("Long walk at lunch")));
+    let out = stdout(freelist(&db, &["journal", "list", "--format", "csv"]));
+    assert!(out.starts_with("id,recorded_at,mood,energy,sleep,sleep_hours,notes\n1,"), "{}", out);
+    assert!(out.ends_with(",,4,,,Long walk at lunch\n"), "{}", out);
+
+    stdout(freelist(&db, &["add", "Laundry", "--raw"]));
+    stdout(freelist(&db, &["done", "1"]));
+    let out = stdout(freelist(&db, &["journal", "days", "--days", "4", "--format", "json"]));
+    let days: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(days.as_array().unwrap().len(), 4);
+    assert_eq!(days[0]["mood"], 2.0);
+    assert_eq!(days[3]["energy"], 4.0);
+    assert_eq!(days[3]["tasks_completed"], 1);
+    let out = stdout(freelist(&db, &["journal", "days", "--days", "2"]));
+    assert!(out.lines().next().unwrap().ends_with("mood  energy  sleep  hours  tasks  meals    kcal"), "{}", out);
+    assert!(out.contains("   5.0       -      -    7.5      0      0       0"), "{}", out);
+
+    let out = stdout(freelist(&db, &["journal", "correlate"]));
+    assert!(out.starts_with("mood         vs tasks completed  "), "{}", out);
+    assert!(out.contains("(3 days)"), "{}", out);
+    assert!(out.contains("not enough to tell"), "{}", out);
+    let out = stdout(freelist(&db, &["journal", "correlate", "--format", "json"]));
+    let correlations: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(correlations[0]["wellbeing"], "mood");
+    let out = stdout(freelist(&db, &["journal", "correlate", "--format", "ndjson"]));
+    assert_eq!(out.lines().count(), correlations.as_array().unwrap().len());
+
+    let out = stdout(freelist(&db, &["journal", "delete", "4"]));
+    assert_eq!(out.trim(), "Deleted journal entry 4");
+    assert!(!freelist(&db, &["journal", "delete", "4"]).status.success());
+}
@@ -0,0 +1,96 @@
+// cli/tests/lists.rs
+mod common;
+
+use common::{freelist, stdout};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn titles(db: &Path, filter: &str) -> Vec<String> {
+    let out = stdout(freelist(db, &["list", filter, "--format", "json"]));
+    let tasks: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    let mut titles: Vec<String> = tasks.iter().map(|task| task["title"].as_str().unwrap().to_string()).collect();
+    titles.sort();
+    titles
+}
+
+#[test]
+fn test_lists_hold_tasks() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let out = stdout(freelist(&db, &["lists"]));
+    assert!(out.starts_with("No lists yet"), "{}", out);
+
+    let out = stdout(freelist(&db, &["lists", "add", "Groceries", "--colour", "#2E8B57", "--icon", "🛒"]));
+    assert_eq!(out.trim(), "Added list Groceries (1)");
+    stdout(freelist(&db, &["lists", "add", "Work"]));
+    let failed = freelist(&db, &["lists", "add", "work"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("there is already a list called work"));
+    let failed = freelist(&db, &["lists", "add", "Home", "--colour", "green"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("'green' is not a colour"));
+
+    stdout(freelist(&db, &["add", "Milk", "--list", "groceries"]));
+    stdout(freelist(&db, &["add", "Eggs", "--list", "1"]));
+    stdout(freelist(&db, &["add", "Report"]));
+    assert!(!freelist(&db, &["add", "Bread", "--list", "Bakery"]).status.success());
+    stdout(freelist(&db, &["lists", "move", "3", "Work"]));
+    stdout(freelist(&db, &["done", "2"]));
+    assert_eq!(titles(&db, "@Groceries"), ["Eggs", "Milk"]);
+    assert_eq!(titles(&db, "@work"), ["Report"]);
+
+    let out = stdout(freelist(&db, &["lists"]));
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines[0].contains("🛒 Groceries") && lines[0].contains("#2e8b57") && lines[0].ends_with("1 open"));
+    assert!(lines[1].contains("Work"));
+
+    stdout(freelist(&db, &["lists", "edit", "work", "--position", "-1", "--name", "Office"]));
+    stdout(freelist(&db, &["lists", "archive", "groceries"]));
+    assert_eq!(stdout(freelist(&db, &["lists"])).lines().count(), 1);
+    let out = stdout(freelist(&db, &["lists", "show", "--all"]));
+    assert!(out.lines().next().unwrap().contains("Office"));
+    assert!(out.lines().nth(1).unwrap().ends_with("(archived)"));
+
+    stdout(freelist(&db, &["lists", "move", "3"]));
+    assert!(titles(&db, "@office").is_empty());
+    stdout(freelist(&db, &["lists", "remove", "Groceries"]));
+    assert!(!freelist(&db, &["list", "@groceries"]).status.success());
+    assert_eq!(titles(&db, "todo"), ["Milk", "Report"]);
+}
+
+#[test]
+fn test_lists_from_tags() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    stdout(freelist(&db, &["add", "Milk #groceries"]));
+    stdout(freelist(&db, &["add", "Bread #groceries"]));
+    stdout(freelist(&db, &["add", "Email Sam #work"]));
+    stdout(freelist(&db, &["add", "Untagged"]));
+
+    let out = stdout(freelist(&db, &["lists", "from-tags"]));
+    assert_eq!(out.trim(), "Added 2 lists and moved 3 tasks into lists named after their tags");
+    assert_eq!(titles(&db, "@groceries"), ["Bread", "Milk"]);
+    assert_eq!(titles(&db, "#groceries"), ["Bread", "Milk"]);
+    let out = stdout(freelist(&db, &["lists", "from-tags"]));
+    assert!(out.starts_with("Added 0 lists and moved 0 tasks"));
+}
+
+#[test]
+fn test_lists_as_json() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    stdout(freelist(&db, &["lists", "add", "Groceries", "--colour", "#2E8B57", "--icon", "🛒"]));
+    stdout(freelist(&db, &["lists", "add", "Work"]));
+    stdout(freelist(&db, &["lists", "archive", "work"]));
+
+    let out = stdout(freelist(&db, &["lists", "--format", "json"]));
+    let lists: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!(lists.len(), 1);
+    assert_eq!((lists[0]["id"].as_i64(), lists[0]["colour"].as_str()), (Some(1), Some("#2e8b57")));
+    assert_eq!(lists[0]["sort_order"], 0);
+    let out = stdout(freelist(&db, &["lists", "show", "--all", "--format", "ndjson"]));
+    let archived: Vec<bool> =
+        out.lines().map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["archived"] == true).collect();
+    assert_eq!(archived, [false, true]);
+    let out = stdout(freelist(&db, &["lists", "--format", "csv"]));
+    assert!(out.starts_with("id,name,colour,icon,sort_order,archived,created_at\n1,Groceries,#2e8b57,🛒,0,false,"));
+    assert!(!freelist(&db, &["lists", "--format", "json", "add", "Home"]).status.success());
+}
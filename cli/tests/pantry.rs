@@ -0,0 +1,71 @@
+// cli/tests/pantry.rs
+mod common;
+
+use common::{freelist, stdout};
+use tempfile::TempDir;
+
+#[test]
+fn test_eating_and_cooking_use_up_the_pantry() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    stdout(freelist(&db, &["food", "add", "Egg", "--kcal", "143", "--serving", "50"]));
+    let out = stdout(freelist(&db, &["pantry", "add", "Eggs", "6", "--food", "Egg"]));
+    assert_eq!(out.trim(), "Added 6 of Eggs to the pantry with ID: 1");
+    stdout(freelist(&db, &["pantry", "add", "Milk", "1l"]));
+    stdout(freelist(&db, &["pantry", "add", "Flour", "1.5kg"]));
+
+    let out = stdout(freelist(&db, &["food", "log", "egg", "2x", "--meal", "breakfast", "--pantry"]));
+    assert!(out.contains("Used from the pantry:\n  Eggs"), "{}", out);
+    assert!(out.contains("4 left"), "{}", out);
+
+    let ingredients = ["200g flour", "300 ml milk", "2 eggs", "1 tbsp sugar"];
+    let pancakes = [&["recipe", "add", "Pancakes", "--serves", "4"][..], &ingredients].concat();
+    assert_eq!(stdout(freelist(&db, &pancakes)).trim(), "Added recipe with ID: 1");
+    assert!(!freelist(&db, &["recipe", "add", "Toast", "some bread"]).status.success());
+    let list = stdout(freelist(&db, &["recipe", "list"]));
+    assert!(list.contains("Pancakes (serves 4)\n        200 g flour\n        300 ml milk"), "{}", list);
+
+    let out = stdout(freelist(&db, &["recipe", "cook", "pancakes", "--batches", "2"]));
+    assert!(out.starts_with("Cooked Pancakes\nUsed from the pantry:\n"), "{}", out);
+    assert!(out.contains("0.4 kg"), "{}", out);
+    assert!(out.contains("  Eggs                             4          0 left"), "{}", out);
+    let missing = "Not taken from the pantry:\n  sugar                            2 tbsp     not in the pantry";
+    assert!(out.contains(missing), "{}", out);
+
+    let pantry = stdout(freelist(&db, &["pantry"]));
+    assert!(pantry.contains("Flour                            1.1 kg"), "{}", pantry);
+    assert!(pantry.contains("Milk                             0.4 l"), "{}", pantry);
+
+    stdout(freelist(&db, &["pantry", "set", "2", "0"]));
+    assert!(stdout(freelist(&db, &["pantry", "list"])).contains("Milk                             out"));
+    assert!(!freelist(&db, &["pantry", "remove", "9"]).status.success());
+}
+
+#[test]
+fn test_pantry_and_recipes_as_json() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    stdout(freelist(&db, &["pantry", "add", "Milk", "1.5l"]));
+    stdout(freelist(&db, &["pantry", "add", "Eggs", "6"]));
+    stdout(freelist(&db, &["recipe", "add", "Omelette", "3 eggs", "50 ml milk", "--serves", "2"]));
+
+    let out = stdout(freelist(&db, &["pantry", "--format", "json"]));
+    let items: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    let amounts: Vec<(&str, f64, &str)> = items
+        .iter()
+        .map(|item| {
+            (item["name"].as_str().unwrap(), item["quantity"].as_f64().unwrap(), item["unit"].as_str().unwrap())
+        })
+        .collect();
+    assert_eq!(amounts, [("Eggs", 6.0, "piece"), ("Milk", 1.5, "l")]);
+    let out = stdout(freelist(&db, &["pantry", "list", "--format", "csv"]));
+    assert!(out.starts_with("id,name,food_id,quantity,unit,updated_at\n2,Eggs,,6.0,piece,"), "{}", out);
+    assert!(out.contains("\n1,Milk,,1.5,l,"), "{}", out);
+
+    let out = stdout(freelist(&db, &["recipe", "list", "--format", "json"]));
+    let recipes: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!((recipes[0]["name"].as_str(), recipes[0]["servings"].as_f64()), (Some("Omelette"), Some(2.0)));
+    assert_eq!(recipes[0]["ingredients"][1]["unit"], "ml");
+    let failed = freelist(&db, &["recipe", "list", "--format", "csv"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("`freelist recipe list` cannot print --format csv"));
+}
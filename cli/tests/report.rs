@@ -0,0 +1,50 @@
+// cli/tests/report.rs
+mod common;
+
+use chrono::{Datelike, Duration, Local};
+use common::{freelist, stdout};
+use tempfile::TempDir;
+
+#[test]
+fn test_report_formats_and_ranges() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let out = stdout(freelist(&db, &["report"]));
+    assert!(out.starts_with("Review "), "{}", out);
+    assert!(out.ends_with("\nCompleted 0 tasks, created 0, 0 overdue\n"), "{}", out);
+
+    for title in ["Water plants #home", "Write report #work", "Send invoice #work", "Renew passport"] {
+        stdout(freelist(&db, &["add", title]));
+    }
+    for id in ["1", "2", "3"] {
+        stdout(freelist(&db, &["done", id]));
+    }
+
+    let out = stdout(freelist(&db, &["report", "--week"]));
+    let today = Local::now().date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+    let header = if monday == today {
+        format!("Review {}\n", today)
+    } else {
+        format!("Review {} to {}\n", monday, today)
+    };
+    assert!(out.starts_with(&header), "{}", out);
+    assert!(out.contains("Completed 3 tasks, created 4, 0 overdue\n"), "{}", out);
+    assert!(out.contains("\nCompleted by tag\n  #work                   2\n  #home                   1\n"), "{}", out);
+    let out = stdout(freelist(&db, &["report", "--day", "--format", "markdown"]));
+    assert!(out.starts_with("# Review "), "{}", out);
+    assert!(!out.contains(" to "), "{}", out);
+    assert!(out.contains("| #work | 2 |\n"), "{}", out);
+
+    let out = stdout(freelist(&db, &["report", "--from", "2 weeks ago", "--format", "json"]));
+    let review: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(review["completed"], 3);
+    assert_eq!(review["by_tag"][0]["tag"], "work");
+    assert_eq!(review["habits"], serde_json::json!([]));
+    let failed = freelist(&db, &["report", "--format", "csv"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("`freelist report` cannot print --format csv"));
+
+    let failed = freelist(&db, &["report", "--from", "today", "--to", "yesterday"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("is before --from"));
+    assert!(!freelist(&db, &["report", "--day", "--week"]).status.success());
+}
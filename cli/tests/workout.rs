@@ -0,0 +1,173 @@
+// cli/tests/workout.rs
+mod common;
+
+use common::{freelist, stdout};
+use tempfile::TempDir;
+
+#[test]
+fn test_logging_sets_records_and_volume() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let out = stdout(freelist(&db, &["workout"]));
+    assert!(out.starts_with("No workout in progress"), "{}", out);
+
+    let out = stdout(freelist(&db, &["workout", "start", "Legs"]));
+    assert_eq!(out.trim(), "Started workout 1");
+    let failed = freelist(&db, &["workout", "start"]);
+    assert!(!failed.status.success());
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("still in progress"));
+
+    let out = stdout(freelist(&db, &["workout", "log", "Squat", "5x100kg", "5 @ 100kg"]));
+    assert_eq!(out, "Added strength exercise Squat\nLogged Squat 5 × 100 kg\nLogged Squat 5 × 100 kg\n");
+    let out = stdout(freelist(&db, &["workout", "log", "squat", "3x110kg"]));
+    assert!(out.contains("New personal record: max weight 110 kg (was 100 kg)"), "{}", out);
+    assert!(!out.contains("max set volume"), "{}", out);
+    let out = stdout(freelist(&db, &["workout", "log", "Run", "5km in 25:30"]));
+    assert!(out.starts_with("Added cardio exercise Run\n"), "{}", out);
+    let failed = freelist(&db, &["workout", "log", "Squat", "heavy"]);
+    assert!(!failed.status.success());
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("cannot understand set 'heavy'"));
+
+    let out = stdout(freelist(&db, &["workout", "show"]));
+    assert!(out.starts_with("Workout 1: Legs ("), "{}", out);
+    assert!(out.contains("in progress"), "{}", out);
+    assert!(out.contains("Squat                    5 × 100 kg, 5 × 100 kg, 3 × 110 kg\n"), "{}", out);
+    assert!(out.contains("Run                      5.00 km in 25:30\n"), "{}", out);
+
+    let out = stdout(freelist(&db, &["workout", "finish"]));
+    assert!(out.starts_with("Finished workout 1: 4 sets in 0:0"), "{}", out);
+    assert!(!freelist(&db, &["workout", "finish"]).status.success());
+
+    let out = stdout(freelist(&db, &["workout", "prs", "SQUAT"]));
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", out);
+    assert!(lines[0].starts_with("Squat                    max weight         110 kg"), "{}", out);
+    assert!(lines[1].contains("estimated 1RM      121 kg"), "{}", out);
+    assert!(lines[2].contains("max set volume     500 kg"), "{}", out);
+    let out = stdout(freelist(&db, &["workout", "prs"]));
+    assert!(out.contains("fastest pace       5:06/km"), "{}", out);
+
+    let out = stdout(freelist(&db, &["workout", "volume", "--weeks", "1", "--format", "json"]));
+    let weeks: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(weeks[0]["workouts"], 1);
+    assert_eq!(weeks[0]["sets"], 4);
+    assert_eq!(weeks[0]["volume_kg"], 1330.0);
+    assert_eq!(weeks[0]["by_exercise"]["Run"]["distance_m"], 5000.0);
+    let out = stdout(freelist(&db, &["workout", "volume", "--weeks", "3", "--format", "ndjson"]));
+    assert_eq!(out.lines().count(), 3);
+    for weeks in ["0", "4000000000"] {
+        let failed = freelist(&db, &["workout", "volume", "--weeks", weeks]);
+        assert!(String::from_utf8_lossy(&failed.stderr).contains("is not in 1..=520"), "--weeks {}", weeks);
+        let failed = freelist(&db, &["workout", "list", "--weeks", weeks]);
+        assert!(String::from_utf8_lossy(&failed.stderr).contains("is not in 1..=520"), "--weeks {}", weeks);
+    }
+    let out = stdout(freelist(&db, &["workout", "volume", "--weeks", "2"]));
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines[0].ends_with(": no workouts"), "{}", out);
+    assert!(lines[1].ends_with(": 1 workout, 4 sets, 13 reps, 1330 kg lifted, 5.00 km, 25:30"), "{}", out);
+
+    let out = stdout(freelist(&db, &["workout", "list"]));
+    assert!(out.contains("Legs"), "{}", out);
+    assert!(out.contains("4 sets"), "{}", out);
+    let out = stdout(freelist(&db, &["workout", "exercises"]));
+    assert!(out.contains("Run                              cardio"), "{}", out);
+
+    let out = stdout(freelist(&db, &["workout", "delete", "1"]));
+    assert_eq!(out.trim(), "Deleted workout 1");
+    assert!(!freelist(&db, &["workout", "delete", "1"]).status.success());
+    let out = stdout(freelist(&db, &["workout", "prs"]));
+    assert!(out.starts_with("No personal records yet"), "{}", out);
+}
+
+#[test]
+fn test_workouts_as_json() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    assert_eq!(stdout(freelist(&db, &["workout", "--format", "json"])), "null\n");
+    stdout(freelist(&db, &["workout", "start", "Legs"]));
+    stdout(freelist(&db, &["workout", "log", "Squat", "5x100kg", "3x110kg"]));
+    stdout(freelist(&db, &["workout", "log", "Run", "5km in 25:30"]));
+
+    let out = stdout(freelist(&db, &["workout", "--format", "json"]));
+    let workout: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!((workout["id"].as_i64(), workout["title"].as_str()), (Some(1), Some("Legs")));
+    assert_eq!(workout["ended_at"], serde_json::Value::Null);
+    assert_eq!(workout["sets"].as_array().unwrap().len(), 3);
+    let out = stdout(freelist(&db, &["workout", "show", "1", "--format", "ndjson"]));
+    assert_eq!(out.lines().count(), 1);
+    stdout(freelist(&db, &["workout", "finish"]));
+
+    let out = stdout(freelist(&db, &["workout", "list", "--format", "json"]));
+    let workouts: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!(workouts.len(), 1);
+    assert!(workouts[0]["ended_at"].is_string(), "{}", out);
+    let failed = freelist(&db, &["workout", "list", "--format", "csv"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("`freelist workout list` cannot print --format csv"));
+
+    let out = stdout(freelist(&db, &["workout", "exercises", "--format", "json"]));
+    let exercises: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    let kinds: Vec<(&str, &str)> = exercises
+        .iter()
+        .map(|exercise| (exercise["name"].as_str().unwrap(), exercise["kind"].as_str().unwrap()))
+        .collect();
+    assert_eq!(kinds, [("Run", "cardio"), ("Squat", "strength")]);
+    let out = stdout(freelist(&db, &["workout", "exercises", "--format", "csv"]));
+    assert!(out.starts_with("id,name,kind,created_at\n2,Run,cardio,"), "{}", out);
+
+    let out = stdout(freelist(&db, &["workout", "prs", "squat", "--format", "json"]));
+    let records: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!((records[0]["kind"].as_str(), records[0]["value"].as_f64()), (Some("max_weight"), Some(110.0)));
+    assert_eq!(records[0]["previous"], 100.0);
+    let out = stdout(freelist(&db, &["workout", "prs", "--format", "csv"]));
+    assert!(out.starts_with("exercise_id,exercise,kind,value,previous,set_id,achieved_at\n"), "{}", out);
+    assert!(out.contains("\n2,Run,longest_distance,5000.0,,3,"), "{}", out);
+}
+
+#[test]
+fn test_training_plans_schedule_tasks() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path().join("tasks.db");
+    let out = stdout(freelist(&db, &["workout", "plan", "add", "Base", "--session", "daily 23:59 Stretch"]));
+    assert_eq!(out, "Added training plan with ID: 1\nScheduled 28 sessions in the next 4 weeks as #training tasks\n");
+    let failed = freelist(&db, &["workout", "plan", "add", "Broken", "--session", "someday Run"]);
+    assert!(!failed.status.success());
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("cannot understand session 'someday Run'"));
+
+    let out = stdout(freelist(&db, &["workout", "plan"]));
+    assert!(out.contains("   1  Base (#training, from "), "{}", out);
+    assert!(out.contains("        mon,tue,wed,thu,fri,sat,sun 23:59 Stretch\n"), "{}", out);
+    let out = stdout(freelist(&db, &["workout", "plan", "--format", "json"]));
+    let plans: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    assert_eq!((plans[0]["name"].as_str(), plans[0]["tag"].as_str()), (Some("Base"), Some("training")));
+    assert_eq!(plans[0]["sessions"][0]["title"], "Stretch");
+    assert_eq!(plans[0]["sessions"][0]["days"].as_array().unwrap().len(), 7);
+    assert_eq!(stdout(freelist(&db, &["workout", "plan", "list", "--format", "ndjson"])).lines().count(), 1);
+    let out = stdout(freelist(&db, &["workout", "plan", "schedule", "base", "--weeks", "5"]));
+    assert_eq!(out.trim(), "Scheduled 7 sessions of Base");
+    let failed = freelist(&db, &["workout", "plan", "schedule", "--weeks", "4000000000"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("is not in 1..=52"));
+    let out = stdout(freelist(&db, &["list", "#training", "--format", "json"]));
+    let tasks: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(tasks.as_array().unwrap().len(), 35);
+
+    // The first task is today's session
+    let out = stdout(freelist(&db, &["done", "1"]));
+    let prompt = "Log the workout with `freelist workout start --task 1`, then `freelist workout log`";
+    assert_eq!(out, format!("Marked task 1 as done\n{}\n", prompt));
+    let out = stdout(freelist(&db, &["workout", "start", "--task", "1"]));
+    assert_eq!(out, "Started workout 1\nMarked task 1 as done\n");
+    assert!(stdout(freelist(&db, &["workout", "show"])).starts_with("Workout 1: Stretch ("));
+    stdout(freelist(&db, &["workout", "finish"]));
+
+    // Not a plan task, so no workout is mentioned
+    stdout(freelist(&db, &["add", "Laundry", "--raw"]));
+    assert_eq!(stdout(freelist(&db, &["done", "36"])), "Marked task 36 as done\n");
+    let failed = freelist(&db, &["workout", "start", "--task", "36"]);
+    assert!(String::from_utf8_lossy(&failed.stderr).contains("Task 36 was not scheduled by a training plan"));
+
+    let out = stdout(freelist(&db, &["workout", "plan", "remove", "Base"]));
+    assert_eq!(out.trim(), "Deleted training plan Base and its open tasks");
+    let out = stdout(freelist(&db, &["list", "#training", "--format", "json"]));
+    let tasks: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(tasks.as_array().unwrap().len(), 1);
+}
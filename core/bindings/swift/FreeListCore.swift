@@ -0,0 +1,1760 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// swiftlint:disable all
+import Foundation
+
+// Depending on the consumer's build setup, the low-level FFI code
+// might be in a separate module, or it might be compiled inline into
+// this module. This is a bit of light hackery to work with both.
+#if canImport(freelistFFI)
+import freelistFFI
+#endif
+
+fileprivate extension RustBuffer {
+    // Allocate a new buffer, copying the contents of a `UInt8` array.
+    init(bytes: [UInt8]) {
+        let rbuf = bytes.withUnsafeBufferPointer { ptr in
+            RustBuffer.from(ptr)
+        }
+        self.init(capacity: rbuf.capacity, len: rbuf.len, data: rbuf.data)
+    }
+
+    static func empty() -> RustBuffer {
+        RustBuffer(capacity: 0, len:0, data: nil)
+    }
+
+    static func from(_ ptr: UnsafeBufferPointer<UInt8>) -> RustBuffer {
+        try! rustCall { ffi_core_rustbuffer_from_bytes(ForeignBytes(bufferPointer: ptr), $0) }
+    }
+
+    // Frees the buffer in place.
+    // The buffer must not be used after this is called.
+    func deallocate() {
+        try! rustCall { ffi_core_rustbuffer_free(self, $0) }
+    }
+}
+
+fileprivate extension ForeignBytes {
+    init(bufferPointer: UnsafeBufferPointer<UInt8>) {
+        self.init(len: Int32(bufferPointer.count), data: bufferPointer.baseAddress)
+    }
+}
+
+// For every type used in the interface, we provide helper methods for conveniently
+// lifting and lowering that type from C-compatible data, and for reading and writing
+// values of that type in a buffer.
+
+// Helper classes/extensions that don't change.
+// Someday, this will be in a library of its own.
+
+fileprivate extension Data {
+    init(rustBuffer: RustBuffer) {
+        self.init(
+            bytesNoCopy: rustBuffer.data!,
+            count: Int(rustBuffer.len),
+            deallocator: .none
+        )
+    }
+}
+
+// Define reader functionality.  Normally this would be defined in a class or
+// struct, but we use standalone functions instead in order to make external
+// types work.
+//
+// With external types, one swift source file needs to be able to call the read
+// method on another source file's FfiConverter, but then what visibility
+// should Reader have?
+// - If Reader is fileprivate, then this means the read() must also
+//   be fileprivate, which doesn't work with external types.
+// - If Reader is internal/public, we'll get compile errors since both source
+//   files will try define the same type.
+//
+// Instead, the read() method and these helper functions input a tuple of data
+
+fileprivate func createReader(data: Data) -> (data: Data, offset: Data.Index) {
+    (data: data, offset: 0)
+}
+
+// Reads an integer at the current offset, in big-endian order, and advances
+// the offset on success. Throws if reading the integer would move the
+// offset past the end of the buffer.
+fileprivate func readInt<T: FixedWidthInteger>(_ reader: inout (data: Data, offset: Data.Index)) throws -> T {
+    let range = reader.offset..<reader.offset + MemoryLayout<T>.size
+    guard reader.data.count >= range.upperBound else {
+        throw UniffiInternalError.bufferOverflow
+    }
+    if T.self == UInt8.self {
+        let value = reader.data[reader.offset]
+        reader.offset += 1
+        return value as! T
+    }
+    var value: T = 0
+    let _ = withUnsafeMutableBytes(of: &value, { reader.data.copyBytes(to: $0, from: range)})
+    reader.offset = range.upperBound
+    return value.bigEndian
+}
+
+// Reads an arbitrary number of bytes, to be used to read
+// raw bytes, this is useful when lifting strings
+fileprivate func readBytes(_ reader: inout (data: Data, offset: Data.Index), count: Int) throws -> Array<UInt8> {
+    let range = reader.offset..<(reader.offset+count)
+    guard reader.data.count >= range.upperBound else {
+        throw UniffiInternalError.bufferOverflow
+    }
+    var value = [UInt8](repeating: 0, count: count)
+    value.withUnsafeMutableBufferPointer({ buffer in
+        reader.data.copyBytes(to: buffer, from: range)
+    })
+    reader.offset = range.upperBound
+    return value
+}
+
+// Reads a float at the current offset.
+fileprivate func readFloat(_ reader: inout (data: Data, offset: Data.Index)) throws -> Float {
+    return Float(bitPattern: try readInt(&reader))
+}
+
+// Reads a float at the current offset.
+fileprivate func readDouble(_ reader: inout (data: Data, offset: Data.Index)) throws -> Double {
+    return Double(bitPattern: try readInt(&reader))
+}
+
+// Indicates if the offset has reached the end of the buffer.
+fileprivate func hasRemaining(_ reader: (data: Data, offset: Data.Index)) -> Bool {
+    return reader.offset < reader.data.count
+}
+
+// Define writer functionality.  Normally this would be defined in a class or
+// struct, but we use standalone functions instead in order to make external
+// types work.  See the above discussion on Readers for details.
+
+fileprivate func createWriter() -> [UInt8] {
+    return []
+}
+
+fileprivate func writeBytes<S>(_ writer: inout [UInt8], _ byteArr: S) where S: Sequence, S.Element == UInt8 {
+    writer.append(contentsOf: byteArr)
+}
+
+// Writes an integer in big-endian order.
+//
+// Warning: make sure what you are trying to write
+// is in the correct type!
+fileprivate func writeInt<T: FixedWidthInteger>(_ writer: inout [UInt8], _ value: T) {
+    var value = value.bigEndian
+    withUnsafeBytes(of: &value) { writer.append(contentsOf: $0) }
+}
+
+fileprivate func writeFloat(_ writer: inout [UInt8], _ value: Float) {
+    writeInt(&writer, value.bitPattern)
+}
+
+fileprivate func writeDouble(_ writer: inout [UInt8], _ value: Double) {
+    writeInt(&writer, value.bitPattern)
+}
+
+// Protocol for types that transfer other types across the FFI. This is
+// analogous to the Rust trait of the same name.
+fileprivate protocol FfiConverter {
+    associatedtype FfiType
+    associatedtype SwiftType
+
+    static func lift(_ value: FfiType) throws -> SwiftType
+    static func lower(_ value: SwiftType) -> FfiType
+    static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType
+    static func write(_ value: SwiftType, into buf: inout [UInt8])
+}
+
+// Types conforming to `Primitive` pass themselves directly over the FFI.
+fileprivate protocol FfiConverterPrimitive: FfiConverter where FfiType == SwiftType { }
+
+extension FfiConverterPrimitive {
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public static func lift(_ value: FfiType) throws -> SwiftType {
+        return value
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public static func lower(_ value: SwiftType) -> FfiType {
+        return value
+    }
+}
+
+// Types conforming to `FfiConverterRustBuffer` lift and lower into a `RustBuffer`.
+// Used for complex types where it's hard to write a custom lift/lower.
+fileprivate protocol FfiConverterRustBuffer: FfiConverter where FfiType == RustBuffer {}
+
+extension FfiConverterRustBuffer {
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public static func lift(_ buf: RustBuffer) throws -> SwiftType {
+        var reader = createReader(data: Data(rustBuffer: buf))
+        let value = try read(from: &reader)
+        if hasRemaining(reader) {
+            throw UniffiInternalError.incompleteData
+        }
+        buf.deallocate()
+        return value
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public static func lower(_ value: SwiftType) -> RustBuffer {
+          var writer = createWriter()
+          write(value, into: &writer)
+          return RustBuffer(bytes: writer)
+    }
+}
+// An error type for FFI errors. These errors occur at the UniFFI level, not
+// the library level.
+fileprivate enum UniffiInternalError: LocalizedError {
+    case bufferOverflow
+    case incompleteData
+    case unexpectedOptionalTag
+    case unexpectedEnumCase
+    case unexpectedNullPointer
+    case unexpectedRustCallStatusCode
+    case unexpectedRustCallError
+    case unexpectedStaleHandle
+    case rustPanic(_ message: String)
+
+    public var errorDescription: String? {
+        switch self {
+        case .bufferOverflow: return "Reading the requested value would read past the end of the buffer"
+        case .incompleteData: return "The buffer still has data after lifting its containing value"
+        case .unexpectedOptionalTag: return "Unexpected optional tag; should be 0 or 1"
+        case .unexpectedEnumCase: return "Raw enum value doesn't match any cases"
+        case .unexpectedNullPointer: return "Raw pointer value was null"
+        case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
+        case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
+        case .unexpectedStaleHandle: return "The object in the handle map has been dropped already"
+        case let .rustPanic(message): return message
+        }
+    }
+}
+
+fileprivate extension NSLock {
+    func withLock<T>(f: () throws -> T) rethrows -> T {
+        self.lock()
+        defer { self.unlock() }
+        return try f()
+    }
+}
+
+fileprivate let CALL_SUCCESS: Int8 = 0
+fileprivate let CALL_ERROR: Int8 = 1
+fileprivate let CALL_UNEXPECTED_ERROR: Int8 = 2
+fileprivate let CALL_CANCELLED: Int8 = 3
+
+fileprivate extension RustCallStatus {
+    init() {
+        self.init(
+            code: CALL_SUCCESS,
+            errorBuf: RustBuffer.init(
+                capacity: 0,
+                len: 0,
+                data: nil
+            )
+        )
+    }
+}
+
+private func rustCall<T>(_ callback: (UnsafeMutablePointer<RustCallStatus>) -> T) throws -> T {
+    let neverThrow: ((RustBuffer) throws -> Never)? = nil
+    return try makeRustCall(callback, errorHandler: neverThrow)
+}
+
+private func rustCallWithError<T, E: Swift.Error>(
+    _ errorHandler: @escaping (RustBuffer) throws -> E,
+    _ callback: (UnsafeMutablePointer<RustCallStatus>) -> T) throws -> T {
+    try makeRustCall(callback, errorHandler: errorHandler)
+}
+
+private func makeRustCall<T, E: Swift.Error>(
+    _ callback: (UnsafeMutablePointer<RustCallStatus>) -> T,
+    errorHandler: ((RustBuffer) throws -> E)?
+) throws -> T {
+    uniffiEnsureInitialized()
+    var callStatus = RustCallStatus.init()
+    let returnedVal = callback(&callStatus)
+    try uniffiCheckCallStatus(callStatus: callStatus, errorHandler: errorHandler)
+    return returnedVal
+}
+
+private func uniffiCheckCallStatus<E: Swift.Error>(
+    callStatus: RustCallStatus,
+    errorHandler: ((RustBuffer) throws -> E)?
+) throws {
+    switch callStatus.code {
+        case CALL_SUCCESS:
+            return
+
+        case CALL_ERROR:
+            if let errorHandler = errorHandler {
+                throw try errorHandler(callStatus.errorBuf)
+            } else {
+                callStatus.errorBuf.deallocate()
+                throw UniffiInternalError.unexpectedRustCallError
+            }
+
+        case CALL_UNEXPECTED_ERROR:
+            // When the rust code sees a panic, it tries to construct a RustBuffer
+            // with the message.  But if that code panics, then it just sends back
+            // an empty buffer.
+            if callStatus.errorBuf.len > 0 {
+                throw UniffiInternalError.rustPanic(try FfiConverterString.lift(callStatus.errorBuf))
+            } else {
+                callStatus.errorBuf.deallocate()
+                throw UniffiInternalError.rustPanic("Rust panic")
+            }
+
+        case CALL_CANCELLED:
+            fatalError("Cancellation not supported yet")
+
+        default:
+            throw UniffiInternalError.unexpectedRustCallStatusCode
+    }
+}
+
+private func uniffiTraitInterfaceCall<T>(
+    callStatus: UnsafeMutablePointer<RustCallStatus>,
+    makeCall: () throws -> T,
+    writeReturn: (T) -> ()
+) {
+    do {
+        try writeReturn(makeCall())
+    } catch let error {
+        callStatus.pointee.code = CALL_UNEXPECTED_ERROR
+        callStatus.pointee.errorBuf = FfiConverterString.lower(String(describing: error))
+    }
+}
+
+private func uniffiTraitInterfaceCallWithError<T, E>(
+    callStatus: UnsafeMutablePointer<RustCallStatus>,
+    makeCall: () throws -> T,
+    writeReturn: (T) -> (),
+    lowerError: (E) -> RustBuffer
+) {
+    do {
+        try writeReturn(makeCall())
+    } catch let error as E {
+        callStatus.pointee.code = CALL_ERROR
+        callStatus.pointee.errorBuf = lowerError(error)
+    } catch {
+        callStatus.pointee.code = CALL_UNEXPECTED_ERROR
+        callStatus.pointee.errorBuf = FfiConverterString.lower(String(describing: error))
+    }
+}
+fileprivate class UniffiHandleMap<T> {
+    private var map: [UInt64: T] = [:]
+    private let lock = NSLock()
+    private var currentHandle: UInt64 = 1
+
+    func insert(obj: T) -> UInt64 {
+        lock.withLock {
+            let handle = currentHandle
+            currentHandle += 1
+            map[handle] = obj
+            return handle
+        }
+    }
+
+     func get(handle: UInt64) throws -> T {
+        try lock.withLock {
+            guard let obj = map[handle] else {
+                throw UniffiInternalError.unexpectedStaleHandle
+            }
+            return obj
+        }
+    }
+
+    @discardableResult
+    func remove(handle: UInt64) throws -> T {
+        try lock.withLock {
+            guard let obj = map.removeValue(forKey: handle) else {
+                throw UniffiInternalError.unexpectedStaleHandle
+            }
+            return obj
+        }
+    }
+
+    var count: Int {
+        get {
+            map.count
+        }
+    }
+}
+
+
+// Public interface members begin here.
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterUInt32: FfiConverterPrimitive {
+    typealias FfiType = UInt32
+    typealias SwiftType = UInt32
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> UInt32 {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterInt32: FfiConverterPrimitive {
+    typealias FfiType = Int32
+    typealias SwiftType = Int32
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Int32 {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: Int32, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterInt64: FfiConverterPrimitive {
+    typealias FfiType = Int64
+    typealias SwiftType = Int64
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Int64 {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: Int64, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterBool : FfiConverter {
+    typealias FfiType = Int8
+    typealias SwiftType = Bool
+
+    public static func lift(_ value: Int8) throws -> Bool {
+        return value != 0
+    }
+
+    public static func lower(_ value: Bool) -> Int8 {
+        return value ? 1 : 0
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Bool {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: Bool, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterString: FfiConverter {
+    typealias SwiftType = String
+    typealias FfiType = RustBuffer
+
+    public static func lift(_ value: RustBuffer) throws -> String {
+        defer {
+            value.deallocate()
+        }
+        if value.data == nil {
+            return String()
+        }
+        let bytes = UnsafeBufferPointer<UInt8>(start: value.data!, count: Int(value.len))
+        return String(bytes: bytes, encoding: String.Encoding.utf8)!
+    }
+
+    public static func lower(_ value: String) -> RustBuffer {
+        return value.utf8CString.withUnsafeBufferPointer { ptr in
+            // The swift string gives us int8_t, we want uint8_t.
+            ptr.withMemoryRebound(to: UInt8.self) { ptr in
+                // The swift string gives us a trailing null byte, we don't want it.
+                let buf = UnsafeBufferPointer(rebasing: ptr.prefix(upTo: ptr.count - 1))
+                return RustBuffer.from(buf)
+            }
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> String {
+        let len: Int32 = try readInt(&buf)
+        return String(bytes: try readBytes(&buf, count: Int(len)), encoding: String.Encoding.utf8)!
+    }
+
+    public static func write(_ value: String, into buf: inout [UInt8]) {
+        let len = Int32(value.utf8.count)
+        writeInt(&buf, len)
+        writeBytes(&buf, value.utf8)
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterTimestamp: FfiConverterRustBuffer {
+    typealias SwiftType = Date
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Date {
+        let seconds: Int64 = try readInt(&buf)
+        let nanoseconds: UInt32 = try readInt(&buf)
+        if seconds >= 0 {
+            let delta = Double(seconds) + (Double(nanoseconds) / 1.0e9)
+            return Date.init(timeIntervalSince1970: delta)
+        } else {
+            let delta = Double(seconds) - (Double(nanoseconds) / 1.0e9)
+            return Date.init(timeIntervalSince1970: delta)
+        }
+    }
+
+    public static func write(_ value: Date, into buf: inout [UInt8]) {
+        var delta = value.timeIntervalSince1970
+        var sign: Int64 = 1
+        if delta < 0 {
+            // The nanoseconds portion of the epoch offset must always be
+            // positive, to simplify the calculation we will use the absolute
+            // value of the offset.
+            sign = -1
+            delta = -delta
+        }
+        if delta.rounded(.down) > Double(Int64.max) {
+            fatalError("Timestamp overflow, exceeds max bounds supported by Uniffi")
+        }
+        let seconds = Int64(delta)
+        let nanoseconds = UInt32((delta - Double(seconds)) * 1.0e9)
+        writeInt(&buf, sign * seconds)
+        writeInt(&buf, nanoseconds)
+    }
+}
+
+
+
+
+public protocol FreeListProtocol : AnyObject {
+    
+    func add(task: Task) throws  -> Int64
+    
+    func addQuick(input: String) throws  -> Int64
+    
+    func all() throws  -> [Task]
+    
+    func clearAll() throws 
+    
+    func delete(id: Int64) throws 
+    
+    func exportArchive() throws  -> String
+    
+    func fetch(filter: TaskFilter) throws  -> [Task]
+    
+    func getAllTags() throws  -> [String]
+    
+    func getById(id: Int64) throws  -> Task?
+    
+    func getByList(listId: Int64) throws  -> [Task]
+    
+    func getByTag(tag: String) throws  -> [Task]
+    
+    func getCompleted() throws  -> [Task]
+    
+    func getOverdue() throws  -> [Task]
+    
+    func getSubtasks(parentId: Int64) throws  -> [Task]
+    
+    func getTodo() throws  -> [Task]
+    
+    func importArchive(archiveJson: String) throws  -> ImportSummary
+    
+    func markDone(id: Int64) throws 
+    
+    func markUndone(id: Int64) throws 
+    
+    func moveTask(id: Int64, before: Int64?, after: Int64?) throws 
+    
+}
+
+open class FreeList:
+    FreeListProtocol {
+    fileprivate let pointer: UnsafeMutableRawPointer!
+
+    /// Used to instantiate a [FFIObject] without an actual pointer, for fakes in tests, mostly.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public struct NoPointer {
+        public init() {}
+    }
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+    required public init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
+        self.pointer = pointer
+    }
+
+    // This constructor can be used to instantiate a fake object.
+    // - Parameter noPointer: Placeholder value so we can have a constructor separate from the default empty one that may be implemented for classes extending [FFIObject].
+    //
+    // - Warning:
+    //     Any object instantiated with this constructor cannot be passed to an actual Rust-backed object. Since there isn't a backing [Pointer] the FFI lower functions will crash.
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public init(noPointer: NoPointer) {
+        self.pointer = nil
+    }
+
+#if swift(>=5.8)
+    @_documentation(visibility: private)
+#endif
+    public func uniffiClonePointer() -> UnsafeMutableRawPointer {
+        return try! rustCall { uniffi_core_fn_clone_freelist(self.pointer, $0) }
+    }
+public convenience init(dbPath: String)throws  {
+    let pointer =
+        try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_constructor_freelist_new(
+        FfiConverterString.lower(dbPath),$0
+    )
+}
+    self.init(unsafeFromRawPointer: pointer)
+}
+
+    deinit {
+        guard let pointer = pointer else {
+            return
+        }
+
+        try! rustCall { uniffi_core_fn_free_freelist(pointer, $0) }
+    }
+
+    
+public static func inMemory()throws  -> FreeList {
+    return try  FfiConverterTypeFreeList.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_constructor_freelist_in_memory($0
+    )
+})
+}
+    
+
+    
+open func add(task: Task)throws  -> Int64 {
+    return try  FfiConverterInt64.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_add(self.uniffiClonePointer(),
+        FfiConverterTypeTask.lower(task),$0
+    )
+})
+}
+    
+open func addQuick(input: String)throws  -> Int64 {
+    return try  FfiConverterInt64.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_add_quick(self.uniffiClonePointer(),
+        FfiConverterString.lower(input),$0
+    )
+})
+}
+    
+open func all()throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_all(self.uniffiClonePointer(),$0
+    )
+})
+}
+    
+open func clearAll()throws  {try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_clear_all(self.uniffiClonePointer(),$0
+    )
+}
+}
+    
+open func delete(id: Int64)throws  {try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_delete(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(id),$0
+    )
+}
+}
+    
+open func exportArchive()throws  -> String {
+    return try  FfiConverterString.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_export_archive(self.uniffiClonePointer(),$0
+    )
+})
+}
+    
+open func fetch(filter: TaskFilter)throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_fetch(self.uniffiClonePointer(),
+        FfiConverterTypeTaskFilter.lower(filter),$0
+    )
+})
+}
+    
+open func getAllTags()throws  -> [String] {
+    return try  FfiConverterSequenceString.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_all_tags(self.uniffiClonePointer(),$0
+    )
+})
+}
+    
+open func getById(id: Int64)throws  -> Task? {
+    return try  FfiConverterOptionTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_by_id(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(id),$0
+    )
+})
+}
+    
+open func getByList(listId: Int64)throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_by_list(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(listId),$0
+    )
+})
+}
+    
+open func getByTag(tag: String)throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_by_tag(self.uniffiClonePointer(),
+        FfiConverterString.lower(tag),$0
+    )
+})
+}
+    
+open func getCompleted()throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_completed(self.uniffiClonePointer(),$0
+    )
+})
+}
+    
+open func getOverdue()throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_overdue(self.uniffiClonePointer(),$0
+    )
+})
+}
+    
+open func getSubtasks(parentId: Int64)throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_subtasks(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(parentId),$0
+    )
+})
+}
+    
+open func getTodo()throws  -> [Task] {
+    return try  FfiConverterSequenceTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_get_todo(self.uniffiClonePointer(),$0
+    )
+})
+}
+    
+open func importArchive(archiveJson: String)throws  -> ImportSummary {
+    return try  FfiConverterTypeImportSummary.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_import_archive(self.uniffiClonePointer(),
+        FfiConverterString.lower(archiveJson),$0
+    )
+})
+}
+    
+open func markDone(id: Int64)throws  {try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_mark_done(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(id),$0
+    )
+}
+}
+    
+open func markUndone(id: Int64)throws  {try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_mark_undone(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(id),$0
+    )
+}
+}
+    
+open func moveTask(id: Int64, before: Int64?, after: Int64?)throws  {try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_method_freelist_move_task(self.uniffiClonePointer(),
+        FfiConverterInt64.lower(id),
+        FfiConverterOptionInt64.lower(before),
+        FfiConverterOptionInt64.lower(after),$0
+    )
+}
+}
+    
+
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeFreeList: FfiConverter {
+
+    typealias FfiType = UnsafeMutableRawPointer
+    typealias SwiftType = FreeList
+
+    public static func lift(_ pointer: UnsafeMutableRawPointer) throws -> FreeList {
+        return FreeList(unsafeFromRawPointer: pointer)
+    }
+
+    public static func lower(_ value: FreeList) -> UnsafeMutableRawPointer {
+        return value.uniffiClonePointer()
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FreeList {
+        let v: UInt64 = try readInt(&buf)
+        // The Rust code won't compile if a pointer won't fit in a UInt64.
+        // We have to go via `UInt` because that's the thing that's the size of a pointer.
+        let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: v))
+        if (ptr == nil) {
+            throw UniffiInternalError.unexpectedNullPointer
+        }
+        return try lift(ptr!)
+    }
+
+    public static func write(_ value: FreeList, into buf: inout [UInt8]) {
+        // This fiddling is because `Int` is the thing that's the same size as a pointer.
+        // The Rust code won't compile if a pointer won't fit in a `UInt64`.
+        writeInt(&buf, UInt64(bitPattern: Int64(Int(bitPattern: lower(value)))))
+    }
+}
+
+
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFreeList_lift(_ pointer: UnsafeMutableRawPointer) throws -> FreeList {
+    return try FfiConverterTypeFreeList.lift(pointer)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeFreeList_lower(_ value: FreeList) -> UnsafeMutableRawPointer {
+    return FfiConverterTypeFreeList.lower(value)
+}
+
+
+public struct ImportSummary {
+    public var tasksAdded: UInt32
+    public var tasksSkipped: UInt32
+    public var historyAdded: UInt32
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(tasksAdded: UInt32, tasksSkipped: UInt32, historyAdded: UInt32) {
+        self.tasksAdded = tasksAdded
+        self.tasksSkipped = tasksSkipped
+        self.historyAdded = historyAdded
+    }
+}
+
+
+
+extension ImportSummary: Equatable, Hashable {
+    public static func ==(lhs: ImportSummary, rhs: ImportSummary) -> Bool {
+        if lhs.tasksAdded != rhs.tasksAdded {
+            return false
+        }
+        if lhs.tasksSkipped != rhs.tasksSkipped {
+            return false
+        }
+        if lhs.historyAdded != rhs.historyAdded {
+            return false
+        }
+        return true
+    }
+
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(tasksAdded)
+        hasher.combine(tasksSkipped)
+        hasher.combine(historyAdded)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeImportSummary: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> ImportSummary {
+        return
+            try ImportSummary(
+                tasksAdded: FfiConverterUInt32.read(from: &buf), 
+                tasksSkipped: FfiConverterUInt32.read(from: &buf), 
+                historyAdded: FfiConverterUInt32.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: ImportSummary, into buf: inout [UInt8]) {
+        FfiConverterUInt32.write(value.tasksAdded, into: &buf)
+        FfiConverterUInt32.write(value.tasksSkipped, into: &buf)
+        FfiConverterUInt32.write(value.historyAdded, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeImportSummary_lift(_ buf: RustBuffer) throws -> ImportSummary {
+    return try FfiConverterTypeImportSummary.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeImportSummary_lower(_ value: ImportSummary) -> RustBuffer {
+    return FfiConverterTypeImportSummary.lower(value)
+}
+
+
+public struct Task {
+    public var id: Int64?
+    public var title: String
+    public var details: String?
+    public var done: Bool
+    public var dueDate: UtcDateTime?
+    public var isRecurring: Bool
+    public var estimatedDuration: Int32?
+    public var lastDuration: Int32?
+    public var tag: String?
+    public var priority: Priority?
+    public var parentId: Int64?
+    public var createdAt: UtcDateTime
+    public var updatedAt: UtcDateTime
+    public var listId: Int64?
+    public var position: String?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(id: Int64?, title: String, details: String?, done: Bool, dueDate: UtcDateTime?, isRecurring: Bool, estimatedDuration: Int32?, lastDuration: Int32?, tag: String?, priority: Priority?, parentId: Int64?, createdAt: UtcDateTime, updatedAt: UtcDateTime, listId: Int64?, position: String?) {
+        self.id = id
+        self.title = title
+        self.details = details
+        self.done = done
+        self.dueDate = dueDate
+        self.isRecurring = isRecurring
+        self.estimatedDuration = estimatedDuration
+        self.lastDuration = lastDuration
+        self.tag = tag
+        self.priority = priority
+        self.parentId = parentId
+        self.createdAt = createdAt
+        self.updatedAt = updatedAt
+        self.listId = listId
+        self.position = position
+    }
+}
+
+
+
+extension Task: Equatable, Hashable {
+    public static func ==(lhs: Task, rhs: Task) -> Bool {
+        if lhs.id != rhs.id {
+            return false
+        }
+        if lhs.title != rhs.title {
+            return false
+        }
+        if lhs.details != rhs.details {
+            return false
+        }
+        if lhs.done != rhs.done {
+            return false
+        }
+        if lhs.dueDate != rhs.dueDate {
+            return false
+        }
+        if lhs.isRecurring != rhs.isRecurring {
+            return false
+        }
+        if lhs.estimatedDuration != rhs.estimatedDuration {
+            return false
+        }
+        if lhs.lastDuration != rhs.lastDuration {
+            return false
+        }
+        if lhs.tag != rhs.tag {
+            return false
+        }
+        if lhs.priority != rhs.priority {
+            return false
+        }
+        if lhs.parentId != rhs.parentId {
+            return false
+        }
+        if lhs.createdAt != rhs.createdAt {
+            return false
+        }
+        if lhs.updatedAt != rhs.updatedAt {
+            return false
+        }
+        if lhs.listId != rhs.listId {
+            return false
+        }
+        if lhs.position != rhs.position {
+            return false
+        }
+        return true
+    }
+
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(id)
+        hasher.combine(title)
+        hasher.combine(details)
+        hasher.combine(done)
+        hasher.combine(dueDate)
+        hasher.combine(isRecurring)
+        hasher.combine(estimatedDuration)
+        hasher.combine(lastDuration)
+        hasher.combine(tag)
+        hasher.combine(priority)
+        hasher.combine(parentId)
+        hasher.combine(createdAt)
+        hasher.combine(updatedAt)
+        hasher.combine(listId)
+        hasher.combine(position)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeTask: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Task {
+        return
+            try Task(
+                id: FfiConverterOptionInt64.read(from: &buf), 
+                title: FfiConverterString.read(from: &buf), 
+                details: FfiConverterOptionString.read(from: &buf), 
+                done: FfiConverterBool.read(from: &buf), 
+                dueDate: FfiConverterOptionTypeUtcDateTime.read(from: &buf), 
+                isRecurring: FfiConverterBool.read(from: &buf), 
+                estimatedDuration: FfiConverterOptionInt32.read(from: &buf), 
+                lastDuration: FfiConverterOptionInt32.read(from: &buf), 
+                tag: FfiConverterOptionString.read(from: &buf), 
+                priority: FfiConverterOptionTypePriority.read(from: &buf), 
+                parentId: FfiConverterOptionInt64.read(from: &buf), 
+                createdAt: FfiConverterTypeUtcDateTime.read(from: &buf), 
+                updatedAt: FfiConverterTypeUtcDateTime.read(from: &buf), 
+                listId: FfiConverterOptionInt64.read(from: &buf), 
+                position: FfiConverterOptionString.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: Task, into buf: inout [UInt8]) {
+        FfiConverterOptionInt64.write(value.id, into: &buf)
+        FfiConverterString.write(value.title, into: &buf)
+        FfiConverterOptionString.write(value.details, into: &buf)
+        FfiConverterBool.write(value.done, into: &buf)
+        FfiConverterOptionTypeUtcDateTime.write(value.dueDate, into: &buf)
+        FfiConverterBool.write(value.isRecurring, into: &buf)
+        FfiConverterOptionInt32.write(value.estimatedDuration, into: &buf)
+        FfiConverterOptionInt32.write(value.lastDuration, into: &buf)
+        FfiConverterOptionString.write(value.tag, into: &buf)
+        FfiConverterOptionTypePriority.write(value.priority, into: &buf)
+        FfiConverterOptionInt64.write(value.parentId, into: &buf)
+        FfiConverterTypeUtcDateTime.write(value.createdAt, into: &buf)
+        FfiConverterTypeUtcDateTime.write(value.updatedAt, into: &buf)
+        FfiConverterOptionInt64.write(value.listId, into: &buf)
+        FfiConverterOptionString.write(value.position, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTask_lift(_ buf: RustBuffer) throws -> Task {
+    return try FfiConverterTypeTask.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTask_lower(_ value: Task) -> RustBuffer {
+    return FfiConverterTypeTask.lower(value)
+}
+
+
+public struct TaskFilter {
+    public var status: TaskStatus?
+    public var tag: String?
+    public var parentId: Int64?
+    public var listId: Int64?
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(status: TaskStatus? = nil, tag: String? = nil, parentId: Int64? = nil, listId: Int64? = nil) {
+        self.status = status
+        self.tag = tag
+        self.parentId = parentId
+        self.listId = listId
+    }
+}
+
+
+
+extension TaskFilter: Equatable, Hashable {
+    public static func ==(lhs: TaskFilter, rhs: TaskFilter) -> Bool {
+        if lhs.status != rhs.status {
+            return false
+        }
+        if lhs.tag != rhs.tag {
+            return false
+        }
+        if lhs.parentId != rhs.parentId {
+            return false
+        }
+        if lhs.listId != rhs.listId {
+            return false
+        }
+        return true
+    }
+
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(status)
+        hasher.combine(tag)
+        hasher.combine(parentId)
+        hasher.combine(listId)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeTaskFilter: FfiConverterRustBuffer {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> TaskFilter {
+        return
+            try TaskFilter(
+                status: FfiConverterOptionTypeTaskStatus.read(from: &buf), 
+                tag: FfiConverterOptionString.read(from: &buf), 
+                parentId: FfiConverterOptionInt64.read(from: &buf), 
+                listId: FfiConverterOptionInt64.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: TaskFilter, into buf: inout [UInt8]) {
+        FfiConverterOptionTypeTaskStatus.write(value.status, into: &buf)
+        FfiConverterOptionString.write(value.tag, into: &buf)
+        FfiConverterOptionInt64.write(value.parentId, into: &buf)
+        FfiConverterOptionInt64.write(value.listId, into: &buf)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTaskFilter_lift(_ buf: RustBuffer) throws -> TaskFilter {
+    return try FfiConverterTypeTaskFilter.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTaskFilter_lower(_ value: TaskFilter) -> RustBuffer {
+    return FfiConverterTypeTaskFilter.lower(value)
+}
+
+
+public enum FreeListError {
+
+    
+    
+    case NotFound(message: String)
+    
+    case Storage(message: String)
+    
+    case InvalidInput(message: String)
+    
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeFreeListError: FfiConverterRustBuffer {
+    typealias SwiftType = FreeListError
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> FreeListError {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+
+        
+
+        
+        case 1: return .NotFound(
+            message: try FfiConverterString.read(from: &buf)
+        )
+        
+        case 2: return .Storage(
+            message: try FfiConverterString.read(from: &buf)
+        )
+        
+        case 3: return .InvalidInput(
+            message: try FfiConverterString.read(from: &buf)
+        )
+        
+
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: FreeListError, into buf: inout [UInt8]) {
+        switch value {
+
+        
+
+        
+        case .NotFound(_ /* message is ignored*/):
+            writeInt(&buf, Int32(1))
+        case .Storage(_ /* message is ignored*/):
+            writeInt(&buf, Int32(2))
+        case .InvalidInput(_ /* message is ignored*/):
+            writeInt(&buf, Int32(3))
+
+        
+        }
+    }
+}
+
+
+extension FreeListError: Equatable, Hashable {}
+
+extension FreeListError: Foundation.LocalizedError {
+    public var errorDescription: String? {
+        String(reflecting: self)
+    }
+}
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum Priority {
+    
+    case low
+    case medium
+    case high
+    case urgent
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypePriority: FfiConverterRustBuffer {
+    typealias SwiftType = Priority
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Priority {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .low
+        
+        case 2: return .medium
+        
+        case 3: return .high
+        
+        case 4: return .urgent
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: Priority, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .low:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .medium:
+            writeInt(&buf, Int32(2))
+        
+        
+        case .high:
+            writeInt(&buf, Int32(3))
+        
+        
+        case .urgent:
+            writeInt(&buf, Int32(4))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypePriority_lift(_ buf: RustBuffer) throws -> Priority {
+    return try FfiConverterTypePriority.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypePriority_lower(_ value: Priority) -> RustBuffer {
+    return FfiConverterTypePriority.lower(value)
+}
+
+
+
+extension Priority: Equatable, Hashable {}
+
+
+
+// Note that we don't yet support `indirect` for enums.
+// See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
+
+public enum TaskStatus {
+    
+    case done
+    case todo
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeTaskStatus: FfiConverterRustBuffer {
+    typealias SwiftType = TaskStatus
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> TaskStatus {
+        let variant: Int32 = try readInt(&buf)
+        switch variant {
+        
+        case 1: return .done
+        
+        case 2: return .todo
+        
+        default: throw UniffiInternalError.unexpectedEnumCase
+        }
+    }
+
+    public static func write(_ value: TaskStatus, into buf: inout [UInt8]) {
+        switch value {
+        
+        
+        case .done:
+            writeInt(&buf, Int32(1))
+        
+        
+        case .todo:
+            writeInt(&buf, Int32(2))
+        
+        }
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTaskStatus_lift(_ buf: RustBuffer) throws -> TaskStatus {
+    return try FfiConverterTypeTaskStatus.lift(buf)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeTaskStatus_lower(_ value: TaskStatus) -> RustBuffer {
+    return FfiConverterTypeTaskStatus.lower(value)
+}
+
+
+
+extension TaskStatus: Equatable, Hashable {}
+
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionInt32: FfiConverterRustBuffer {
+    typealias SwiftType = Int32?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterInt32.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterInt32.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionInt64: FfiConverterRustBuffer {
+    typealias SwiftType = Int64?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterInt64.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterInt64.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionString: FfiConverterRustBuffer {
+    typealias SwiftType = String?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterString.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterString.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeTask: FfiConverterRustBuffer {
+    typealias SwiftType = Task?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeTask.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeTask.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypePriority: FfiConverterRustBuffer {
+    typealias SwiftType = Priority?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypePriority.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypePriority.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeTaskStatus: FfiConverterRustBuffer {
+    typealias SwiftType = TaskStatus?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeTaskStatus.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeTaskStatus.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterOptionTypeUtcDateTime: FfiConverterRustBuffer {
+    typealias SwiftType = UtcDateTime?
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        guard let value = value else {
+            writeInt(&buf, Int8(0))
+            return
+        }
+        writeInt(&buf, Int8(1))
+        FfiConverterTypeUtcDateTime.write(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as Int8 {
+        case 0: return nil
+        case 1: return try FfiConverterTypeUtcDateTime.read(from: &buf)
+        default: throw UniffiInternalError.unexpectedOptionalTag
+        }
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceString: FfiConverterRustBuffer {
+    typealias SwiftType = [String]
+
+    public static func write(_ value: [String], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterString.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [String] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [String]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterString.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+fileprivate struct FfiConverterSequenceTypeTask: FfiConverterRustBuffer {
+    typealias SwiftType = [Task]
+
+    public static func write(_ value: [Task], into buf: inout [UInt8]) {
+        let len = Int32(value.count)
+        writeInt(&buf, len)
+        for item in value {
+            FfiConverterTypeTask.write(item, into: &buf)
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> [Task] {
+        let len: Int32 = try readInt(&buf)
+        var seq = [Task]()
+        seq.reserveCapacity(Int(len))
+        for _ in 0 ..< len {
+            seq.append(try FfiConverterTypeTask.read(from: &buf))
+        }
+        return seq
+    }
+}
+
+
+/**
+ * Typealias from the type name used in the UDL file to the builtin type.  This
+ * is needed because the UDL type name is used in function/method signatures.
+ */
+public typealias UtcDateTime = Date
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public struct FfiConverterTypeUtcDateTime: FfiConverter {
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> UtcDateTime {
+        return try FfiConverterTimestamp.read(from: &buf)
+    }
+
+    public static func write(_ value: UtcDateTime, into buf: inout [UInt8]) {
+        return FfiConverterTimestamp.write(value, into: &buf)
+    }
+
+    public static func lift(_ value: RustBuffer) throws -> UtcDateTime {
+        return try FfiConverterTimestamp.lift(value)
+    }
+
+    public static func lower(_ value: UtcDateTime) -> RustBuffer {
+        return FfiConverterTimestamp.lower(value)
+    }
+}
+
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeUtcDateTime_lift(_ value: RustBuffer) throws -> UtcDateTime {
+    return try FfiConverterTypeUtcDateTime.lift(value)
+}
+
+#if swift(>=5.8)
+@_documentation(visibility: private)
+#endif
+public func FfiConverterTypeUtcDateTime_lower(_ value: UtcDateTime) -> RustBuffer {
+    return FfiConverterTypeUtcDateTime.lower(value)
+}
+
+public func parseQuickAdd(input: String)throws  -> Task {
+    return try  FfiConverterTypeTask.lift(try rustCallWithError(FfiConverterTypeFreeListError.lift) {
+    uniffi_core_fn_func_parse_quick_add(
+        FfiConverterString.lower(input),$0
+    )
+})
+}
+
+private enum InitializationResult {
+    case ok
+    case contractVersionMismatch
+    case apiChecksumMismatch
+}
+// Use a global variable to perform the versioning checks. Swift ensures that
+// the code inside is only computed once.
+private var initializationResult: InitializationResult = {
+    // Get the bindings contract version from our ComponentInterface
+    let bindings_contract_version = 26
+    // Get the scaffolding contract version by calling the into the dylib
+    let scaffolding_contract_version = ffi_core_uniffi_contract_version()
+    if bindings_contract_version != scaffolding_contract_version {
+        return InitializationResult.contractVersionMismatch
+    }
+    if (uniffi_core_checksum_func_parse_quick_add() != 64343) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_add() != 14899) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_add_quick() != 22017) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_all() != 30222) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_clear_all() != 59714) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_delete() != 62187) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_export_archive() != 10904) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_fetch() != 55058) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_all_tags() != 38044) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_by_id() != 55258) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_by_list() != 55284) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_by_tag() != 6065) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_completed() != 52008) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_overdue() != 47503) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_subtasks() != 11690) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_get_todo() != 41726) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_import_archive() != 58627) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_mark_done() != 61946) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_mark_undone() != 15654) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_method_freelist_move_task() != 11903) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_constructor_freelist_in_memory() != 58957) {
+        return InitializationResult.apiChecksumMismatch
+    }
+    if (uniffi_core_checksum_constructor_freelist_new() != 65094) {
+        return InitializationResult.apiChecksumMismatch
+    }
+
+    return InitializationResult.ok
+}()
+
+private func uniffiEnsureInitialized() {
+    switch initializationResult {
+    case .ok:
+        break
+    case .contractVersionMismatch:
+        fatalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+    case .apiChecksumMismatch:
+        fatalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    }
+}
+
+// swiftlint:enable all
\ No newline at end of file
@@ -0,0 +1,793 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The following structs are used to implement the lowest level
+// of the FFI, and thus useful to multiple uniffied crates.
+// We ensure they are declared exactly once, with a header guard, UNIFFI_SHARED_H.
+#ifdef UNIFFI_SHARED_H
+    // We also try to prevent mixing versions of shared uniffi header structs.
+    // If you add anything to the #else block, you must increment the version suffix in UNIFFI_SHARED_HEADER_V4
+    #ifndef UNIFFI_SHARED_HEADER_V4
+        #error Combining helper code from multiple versions of uniffi is not supported
+    #endif // ndef UNIFFI_SHARED_HEADER_V4
+#else
+#define UNIFFI_SHARED_H
+#define UNIFFI_SHARED_HEADER_V4
+// ⚠️ Attention: If you change this #else block (ending in `#endif // def UNIFFI_SHARED_H`) you *must* ⚠️
+// ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V4 in this file.           ⚠️
+
+typedef struct RustBuffer
+{
+    uint64_t capacity;
+    uint64_t len;
+    uint8_t *_Nullable data;
+} RustBuffer;
+
+typedef struct ForeignBytes
+{
+    int32_t len;
+    const uint8_t *_Nullable data;
+} ForeignBytes;
+
+// Error definitions
+typedef struct RustCallStatus {
+    int8_t code;
+    RustBuffer errorBuf;
+} RustCallStatus;
+
+// ⚠️ Attention: If you change this #else block (ending in `#endif // def UNIFFI_SHARED_H`) you *must* ⚠️
+// ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V4 in this file.           ⚠️
+#endif // def UNIFFI_SHARED_H
+#ifndef UNIFFI_FFIDEF_RUST_FUTURE_CONTINUATION_CALLBACK
+#define UNIFFI_FFIDEF_RUST_FUTURE_CONTINUATION_CALLBACK
+typedef void (*UniffiRustFutureContinuationCallback)(uint64_t, int8_t
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_FREE
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_FREE
+typedef void (*UniffiForeignFutureFree)(uint64_t
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_CALLBACK_INTERFACE_FREE
+#define UNIFFI_FFIDEF_CALLBACK_INTERFACE_FREE
+typedef void (*UniffiCallbackInterfaceFree)(uint64_t
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE
+typedef struct UniffiForeignFuture {
+    uint64_t handle;
+    UniffiForeignFutureFree _Nonnull free;
+} UniffiForeignFuture;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U8
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U8
+typedef struct UniffiForeignFutureStructU8 {
+    uint8_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructU8;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U8
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U8
+typedef void (*UniffiForeignFutureCompleteU8)(uint64_t, UniffiForeignFutureStructU8
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I8
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I8
+typedef struct UniffiForeignFutureStructI8 {
+    int8_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructI8;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I8
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I8
+typedef void (*UniffiForeignFutureCompleteI8)(uint64_t, UniffiForeignFutureStructI8
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U16
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U16
+typedef struct UniffiForeignFutureStructU16 {
+    uint16_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructU16;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U16
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U16
+typedef void (*UniffiForeignFutureCompleteU16)(uint64_t, UniffiForeignFutureStructU16
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I16
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I16
+typedef struct UniffiForeignFutureStructI16 {
+    int16_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructI16;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I16
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I16
+typedef void (*UniffiForeignFutureCompleteI16)(uint64_t, UniffiForeignFutureStructI16
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U32
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U32
+typedef struct UniffiForeignFutureStructU32 {
+    uint32_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructU32;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U32
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U32
+typedef void (*UniffiForeignFutureCompleteU32)(uint64_t, UniffiForeignFutureStructU32
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I32
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I32
+typedef struct UniffiForeignFutureStructI32 {
+    int32_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructI32;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I32
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I32
+typedef void (*UniffiForeignFutureCompleteI32)(uint64_t, UniffiForeignFutureStructI32
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U64
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_U64
+typedef struct UniffiForeignFutureStructU64 {
+    uint64_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructU64;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U64
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_U64
+typedef void (*UniffiForeignFutureCompleteU64)(uint64_t, UniffiForeignFutureStructU64
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I64
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_I64
+typedef struct UniffiForeignFutureStructI64 {
+    int64_t returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructI64;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I64
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_I64
+typedef void (*UniffiForeignFutureCompleteI64)(uint64_t, UniffiForeignFutureStructI64
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_F32
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_F32
+typedef struct UniffiForeignFutureStructF32 {
+    float returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructF32;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_F32
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_F32
+typedef void (*UniffiForeignFutureCompleteF32)(uint64_t, UniffiForeignFutureStructF32
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_F64
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_F64
+typedef struct UniffiForeignFutureStructF64 {
+    double returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructF64;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_F64
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_F64
+typedef void (*UniffiForeignFutureCompleteF64)(uint64_t, UniffiForeignFutureStructF64
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_POINTER
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_POINTER
+typedef struct UniffiForeignFutureStructPointer {
+    void*_Nonnull returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructPointer;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_POINTER
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_POINTER
+typedef void (*UniffiForeignFutureCompletePointer)(uint64_t, UniffiForeignFutureStructPointer
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_RUST_BUFFER
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_RUST_BUFFER
+typedef struct UniffiForeignFutureStructRustBuffer {
+    RustBuffer returnValue;
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructRustBuffer;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_RUST_BUFFER
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_RUST_BUFFER
+typedef void (*UniffiForeignFutureCompleteRustBuffer)(uint64_t, UniffiForeignFutureStructRustBuffer
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_VOID
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_STRUCT_VOID
+typedef struct UniffiForeignFutureStructVoid {
+    RustCallStatus callStatus;
+} UniffiForeignFutureStructVoid;
+
+#endif
+#ifndef UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_VOID
+#define UNIFFI_FFIDEF_FOREIGN_FUTURE_COMPLETE_VOID
+typedef void (*UniffiForeignFutureCompleteVoid)(uint64_t, UniffiForeignFutureStructVoid
+    );
+
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_CLONE_FREELIST
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_CLONE_FREELIST
+void*_Nonnull uniffi_core_fn_clone_freelist(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_FREE_FREELIST
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_FREE_FREELIST
+void uniffi_core_fn_free_freelist(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_CONSTRUCTOR_FREELIST_IN_MEMORY
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_CONSTRUCTOR_FREELIST_IN_MEMORY
+void*_Nonnull uniffi_core_fn_constructor_freelist_in_memory(RustCallStatus *_Nonnull out_status
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_CONSTRUCTOR_FREELIST_NEW
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_CONSTRUCTOR_FREELIST_NEW
+void*_Nonnull uniffi_core_fn_constructor_freelist_new(RustBuffer db_path, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_ADD
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_ADD
+int64_t uniffi_core_fn_method_freelist_add(void*_Nonnull ptr, RustBuffer task, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_ADD_QUICK
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_ADD_QUICK
+int64_t uniffi_core_fn_method_freelist_add_quick(void*_Nonnull ptr, RustBuffer input, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_ALL
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_ALL
+RustBuffer uniffi_core_fn_method_freelist_all(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_CLEAR_ALL
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_CLEAR_ALL
+void uniffi_core_fn_method_freelist_clear_all(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_DELETE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_DELETE
+void uniffi_core_fn_method_freelist_delete(void*_Nonnull ptr, int64_t id, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_EXPORT_ARCHIVE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_EXPORT_ARCHIVE
+RustBuffer uniffi_core_fn_method_freelist_export_archive(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_FETCH
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_FETCH
+RustBuffer uniffi_core_fn_method_freelist_fetch(void*_Nonnull ptr, RustBuffer filter, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_ALL_TAGS
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_ALL_TAGS
+RustBuffer uniffi_core_fn_method_freelist_get_all_tags(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_BY_ID
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_BY_ID
+RustBuffer uniffi_core_fn_method_freelist_get_by_id(void*_Nonnull ptr, int64_t id, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_BY_LIST
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_BY_LIST
+RustBuffer uniffi_core_fn_method_freelist_get_by_list(void*_Nonnull ptr, int64_t list_id, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_BY_TAG
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_BY_TAG
+RustBuffer uniffi_core_fn_method_freelist_get_by_tag(void*_Nonnull ptr, RustBuffer tag, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_COMPLETED
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_COMPLETED
+RustBuffer uniffi_core_fn_method_freelist_get_completed(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_OVERDUE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_OVERDUE
+RustBuffer uniffi_core_fn_method_freelist_get_overdue(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_SUBTASKS
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_SUBTASKS
+RustBuffer uniffi_core_fn_method_freelist_get_subtasks(void*_Nonnull ptr, int64_t parent_id, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_TODO
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_GET_TODO
+RustBuffer uniffi_core_fn_method_freelist_get_todo(void*_Nonnull ptr, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_IMPORT_ARCHIVE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_IMPORT_ARCHIVE
+RustBuffer uniffi_core_fn_method_freelist_import_archive(void*_Nonnull ptr, RustBuffer archive_json, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_MARK_DONE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_MARK_DONE
+void uniffi_core_fn_method_freelist_mark_done(void*_Nonnull ptr, int64_t id, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_MARK_UNDONE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_MARK_UNDONE
+void uniffi_core_fn_method_freelist_mark_undone(void*_Nonnull ptr, int64_t id, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_MOVE_TASK
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_METHOD_FREELIST_MOVE_TASK
+void uniffi_core_fn_method_freelist_move_task(void*_Nonnull ptr, int64_t id, RustBuffer before, RustBuffer after, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_FN_FUNC_PARSE_QUICK_ADD
+#define UNIFFI_FFIDEF_UNIFFI_CORE_FN_FUNC_PARSE_QUICK_ADD
+RustBuffer uniffi_core_fn_func_parse_quick_add(RustBuffer input, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_ALLOC
+#define UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_ALLOC
+RustBuffer ffi_core_rustbuffer_alloc(uint64_t size, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_FROM_BYTES
+#define UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_FROM_BYTES
+RustBuffer ffi_core_rustbuffer_from_bytes(ForeignBytes bytes, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_FREE
+#define UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_FREE
+void ffi_core_rustbuffer_free(RustBuffer buf, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_RESERVE
+#define UNIFFI_FFIDEF_FFI_CORE_RUSTBUFFER_RESERVE
+RustBuffer ffi_core_rustbuffer_reserve(RustBuffer buf, uint64_t additional, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U8
+void ffi_core_rust_future_poll_u8(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U8
+void ffi_core_rust_future_cancel_u8(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U8
+void ffi_core_rust_future_free_u8(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U8
+uint8_t ffi_core_rust_future_complete_u8(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I8
+void ffi_core_rust_future_poll_i8(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I8
+void ffi_core_rust_future_cancel_i8(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I8
+void ffi_core_rust_future_free_i8(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I8
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I8
+int8_t ffi_core_rust_future_complete_i8(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U16
+void ffi_core_rust_future_poll_u16(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U16
+void ffi_core_rust_future_cancel_u16(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U16
+void ffi_core_rust_future_free_u16(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U16
+uint16_t ffi_core_rust_future_complete_u16(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I16
+void ffi_core_rust_future_poll_i16(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I16
+void ffi_core_rust_future_cancel_i16(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I16
+void ffi_core_rust_future_free_i16(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I16
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I16
+int16_t ffi_core_rust_future_complete_i16(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U32
+void ffi_core_rust_future_poll_u32(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U32
+void ffi_core_rust_future_cancel_u32(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U32
+void ffi_core_rust_future_free_u32(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U32
+uint32_t ffi_core_rust_future_complete_u32(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I32
+void ffi_core_rust_future_poll_i32(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I32
+void ffi_core_rust_future_cancel_i32(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I32
+void ffi_core_rust_future_free_i32(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I32
+int32_t ffi_core_rust_future_complete_i32(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_U64
+void ffi_core_rust_future_poll_u64(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_U64
+void ffi_core_rust_future_cancel_u64(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_U64
+void ffi_core_rust_future_free_u64(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_U64
+uint64_t ffi_core_rust_future_complete_u64(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_I64
+void ffi_core_rust_future_poll_i64(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_I64
+void ffi_core_rust_future_cancel_i64(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_I64
+void ffi_core_rust_future_free_i64(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_I64
+int64_t ffi_core_rust_future_complete_i64(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_F32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_F32
+void ffi_core_rust_future_poll_f32(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_F32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_F32
+void ffi_core_rust_future_cancel_f32(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_F32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_F32
+void ffi_core_rust_future_free_f32(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_F32
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_F32
+float ffi_core_rust_future_complete_f32(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_F64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_F64
+void ffi_core_rust_future_poll_f64(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_F64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_F64
+void ffi_core_rust_future_cancel_f64(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_F64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_F64
+void ffi_core_rust_future_free_f64(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_F64
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_F64
+double ffi_core_rust_future_complete_f64(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_POINTER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_POINTER
+void ffi_core_rust_future_poll_pointer(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_POINTER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_POINTER
+void ffi_core_rust_future_cancel_pointer(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_POINTER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_POINTER
+void ffi_core_rust_future_free_pointer(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_POINTER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_POINTER
+void*_Nonnull ffi_core_rust_future_complete_pointer(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_RUST_BUFFER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_RUST_BUFFER
+void ffi_core_rust_future_poll_rust_buffer(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_RUST_BUFFER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_RUST_BUFFER
+void ffi_core_rust_future_cancel_rust_buffer(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_RUST_BUFFER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_RUST_BUFFER
+void ffi_core_rust_future_free_rust_buffer(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_RUST_BUFFER
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_RUST_BUFFER
+RustBuffer ffi_core_rust_future_complete_rust_buffer(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_VOID
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_POLL_VOID
+void ffi_core_rust_future_poll_void(uint64_t handle, UniffiRustFutureContinuationCallback _Nonnull callback, uint64_t callback_data
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_VOID
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_CANCEL_VOID
+void ffi_core_rust_future_cancel_void(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_VOID
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_FREE_VOID
+void ffi_core_rust_future_free_void(uint64_t handle
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_VOID
+#define UNIFFI_FFIDEF_FFI_CORE_RUST_FUTURE_COMPLETE_VOID
+void ffi_core_rust_future_complete_void(uint64_t handle, RustCallStatus *_Nonnull out_status
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_FUNC_PARSE_QUICK_ADD
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_FUNC_PARSE_QUICK_ADD
+uint16_t uniffi_core_checksum_func_parse_quick_add(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_ADD
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_ADD
+uint16_t uniffi_core_checksum_method_freelist_add(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_ADD_QUICK
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_ADD_QUICK
+uint16_t uniffi_core_checksum_method_freelist_add_quick(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_ALL
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_ALL
+uint16_t uniffi_core_checksum_method_freelist_all(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_CLEAR_ALL
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_CLEAR_ALL
+uint16_t uniffi_core_checksum_method_freelist_clear_all(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_DELETE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_DELETE
+uint16_t uniffi_core_checksum_method_freelist_delete(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_EXPORT_ARCHIVE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_EXPORT_ARCHIVE
+uint16_t uniffi_core_checksum_method_freelist_export_archive(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_FETCH
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_FETCH
+uint16_t uniffi_core_checksum_method_freelist_fetch(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_ALL_TAGS
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_ALL_TAGS
+uint16_t uniffi_core_checksum_method_freelist_get_all_tags(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_BY_ID
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_BY_ID
+uint16_t uniffi_core_checksum_method_freelist_get_by_id(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_BY_LIST
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_BY_LIST
+uint16_t uniffi_core_checksum_method_freelist_get_by_list(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_BY_TAG
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_BY_TAG
+uint16_t uniffi_core_checksum_method_freelist_get_by_tag(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_COMPLETED
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_COMPLETED
+uint16_t uniffi_core_checksum_method_freelist_get_completed(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_OVERDUE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_OVERDUE
+uint16_t uniffi_core_checksum_method_freelist_get_overdue(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_SUBTASKS
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_SUBTASKS
+uint16_t uniffi_core_checksum_method_freelist_get_subtasks(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_TODO
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_GET_TODO
+uint16_t uniffi_core_checksum_method_freelist_get_todo(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_IMPORT_ARCHIVE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_IMPORT_ARCHIVE
+uint16_t uniffi_core_checksum_method_freelist_import_archive(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_MARK_DONE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_MARK_DONE
+uint16_t uniffi_core_checksum_method_freelist_mark_done(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_MARK_UNDONE
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_MARK_UNDONE
+uint16_t uniffi_core_checksum_method_freelist_mark_undone(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_MOVE_TASK
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_METHOD_FREELIST_MOVE_TASK
+uint16_t uniffi_core_checksum_method_freelist_move_task(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_CONSTRUCTOR_FREELIST_IN_MEMORY
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_CONSTRUCTOR_FREELIST_IN_MEMORY
+uint16_t uniffi_core_checksum_constructor_freelist_in_memory(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_CONSTRUCTOR_FREELIST_NEW
+#define UNIFFI_FFIDEF_UNIFFI_CORE_CHECKSUM_CONSTRUCTOR_FREELIST_NEW
+uint16_t uniffi_core_checksum_constructor_freelist_new(void
+    
+);
+#endif
+#ifndef UNIFFI_FFIDEF_FFI_CORE_UNIFFI_CONTRACT_VERSION
+#define UNIFFI_FFIDEF_FFI_CORE_UNIFFI_CONTRACT_VERSION
+uint32_t ffi_core_uniffi_contract_version(void
+    
+);
+#endif
+
@@ -0,0 +1,255 @@
+// core/src/bindings.rs
+// Rust side of the UniFFI interface in freelist.udl
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+use crate::{
+    Archive, ArchiveError, ImportSummary, OrderingError, QuickAddError, Task, TaskFilter, TaskList,
+    UniffiCustomTypeConverter,
+};
+
+pub type UtcDateTime = DateTime<Utc>;
+
+impl UniffiCustomTypeConverter for UtcDateTime {
+    type Builtin = SystemTime;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(DateTime::<Utc>::from(val))
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum FreeListError {
+    NotFound { id: i64 },
+    Storage { message: String },
+    InvalidInput { message: String },
+}
+
+impl std::fmt::Display for FreeListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FreeListError::NotFound { id } => write!(f, "task {} not found", id),
+            FreeListError::Storage { message } => write!(f, "storage error: {}", message),
+            FreeListError::InvalidInput { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for FreeListError {}
+
+impl From<Box<dyn std::error::Error>> for FreeListError {
+    fn from(err: Box<dyn std::error::Error>) -> Self {
+        FreeListError::Storage { message: err.to_string() }
+    }
+}
+
+impl From<QuickAddError> for FreeListError {
+    fn from(err: QuickAddError) -> Self {
+        FreeListError::InvalidInput { message: err.to_string() }
+    }
+}
+
+impl From<OrderingError> for FreeListError {
+    fn from(err: OrderingError) -> Self {
+        match err {
+            OrderingError::TaskNotFound(id) => FreeListError::NotFound { id },
+            _ => FreeListError::InvalidInput { message: err.to_string() },
+        }
+    }
+}
+
+impl From<ArchiveError> for FreeListError {
+    fn from(err: ArchiveError) -> Self {
+        FreeListError::InvalidInput { message: err.to_string() }
+    }
+}
+
+pub fn parse_quick_add(input: String) -> Result<Task, FreeListError> {
+    Ok(Task::parse_quick_add(&input)?)
+}
+
+/// Thread-safe handle around `TaskList` exposed to Swift and Kotlin.
+pub struct FreeList {
+    task_list: Mutex<TaskList>,
+}
+
+impl FreeList {
+    pub fn new(db_path: String) -> Result<Self, FreeListError> {
+        Ok(FreeList { task_list: Mutex::new(TaskList::new(db_path)?) })
+    }
+
+    pub fn in_memory() -> Result<Self, FreeListError> {
+        Ok(FreeList { task_list: Mutex::new(TaskList::new_in_memory()?) })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TaskList> {
+        self.task_list.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn ensure_exists(task_list: &TaskList, id: i64) -> Result<(), FreeListError> {
+        match task_list.get_by_id(id)? {
+            Some(_) => Ok(()),
+            None => Err(FreeListError::NotFound { id }),
+        }
+    }
+
+    pub fn add(&self, task: Task) -> Result<i64, FreeListError> {
+        Ok(self.lock().add(task)?)
+    }
+
+    /// Adds a task from quick-add text, checking that a `^parent` reference exists.
+    pub fn add_quick(&self, input: String) -> Result<i64, FreeListError> {
+        let task = Task::parse_quick_add(&input)?;
+        let mut task_list = self.lock();
+        if let Some(parent_id) = task.parent_id {
+            Self::ensure_exists(&task_list, parent_id)?;
+        }
+        Ok(task_list.add(task)?)
+    }
+
+    pub fn fetch(&self, filter: TaskFilter) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().fetch(filter)?)
+    }
+
+    pub fn all(&self) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().all()?)
+    }
+
+    pub fn get_todo(&self) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().get_todo()?)
+    }
+
+    pub fn get_completed(&self) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().get_completed()?)
+    }
+
+    pub fn get_overdue(&self) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().get_overdue()?)
+    }
+
+    pub fn get_by_tag(&self, tag: String) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().get_by_tag(&tag)?)
+    }
+
+    pub fn get_by_list(&self, list_id: i64) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().get_by_list(list_id)?)
+    }
+
+    pub fn get_subtasks(&self, parent_id: i64) -> Result<Vec<Task>, FreeListError> {
+        Ok(self.lock().get_subtasks(parent_id)?)
+    }
+
+    pub fn get_by_id(&self, id: i64) -> Result<Option<Task>, FreeListError> {
+        Ok(self.lock().get_by_id(id)?)
+    }
+
+    pub fn mark_done(&self, id: i64) -> Result<(), FreeListError> {
+        let mut task_list = self.lock();
+        Self::ensure_exists(&task_list, id)?;
+        Ok(task_list.mark_done(id)?)
+    }
+
+    pub fn mark_undone(&self, id: i64) -> Result<(), FreeListError> {
+        let mut task_list = self.lock();
+        Self::ensure_exists(&task_list, id)?;
+        Ok(task_list.mark_undone(id)?)
+    }
+
+    pub fn delete(&self, id: i64) -> Result<(), FreeListError> {
+        let mut task_list = self.lock();
+        Self::ensure_exists(&task_list, id)?;
+        Ok(task_list.delete(id)?)
+    }
+
+    pub fn move_task(&self, id: i64, before: Option<i64>, after: Option<i64>) -> Result<(), FreeListError> {
+        self.lock().move_task(id, before, after).map_err(|err| match err.downcast::<OrderingError>() {
+            Ok(err) => (*err).into(),
+            Err(err) => err.into(),
+        })
+    }
+
+    pub fn clear_all(&self) -> Result<(), FreeListError> {
+        Ok(self.lock().clear_all()?)
+    }
+
+    pub fn get_all_tags(&self) -> Result<Vec<String>, FreeListError> {
+        Ok(self.lock().get_all_tags()?)
+    }
+
+    pub fn export_archive(&self) -> Result<String, FreeListError> {
+        Ok(self.lock().export_archive()?.to_json()?)
+    }
+
+    pub fn import_archive(&self, archive_json: String) -> Result<ImportSummary, FreeListError> {
+        let archive = Archive::from_json(&archive_json)?;
+        Ok(self.lock().import_archive(&archive)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskStatus;
+
+    #[test]
+    fn test_free_list_wrapper() {
+        let free_list = FreeList::in_memory().unwrap();
+        let id = free_list.add(Task::new("Stretch").with_tag("health")).unwrap();
+        free_list.add(Task::new("Read")).unwrap();
+
+        free_list.mark_done(id).unwrap();
+        let done = free_list
+            .fetch(TaskFilter { status: Some(TaskStatus::Done), ..Default::default() })
+            .unwrap();
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].id, Some(id));
+
+        assert!(matches!(free_list.delete(999), Err(FreeListError::NotFound { id: 999 })));
+        assert_eq!(free_list.get_all_tags().unwrap(), vec!["health".to_string()]);
+
+        let read = free_list.get_todo().unwrap()[0].id.unwrap();
+        free_list.move_task(id, None, Some(read)).unwrap();
+        assert_eq!(free_list.all().unwrap()[0].id, Some(id));
+        assert!(matches!(free_list.move_task(id, Some(999), None), Err(FreeListError::NotFound { id: 999 })));
+        assert!(matches!(free_list.move_task(id, None, None), Err(FreeListError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_add_quick() {
+        let free_list = FreeList::in_memory().unwrap();
+        let parent = free_list.add_quick("Plan trip #travel !high".to_string()).unwrap();
+        let child = free_list.add_quick(format!("Book flights ~30m ^{}", parent)).unwrap();
+
+        let stored = free_list.get_by_id(child).unwrap().unwrap();
+        assert_eq!(stored.title, "Book flights");
+        assert_eq!(stored.parent_id, Some(parent));
+        assert_eq!(stored.estimated_duration, Some(30));
+        assert_eq!(free_list.get_by_id(parent).unwrap().unwrap().priority, Some(crate::Priority::High));
+
+        assert!(matches!(free_list.add_quick("Orphan ^999".to_string()), Err(FreeListError::NotFound { id: 999 })));
+        assert!(matches!(parse_quick_add("#only-a-tag".to_string()), Err(FreeListError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let source = FreeList::in_memory().unwrap();
+        source.add_quick("Pack bags #travel".to_string()).unwrap();
+        let json = source.export_archive().unwrap();
+
+        let target = FreeList::in_memory().unwrap();
+        assert_eq!(target.import_archive(json.clone()).unwrap().tasks_added, 1);
+        assert_eq!(target.import_archive(json).unwrap().tasks_skipped, 1);
+        assert!(matches!(target.import_archive("[]".to_string()), Err(FreeListError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_timestamp_conversion_round_trips() {
+        let now = Utc::now();
+        let system_time = UtcDateTime::from_custom(now);
+        assert_eq!(UtcDateTime::into_custom(system_time).unwrap(), now);
+    }
+}
@@ -0,0 +1,182 @@
+// core/src/changes.rs
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, PoisonError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// A single row touched by a committed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub table: String,
+    pub id: i64,
+    pub kind: ChangeKind,
+}
+
+/// Changes delivered to observers after a commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub changes: Vec<Change>,
+    /// Another connection (e.g. the CLI or a sync process) wrote to the database.
+    /// The affected rows are unknown, so observers should refetch.
+    pub external: bool,
+}
+
+impl ChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && !self.external
+    }
+}
+
+pub trait ChangeObserver: Send {
+    fn on_change(&self, changes: &ChangeSet);
+}
+
+impl<F: Fn(&ChangeSet) + Send> ChangeObserver for F {
+    fn on_change(&self, changes: &ChangeSet) {
+        self(changes)
+    }
+}
+
+/// Collects rows reported by the SQLite update hook until their transaction
+/// commits (kept) or rolls back (discarded).
+#[derive(Debug, Default)]
+pub struct ChangeTracker {
+    pending: Vec<Change>,
+    committed: Vec<Change>,
+}
+
+impl ChangeTracker {
+    pub fn shared() -> Arc<Mutex<ChangeTracker>> {
+        Arc::new(Mutex::new(ChangeTracker::default()))
+    }
+
+    pub fn record(tracker: &Mutex<ChangeTracker>, change: Change) {
+        lock(tracker).pending.push(change);
+    }
+
+    pub fn commit(tracker: &Mutex<ChangeTracker>) {
+        let mut tracker = lock(tracker);
+        let pending = std::mem::take(&mut tracker.pending);
+        tracker.committed.extend(pending);
+    }
+
+    pub fn rollback(tracker: &Mutex<ChangeTracker>) {
+        lock(tracker).pending.clear();
+    }
+
+    pub fn take_committed(tracker: &Mutex<ChangeTracker>) -> Vec<Change> {
+        std::mem::take(&mut lock(tracker).committed)
+    }
+}
+
+fn lock(tracker: &Mutex<ChangeTracker>) -> std::sync::MutexGuard<'_, ChangeTracker> {
+    tracker.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Task, TaskList};
+
+    fn collecting_observer(task_list: &mut TaskList) -> Arc<Mutex<Vec<ChangeSet>>> {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        task_list.subscribe(move |changes: &ChangeSet| sink.lock().unwrap().push(changes.clone()));
+        received
+    }
+
+    fn kinds(change_sets: &[ChangeSet]) -> Vec<(i64, ChangeKind)> {
+        change_sets
+            .iter()
+            .flat_map(|set| set.changes.iter().map(|c| (c.id, c.kind)))
+            .collect()
+    }
+
+    #[test]
+    fn test_observer_receives_committed_mutations() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let received = collecting_observer(&mut task_list);
+
+        let parent = task_list.add(Task::new("Parent"))?;
+        let child = task_list.add(Task::new("Child").with_parent(parent))?;
+        task_list.mark_done(child)?;
+        task_list.delete(parent)?;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 4);
+        assert!(received.iter().all(|set| !set.external && set.changes[0].table == "tasks"));
+        assert_eq!(
+            kinds(&received),
+            vec![
+                (parent, ChangeKind::Insert),
+                (child, ChangeKind::Insert),
+                (child, ChangeKind::Update),
+                (child, ChangeKind::Delete),
+                (parent, ChangeKind::Delete),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_all_reports_every_row() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let a = task_list.add(Task::new("A"))?;
+        let b = task_list.add(Task::new("B"))?;
+        let received = collecting_observer(&mut task_list);
+
+        task_list.clear_all()?;
+        let mut deleted = kinds(&received.lock().unwrap());
+        deleted.sort_by_key(|(id, _)| *id);
+        assert_eq!(deleted, vec![(a, ChangeKind::Delete), (b, ChangeKind::Delete)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsubscribe_stops_notifications() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let received = Arc::new(Mutex::new(0));
+        let sink = Arc::clone(&received);
+        let id = task_list.subscribe(move |_: &ChangeSet| *sink.lock().unwrap() += 1);
+
+        task_list.add(Task::new("Seen"))?;
+        assert!(task_list.unsubscribe(id));
+        task_list.add(Task::new("Unseen"))?;
+        assert_eq!(*received.lock().unwrap(), 1);
+        assert!(!task_list.unsubscribe(id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_external_writer_is_detected() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!("freelist-changes-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut app = TaskList::new(&path)?;
+        let received = collecting_observer(&mut app);
+        let mut cli = TaskList::new(&path)?;
+
+        app.poll_changes()?;
+        assert!(received.lock().unwrap().is_empty());
+
+        cli.add(Task::new("Added from the CLI"))?;
+        app.poll_changes()?;
+        {
+            let received = received.lock().unwrap();
+            assert_eq!(received.len(), 1);
+            assert!(received[0].external);
+            assert!(received[0].changes.is_empty());
+        }
+
+        drop(app);
+        drop(cli);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
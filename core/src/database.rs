@@ -1,28 +1,94 @@
 // core/src/database.rs
 use rusqlite::{Connection, Result, params};
+use rusqlite::hooks::Action;
+use std::collections::HashMap;
 use std::path::Path;
-use crate::task::Task;
+use std::sync::{Arc, Mutex};
+use crate::changes::{Change, ChangeKind, ChangeTracker};
+use crate::ordering;
+use crate::task::{Priority, Task};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const HISTORY_TABLE: &str = "task_history";
+// Shadow tables of the food search index, see `nutrition::catalogue`
+const FOOD_SEARCH_TABLES: &str = "foods_fts";
+
+// Column order read by `task_from_row`
+const TASK_COLUMNS: &str = "id, title, details, done, due_date, is_recurring,
+    estimated_duration, last_duration, tag, parent_id,
+    created_at, updated_at, priority, list_id, position";
+
+/// How tasks are listed: manual order, newest first where keys are equal
+pub(crate) const TASK_ORDER: &str = "position, created_at DESC, id DESC";
 
 pub struct Database {
     conn: Connection,
+    changes: Arc<Mutex<ChangeTracker>>,
 }
 
 impl Database {
     pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
         let conn = Connection::open(db_path)?;
-        let db = Database { conn };
-        db.init_schema()?;
-        Ok(db)
+        Self::from_connection(conn)
     }
 
     pub fn new_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
-        let db = Database { conn };
+        Self::from_connection(conn)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        let db = Database { conn, changes: ChangeTracker::shared() };
         db.init_schema()?;
+        db.install_change_hooks();
         Ok(db)
     }
 
+    // Every write made through this connection, including raw SQL from other
+    // modules, is recorded and handed out by `take_changes` once committed.
+    fn install_change_hooks(&self) {
+        let changes = Arc::clone(&self.changes);
+        self.conn.update_hook(Some(move |action: Action, _db: &str, table: &str, id: i64| {
+            let kind = match action {
+                Action::SQLITE_INSERT => ChangeKind::Insert,
+                Action::SQLITE_UPDATE => ChangeKind::Update,
+                Action::SQLITE_DELETE => ChangeKind::Delete,
+                _ => return,
+            };
+            // History rows are written by triggers alongside the task change they describe,
+            // and the search index follows the foods table the same way
+            if table == HISTORY_TABLE || table.starts_with(FOOD_SEARCH_TABLES) {
+                return;
+            }
+            ChangeTracker::record(&changes, Change { table: table.to_string(), id, kind });
+        }));
+
+        let changes = Arc::clone(&self.changes);
+        self.conn.commit_hook(Some(move || {
+            ChangeTracker::commit(&changes);
+            false // let the commit proceed
+        }));
+
+        let changes = Arc::clone(&self.changes);
+        self.conn.rollback_hook(Some(move || ChangeTracker::rollback(&changes)));
+    }
+
+    /// Rows changed by committed transactions on this connection since the last call.
+    pub fn take_changes(&self) -> Vec<Change> {
+        ChangeTracker::take_committed(&self.changes)
+    }
+
+    /// For modules that keep their own tables in the shared database.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Changes whenever another connection commits to the same database file.
+    pub fn data_version(&self) -> Result<i64> {
+        self.conn.query_row("PRAGMA data_version", [], |row| row.get(0))
+    }
+
     fn init_schema(&self) -> Result<()> {
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS tasks (
@@ -38,6 +104,7 @@ impl Database {
                 parent_id INTEGER,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
+                priority INTEGER,
                 FOREIGN KEY(parent_id) REFERENCES tasks(id)
             )",
             [],
@@ -54,18 +121,127 @@ impl Database {
             [],
         )?;
 
+        // Columns added after the first release
+        self.add_column_if_missing("tasks", "priority", "INTEGER")?;
+        self.add_column_if_missing("tasks", "list_id", "INTEGER")?;
+        self.conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_list ON tasks(list_id)", [])?;
+        self.add_column_if_missing("tasks", "position", "TEXT")?;
+        self.conn.execute("CREATE INDEX IF NOT EXISTS idx_tasks_position ON tasks(position)", [])?;
+        ordering::place_unordered(self)?;
+
+        self.init_history()?;
+
+        // UIDs given to tasks by calendar clients; other tasks use `ics::task_uid`
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS task_uids (
+                task_id INTEGER PRIMARY KEY,
+                uid TEXT NOT NULL UNIQUE
+            );
+            CREATE TRIGGER IF NOT EXISTS task_uids_deleted AFTER DELETE ON tasks
+            BEGIN
+                DELETE FROM task_uids WHERE task_id = OLD.id;
+            END;",
+        )?;
+
+        crate::nutrition::init_schema(self)?;
+        crate::fitness::init_schema(self)?;
+        crate::journal::init_schema(self)?;
+        crate::lists::init_schema(self)?;
+
+        Ok(())
+    }
+
+    fn init_history(&self) -> Result<()> {
+        let exists: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [HISTORY_TABLE],
+            |row| row.get(0),
+        )?;
+
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS task_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id INTEGER NOT NULL,
+                event TEXT NOT NULL,
+                at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_task_history_task ON task_history(task_id);
+
+            CREATE TRIGGER IF NOT EXISTS task_history_created AFTER INSERT ON tasks
+            BEGIN
+                INSERT INTO task_history (task_id, event, at) VALUES (NEW.id, 'created', NEW.created_at);
+            END;
+            CREATE TRIGGER IF NOT EXISTS task_history_done AFTER UPDATE OF done ON tasks
+            WHEN NEW.done != OLD.done
+            BEGIN
+                INSERT INTO task_history (task_id, event, at)
+                VALUES (NEW.id, CASE NEW.done WHEN 1 THEN 'completed' ELSE 'reopened' END, NEW.updated_at);
+            END;
+            CREATE TRIGGER IF NOT EXISTS task_history_rescheduled AFTER UPDATE OF due_date ON tasks
+            WHEN OLD.due_date IS NOT NULL AND NEW.due_date > OLD.due_date
+            BEGIN
+                INSERT INTO task_history (task_id, event, at) VALUES (NEW.id, 'rescheduled', NEW.updated_at);
+            END;
+            CREATE TRIGGER IF NOT EXISTS task_history_deleted AFTER DELETE ON tasks
+            BEGIN
+                DELETE FROM task_history WHERE task_id = OLD.id;
+            END;",
+        )?;
+
+        if !exists {
+            // Best guess for databases created before history was kept
+            self.conn.execute_batch(
+                "INSERT INTO task_history (task_id, event, at) SELECT id, 'created', created_at FROM tasks;
+                 INSERT INTO task_history (task_id, event, at)
+                    SELECT id, 'completed', updated_at FROM tasks WHERE done = 1;",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Runs `f` in a transaction, committing only if it succeeds.
+    pub fn with_transaction<T, E: From<rusqlite::Error>>(
+        &self,
+        f: impl FnOnce(&Database) -> std::result::Result<T, E>,
+    ) -> std::result::Result<T, E> {
+        let tx = self.conn.unchecked_transaction()?;
+        let value = f(self)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// Runs `f` in a transaction that is always rolled back, to see what a change would do.
+    pub fn with_rollback<T>(&self, f: impl FnOnce(&Database) -> Result<T>) -> Result<T> {
+        let tx = self.conn.unchecked_transaction()?;
+        let value = f(self)?;
+        tx.rollback()?;
+        Ok(value)
+    }
+
+    pub(crate) fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let exists: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+            params![table, column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            self.conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        }
         Ok(())
     }
 
+    /// Stores a new task, on top of the manual order unless it brings a valid `position`.
     pub fn insert_task(&self, task: &Task) -> Result<i64> {
-        let now = Utc::now().to_rfc3339();
-        
+        let position = match task.position.as_deref().filter(|key| ordering::is_valid_key(key)) {
+            Some(key) => key.to_string(),
+            None => ordering::top_key(self)?,
+        };
         self.conn.execute(
             "INSERT INTO tasks (
                 title, details, done, due_date, is_recurring, 
                 estimated_duration, last_duration, tag, parent_id, 
-                created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                created_at, updated_at, priority, list_id, position
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             params![
                 task.title,
                 task.details,
@@ -76,8 +252,11 @@ impl Database {
                 task.last_duration,
                 task.tag,
                 task.parent_id,
-                now,
-                now
+                task.created_at.to_rfc3339(),
+                task.updated_at.to_rfc3339(),
+                task.priority.map(Priority::rank),
+                task.list_id,
+                position
             ],
         )?;
 
@@ -85,11 +264,7 @@ impl Database {
     }
 
     pub fn fetch_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>> {
-        let mut query = String::from(
-            "SELECT id, title, details, done, due_date, is_recurring, 
-             estimated_duration, last_duration, tag, parent_id, 
-             created_at, updated_at FROM tasks"
-        );
+        let mut query = format!("SELECT {} FROM tasks", TASK_COLUMNS);
         
         let mut conditions = Vec::new();
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
@@ -114,47 +289,23 @@ impl Database {
             params.push(Box::new(parent_id));
         }
 
+        if let Some(list_id) = filter.list_id {
+            conditions.push("list_id = ?");
+            params.push(Box::new(list_id));
+        }
+
         if !conditions.is_empty() {
             query.push_str(" WHERE ");
             query.push_str(&conditions.join(" AND "));
         }
 
-        query.push_str(" ORDER BY created_at DESC");
+        query.push_str(" ORDER BY ");
+        query.push_str(TASK_ORDER);
 
         let mut stmt = self.conn.prepare(&query)?;
         let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         
-        let task_iter = stmt.query_map(&param_refs[..], |row| {
-            let due_date_str: Option<String> = row.get(4)?;
-            let due_date = due_date_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
-
-            let created_at_str: String = row.get(10)?;
-            let updated_at_str: String = row.get(11)?;
-            
-            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-                
-            let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-
-            Ok(Task {
-                id: Some(row.get(0)?),
-                title: row.get(1)?,
-                details: row.get(2)?,
-                done: row.get::<_, i32>(3)? == 1,
-                due_date,
-                is_recurring: row.get::<_, i32>(5)? == 1,
-                estimated_duration: row.get(6)?,
-                last_duration: row.get(7)?,
-                tag: row.get(8)?,
-                parent_id: row.get(9)?,
-                created_at,
-                updated_at,
-            })
-        })?;
+        let task_iter = stmt.query_map(&param_refs[..], task_from_row)?;
 
         let mut tasks = Vec::new();
         for task in task_iter {
@@ -164,13 +315,61 @@ impl Database {
         Ok(tasks)
     }
 
+    /// Id of the task with this title created at exactly this time, used to match imported tasks.
+    pub fn find_task(&self, title: &str, created_at: &DateTime<Utc>) -> Result<Option<i64>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM tasks WHERE title = ?1 AND created_at = ?2")?;
+        let mut rows = stmt.query_map(params![title, created_at.to_rfc3339()], |row| row.get(0))?;
+        rows.next().transpose()
+    }
+
+    pub fn set_parent(&self, id: i64, parent_id: Option<i64>) -> Result<()> {
+        self.conn.execute("UPDATE tasks SET parent_id = ?1 WHERE id = ?2", params![parent_id, id])?;
+        Ok(())
+    }
+
+    /// History of one task, or of every task, oldest first.
+    pub fn fetch_history(&self, task_id: Option<i64>) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, event, at FROM task_history WHERE ?1 IS NULL OR task_id = ?1 ORDER BY at, id",
+        )?;
+        let rows = stmt.query_map([task_id], |row| {
+            let event: String = row.get(1)?;
+            let at: String = row.get(2)?;
+            Ok((row.get(0)?, event, at))
+        })?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            let (task_id, event, at) = row?;
+            // Rows written by a newer version with unknown events are left out
+            let Some(event) = TaskEvent::parse(&event) else { continue };
+            let at = DateTime::parse_from_rfc3339(&at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+            history.push(HistoryEntry { task_id, event, at });
+        }
+        Ok(history)
+    }
+
+    /// Records a history entry unless the same one already exists. Returns whether it was added.
+    pub fn insert_history(&self, entry: &HistoryEntry) -> Result<bool> {
+        let added = self.conn.execute(
+            "INSERT INTO task_history (task_id, event, at)
+             SELECT ?1, ?2, ?3 WHERE NOT EXISTS (
+                SELECT 1 FROM task_history WHERE task_id = ?1 AND event = ?2 AND at = ?3
+             )",
+            params![entry.task_id, entry.event.as_str(), entry.at.to_rfc3339()],
+        )?;
+        Ok(added > 0)
+    }
+
     pub fn fetch_all_tags(&self) -> Result<Vec<String>> {
         let mut stmt = self.conn.prepare(
             "SELECT DISTINCT tag FROM tasks WHERE tag IS NOT NULL AND tag != '' ORDER BY tag"
         )?;
         
         let tag_iter = stmt.query_map([], |row| {
-            Ok(row.get::<_, String>(0)?)
+            row.get::<_, String>(0)
         })?;
 
         let mut tags = Vec::new();
@@ -190,57 +389,76 @@ impl Database {
         Ok(())
     }
 
-    pub fn delete_task(&self, id: i64) -> Result<()> {
-        // Delete subtasks first
-        self.conn.execute("DELETE FROM tasks WHERE parent_id = ?1", params![id])?;
-        // Then delete the task itself
-        self.conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
+    /// Overwrites every editable field of task `id`; `created_at` is kept, and so
+    /// is `position` when the task has no valid one.
+    pub fn update_task(&self, id: i64, task: &Task) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE tasks SET title = ?1, details = ?2, done = ?3, due_date = ?4, is_recurring = ?5,
+             estimated_duration = ?6, last_duration = ?7, tag = ?8, parent_id = ?9,
+             priority = ?10, updated_at = ?11, list_id = ?12, position = COALESCE(?13, position)
+             WHERE id = ?14",
+            params![
+                task.title,
+                task.details,
+                if task.done { 1 } else { 0 },
+                task.due_date.as_ref().map(|d| d.to_rfc3339()),
+                if task.is_recurring { 1 } else { 0 },
+                task.estimated_duration,
+                task.last_duration,
+                task.tag,
+                task.parent_id,
+                task.priority.map(Priority::rank),
+                now,
+                task.list_id,
+                task.position.as_deref().filter(|key| ordering::is_valid_key(key)),
+                id
+            ],
+        )?;
         Ok(())
     }
 
+    pub fn delete_task(&self, id: i64) -> Result<()> {
+        self.with_transaction(|db| {
+            // Delete subtasks first
+            db.conn.execute("DELETE FROM tasks WHERE parent_id = ?1", params![id])?;
+            // Then delete the task itself
+            db.conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+
     pub fn clear_all_tasks(&self) -> Result<()> {
-        self.conn.execute("DELETE FROM tasks", [])?;
+        // The WHERE clause disables SQLite's truncate optimisation, which would
+        // otherwise skip the update hook and hide the deleted rows from observers
+        self.conn.execute("DELETE FROM tasks WHERE 1", [])?;
         Ok(())
     }
 
-    pub fn get_task_by_id(&self, id: i64) -> Result<Option<Task>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, title, details, done, due_date, is_recurring, 
-             estimated_duration, last_duration, tag, parent_id, 
-             created_at, updated_at FROM tasks WHERE id = ?1"
+    pub fn set_uid(&self, task_id: i64, uid: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO task_uids (task_id, uid) VALUES (?1, ?2)",
+            params![task_id, uid],
         )?;
+        Ok(())
+    }
 
-        let mut task_iter = stmt.query_map([id], |row| {
-            let due_date_str: Option<String> = row.get(4)?;
-            let due_date = due_date_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+    pub fn find_uid(&self, uid: &str) -> Result<Option<i64>> {
+        let mut stmt = self.conn.prepare("SELECT task_id FROM task_uids WHERE uid = ?1")?;
+        let mut rows = stmt.query_map([uid], |row| row.get(0))?;
+        rows.next().transpose()
+    }
 
-            let created_at_str: String = row.get(10)?;
-            let updated_at_str: String = row.get(11)?;
-            
-            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-                
-            let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
+    /// Stored UIDs by task id.
+    pub fn uids(&self) -> Result<HashMap<i64, String>> {
+        let mut stmt = self.conn.prepare("SELECT task_id, uid FROM task_uids")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
 
-            Ok(Task {
-                id: Some(row.get(0)?),
-                title: row.get(1)?,
-                details: row.get(2)?,
-                done: row.get::<_, i32>(3)? == 1,
-                due_date,
-                is_recurring: row.get::<_, i32>(5)? == 1,
-                estimated_duration: row.get(6)?,
-                last_duration: row.get(7)?,
-                tag: row.get(8)?,
-                parent_id: row.get(9)?,
-                created_at,
-                updated_at,
-            })
-        })?;
+    pub fn get_task_by_id(&self, id: i64) -> Result<Option<Task>> {
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS))?;
+        let mut task_iter = stmt.query_map([id], task_from_row)?;
 
         match task_iter.next() {
             Some(task) => Ok(Some(task?)),
@@ -249,11 +467,86 @@ impl Database {
     }
 }
 
+fn task_from_row(row: &rusqlite::Row) -> Result<Task> {
+    let due_date_str: Option<String> = row.get(4)?;
+    let due_date = due_date_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    let created_at_str: String = row.get(10)?;
+    let updated_at_str: String = row.get(11)?;
+
+    let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    let updated_at = DateTime::parse_from_rfc3339(&updated_at_str)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    Ok(Task {
+        id: Some(row.get(0)?),
+        title: row.get(1)?,
+        details: row.get(2)?,
+        done: row.get::<_, i32>(3)? == 1,
+        due_date,
+        is_recurring: row.get::<_, i32>(5)? == 1,
+        estimated_duration: row.get(6)?,
+        last_duration: row.get(7)?,
+        tag: row.get(8)?,
+        priority: row.get::<_, Option<i32>>(12)?.and_then(Priority::from_rank),
+        parent_id: row.get(9)?,
+        created_at,
+        updated_at,
+        list_id: row.get(13)?,
+        position: row.get(14)?,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskEvent {
+    Created,
+    Completed,
+    Reopened,
+    /// The due date was moved later
+    Rescheduled,
+}
+
+impl TaskEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskEvent::Created => "created",
+            TaskEvent::Completed => "completed",
+            TaskEvent::Reopened => "reopened",
+            TaskEvent::Rescheduled => "rescheduled",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "created" => Some(TaskEvent::Created),
+            "completed" => Some(TaskEvent::Completed),
+            "reopened" => Some(TaskEvent::Reopened),
+            "rescheduled" => Some(TaskEvent::Rescheduled),
+            _ => None,
+        }
+    }
+}
+
+/// Something that happened to a task, recorded by triggers on the `tasks` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub task_id: i64,
+    pub event: TaskEvent,
+    pub at: DateTime<Utc>,
+}
+
 #[derive(Debug, Default)]
 pub struct TaskFilter {
     pub status: Option<TaskStatus>,
     pub tag: Option<String>,
     pub parent_id: Option<i64>,
+    pub list_id: Option<i64>,
 }
 
 #[derive(Debug)]
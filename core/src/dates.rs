@@ -0,0 +1,422 @@
+// core/src/dates.rs
+// Natural-language due dates: "tomorrow 5pm", "next friday", "in 3 days", "eod", "2026-11-01".
+use chrono::{
+    DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateParseError(pub String);
+
+impl std::fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot understand date '{}'", self.0)
+    }
+}
+
+impl std::error::Error for DateParseError {}
+
+/// Time used when only a day is given, so a task due "today" is not overdue until the day ends.
+pub fn end_of_day() -> NaiveTime {
+    NaiveTime::from_hms_opt(23, 59, 59).unwrap()
+}
+
+/// Parses `input` relative to `now`, interpreting wall-clock times in `now`'s timezone.
+///
+/// Dates without a time are due at the end of that day. Weekday names mean the
+/// coming occurrence (today included); `next <weekday>` always skips today.
+/// A bare time that has already passed today means tomorrow.
+pub fn parse_due_date<Tz: TimeZone>(input: &str, now: &DateTime<Tz>) -> Result<DateTime<Utc>, DateParseError> {
+    let error = || DateParseError(input.to_string());
+    let trimmed = input.trim();
+
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(trimmed, format) {
+            return to_utc(&now.timezone(), naive).ok_or_else(error);
+        }
+    }
+
+    let lowered = trimmed.to_lowercase().replace(',', " ");
+    let tokens = merge_meridiem(lowered.split_whitespace().collect());
+    if tokens.is_empty() {
+        return Err(error());
+    }
+
+    // Relative instants: "now", "in 2 hours", "in 30 minutes"
+    if tokens == ["now"] {
+        return Ok(now.with_timezone(&Utc));
+    }
+    if let Some(offset) = parse_instant_offset(&tokens) {
+        let due = now.clone().checked_add_signed(offset).ok_or_else(error)?;
+        return Ok(due.with_timezone(&Utc));
+    }
+
+    let (time, date_tokens) = extract_time(&tokens).ok_or_else(error)?;
+    let today = now.date_naive();
+    let (date, default_time) = if date_tokens.is_empty() {
+        match time {
+            // A bare time that has already passed refers to tomorrow
+            Some(t) if t <= now.time() => (today + Duration::days(1), t),
+            _ => (today, end_of_day()),
+        }
+    } else {
+        parse_date(&date_tokens, today).ok_or_else(error)?
+    };
+
+    to_utc(&now.timezone(), date.and_time(time.unwrap_or(default_time))).ok_or_else(error)
+}
+
+/// `parse_due_date` against the system clock and timezone.
+pub fn parse_due_date_local(input: &str) -> Result<DateTime<Utc>, DateParseError> {
+    parse_due_date(input, &chrono::Local::now())
+}
+
+/// Parses when something already happened, relative to `now`: "8am" (the most
+/// recent one), "yesterday 7pm", "2 hours ago", "3 days ago", or anything
+/// `parse_due_date` accepts. "yesterday" alone means this time yesterday.
+pub fn parse_past<Tz: TimeZone>(input: &str, now: &DateTime<Tz>) -> Result<DateTime<Utc>, DateParseError> {
+    let error = || DateParseError(input.to_string());
+    let lowered = input.trim().to_lowercase().replace(',', " ");
+    let tokens = merge_meridiem(lowered.split_whitespace().collect());
+
+    if let Some(offset) = parse_past_offset(&tokens) {
+        let at = now.clone().checked_sub_signed(offset).ok_or_else(error)?;
+        return Ok(at.with_timezone(&Utc));
+    }
+    if tokens.first().map(String::as_str) == Some("yesterday") {
+        let yesterday = now.clone() - Duration::days(1);
+        if tokens.len() == 1 {
+            return Ok(yesterday.with_timezone(&Utc));
+        }
+        return parse_due_date(&format!("today {}", tokens[1..].join(" ")), &yesterday).map_err(|_| error());
+    }
+    if let Some((Some(time), rest)) = extract_time(&tokens) {
+        if rest.is_empty() {
+            let mut date = now.date_naive();
+            if time > now.time() {
+                date -= Duration::days(1);
+            }
+            return to_utc(&now.timezone(), date.and_time(time)).ok_or_else(error);
+        }
+    }
+    parse_due_date(input, now)
+}
+
+/// `parse_past` against the system clock and timezone.
+pub fn parse_past_local(input: &str) -> Result<DateTime<Utc>, DateParseError> {
+    parse_past(input, &chrono::Local::now())
+}
+
+pub(crate) fn to_utc<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    // Times skipped by a DST change move forward an hour; repeated times take the first
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(naive + Duration::hours(1))).earliest())
+        .map(|date_time| date_time.with_timezone(&Utc))
+}
+
+/// Midnight at the start of `date` in `tz`.
+pub(crate) fn start_of_day<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> Option<DateTime<Utc>> {
+    to_utc(tz, date.and_hms_opt(0, 0, 0)?)
+}
+
+/// Joins "5 pm" into "5pm" so times can be parsed one token at a time.
+fn merge_meridiem(tokens: Vec<&str>) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        match (token, merged.last_mut()) {
+            ("am" | "pm", Some(previous)) if previous.chars().all(|c| c.is_ascii_digit() || c == ':') => {
+                previous.push_str(token);
+            }
+            _ => merged.push(token.to_string()),
+        }
+    }
+    merged
+}
+
+fn parse_instant_offset(tokens: &[String]) -> Option<Duration> {
+    let [in_word, amount, unit] = tokens else { return None };
+    if in_word != "in" {
+        return None;
+    }
+    let amount = i64::from(amount.parse::<u32>().ok()?);
+    match unit.as_str() {
+        "minute" | "minutes" | "min" | "mins" | "m" => Duration::try_minutes(amount),
+        "hour" | "hours" | "hr" | "hrs" | "h" => Duration::try_hours(amount),
+        _ => None,
+    }
+}
+
+/// "2 hours ago", "3 days ago", "1 week ago"
+fn parse_past_offset(tokens: &[String]) -> Option<Duration> {
+    let [amount, unit, ago] = tokens else { return None };
+    if ago != "ago" {
+        return None;
+    }
+    let amount = i64::from(amount.parse::<u32>().ok()?);
+    match unit.as_str() {
+        "minute" | "minutes" | "min" | "mins" => Duration::try_minutes(amount),
+        "hour" | "hours" | "hr" | "hrs" => Duration::try_hours(amount),
+        "day" | "days" => Duration::try_days(amount),
+        "week" | "weeks" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// Splits out a time of day ("5pm", "17:30", "at 9am", "noon"). Returns None if more than one is given.
+fn extract_time(tokens: &[String]) -> Option<(Option<NaiveTime>, Vec<String>)> {
+    let mut time = None;
+    let mut rest = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        let (candidate, consumed) = match parse_time(token) {
+            Some(t) => (Some(t), 1),
+            None if token == "at" => (tokens.get(index + 1).and_then(|next| parse_time(next)), 2),
+            None => (None, 1),
+        };
+        match candidate {
+            Some(t) if time.is_none() => time = Some(t),
+            Some(_) => return None,
+            None if token == "at" => return None,
+            None => rest.push(token.clone()),
+        }
+        index += consumed;
+    }
+    Some((time, rest))
+}
+
+pub(crate) fn parse_time(token: &str) -> Option<NaiveTime> {
+    if token == "noon" {
+        return NaiveTime::from_hms_opt(12, 0, 0);
+    }
+    let (clock, meridiem) = if let Some(clock) = token.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = token.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (token, None)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) if m.len() == 2 => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        // A bare number is only a time with am/pm ("3" alone could be "in 3 days")
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    let hour = match meridiem {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Parses the date part, returning the day and the time to use if none was given.
+fn parse_date(tokens: &[String], today: NaiveDate) -> Option<(NaiveDate, NaiveTime)> {
+    let words: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    let date = match words.as_slice() {
+        ["today"] | ["eod"] => today,
+        ["tonight"] => return Some((today, NaiveTime::from_hms_opt(20, 0, 0)?)),
+        ["tomorrow"] | ["tmr"] | ["tmrw"] => today + Duration::days(1),
+        ["eow"] => return Some((next_weekday(today, Weekday::Fri, false), NaiveTime::from_hms_opt(17, 0, 0)?)),
+        ["next", "week"] => today + Duration::weeks(1),
+        ["next", "month"] => today.checked_add_months(Months::new(1))?,
+        ["next", "year"] => today.checked_add_months(Months::new(12))?,
+        ["next", day] => next_weekday(today, parse_weekday(day)?, true),
+        ["this", day] => next_weekday(today, parse_weekday(day)?, false),
+        ["in", amount, unit] => {
+            let amount: u32 = amount.parse().ok()?;
+            match *unit {
+                "day" | "days" | "d" => today.checked_add_days(Days::new(amount.into()))?,
+                "week" | "weeks" | "w" => today.checked_add_days(Days::new(u64::from(amount) * 7))?,
+                "month" | "months" => today.checked_add_months(Months::new(amount))?,
+                "year" | "years" => today.checked_add_months(Months::new(amount.checked_mul(12)?))?,
+                _ => return None,
+            }
+        }
+        [word] => match parse_weekday(word) {
+            Some(weekday) => next_weekday(today, weekday, false),
+            None => NaiveDate::parse_from_str(word, "%Y-%m-%d").ok()?,
+        },
+        _ => parse_month_day(&words, today)?,
+    };
+    Some((date, end_of_day()))
+}
+
+pub(crate) fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thur" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// The next `weekday` on or after `today`, or strictly after it when `skip_today` is set.
+fn next_weekday(today: NaiveDate, weekday: Weekday, skip_today: bool) -> NaiveDate {
+    let mut days_ahead =
+        (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    if days_ahead == 0 && skip_today {
+        days_ahead = 7;
+    }
+    today + Duration::days(days_ahead.into())
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+    let prefix = word.get(..3)?;
+    MONTHS.iter().position(|m| *m == prefix).map(|index| index as u32 + 1)
+}
+
+/// "3", "3rd", "21st"
+fn parse_day_of_month(word: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .unwrap_or(word);
+    digits.parse().ok()
+}
+
+/// "nov 1", "1 november", "november 1 2027". Without a year, a date already past means next year.
+fn parse_month_day(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    let (month, day, year) = match words {
+        [a, b] | [a, b, _] => match (parse_month(a), parse_month(b)) {
+            (Some(month), None) => (month, parse_day_of_month(b)?, words.get(2)),
+            (None, Some(month)) => (month, parse_day_of_month(a)?, words.get(2)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year.parse().ok()?, month, day),
+        None => {
+            let this_year = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+            if this_year < today {
+                NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+            } else {
+                Some(this_year)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    // Sunday 18 October 2026, 10:00 in UTC-7
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-10-18T10:00:00-07:00").unwrap()
+    }
+
+    fn parse(input: &str) -> String {
+        parse_due_date(input, &now())
+            .unwrap_or_else(|e| panic!("{}", e))
+            .with_timezone(&now().timezone())
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn test_absolute_dates() {
+        assert_eq!(parse("2026-11-01"), "2026-11-01 23:59");
+        assert_eq!(parse("2026-11-01 08:15"), "2026-11-01 08:15");
+        assert_eq!(parse("2026-11-01T17:00:00Z"), "2026-11-01 10:00");
+        assert_eq!(parse("nov 3"), "2026-11-03 23:59");
+        assert_eq!(parse("3rd november 2027"), "2027-11-03 23:59");
+        assert_eq!(parse("jan 5"), "2027-01-05 23:59");
+    }
+
+    #[test]
+    fn test_relative_days() {
+        assert_eq!(parse("today"), "2026-10-18 23:59");
+        assert_eq!(parse("eod"), "2026-10-18 23:59");
+        assert_eq!(parse("tonight"), "2026-10-18 20:00");
+        assert_eq!(parse("Tomorrow 5pm"), "2026-10-19 17:00");
+        assert_eq!(parse("tomorrow at 9:30 am"), "2026-10-19 09:30");
+        assert_eq!(parse("in 3 days"), "2026-10-21 23:59");
+        assert_eq!(parse("in 2 weeks at noon"), "2026-11-01 12:00");
+        assert_eq!(parse("next month"), "2026-11-18 23:59");
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(parse("friday"), "2026-10-23 23:59");
+        assert_eq!(parse("next friday"), "2026-10-23 23:59");
+        assert_eq!(parse("sunday"), "2026-10-18 23:59");
+        assert_eq!(parse("next sunday"), "2026-10-25 23:59");
+        assert_eq!(parse("mon 8am"), "2026-10-19 08:00");
+        assert_eq!(parse("eow"), "2026-10-23 17:00");
+    }
+
+    #[test]
+    fn test_past() {
+        let past = |input: &str| {
+            parse_past(input, &now())
+                .unwrap_or_else(|e| panic!("{}", e))
+                .with_timezone(&now().timezone())
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        };
+        assert_eq!(past("8am"), "2026-10-18 08:00");
+        assert_eq!(past("7pm"), "2026-10-17 19:00");
+        assert_eq!(past("yesterday"), "2026-10-17 10:00");
+        assert_eq!(past("Yesterday at 7:30 pm"), "2026-10-17 19:30");
+        assert_eq!(past("2 hours ago"), "2026-10-18 08:00");
+        assert_eq!(past("3 days ago"), "2026-10-15 10:00");
+        assert_eq!(past("today"), "2026-10-18 23:59");
+        assert_eq!(past("2026-10-01 12:30"), "2026-10-01 12:30");
+        assert!(parse_past("yesterday whenever", &now()).is_err());
+    }
+
+    #[test]
+    fn test_times_and_instants() {
+        assert_eq!(parse("5pm"), "2026-10-18 17:00");
+        assert_eq!(parse("9am"), "2026-10-19 09:00");
+        assert_eq!(parse("in 90 minutes"), "2026-10-18 11:30");
+        assert_eq!(parse("in 2 hours"), "2026-10-18 12:00");
+        assert_eq!(parse("now"), "2026-10-18 10:00");
+    }
+
+    #[test]
+    fn test_timezone_is_respected() {
+        let utc_now = now().with_timezone(&Utc);
+        let tomorrow_utc = parse_due_date("tomorrow 5pm", &utc_now).unwrap();
+        let tomorrow_local = parse_due_date("tomorrow 5pm", &now()).unwrap();
+        assert_eq!(tomorrow_utc.to_rfc3339(), "2026-10-19T17:00:00+00:00");
+        assert_eq!(tomorrow_local.to_rfc3339(), "2026-10-20T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_rejects_nonsense() {
+        for input in ["", "whenever", "13pm", "tomorrow 5pm 6pm", "in 3 fortnights", "feb 30", "at"] {
+            assert!(parse_due_date(input, &now()).is_err(), "accepted '{}'", input);
+        }
+        // Too large for an offset, or land outside the calendar
+        let overflowing = [
+            "in 99999999999 hours",
+            "in 9999999999999999 minutes",
+            "in 4000000000 days",
+            "in 4000000000 weeks",
+        ];
+        for input in overflowing {
+            assert!(parse_due_date(input, &now()).is_err(), "accepted '{}'", input);
+        }
+        for input in ["99999999999 hours ago", "9999999999999 days ago", "99999999999999 weeks ago"] {
+            assert!(parse_past(input, &now()).is_err(), "accepted '{}'", input);
+        }
+        // Offsets count forwards for due dates and backwards with "ago", never the other way
+        for input in ["in -3 hours", "in -90 min", "in -2 days", "in -1 weeks"] {
+            assert!(parse_due_date(input, &now()).is_err(), "accepted '{}'", input);
+        }
+        assert!(parse_past("-3 days ago", &now()).is_err());
+    }
+}
@@ -0,0 +1,338 @@
+// core/src/export.rs
+// Whole-database backups: a versioned JSON archive, or one CSV file per entity.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use crate::database::{Database, HistoryEntry};
+use crate::lists::{self, List};
+use crate::task::Task;
+use crate::task_list::TaskList;
+
+/// Version written to new archives; archives from newer versions are rejected.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+// File names used by `Archive::write_csv` and `Archive::read_csv`
+pub const TASKS_CSV: &str = "tasks.csv";
+pub const TAGS_CSV: &str = "tags.csv";
+pub const HISTORY_CSV: &str = "history.csv";
+pub const LISTS_CSV: &str = "lists.csv";
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    UnsupportedVersion(u32),
+    Json(String),
+    Csv(String),
+    Io(String),
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveError::UnsupportedVersion(version) => write!(
+                f,
+                "archive version {} is newer than this version of FreeList supports ({})",
+                version, ARCHIVE_VERSION
+            ),
+            ArchiveError::Json(msg) => write!(f, "invalid archive: {}", msg),
+            ArchiveError::Csv(msg) => write!(f, "invalid CSV: {}", msg),
+            ArchiveError::Io(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+impl From<csv::Error> for ArchiveError {
+    fn from(err: csv::Error) -> Self {
+        ArchiveError::Csv(err.to_string())
+    }
+}
+
+/// Everything in a database. Task ids are those of the exporting database;
+/// `parent_id` and `history[].task_id` refer to them, and `list_id` to `lists[].id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archive {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub tasks: Vec<Task>,
+    /// Informational; tags travel with their tasks
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub history: Vec<HistoryEntry>,
+    #[serde(default)]
+    pub lists: Vec<List>,
+}
+
+/// What `TaskList::import_archive` did.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub tasks_added: u32,
+    /// Tasks already present, matched by title and creation time
+    pub tasks_skipped: u32,
+    pub history_added: u32,
+}
+
+impl Archive {
+    pub fn to_json(&self) -> Result<String, ArchiveError> {
+        serde_json::to_string_pretty(self).map_err(|e| ArchiveError::Json(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Archive, ArchiveError> {
+        Archive::from_value(serde_json::from_str(json).map_err(|e| ArchiveError::Json(e.to_string()))?)
+    }
+
+    pub fn from_value(raw: serde_json::Value) -> Result<Archive, ArchiveError> {
+        // Check the version first so a newer archive fails clearly rather than on an unknown field
+        let version = raw
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| ArchiveError::Json("missing `version`".to_string()))?;
+        if version > u64::from(ARCHIVE_VERSION) {
+            return Err(ArchiveError::UnsupportedVersion(u32::try_from(version).unwrap_or(u32::MAX)));
+        }
+        serde_json::from_value(raw).map_err(|e| ArchiveError::Json(e.to_string()))
+    }
+
+    /// Writes `tasks.csv`, `tags.csv`, `history.csv` and `lists.csv` into `dir`, creating it if needed.
+    pub fn write_csv(&self, dir: &Path) -> Result<(), ArchiveError> {
+        std::fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+        write_csv_file(&dir.join(TASKS_CSV), &self.tasks)?;
+        let tags: Vec<TagRow> = self.tags.iter().map(|tag| TagRow { tag: tag.clone() }).collect();
+        write_csv_file(&dir.join(TAGS_CSV), &tags)?;
+        write_csv_file(&dir.join(HISTORY_CSV), &self.history)?;
+        write_csv_file(&dir.join(LISTS_CSV), &self.lists)
+    }
+
+    /// Reads a directory written by `write_csv`. Only `tasks.csv` is required.
+    pub fn read_csv(dir: &Path) -> Result<Archive, ArchiveError> {
+        let tags: Vec<TagRow> = read_csv_file(&dir.join(TAGS_CSV), false)?;
+        Ok(Archive {
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            tasks: read_csv_file(&dir.join(TASKS_CSV), true)?,
+            tags: tags.into_iter().map(|row| row.tag).collect(),
+            history: read_csv_file(&dir.join(HISTORY_CSV), false)?,
+            lists: read_csv_file(&dir.join(LISTS_CSV), false)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TagRow {
+    tag: String,
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> ArchiveError {
+    ArchiveError::Io(format!("{}: {}", path.display(), err))
+}
+
+fn write_csv_file<T: Serialize>(path: &Path, rows: &[T]) -> Result<(), ArchiveError> {
+    let file = File::create(path).map_err(|e| io_error(path, e))?;
+    let mut writer = csv::Writer::from_writer(file);
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush().map_err(|e| io_error(path, e))
+}
+
+fn read_csv_file<T: for<'de> Deserialize<'de>>(path: &Path, required: bool) -> Result<Vec<T>, ArchiveError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(path, e)),
+    };
+    Ok(csv::Reader::from_reader(file).deserialize().collect::<Result<_, _>>()?)
+}
+
+impl TaskList {
+    pub fn export_archive(&self) -> Result<Archive, Box<dyn std::error::Error>> {
+        let mut tasks = self.all()?;
+        tasks.sort_by_key(|task| task.id);
+        Ok(Archive {
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            tasks,
+            tags: self.get_all_tags()?,
+            history: self.history(None)?,
+            lists: self.lists(true)?,
+        })
+    }
+
+    /// Adds the archive's tasks and history in one transaction, giving tasks new ids
+    /// and remapping `parent_id` to match. Tasks that are already present are
+    /// skipped, so importing the same archive twice changes nothing. Lists are
+    /// matched by name and added when missing.
+    pub fn import_archive(&mut self, archive: &Archive) -> Result<ImportSummary, Box<dyn std::error::Error>> {
+        let summary = self.database().with_transaction(|db| import_into(db, archive))?;
+        self.poll_changes()?;
+        Ok(summary)
+    }
+
+    /// What `import_archive` would do, without changing anything.
+    pub fn preview_import(&self, archive: &Archive) -> Result<ImportSummary, Box<dyn std::error::Error>> {
+        Ok(self.database().with_rollback(|db| import_into(db, archive))?)
+    }
+}
+
+fn import_into(db: &Database, archive: &Archive) -> rusqlite::Result<ImportSummary> {
+    let mut summary = ImportSummary::default();
+    let mut ids: HashMap<i64, i64> = HashMap::new();
+    let mut added: Vec<(i64, i64)> = Vec::new();
+
+    let mut list_ids: HashMap<i64, i64> = HashMap::new();
+    for list in &archive.lists {
+        let id = match lists::find_list_id(db, &list.name)? {
+            Some(id) => id,
+            None => lists::insert_list(db, list)?,
+        };
+        if let Some(old_id) = list.id {
+            list_ids.insert(old_id, id);
+        }
+    }
+
+    for task in &archive.tasks {
+        let id = match db.find_task(&task.title, &task.created_at)? {
+            Some(existing) => {
+                summary.tasks_skipped += 1;
+                existing
+            }
+            None => {
+                // Parents may come later in the archive, so they are linked afterwards
+                let list_id = task.list_id.and_then(|old_id| list_ids.get(&old_id).copied());
+                let id = db.insert_task(&Task { parent_id: None, list_id, ..task.clone() })?;
+                if let Some(parent_id) = task.parent_id {
+                    added.push((id, parent_id));
+                }
+                summary.tasks_added += 1;
+                id
+            }
+        };
+        if let Some(old_id) = task.id {
+            ids.insert(old_id, id);
+        }
+    }
+
+    for (id, old_parent) in added {
+        db.set_parent(id, ids.get(&old_parent).copied())?;
+    }
+
+    for entry in &archive.history {
+        let Some(&task_id) = ids.get(&entry.task_id) else { continue };
+        if db.insert_history(&HistoryEntry { task_id, ..entry.clone() })? {
+            summary.history_added += 1;
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Priority, TaskEvent};
+
+    fn sample_list() -> TaskList {
+        let mut task_list = TaskList::new_in_memory().unwrap();
+        let trip = task_list.add(Task::new("Plan trip").with_tag("travel").with_priority(Priority::High)).unwrap();
+        task_list.add(Task::new("Book flights").with_parent(trip).with_details("aisle, \"please\"")).unwrap();
+        let errands = task_list.add_list(&List::new("Errands").with_colour("#e67e22")).unwrap();
+        let milk = task_list.add(Task::new("Buy milk").with_tag("groceries").with_list(errands)).unwrap();
+        task_list.mark_done(milk).unwrap();
+        task_list
+    }
+
+    fn titles_and_parents(task_list: &TaskList) -> Vec<(String, Option<String>)> {
+        let tasks = task_list.all().unwrap();
+        let title = |id: i64| tasks.iter().find(|t| t.id == Some(id)).map(|t| t.title.clone());
+        let mut pairs: Vec<_> = tasks.iter().map(|t| (t.title.clone(), t.parent_id.and_then(title))).collect();
+        pairs.sort();
+        pairs
+    }
+
+    #[test]
+    fn test_history_is_recorded() {
+        let task_list = sample_list();
+        let events: Vec<TaskEvent> = task_list.history(Some(3)).unwrap().iter().map(|e| e.event).collect();
+        assert_eq!(events, vec![TaskEvent::Created, TaskEvent::Completed]);
+        assert_eq!(task_list.history(None).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_json_round_trip_remaps_ids() {
+        let source = sample_list();
+        let json = source.export_archive().unwrap().to_json().unwrap();
+
+        let mut target = TaskList::new_in_memory().unwrap();
+        // Occupy the low ids so imported tasks must be renumbered
+        target.add(Task::new("Existing")).unwrap();
+        target.add(Task::new("Also existing")).unwrap();
+        target.add_list(&List::new("Home")).unwrap();
+
+        let archive = Archive::from_json(&json).unwrap();
+        let summary = target.import_archive(&archive).unwrap();
+        assert_eq!(summary, ImportSummary { tasks_added: 3, tasks_skipped: 0, history_added: 1 });
+
+        let mut expected = titles_and_parents(&source);
+        expected.push(("Also existing".to_string(), None));
+        expected.push(("Existing".to_string(), None));
+        expected.sort();
+        assert_eq!(titles_and_parents(&target), expected);
+
+        let milk = target.all().unwrap().into_iter().find(|t| t.title == "Buy milk").unwrap();
+        assert!(milk.done);
+        let errands = target.find_list("errands").unwrap().unwrap();
+        assert_eq!((milk.list_id, errands.colour.as_deref()), (errands.id, Some("#e67e22")));
+        let original = source.get_by_id(3).unwrap().unwrap();
+        assert_eq!(milk.created_at, original.created_at);
+        let events: Vec<TaskEvent> = target.history(milk.id).unwrap().iter().map(|e| e.event).collect();
+        assert_eq!(events, vec![TaskEvent::Created, TaskEvent::Completed]);
+    }
+
+    #[test]
+    fn test_import_is_idempotent() {
+        let source = sample_list();
+        let archive = source.export_archive().unwrap();
+        let mut target = TaskList::new_in_memory().unwrap();
+
+        let preview = target.preview_import(&archive).unwrap();
+        assert_eq!(preview, ImportSummary { tasks_added: 3, tasks_skipped: 0, history_added: 1 });
+        assert!(target.all().unwrap().is_empty());
+        assert!(target.history(None).unwrap().is_empty());
+
+        target.import_archive(&archive).unwrap();
+        let again = target.import_archive(&archive).unwrap();
+        assert_eq!(again, ImportSummary { tasks_added: 0, tasks_skipped: 3, history_added: 0 });
+        assert_eq!(target.all().unwrap().len(), 3);
+        assert_eq!(target.lists(true).unwrap().len(), 1);
+        assert_eq!(target.history(None).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let source = sample_list();
+        let dir = std::env::temp_dir().join(format!("freelist-export-{}", std::process::id()));
+        source.export_archive().unwrap().write_csv(&dir).unwrap();
+
+        let archive = Archive::read_csv(&dir).unwrap();
+        assert_eq!(archive.tags, vec!["groceries".to_string(), "travel".to_string()]);
+        assert_eq!(archive.history.len(), 4);
+        assert_eq!(archive.lists[0].name, "Errands");
+        assert_eq!(archive.tasks[1].details.as_deref(), Some("aisle, \"please\""));
+
+        let mut target = TaskList::new_in_memory().unwrap();
+        target.import_archive(&archive).unwrap();
+        assert_eq!(titles_and_parents(&target), titles_and_parents(&source));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_newer_archives_are_rejected() {
+        let json = r#"{"version": 99, "exported_at": "2026-10-18T00:00:00Z", "tasks": []}"#;
+        assert!(matches!(Archive::from_json(json), Err(ArchiveError::UnsupportedVersion(99))));
+        assert!(matches!(Archive::from_json("{}"), Err(ArchiveError::Json(_))));
+        let minimal = r#"{"version": 1, "exported_at": "2026-10-18T00:00:00Z", "tasks": []}"#;
+        assert!(Archive::from_json(minimal).unwrap().history.is_empty());
+    }
+}
@@ -1,210 +1,2349 @@
 // core/src/ffi.rs
+use std::any::Any;
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
-use std::os::raw::c_char;
-use std::sync::Mutex;
-use crate::{TaskList, Task};
-use serde_json;
+use std::os::raw::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use crate::{Archive, ArchiveError, ChangeSet, Priority, QuickAddError, TaskList, Task};
+use crate::nutrition::{
+    normalise_barcode, Amount, Food, Meal, MealEntry, Nutrients, NutritionError, PantryItem, Quantity, Recipe,
+};
+use crate::fitness::{
+    Exercise, ExerciseKind, FitnessError, PlanSession, SetMeasures, TrainingPlan, Workout, WorkoutSet,
+    MAX_SCHEDULE_WEEKS,
+};
+use crate::journal::{JournalEntry, JournalError};
+use crate::lists::{List, ListError};
+use crate::ordering::OrderingError;
+use crate::reports::{GroupBy, ReportError};
+use crate::dates::{self, DateParseError};
+use serde::{Deserialize, Serialize};
+use serde_json::{self, Value};
+use chrono::{Days, Duration, Local, NaiveDate, Utc};
 
 // Global task list instance with thread safety
 static TASK_LIST: Mutex<Option<TaskList>> = Mutex::new(None);
 
-#[no_mangle]
-pub extern "C" fn init_freelist(db_path: *const c_char) -> i32 {
-    let path_str = if db_path.is_null() {
-        return -1;
+/// Called with a JSON-encoded `ChangeSet`; the string is only valid for the duration of the call.
+pub type FreelistChangeCallback = extern "C" fn(ctx: *mut c_void, change_set_json: *const c_char);
+
+#[derive(Clone, Copy)]
+struct Subscriber {
+    id: i64,
+    callback: FreelistChangeCallback,
+    ctx: *mut c_void,
+}
+
+// SAFETY: callers of `freelist_subscribe` guarantee that `ctx` may be used from any thread
+unsafe impl Send for Subscriber {}
+
+// Subscribers outlive re-initialisation, so they are kept apart from the task list
+static SUBSCRIBERS: Mutex<Vec<Subscriber>> = Mutex::new(Vec::new());
+static NEXT_SUBSCRIBER_ID: AtomicI64 = AtomicI64::new(1);
+// Change sets waiting to be delivered once the task list lock is released
+static PENDING_CHANGES: Mutex<Vec<ChangeSet>> = Mutex::new(Vec::new());
+
+// Error codes reported by `freelist_last_error_code`
+pub const FREELIST_OK: i32 = 0;
+pub const FREELIST_ERR_NOT_INITIALISED: i32 = 1;
+pub const FREELIST_ERR_INVALID_UTF8: i32 = 2;
+pub const FREELIST_ERR_NOT_FOUND: i32 = 3;
+pub const FREELIST_ERR_STORAGE: i32 = 4;
+pub const FREELIST_ERR_NULL_ARGUMENT: i32 = 5;
+pub const FREELIST_ERR_SERIALIZATION: i32 = 6;
+pub const FREELIST_ERR_PANIC: i32 = 7;
+pub const FREELIST_ERR_INVALID_REQUEST: i32 = 8;
+pub const FREELIST_ERR_UNSUPPORTED_VERSION: i32 = 9;
+pub const FREELIST_ERR_INVALID_DATE: i32 = 10;
+pub const FREELIST_ERR_INVALID_INPUT: i32 = 11;
+
+// Schema version of `freelist_call` requests and responses
+pub const FREELIST_API_VERSION: u32 = 1;
+
+// Foods returned by `search_foods` when no limit is given
+const SEARCH_LIMIT: usize = 20;
+// Weeks of volume reported, or of a training plan scheduled, when a caller does not say
+const DEFAULT_WEEKS: u32 = 4;
+// Days of journal history compared when a caller does not say
+const DEFAULT_JOURNAL_DAYS: u32 = 30;
+// Days of statistics when a caller gives no first day
+const DEFAULT_STATS_DAYS: u32 = 28;
+// Most days of history a caller may ask for, about ten years
+const MAX_DAYS: u32 = 3660;
+// Most weeks of history a caller may ask for, about ten years
+const MAX_WEEKS: u32 = 520;
+
+#[derive(Debug, Clone)]
+pub enum FfiError {
+    NotInitialised,
+    InvalidUtf8(&'static str),
+    NotFound(i64),
+    /// Something other than a task, e.g. ("food", 3)
+    RecordNotFound(&'static str, i64),
+    Storage(String),
+    NullArgument(&'static str),
+    Serialization(String),
+    Panic(String),
+    InvalidRequest(String),
+    UnsupportedVersion(u32),
+    UnknownSubscription(i64),
+    InvalidDate(String),
+    InvalidInput(String),
+}
+
+impl std::fmt::Display for FfiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FfiError::NotInitialised => write!(
+                f,
+                "FreeList is not initialised; call init_freelist or init_freelist_memory first"
+            ),
+            FfiError::InvalidUtf8(name) => write!(f, "argument `{}` is not valid UTF-8", name),
+            FfiError::NotFound(id) => write!(f, "task {} not found", id),
+            FfiError::RecordNotFound(kind, id) => write!(f, "{} {} not found", kind, id),
+            FfiError::Storage(msg) => write!(f, "storage error: {}", msg),
+            FfiError::NullArgument(name) => write!(f, "argument `{}` must not be null", name),
+            FfiError::Serialization(msg) => write!(f, "serialization error: {}", msg),
+            FfiError::Panic(msg) => write!(f, "internal panic: {}", msg),
+            FfiError::InvalidRequest(msg) => write!(f, "invalid request: {}", msg),
+            FfiError::UnknownSubscription(id) => write!(f, "subscription {} not found", id),
+            FfiError::InvalidDate(msg) | FfiError::InvalidInput(msg) => write!(f, "{}", msg),
+            FfiError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported request version {} (expected {})",
+                version, FREELIST_API_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FfiError {}
+
+impl FfiError {
+    pub fn code(&self) -> i32 {
+        match self {
+            FfiError::NotInitialised => FREELIST_ERR_NOT_INITIALISED,
+            FfiError::InvalidUtf8(_) => FREELIST_ERR_INVALID_UTF8,
+            FfiError::NotFound(_) | FfiError::RecordNotFound(..) | FfiError::UnknownSubscription(_) => {
+                FREELIST_ERR_NOT_FOUND
+            }
+            FfiError::Storage(_) => FREELIST_ERR_STORAGE,
+            FfiError::NullArgument(_) => FREELIST_ERR_NULL_ARGUMENT,
+            FfiError::Serialization(_) => FREELIST_ERR_SERIALIZATION,
+            FfiError::Panic(_) => FREELIST_ERR_PANIC,
+            FfiError::InvalidRequest(_) => FREELIST_ERR_INVALID_REQUEST,
+            FfiError::UnsupportedVersion(_) => FREELIST_ERR_UNSUPPORTED_VERSION,
+            FfiError::InvalidDate(_) => FREELIST_ERR_INVALID_DATE,
+            FfiError::InvalidInput(_) => FREELIST_ERR_INVALID_INPUT,
+        }
+    }
+}
+
+thread_local! {
+    // Error from the most recent FFI call made on this thread
+    static LAST_ERROR: RefCell<Option<FfiError>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(err: FfiError) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(err));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+#[cfg(test)]
+thread_local! {
+    // Makes the next FFI call on this thread panic, so tests can exercise unwinding
+    static FORCE_PANIC: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+fn panic_point() {
+    #[cfg(test)]
+    if FORCE_PANIC.with(|force| force.replace(false)) {
+        panic!("forced panic");
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
     } else {
-        match unsafe { CStr::from_ptr(db_path) }.to_str() {
-            Ok(s) => s,
-            Err(_) => return -1,
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs `f`, turning a panic into `FfiError::Panic` so it never unwinds into the caller.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T, FfiError>) -> Result<T, FfiError> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        panic_point();
+        f()
+    }))
+    .unwrap_or_else(|payload| Err(FfiError::Panic(panic_message(payload))))
+}
+
+/// Runs `f` for exports that do not report through the last error, returning `fallback` on panic.
+fn catch_panic_or<T>(fallback: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        panic_point();
+        f()
+    }))
+    .unwrap_or(fallback)
+}
+
+/// Records the outcome of a call returning a status code: 0 on success, -1 on error.
+fn report_status(f: impl FnOnce() -> Result<(), FfiError>) -> i32 {
+    match catch_panic(f) {
+        Ok(()) => {
+            clear_last_error();
+            0
+        }
+        Err(err) => {
+            set_last_error(err);
+            -1
+        }
+    }
+}
+
+/// Records the outcome of a call returning a task id, or -1 on error.
+fn report_id(f: impl FnOnce() -> Result<i64, FfiError>) -> i64 {
+    match catch_panic(f) {
+        Ok(id) => {
+            clear_last_error();
+            id
+        }
+        Err(err) => {
+            set_last_error(err);
+            -1
+        }
+    }
+}
+
+/// Records the outcome of a call returning an owned string, or null on error.
+fn report_string(f: impl FnOnce() -> Result<*mut c_char, FfiError>) -> *mut c_char {
+    match catch_panic(f) {
+        Ok(ptr) => {
+            clear_last_error();
+            ptr
+        }
+        Err(err) => {
+            set_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Reads a required string argument.
+///
+/// # Safety
+/// `ptr` must be null or point to a valid NUL-terminated string.
+unsafe fn required_str<'a>(ptr: *const c_char, name: &'static str) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::NullArgument(name));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|_| FfiError::InvalidUtf8(name))
+}
+
+/// Reads an optional string argument, treating null and "" as absent.
+///
+/// # Safety
+/// `ptr` must be null or point to a valid NUL-terminated string.
+unsafe fn optional_str<'a>(ptr: *const c_char, name: &'static str) -> Result<Option<&'a str>, FfiError> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    let s = CStr::from_ptr(ptr).to_str().map_err(|_| FfiError::InvalidUtf8(name))?;
+    Ok(if s.is_empty() { None } else { Some(s) })
+}
+
+impl From<DateParseError> for FfiError {
+    fn from(err: DateParseError) -> Self {
+        FfiError::InvalidDate(err.to_string())
+    }
+}
+
+impl From<QuickAddError> for FfiError {
+    fn from(err: QuickAddError) -> Self {
+        match err {
+            QuickAddError::InvalidDate(err) => err.into(),
+            QuickAddError::MissingTitle => FfiError::InvalidInput(err.to_string()),
+        }
+    }
+}
+
+impl From<ArchiveError> for FfiError {
+    fn from(err: ArchiveError) -> Self {
+        match err {
+            ArchiveError::Io(msg) => FfiError::Storage(msg),
+            _ => FfiError::InvalidInput(err.to_string()),
+        }
+    }
+}
+
+impl From<NutritionError> for FfiError {
+    fn from(err: NutritionError) -> Self {
+        FfiError::InvalidInput(err.to_string())
+    }
+}
+
+impl From<FitnessError> for FfiError {
+    fn from(err: FitnessError) -> Self {
+        FfiError::InvalidInput(err.to_string())
+    }
+}
+
+impl From<JournalError> for FfiError {
+    fn from(err: JournalError) -> Self {
+        FfiError::InvalidInput(err.to_string())
+    }
+}
+
+impl From<ListError> for FfiError {
+    fn from(err: ListError) -> Self {
+        FfiError::InvalidInput(err.to_string())
+    }
+}
+
+impl From<OrderingError> for FfiError {
+    fn from(err: OrderingError) -> Self {
+        match err {
+            OrderingError::TaskNotFound(id) => FfiError::NotFound(id),
+            _ => FfiError::InvalidInput(err.to_string()),
+        }
+    }
+}
+
+impl From<ReportError> for FfiError {
+    fn from(err: ReportError) -> Self {
+        FfiError::InvalidInput(err.to_string())
+    }
+}
+
+fn storage_error(err: Box<dyn std::error::Error>) -> FfiError {
+    FfiError::Storage(err.to_string())
+}
+
+// A clashing list name is the caller's mistake, not a storage failure
+fn list_error(err: Box<dyn std::error::Error>) -> FfiError {
+    match err.downcast::<ListError>() {
+        Ok(err) => (*err).into(),
+        Err(err) => storage_error(err),
+    }
+}
+
+fn ordering_error(err: Box<dyn std::error::Error>) -> FfiError {
+    match err.downcast::<OrderingError>() {
+        Ok(err) => (*err).into(),
+        Err(err) => storage_error(err),
+    }
+}
+
+fn lock_task_list() -> MutexGuard<'static, Option<TaskList>> {
+    // A panic while the lock was held poisons it. The task list is still usable:
+    // every mutation is a single SQLite statement or runs in a transaction, so
+    // there is no half-applied state.
+    lock_or_recover(&TASK_LIST)
+}
+
+fn lock_or_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn with_task_list<T>(f: impl FnOnce(&mut TaskList) -> Result<T, FfiError>) -> Result<T, FfiError> {
+    let result = {
+        let mut guard = lock_task_list();
+        match guard.as_mut() {
+            Some(task_list) => f(task_list),
+            None => Err(FfiError::NotInitialised),
         }
     };
+    // Callbacks run without the lock held so they can call back into the library
+    deliver_pending_changes();
+    result
+}
 
-    match TaskList::new(path_str) {
-        Ok(task_list) => {
-            let mut guard = TASK_LIST.lock().unwrap();
-            *guard = Some(task_list);
-            0 // Success
+fn deliver_pending_changes() {
+    let change_sets = std::mem::take(&mut *lock_or_recover(&PENDING_CHANGES));
+    if change_sets.is_empty() {
+        return;
+    }
+    let subscribers = lock_or_recover(&SUBSCRIBERS).clone();
+    for change_set in change_sets {
+        let Ok(json) = serde_json::to_string(&change_set) else { continue };
+        let Ok(json) = CString::new(json) else { continue };
+        for subscriber in &subscribers {
+            (subscriber.callback)(subscriber.ctx, json.as_ptr());
         }
-        Err(_) => -1, // Error
     }
 }
 
+fn ensure_task_exists(task_list: &TaskList, id: i64) -> Result<(), FfiError> {
+    match task_list.get_by_id(id).map_err(storage_error)? {
+        Some(_) => Ok(()),
+        None => Err(FfiError::NotFound(id)),
+    }
+}
+
+/// The local calendar day meant by `date` ("2026-10-18", "yesterday"), or today.
+fn local_day(date: Option<&str>) -> Result<NaiveDate, FfiError> {
+    Ok(match date {
+        Some(date) => dates::parse_past_local(date)?.with_timezone(&Local).date_naive(),
+        None => Local::now().date_naive(),
+    })
+}
+
+// The `weeks` local weeks ending with this one, as the first and last day to report
+fn recent_weeks(weeks: u32) -> Result<(NaiveDate, NaiveDate), FfiError> {
+    let out_of_range = || FfiError::InvalidInput(format!("weeks must be from 1 to {}", MAX_WEEKS));
+    if weeks == 0 || weeks > MAX_WEEKS {
+        return Err(out_of_range());
+    }
+    let today = Local::now().date_naive();
+    Ok((today.checked_sub_days(Days::new(7 * (u64::from(weeks) - 1))).ok_or_else(out_of_range)?, today))
+}
+
+// The last `days` local days, ending today
+fn recent_days(days: u32) -> Result<(NaiveDate, NaiveDate), FfiError> {
+    let out_of_range = || FfiError::InvalidInput(format!("days must be from 1 to {}", MAX_DAYS));
+    if days == 0 || days > MAX_DAYS {
+        return Err(out_of_range());
+    }
+    let today = Local::now().date_naive();
+    Ok((today.checked_sub_days(Days::new(u64::from(days) - 1)).ok_or_else(out_of_range)?, today))
+}
+
+fn to_json_c_string<T: Serialize>(value: &T) -> Result<*mut c_char, FfiError> {
+    let json = serde_json::to_string(value).map_err(|e| FfiError::Serialization(e.to_string()))?;
+    let c_string = CString::new(json).map_err(|e| FfiError::Serialization(e.to_string()))?;
+    Ok(c_string.into_raw())
+}
+
+fn install_task_list(task_list: Result<TaskList, Box<dyn std::error::Error>>) -> Result<(), FfiError> {
+    let mut task_list = task_list.map_err(storage_error)?;
+    task_list.subscribe(|change_set: &ChangeSet| {
+        lock_or_recover(&PENDING_CHANGES).push(change_set.clone());
+    });
+    let mut guard = lock_task_list();
+    *guard = Some(task_list);
+    Ok(())
+}
+
+/// Opens (or creates) the database at `db_path`. Returns 0 on success, -1 on error.
+///
+/// # Safety
+/// `db_path` must be null or point to a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn init_freelist(db_path: *const c_char) -> i32 {
+    report_status(|| {
+        let path = required_str(db_path, "db_path")?;
+        install_task_list(TaskList::new(path))
+    })
+}
+
+/// Opens a fresh in-memory database. Returns 0 on success, -1 on error.
 #[no_mangle]
 pub extern "C" fn init_freelist_memory() -> i32 {
-    match TaskList::new_in_memory() {
-        Ok(task_list) => {
-            let mut guard = TASK_LIST.lock().unwrap();
-            *guard = Some(task_list);
-            0 // Success
+    report_status(|| install_task_list(TaskList::new_in_memory()))
+}
+
+/// Adds a task and returns its id, or -1 on error. `tag` and `due_date` are optional;
+/// `due_date` accepts anything `dates::parse_due_date` understands.
+///
+/// # Safety
+/// Each argument must be null or point to a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn add_task(title: *const c_char, tag: *const c_char, due_date: *const c_char) -> i64 {
+    report_id(|| {
+        let title_str = required_str(title, "title")?;
+        let mut task = Task::new(title_str);
+
+        // Add tag if provided
+        if let Some(tag_str) = optional_str(tag, "tag")? {
+            task = task.with_tag(tag_str);
+        }
+
+        // Add due date if provided: RFC 3339 or natural language ("tomorrow 5pm")
+        if let Some(due_str) = optional_str(due_date, "due_date")? {
+            task = task.with_due_date(dates::parse_due_date_local(due_str)?);
         }
-        Err(_) => -1, // Error
+
+        with_task_list(|task_list| task_list.add(task).map_err(storage_error))
+    })
+}
+
+/// Adds a task from quick-add text such as "Buy milk #groceries !high due:tomorrow ~15m"
+/// (see `Task::parse_quick_add`) and returns its id, or -1 on error.
+///
+/// # Safety
+/// `input` must be null or point to a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn add_task_quick(input: *const c_char) -> i64 {
+    report_id(|| {
+        let task = Task::parse_quick_add(required_str(input, "input")?)?;
+        with_task_list(|task_list| add_quick(task_list, task))
+    })
+}
+
+fn add_quick(task_list: &mut TaskList, task: Task) -> Result<i64, FfiError> {
+    if let Some(parent_id) = task.parent_id {
+        ensure_task_exists(task_list, parent_id)?;
     }
+    task_list.add(task).map_err(storage_error)
 }
 
+/// Returns tasks as a JSON array. `filter` is "all" (default), "todo" or "done".
+/// The returned string must be released with `free_string`.
+///
+/// # Safety
+/// `filter` must be null or point to a valid NUL-terminated string.
 #[no_mangle]
-pub extern "C" fn add_task(title: *const c_char, tag: *const c_char, due_date: *const c_char) -> i64 {
-    let title_str = match unsafe { CStr::from_ptr(title) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return -1,
-    };
+pub unsafe extern "C" fn get_tasks_json(filter: *const c_char) -> *mut c_char {
+    report_string(|| {
+        let filter_str = optional_str(filter, "filter")?;
+        with_task_list(|task_list| {
+            let tasks = match filter_str.unwrap_or("all") {
+                "todo" => task_list.get_todo(),
+                "done" => task_list.get_completed(),
+                _ => task_list.all(),
+            }
+            .map_err(storage_error)?;
+            to_json_c_string(&tasks)
+        })
+    })
+}
 
-    let mut task = Task::new(title_str);
-    
-    // Add tag if provided
-    if !tag.is_null() {
-        if let Ok(tag_str) = unsafe { CStr::from_ptr(tag) }.to_str() {
-            if !tag_str.is_empty() {
-                task = task.with_tag(tag_str);
+/// Returns the tasks carrying `tag` as a JSON array.
+/// The returned string must be released with `free_string`.
+///
+/// # Safety
+/// `tag` must be null or point to a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn get_tasks_by_tag_json(tag: *const c_char) -> *mut c_char {
+    report_string(|| {
+        let tag_str = required_str(tag, "tag")?;
+        with_task_list(|task_list| {
+            let tasks = task_list.get_by_tag(tag_str).map_err(storage_error)?;
+            to_json_c_string(&tasks)
+        })
+    })
+}
+
+/// Marks a task done (`done == 1`) or not done. Returns 0 on success, -1 on error.
+#[no_mangle]
+pub extern "C" fn mark_task_done(id: i64, done: i32) -> i32 {
+    report_status(|| {
+        with_task_list(|task_list| {
+            ensure_task_exists(task_list, id)?;
+            if done == 1 {
+                task_list.mark_done(id)
+            } else {
+                task_list.mark_undone(id)
             }
+            .map_err(storage_error)
+        })
+    })
+}
+
+/// Deletes a task and its subtasks. Returns 0 on success, -1 on error.
+#[no_mangle]
+pub extern "C" fn delete_task(id: i64) -> i32 {
+    report_status(|| {
+        with_task_list(|task_list| {
+            ensure_task_exists(task_list, id)?;
+            task_list.delete(id).map_err(storage_error)
+        })
+    })
+}
+
+/// Returns all distinct tags as a JSON array.
+/// The returned string must be released with `free_string`.
+#[no_mangle]
+pub extern "C" fn get_all_tags_json() -> *mut c_char {
+    report_string(|| {
+        with_task_list(|task_list| {
+            let tags = task_list.get_all_tags().map_err(storage_error)?;
+            to_json_c_string(&tags)
+        })
+    })
+}
+
+/// Deletes every task. Returns 0 on success, -1 on error.
+#[no_mangle]
+pub extern "C" fn clear_all_tasks() -> i32 {
+    report_status(|| with_task_list(|task_list| task_list.clear_all().map_err(storage_error)))
+}
+
+/// Registers `callback` to receive a JSON `ChangeSet` after every committed change.
+/// Callbacks run on the thread that made the change, after the library lock is released.
+/// Returns a subscription id for `freelist_unsubscribe`, or -1 on error.
+///
+/// # Safety
+/// `ctx` is passed back to `callback` untouched and must be safe to use from any thread
+/// until the subscription is removed.
+#[no_mangle]
+pub unsafe extern "C" fn freelist_subscribe(callback: Option<FreelistChangeCallback>, ctx: *mut c_void) -> i64 {
+    report_id(|| {
+        let callback = callback.ok_or(FfiError::NullArgument("callback"))?;
+        let id = NEXT_SUBSCRIBER_ID.fetch_add(1, Ordering::Relaxed);
+        lock_or_recover(&SUBSCRIBERS).push(Subscriber { id, callback, ctx });
+        Ok(id)
+    })
+}
+
+/// Removes a subscription. Returns 0 on success, -1 on error.
+#[no_mangle]
+pub extern "C" fn freelist_unsubscribe(subscription_id: i64) -> i32 {
+    report_status(|| {
+        let mut subscribers = lock_or_recover(&SUBSCRIBERS);
+        let before = subscribers.len();
+        subscribers.retain(|subscriber| subscriber.id != subscription_id);
+        if subscribers.len() == before {
+            return Err(FfiError::UnknownSubscription(subscription_id));
         }
-    }
+        Ok(())
+    })
+}
 
-    // Add due date if provided
-    if !due_date.is_null() {
-        if let Ok(due_str) = unsafe { CStr::from_ptr(due_date) }.to_str() {
-            if !due_str.is_empty() {
-                // Try to parse the date string (assuming ISO format)
-                if let Ok(parsed_date) = chrono::DateTime::parse_from_rfc3339(due_str) {
-                    task = task.with_due_date(parsed_date.with_timezone(&chrono::Utc));
-                }
-            }
+/// Checks for writes made by other connections (CLI, sync) and notifies subscribers.
+/// Returns 0 on success, -1 on error.
+#[no_mangle]
+pub extern "C" fn freelist_poll_changes() -> i32 {
+    report_status(|| with_task_list(|task_list| task_list.poll_changes().map_err(storage_error)))
+}
+
+/// A `freelist_call` request: `{"version": 1, "method": "...", "params": {...}}`.
+/// `params` may be omitted for methods that take none.
+#[derive(Debug, Deserialize)]
+pub struct CallRequest {
+    pub version: u32,
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+/// Operations available through `freelist_call`, named after their `TaskList` methods.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Command {
+    Init { path: String },
+    InitMemory,
+    Add(NewTask),
+    AddQuick { input: String },
+    All,
+    GetTodo,
+    GetCompleted,
+    GetOverdue,
+    GetByTag { tag: String },
+    GetByList { list_id: i64 },
+    GetSubtasks { parent_id: i64 },
+    GetById { id: i64 },
+    MarkDone { id: i64 },
+    MarkUndone { id: i64 },
+    Delete { id: i64 },
+    /// Puts the task right after `before` and right before `after`; give one or both
+    MoveTask { id: i64, before: Option<i64>, after: Option<i64> },
+    /// Gives every task a fresh position key; returns how many changed
+    RebalancePositions,
+    ClearAll,
+    GetAllTags,
+    PollChanges,
+    ParseDueDate { input: String },
+    ParseQuickAdd { input: String },
+    ExportArchive,
+    ImportArchive { archive: Value },
+    AddFood(NewFood),
+    GetFood { id: i64 },
+    Foods,
+    LogMeal(NewMealEntry),
+    DeleteMealEntry { id: i64 },
+    /// `date` is a local day as accepted by `dates::parse_past`; today when omitted
+    DayNutrition { date: Option<String> },
+    /// Clears the target when `amount` is null
+    SetNutritionTarget { nutrient: String, amount: Option<f64> },
+    NutritionTargets,
+    /// Up to `limit` foods (20 by default) matching every word of `query`
+    SearchFoods { query: String, limit: Option<usize> },
+    /// The food with this EAN/UPC barcode, or null
+    LookupBarcode { barcode: String },
+    AddPantryItem(NewPantryItem),
+    Pantry,
+    /// `amount` as accepted by `Amount`, e.g. "500g" or "2 l"
+    SetPantryAmount { id: i64, amount: String },
+    DeletePantryItem { id: i64 },
+    AddRecipe(NewRecipe),
+    Recipes,
+    DeleteRecipe { id: i64 },
+    /// Takes the ingredients out of the pantry and returns a `PantryReport`; one batch when omitted
+    CookRecipe { id: i64, batches: Option<f64> },
+    /// A strength exercise unless `kind` says otherwise
+    AddExercise { name: String, kind: Option<ExerciseKind> },
+    Exercises,
+    /// `started_at` as accepted by `dates::parse_past`; now when omitted
+    StartWorkout { title: Option<String>, started_at: Option<String> },
+    FinishWorkout { id: i64, ended_at: Option<String> },
+    /// The workout still going on, or null
+    CurrentWorkout,
+    GetWorkout { id: i64 },
+    /// Workouts started on the local days from `from` to `to` (today when omitted)
+    Workouts { from: String, to: Option<String> },
+    DeleteWorkout { id: i64 },
+    /// Returns the set's `id` and the personal `records` it beat
+    LogSet(NewSet),
+    DeleteSet { id: i64 },
+    /// Current bests of one exercise, or of every exercise when `exercise_id` is null
+    PersonalRecords { exercise_id: Option<i64> },
+    /// The last `weeks` weeks (4 by default) up to this one, Monday to Sunday
+    WeeklyVolume { weeks: Option<u32> },
+    AddPlan(NewPlan),
+    Plans,
+    /// Also deletes the plan's tasks that are not done
+    DeletePlan { id: i64 },
+    /// Adds tasks for the plan's sessions over the next `weeks` weeks (4 by default); returns their ids
+    SchedulePlan { id: i64, weeks: Option<u32> },
+    PlannedTasks { plan_id: i64 },
+    /// Marks a task done and returns its `PlannedTask`, or null if no plan scheduled it.
+    /// A null `workout_id` means the workout should be logged and linked with `link_workout`
+    CompletePlannedTask { task_id: i64 },
+    LinkWorkout { task_id: i64, workout_id: i64 },
+    AddJournalEntry(NewJournalEntry),
+    /// Entries recorded on the local days from `from` to `to` (today when omitted)
+    JournalEntries { from: String, to: Option<String> },
+    DeleteJournalEntry { id: i64 },
+    /// One `JournalDay` for each of the last `days` days (30 by default), oldest first
+    JournalDays { days: Option<u32> },
+    /// Each rating against each daily activity over the last `days` days (30 by default)
+    JournalCorrelations { days: Option<u32> },
+    /// A `Review` of the local days from `from` to `to`, both today when omitted. With `format`
+    /// "markdown" or "text" the rendered review is returned as a string instead
+    Review { from: Option<String>, to: Option<String>, format: Option<String> },
+    /// `Stats` for the local days from `from` (27 days before `to` when omitted) to `to`
+    /// (today when omitted), with completions grouped by "day" (the default) or "week"
+    Stats { from: Option<String>, to: Option<String>, group_by: Option<GroupBy> },
+    AddList(NewList),
+    /// In their sort order; archived lists only with `include_archived`
+    Lists { include_archived: Option<bool> },
+    /// Changes only the fields given; "" clears `colour` or `icon`
+    UpdateList(ListChanges),
+    /// The list's tasks are kept, in no list
+    DeleteList { id: i64 },
+    /// Takes the task out of its list when `list_id` is null
+    SetTaskList { task_id: i64, list_id: Option<i64> },
+    /// Puts tagged tasks that are in no list into a list named after the tag; returns a `TagMigration`
+    ListsFromTags,
+}
+
+/// Fields accepted by the `add` method; everything but `title` is optional.
+#[derive(Debug, Deserialize)]
+pub struct NewTask {
+    pub title: String,
+    pub details: Option<String>,
+    pub tag: Option<String>,
+    pub priority: Option<Priority>,
+    /// RFC 3339 or natural language, see `dates::parse_due_date`
+    pub due_date: Option<String>,
+    pub parent_id: Option<i64>,
+    pub estimated_duration: Option<i32>,
+    #[serde(default)]
+    pub is_recurring: bool,
+    pub list_id: Option<i64>,
+}
+
+impl TryFrom<NewTask> for Task {
+    type Error = DateParseError;
+
+    fn try_from(new_task: NewTask) -> Result<Self, Self::Error> {
+        let mut task = Task::new(&new_task.title);
+        if let Some(details) = new_task.details {
+            task = task.with_details(&details);
+        }
+        if let Some(tag) = new_task.tag {
+            task = task.with_tag(&tag);
+        }
+        if let Some(priority) = new_task.priority {
+            task = task.with_priority(priority);
+        }
+        if let Some(due_date) = new_task.due_date {
+            task = task.with_due_date(dates::parse_due_date_local(&due_date)?);
         }
+        if let Some(parent_id) = new_task.parent_id {
+            task = task.with_parent(parent_id);
+        }
+        if let Some(minutes) = new_task.estimated_duration {
+            task.set_estimated_duration(minutes);
+        }
+        task.is_recurring = new_task.is_recurring;
+        task.list_id = new_task.list_id;
+        Ok(task)
     }
+}
 
-    let mut guard = TASK_LIST.lock().unwrap();
-    if let Some(ref mut task_list) = *guard {
-        match task_list.add(task) {
-            Ok(id) => id,
-            Err(_) => -1,
+/// Fields accepted by the `add_food` method; missing nutrients are 0.
+#[derive(Debug, Deserialize)]
+pub struct NewFood {
+    pub name: String,
+    pub brand: Option<String>,
+    pub serving_g: Option<f64>,
+    /// Ignored unless it is a valid EAN/UPC, see `nutrition::normalise_barcode`
+    pub barcode: Option<String>,
+    #[serde(default)]
+    pub per_100g: Nutrients,
+}
+
+impl From<NewFood> for Food {
+    fn from(new_food: NewFood) -> Self {
+        Food {
+            brand: new_food.brand,
+            serving_g: new_food.serving_g,
+            barcode: new_food.barcode.as_deref().and_then(normalise_barcode),
+            ..Food::new(&new_food.name, new_food.per_100g)
         }
-    } else {
-        -1
     }
 }
 
-#[no_mangle]
-pub extern "C" fn get_tasks_json(filter: *const c_char) -> *mut c_char {
-    let filter_str = if filter.is_null() {
-        "all"
-    } else {
-        match unsafe { CStr::from_ptr(filter) }.to_str() {
-            Ok(s) => s,
-            Err(_) => "all",
-        }
+/// Fields accepted by the `log_meal` method.
+#[derive(Debug, Deserialize)]
+pub struct NewMealEntry {
+    pub food_id: i64,
+    /// "150g", "2x", ...; see `Quantity`
+    pub quantity: String,
+    /// Guessed from the time eaten when omitted
+    pub meal: Option<Meal>,
+    /// RFC 3339 or natural language, see `dates::parse_past`; now when omitted
+    pub eaten_at: Option<String>,
+    /// Also take what was eaten out of the pantry; the response then has a `pantry` report
+    #[serde(default)]
+    pub from_pantry: bool,
+}
+
+fn log_meal(task_list: &mut TaskList, new_entry: NewMealEntry) -> Result<Value, FfiError> {
+    let food = task_list
+        .get_food(new_entry.food_id)
+        .map_err(storage_error)?
+        .ok_or(FfiError::RecordNotFound("food", new_entry.food_id))?;
+    let grams = food.grams(new_entry.quantity.parse::<Quantity>()?)?;
+    let eaten_at = match new_entry.eaten_at {
+        Some(eaten_at) => dates::parse_past_local(&eaten_at)?,
+        None => Utc::now(),
     };
+    let meal = new_entry.meal.unwrap_or_else(|| Meal::at(&eaten_at.with_timezone(&Local)));
+    let entry = MealEntry::new(new_entry.food_id, grams, meal, eaten_at);
+    if new_entry.from_pantry {
+        let report = task_list.log_meal_from_pantry(&entry).map_err(storage_error)?;
+        Ok(serde_json::json!({ "id": report.meal_entry_id, "pantry": report }))
+    } else {
+        Ok(serde_json::json!({ "id": task_list.log_meal(&entry).map_err(storage_error)? }))
+    }
+}
+
+/// Fields accepted by the `add_pantry_item` method.
+#[derive(Debug, Deserialize)]
+pub struct NewPantryItem {
+    pub name: String,
+    /// "500g", "1.5 l", "6", ...; see `Amount`
+    pub amount: String,
+    pub food_id: Option<i64>,
+}
+
+/// Fields accepted by the `add_recipe` method.
+#[derive(Debug, Deserialize)]
+pub struct NewRecipe {
+    pub name: String,
+    /// Servings one batch makes; 1 when omitted
+    pub servings: Option<f64>,
+    /// Lines such as "200g rolled oats" or "3 eggs", see `Ingredient`
+    pub ingredients: Vec<String>,
+}
+
+impl TryFrom<NewRecipe> for Recipe {
+    type Error = NutritionError;
+
+    fn try_from(new_recipe: NewRecipe) -> Result<Self, Self::Error> {
+        let ingredients = new_recipe.ingredients.iter().map(|line| line.parse()).collect::<Result<_, _>>()?;
+        Ok(Recipe::new(&new_recipe.name, new_recipe.servings.unwrap_or(1.0), ingredients))
+    }
+}
+
+/// Fields accepted by the `log_set` method: either `set` or the measures themselves.
+#[derive(Debug, Deserialize)]
+pub struct NewSet {
+    pub workout_id: i64,
+    pub exercise_id: i64,
+    /// "5x100kg", "5km in 25:30", ...; see `SetMeasures`
+    pub set: Option<String>,
+    #[serde(flatten)]
+    pub measures: SetMeasures,
+    /// RFC 3339 or natural language, see `dates::parse_past`; now when omitted
+    pub performed_at: Option<String>,
+}
+
+impl TryFrom<NewSet> for WorkoutSet {
+    type Error = FfiError;
 
-    let guard = TASK_LIST.lock().unwrap();
-    if let Some(ref task_list) = *guard {
-        let tasks = match filter_str {
-            "todo" => task_list.get_todo().unwrap_or_default(),
-            "done" => task_list.get_completed().unwrap_or_default(),
-            _ => task_list.all().unwrap_or_default(),
+    fn try_from(new_set: NewSet) -> Result<Self, Self::Error> {
+        let measures = match new_set.set {
+            Some(set) => set.parse::<SetMeasures>()?,
+            None => new_set.measures,
         };
+        let performed_at = match new_set.performed_at {
+            Some(performed_at) => dates::parse_past_local(&performed_at)?,
+            None => Utc::now(),
+        };
+        Ok(WorkoutSet::new(new_set.workout_id, new_set.exercise_id, measures, performed_at))
+    }
+}
 
-        match serde_json::to_string(&tasks) {
-            Ok(json) => match CString::new(json) {
-                Ok(c_string) => c_string.into_raw(),
-                Err(_) => std::ptr::null_mut(),
-            },
-            Err(_) => std::ptr::null_mut(),
+/// Fields accepted by the `add_plan` method.
+#[derive(Debug, Deserialize)]
+pub struct NewPlan {
+    pub name: String,
+    /// `fitness::PLAN_TAG` when omitted
+    pub tag: Option<String>,
+    /// Local days as accepted by `dates::parse_past`; today when `starts_on` is omitted
+    pub starts_on: Option<String>,
+    pub ends_on: Option<String>,
+    /// Lines such as "mon,wed,fri 7am Strength", see `PlanSession`
+    pub sessions: Vec<String>,
+}
+
+impl TryFrom<NewPlan> for TrainingPlan {
+    type Error = FfiError;
+
+    fn try_from(new_plan: NewPlan) -> Result<Self, Self::Error> {
+        let sessions = new_plan.sessions.iter().map(|line| line.parse::<PlanSession>()).collect::<Result<_, _>>()?;
+        let mut plan = TrainingPlan::new(&new_plan.name, local_day(new_plan.starts_on.as_deref())?, sessions);
+        if let Some(tag) = new_plan.tag {
+            plan = plan.with_tag(&tag);
         }
-    } else {
-        std::ptr::null_mut()
+        if let Some(ends_on) = new_plan.ends_on {
+            plan.ends_on = Some(local_day(Some(&ends_on))?);
+        }
+        Ok(plan)
     }
 }
 
-#[no_mangle]
-pub extern "C" fn get_tasks_by_tag_json(tag: *const c_char) -> *mut c_char {
-    let tag_str = match unsafe { CStr::from_ptr(tag) }.to_str() {
-        Ok(s) => s,
-        Err(_) => return std::ptr::null_mut(),
-    };
+/// Fields accepted by the `add_list` method.
+#[derive(Debug, Deserialize)]
+pub struct NewList {
+    pub name: String,
+    /// "#rgb" or "#rrggbb"
+    pub colour: Option<String>,
+    pub icon: Option<String>,
+}
 
-    let guard = TASK_LIST.lock().unwrap();
-    if let Some(ref task_list) = *guard {
-        let tasks = task_list.get_by_tag(tag_str).unwrap_or_default();
-        
-        match serde_json::to_string(&tasks) {
-            Ok(json) => match CString::new(json) {
-                Ok(c_string) => c_string.into_raw(),
-                Err(_) => std::ptr::null_mut(),
-            },
-            Err(_) => std::ptr::null_mut(),
+impl From<NewList> for List {
+    fn from(new_list: NewList) -> Self {
+        let mut list = List::new(&new_list.name);
+        if let Some(colour) = new_list.colour {
+            list = list.with_colour(&colour);
         }
-    } else {
-        std::ptr::null_mut()
+        if let Some(icon) = new_list.icon {
+            list = list.with_icon(&icon);
+        }
+        list
     }
 }
 
-#[no_mangle]
-pub extern "C" fn mark_task_done(id: i64, done: i32) -> i32 {
-    let mut guard = TASK_LIST.lock().unwrap();
-    if let Some(ref mut task_list) = *guard {
-        let result = if done == 1 {
-            task_list.mark_done(id)
-        } else {
-            task_list.mark_undone(id)
+/// Fields accepted by the `update_list` method; everything but `id` is optional.
+#[derive(Debug, Deserialize)]
+pub struct ListChanges {
+    pub id: i64,
+    pub name: Option<String>,
+    pub colour: Option<String>,
+    pub icon: Option<String>,
+    pub sort_order: Option<i64>,
+    pub archived: Option<bool>,
+}
+
+impl ListChanges {
+    fn apply(self, list: List) -> List {
+        let mut list = match self.name {
+            Some(name) => List { name: name.trim().to_string(), ..list },
+            None => list,
         };
-        
+        match self.colour.as_deref().map(str::trim) {
+            Some("") => list.colour = None,
+            Some(colour) => list = list.with_colour(colour),
+            None => {}
+        }
+        match self.icon.as_deref().map(str::trim) {
+            Some("") => list.icon = None,
+            Some(icon) => list = list.with_icon(icon),
+            None => {}
+        }
+        list.sort_order = self.sort_order.unwrap_or(list.sort_order);
+        list.archived = self.archived.unwrap_or(list.archived);
+        list
+    }
+}
+
+/// Fields accepted by the `add_journal_entry` method; at least one rating or `notes` is needed.
+#[derive(Debug, Deserialize)]
+pub struct NewJournalEntry {
+    /// RFC 3339 or natural language, see `dates::parse_past`; now when omitted
+    pub recorded_at: Option<String>,
+    /// Ratings from 1 to 5
+    pub mood: Option<u8>,
+    pub energy: Option<u8>,
+    pub sleep: Option<u8>,
+    pub sleep_hours: Option<f64>,
+    pub notes: Option<String>,
+}
+
+impl TryFrom<NewJournalEntry> for JournalEntry {
+    type Error = FfiError;
+
+    fn try_from(new_entry: NewJournalEntry) -> Result<Self, Self::Error> {
+        let recorded_at = match new_entry.recorded_at {
+            Some(recorded_at) => dates::parse_past_local(&recorded_at)?,
+            None => Utc::now(),
+        };
+        let mut entry = JournalEntry::new(recorded_at).with_notes(new_entry.notes.as_deref().unwrap_or(""));
+        entry.mood = new_entry.mood;
+        entry.energy = new_entry.energy;
+        entry.sleep = new_entry.sleep;
+        entry.sleep_hours = new_entry.sleep_hours;
+        entry.validate()?;
+        Ok(entry)
+    }
+}
+
+/// A `freelist_call` response. Exactly one of `result` and `error` is present.
+#[derive(Debug, Serialize)]
+pub struct CallResponse {
+    pub version: u32,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<CallError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl CallResponse {
+    fn from_result(result: &Result<Value, FfiError>) -> Self {
         match result {
-            Ok(_) => 0,
-            Err(_) => -1,
+            Ok(value) => CallResponse {
+                version: FREELIST_API_VERSION,
+                ok: true,
+                result: Some(value.clone()),
+                error: None,
+            },
+            Err(err) => CallResponse {
+                version: FREELIST_API_VERSION,
+                ok: false,
+                result: None,
+                error: Some(CallError { code: err.code(), message: err.to_string() }),
+            },
         }
-    } else {
-        -1
     }
 }
 
-#[no_mangle]
-pub extern "C" fn delete_task(id: i64) -> i32 {
-    let mut guard = TASK_LIST.lock().unwrap();
-    if let Some(ref mut task_list) = *guard {
-        match task_list.delete(id) {
-            Ok(_) => 0,
-            Err(_) => -1,
+fn to_value<T: Serialize>(value: T) -> Result<Value, FfiError> {
+    serde_json::to_value(value).map_err(|e| FfiError::Serialization(e.to_string()))
+}
+
+fn parse_request(json: &str) -> Result<Command, FfiError> {
+    // Check the version first so older clients get a clear error for unknown methods
+    let raw: Value = serde_json::from_str(json).map_err(|e| FfiError::InvalidRequest(e.to_string()))?;
+    match raw.get("version").and_then(Value::as_u64) {
+        Some(version) if version == u64::from(FREELIST_API_VERSION) => {}
+        Some(version) => {
+            return Err(FfiError::UnsupportedVersion(u32::try_from(version).unwrap_or(u32::MAX)));
         }
-    } else {
-        -1
+        None => return Err(FfiError::InvalidRequest("missing `version`".to_string())),
     }
+    let request: CallRequest =
+        serde_json::from_value(raw).map_err(|e| FfiError::InvalidRequest(e.to_string()))?;
+    Ok(request.command)
 }
 
-#[no_mangle]
-pub extern "C" fn get_all_tags_json() -> *mut c_char {
-    let guard = TASK_LIST.lock().unwrap();
-    if let Some(ref task_list) = *guard {
-        match task_list.get_all_tags() {
-            Ok(tags) => match serde_json::to_string(&tags) {
-                Ok(json) => match CString::new(json) {
-                    Ok(c_string) => c_string.into_raw(),
-                    Err(_) => std::ptr::null_mut(),
-                },
-                Err(_) => std::ptr::null_mut(),
-            },
-            Err(_) => std::ptr::null_mut(),
+fn dispatch(command: Command) -> Result<Value, FfiError> {
+    match command {
+        Command::Init { path } => install_task_list(TaskList::new(path)).map(|()| Value::Null),
+        Command::InitMemory => install_task_list(TaskList::new_in_memory()).map(|()| Value::Null),
+        Command::Add(new_task) => with_task_list(|task_list| {
+            let id = task_list.add(new_task.try_into()?).map_err(storage_error)?;
+            Ok(serde_json::json!({ "id": id }))
+        }),
+        Command::AddQuick { input } => {
+            let task = Task::parse_quick_add(&input)?;
+            with_task_list(|task_list| Ok(serde_json::json!({ "id": add_quick(task_list, task)? })))
+        }
+        Command::All => with_task_list(|task_list| to_value(task_list.all().map_err(storage_error)?)),
+        Command::GetTodo => {
+            with_task_list(|task_list| to_value(task_list.get_todo().map_err(storage_error)?))
+        }
+        Command::GetCompleted => {
+            with_task_list(|task_list| to_value(task_list.get_completed().map_err(storage_error)?))
+        }
+        Command::GetOverdue => {
+            with_task_list(|task_list| to_value(task_list.get_overdue().map_err(storage_error)?))
+        }
+        Command::GetByTag { tag } => {
+            with_task_list(|task_list| to_value(task_list.get_by_tag(&tag).map_err(storage_error)?))
+        }
+        Command::GetByList { list_id } => {
+            with_task_list(|task_list| to_value(task_list.get_by_list(list_id).map_err(storage_error)?))
+        }
+        Command::GetSubtasks { parent_id } => with_task_list(|task_list| {
+            to_value(task_list.get_subtasks(parent_id).map_err(storage_error)?)
+        }),
+        Command::GetById { id } => with_task_list(|task_list| {
+            match task_list.get_by_id(id).map_err(storage_error)? {
+                Some(task) => to_value(task),
+                None => Err(FfiError::NotFound(id)),
+            }
+        }),
+        Command::MarkDone { id } => with_task_list(|task_list| {
+            ensure_task_exists(task_list, id)?;
+            task_list.mark_done(id).map_err(storage_error)?;
+            Ok(Value::Null)
+        }),
+        Command::MarkUndone { id } => with_task_list(|task_list| {
+            ensure_task_exists(task_list, id)?;
+            task_list.mark_undone(id).map_err(storage_error)?;
+            Ok(Value::Null)
+        }),
+        Command::Delete { id } => with_task_list(|task_list| {
+            ensure_task_exists(task_list, id)?;
+            task_list.delete(id).map_err(storage_error)?;
+            Ok(Value::Null)
+        }),
+        Command::MoveTask { id, before, after } => with_task_list(|task_list| {
+            task_list.move_task(id, before, after).map_err(ordering_error)?;
+            Ok(Value::Null)
+        }),
+        Command::RebalancePositions => with_task_list(|task_list| {
+            let changed = task_list.rebalance_positions().map_err(storage_error)?;
+            Ok(serde_json::json!({ "changed": changed }))
+        }),
+        Command::ClearAll => with_task_list(|task_list| {
+            task_list.clear_all().map_err(storage_error)?;
+            Ok(Value::Null)
+        }),
+        Command::GetAllTags => {
+            with_task_list(|task_list| to_value(task_list.get_all_tags().map_err(storage_error)?))
+        }
+        Command::PollChanges => with_task_list(|task_list| {
+            task_list.poll_changes().map_err(storage_error)?;
+            Ok(Value::Null)
+        }),
+        Command::ParseDueDate { input } => Ok(Value::String(dates::parse_due_date_local(&input)?.to_rfc3339())),
+        Command::ParseQuickAdd { input } => to_value(Task::parse_quick_add(&input)?),
+        Command::ExportArchive => {
+            with_task_list(|task_list| to_value(task_list.export_archive().map_err(storage_error)?))
+        }
+        Command::ImportArchive { archive } => {
+            let archive = Archive::from_value(archive)?;
+            with_task_list(|task_list| to_value(task_list.import_archive(&archive).map_err(storage_error)?))
+        }
+        Command::AddFood(new_food) => with_task_list(|task_list| {
+            let id = task_list.add_food(&new_food.into()).map_err(storage_error)?;
+            Ok(serde_json::json!({ "id": id }))
+        }),
+        Command::GetFood { id } => with_task_list(|task_list| {
+            match task_list.get_food(id).map_err(storage_error)? {
+                Some(food) => to_value(food),
+                None => Err(FfiError::RecordNotFound("food", id)),
+            }
+        }),
+        Command::Foods => with_task_list(|task_list| to_value(task_list.foods().map_err(storage_error)?)),
+        Command::LogMeal(new_entry) => with_task_list(|task_list| log_meal(task_list, new_entry)),
+        Command::DeleteMealEntry { id } => with_task_list(|task_list| {
+            if task_list.delete_meal_entry(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("meal entry", id))
+            }
+        }),
+        Command::DayNutrition { date } => {
+            let day = local_day(date.as_deref())?;
+            with_task_list(|task_list| to_value(task_list.day_nutrition(day, &Local).map_err(storage_error)?))
+        }
+        Command::SetNutritionTarget { nutrient, amount } => {
+            crate::nutrition::nutrient_name(&nutrient)?;
+            with_task_list(|task_list| {
+                task_list.set_nutrition_target(&nutrient, amount).map_err(storage_error)?;
+                Ok(Value::Null)
+            })
+        }
+        Command::NutritionTargets => {
+            with_task_list(|task_list| to_value(task_list.nutrition_targets().map_err(storage_error)?))
+        }
+        Command::SearchFoods { query, limit } => with_task_list(|task_list| {
+            to_value(task_list.search_foods(&query, limit.unwrap_or(SEARCH_LIMIT)).map_err(storage_error)?)
+        }),
+        Command::LookupBarcode { barcode } => {
+            with_task_list(|task_list| to_value(task_list.lookup_barcode(&barcode).map_err(storage_error)?))
+        }
+        Command::AddPantryItem(new_item) => {
+            let mut item = PantryItem::new(&new_item.name, new_item.amount.parse::<Amount>()?);
+            item.food_id = new_item.food_id;
+            with_task_list(|task_list| {
+                let id = task_list.add_pantry_item(&item).map_err(storage_error)?;
+                Ok(serde_json::json!({ "id": id }))
+            })
+        }
+        Command::Pantry => with_task_list(|task_list| to_value(task_list.pantry().map_err(storage_error)?)),
+        Command::SetPantryAmount { id, amount } => {
+            let amount = amount.parse::<Amount>()?;
+            with_task_list(|task_list| {
+                if task_list.set_pantry_amount(id, amount).map_err(storage_error)? {
+                    Ok(Value::Null)
+                } else {
+                    Err(FfiError::RecordNotFound("pantry item", id))
+                }
+            })
+        }
+        Command::DeletePantryItem { id } => with_task_list(|task_list| {
+            if task_list.delete_pantry_item(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("pantry item", id))
+            }
+        }),
+        Command::AddRecipe(new_recipe) => {
+            let recipe = Recipe::try_from(new_recipe)?;
+            with_task_list(|task_list| {
+                let id = task_list.add_recipe(&recipe).map_err(storage_error)?;
+                Ok(serde_json::json!({ "id": id }))
+            })
+        }
+        Command::Recipes => with_task_list(|task_list| to_value(task_list.recipes().map_err(storage_error)?)),
+        Command::DeleteRecipe { id } => with_task_list(|task_list| {
+            if task_list.delete_recipe(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("recipe", id))
+            }
+        }),
+        Command::CookRecipe { id, batches } => {
+            let batches = batches.unwrap_or(1.0);
+            if !batches.is_finite() || batches <= 0.0 {
+                return Err(FfiError::InvalidInput(format!("cannot cook {} batches", batches)));
+            }
+            with_task_list(|task_list| {
+                if task_list.get_recipe(id).map_err(storage_error)?.is_none() {
+                    return Err(FfiError::RecordNotFound("recipe", id));
+                }
+                to_value(task_list.cook_recipe(id, batches).map_err(storage_error)?)
+            })
+        }
+        Command::AddExercise { name, kind } => with_task_list(|task_list| {
+            let exercise = Exercise::new(&name, kind.unwrap_or(ExerciseKind::Strength));
+            let id = task_list.add_exercise(&exercise).map_err(storage_error)?;
+            Ok(serde_json::json!({ "id": id }))
+        }),
+        Command::Exercises => with_task_list(|task_list| to_value(task_list.exercises().map_err(storage_error)?)),
+        Command::StartWorkout { title, started_at } => {
+            let mut workout = Workout::new(match started_at {
+                Some(started_at) => dates::parse_past_local(&started_at)?,
+                None => Utc::now(),
+            });
+            workout.title = title;
+            with_task_list(|task_list| {
+                let id = task_list.start_workout(&workout).map_err(storage_error)?;
+                Ok(serde_json::json!({ "id": id }))
+            })
+        }
+        Command::FinishWorkout { id, ended_at } => {
+            let ended_at = match ended_at {
+                Some(ended_at) => dates::parse_past_local(&ended_at)?,
+                None => Utc::now(),
+            };
+            with_task_list(|task_list| {
+                if task_list.finish_workout(id, ended_at).map_err(storage_error)? {
+                    Ok(Value::Null)
+                } else {
+                    Err(FfiError::RecordNotFound("workout", id))
+                }
+            })
+        }
+        Command::CurrentWorkout => {
+            with_task_list(|task_list| to_value(task_list.current_workout().map_err(storage_error)?))
+        }
+        Command::GetWorkout { id } => with_task_list(|task_list| {
+            match task_list.get_workout(id).map_err(storage_error)? {
+                Some(workout) => to_value(workout),
+                None => Err(FfiError::RecordNotFound("workout", id)),
+            }
+        }),
+        Command::Workouts { from, to } => {
+            let first = local_day(Some(&from))?;
+            let last = local_day(to.as_deref())?;
+            let start = dates::start_of_day(first, &Local).ok_or(FfiError::InvalidDate(from))?;
+            let end = dates::start_of_day(last + Duration::days(1), &Local)
+                .ok_or_else(|| FfiError::InvalidDate(last.to_string()))?;
+            with_task_list(|task_list| to_value(task_list.workouts_between(start, end).map_err(storage_error)?))
+        }
+        Command::DeleteWorkout { id } => with_task_list(|task_list| {
+            if task_list.delete_workout(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("workout", id))
+            }
+        }),
+        Command::LogSet(new_set) => {
+            let set = WorkoutSet::try_from(new_set)?;
+            if set.measures.is_empty() {
+                return Err(FfiError::InvalidInput("a set needs reps, weight, distance or time".to_string()));
+            }
+            with_task_list(|task_list| {
+                if task_list.get_workout(set.workout_id).map_err(storage_error)?.is_none() {
+                    return Err(FfiError::RecordNotFound("workout", set.workout_id));
+                }
+                if task_list.get_exercise(set.exercise_id).map_err(storage_error)?.is_none() {
+                    return Err(FfiError::RecordNotFound("exercise", set.exercise_id));
+                }
+                let (id, records) = task_list.log_set(&set).map_err(storage_error)?;
+                Ok(serde_json::json!({ "id": id, "records": records }))
+            })
+        }
+        Command::DeleteSet { id } => with_task_list(|task_list| {
+            if task_list.delete_set(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("set", id))
+            }
+        }),
+        Command::PersonalRecords { exercise_id } => with_task_list(|task_list| {
+            to_value(task_list.personal_records(exercise_id).map_err(storage_error)?)
+        }),
+        Command::WeeklyVolume { weeks } => {
+            let (first, last) = recent_weeks(weeks.unwrap_or(DEFAULT_WEEKS))?;
+            with_task_list(|task_list| to_value(task_list.weekly_volume(first, last, &Local).map_err(storage_error)?))
+        }
+        Command::AddPlan(new_plan) => {
+            let plan = TrainingPlan::try_from(new_plan)?;
+            if plan.sessions.is_empty() {
+                return Err(FfiError::InvalidInput("a training plan needs at least one session".to_string()));
+            }
+            with_task_list(|task_list| {
+                let id = task_list.add_plan(&plan).map_err(storage_error)?;
+                Ok(serde_json::json!({ "id": id }))
+            })
+        }
+        Command::Plans => with_task_list(|task_list| to_value(task_list.plans().map_err(storage_error)?)),
+        Command::DeletePlan { id } => with_task_list(|task_list| {
+            if task_list.delete_plan(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("training plan", id))
+            }
+        }),
+        Command::SchedulePlan { id, weeks } => {
+            let weeks = weeks.unwrap_or(DEFAULT_WEEKS);
+            let out_of_range = || FfiError::InvalidInput(format!("weeks must be from 1 to {}", MAX_SCHEDULE_WEEKS));
+            if weeks == 0 || weeks > MAX_SCHEDULE_WEEKS {
+                return Err(out_of_range());
+            }
+            let now = Local::now();
+            let through =
+                now.date_naive().checked_add_days(Days::new(7 * u64::from(weeks) - 1)).ok_or_else(out_of_range)?;
+            with_task_list(|task_list| {
+                if task_list.get_plan(id).map_err(storage_error)?.is_none() {
+                    return Err(FfiError::RecordNotFound("training plan", id));
+                }
+                to_value(task_list.schedule_plan(id, through, &now).map_err(storage_error)?)
+            })
+        }
+        Command::PlannedTasks { plan_id } => {
+            with_task_list(|task_list| to_value(task_list.planned_tasks(plan_id).map_err(storage_error)?))
+        }
+        Command::CompletePlannedTask { task_id } => with_task_list(|task_list| {
+            ensure_task_exists(task_list, task_id)?;
+            to_value(task_list.complete_planned_task(task_id, &Local::now()).map_err(storage_error)?)
+        }),
+        Command::LinkWorkout { task_id, workout_id } => with_task_list(|task_list| {
+            ensure_task_exists(task_list, task_id)?;
+            if task_list.get_workout(workout_id).map_err(storage_error)?.is_none() {
+                return Err(FfiError::RecordNotFound("workout", workout_id));
+            }
+            if task_list.link_workout(task_id, workout_id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::InvalidInput(format!("task {} was not scheduled by a training plan", task_id)))
+            }
+        }),
+        Command::AddJournalEntry(new_entry) => {
+            let entry = JournalEntry::try_from(new_entry)?;
+            with_task_list(|task_list| {
+                let id = task_list.add_journal_entry(&entry).map_err(storage_error)?;
+                Ok(serde_json::json!({ "id": id }))
+            })
+        }
+        Command::JournalEntries { from, to } => {
+            let first = local_day(Some(&from))?;
+            let last = local_day(to.as_deref())?;
+            let start = dates::start_of_day(first, &Local).ok_or(FfiError::InvalidDate(from))?;
+            let end = dates::start_of_day(last + Duration::days(1), &Local)
+                .ok_or_else(|| FfiError::InvalidDate(last.to_string()))?;
+            with_task_list(|task_list| to_value(task_list.journal_between(start, end).map_err(storage_error)?))
+        }
+        Command::DeleteJournalEntry { id } => with_task_list(|task_list| {
+            if task_list.delete_journal_entry(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("journal entry", id))
+            }
+        }),
+        Command::JournalDays { days } => {
+            let (first, last) = recent_days(days.unwrap_or(DEFAULT_JOURNAL_DAYS))?;
+            with_task_list(|task_list| to_value(task_list.journal_days(first, last, &Local).map_err(storage_error)?))
+        }
+        Command::JournalCorrelations { days } => {
+            let (first, last) = recent_days(days.unwrap_or(DEFAULT_JOURNAL_DAYS))?;
+            with_task_list(|task_list| {
+                to_value(task_list.journal_correlations(first, last, &Local).map_err(storage_error)?)
+            })
+        }
+        Command::Review { from, to, format } => {
+            let first = local_day(from.as_deref())?;
+            let last = local_day(to.as_deref())?;
+            if last < first {
+                return Err(ReportError::EmptyRange(first, last).into());
+            }
+            with_task_list(|task_list| {
+                let review = task_list.review(first, last, &Local).map_err(storage_error)?;
+                match format.as_deref() {
+                    None | Some("json") => to_value(review),
+                    Some("markdown") => Ok(Value::String(review.to_markdown())),
+                    Some("text") => Ok(Value::String(review.to_text())),
+                    Some(other) => Err(FfiError::InvalidInput(format!("unknown review format '{}'", other))),
+                }
+            })
+        }
+        Command::Stats { from, to, group_by } => {
+            let last = local_day(to.as_deref())?;
+            let first = match from {
+                Some(from) => local_day(Some(&from))?,
+                None => last - Duration::days(i64::from(DEFAULT_STATS_DAYS) - 1),
+            };
+            if last < first {
+                return Err(ReportError::EmptyRange(first, last).into());
+            }
+            let group_by = group_by.unwrap_or(GroupBy::Day);
+            with_task_list(|task_list| {
+                to_value(task_list.stats(first..=last, group_by, &Local).map_err(storage_error)?)
+            })
+        }
+        Command::AddList(new_list) => {
+            let list = List::from(new_list);
+            list.validate()?;
+            with_task_list(|task_list| {
+                let id = task_list.add_list(&list).map_err(list_error)?;
+                Ok(serde_json::json!({ "id": id }))
+            })
+        }
+        Command::Lists { include_archived } => with_task_list(|task_list| {
+            to_value(task_list.lists(include_archived.unwrap_or(false)).map_err(storage_error)?)
+        }),
+        Command::UpdateList(changes) => with_task_list(|task_list| {
+            let id = changes.id;
+            let list = task_list.get_list(id).map_err(storage_error)?.ok_or(FfiError::RecordNotFound("list", id))?;
+            let list = changes.apply(list);
+            list.validate()?;
+            task_list.update_list(&list).map_err(list_error)?;
+            to_value(list)
+        }),
+        Command::DeleteList { id } => with_task_list(|task_list| {
+            if task_list.delete_list(id).map_err(storage_error)? {
+                Ok(Value::Null)
+            } else {
+                Err(FfiError::RecordNotFound("list", id))
+            }
+        }),
+        Command::SetTaskList { task_id, list_id } => with_task_list(|task_list| {
+            ensure_task_exists(task_list, task_id)?;
+            if let Some(list_id) = list_id {
+                if task_list.get_list(list_id).map_err(storage_error)?.is_none() {
+                    return Err(FfiError::RecordNotFound("list", list_id));
+                }
+            }
+            task_list.set_task_list(task_id, list_id).map_err(storage_error)?;
+            Ok(Value::Null)
+        }),
+        Command::ListsFromTags => {
+            with_task_list(|task_list| to_value(task_list.lists_from_tags().map_err(storage_error)?))
         }
-    } else {
-        std::ptr::null_mut()
     }
 }
 
+/// Runs a JSON-encoded `CallRequest` and returns a JSON-encoded `CallResponse`.
+/// Failures are reported inside the response as well as through the last error;
+/// null is only returned if the response itself cannot be produced.
+/// The returned string must be released with `free_string`.
+///
+/// # Safety
+/// `json_request` must be null or point to a valid NUL-terminated string.
 #[no_mangle]
-pub extern "C" fn clear_all_tasks() -> i32 {
-    let mut guard = TASK_LIST.lock().unwrap();
-    if let Some(ref mut task_list) = *guard {
-        match task_list.clear_all() {
-            Ok(_) => 0,
-            Err(_) => -1,
+pub unsafe extern "C" fn freelist_call(json_request: *const c_char) -> *mut c_char {
+    catch_panic_or(std::ptr::null_mut(), || {
+        let result = catch_panic(|| {
+            let json = required_str(json_request, "json_request")?;
+            dispatch(parse_request(json)?)
+        });
+        let response = to_json_c_string(&CallResponse::from_result(&result));
+        match (result, response) {
+            (Ok(_), Ok(ptr)) => {
+                clear_last_error();
+                ptr
+            }
+            (Err(err), Ok(ptr)) => {
+                set_last_error(err);
+                ptr
+            }
+            (_, Err(err)) => {
+                set_last_error(err);
+                std::ptr::null_mut()
+            }
         }
-    } else {
-        -1
-    }
+    })
+}
+
+/// Returns the error code of the last failed call on this thread, or 0 if it succeeded.
+#[no_mangle]
+pub extern "C" fn freelist_last_error_code() -> i32 {
+    catch_panic_or(FREELIST_ERR_PANIC, || {
+        LAST_ERROR.with(|last| last.borrow().as_ref().map_or(FREELIST_OK, FfiError::code))
+    })
+}
+
+/// Returns a description of the last failed call on this thread, or null if it succeeded.
+/// The returned string must be released with `free_string`.
+#[no_mangle]
+pub extern "C" fn freelist_last_error_message() -> *mut c_char {
+    catch_panic_or(std::ptr::null_mut(), || {
+        LAST_ERROR.with(|last| match last.borrow().as_ref() {
+            Some(err) => CString::new(err.to_string())
+                .map(CString::into_raw)
+                .unwrap_or(std::ptr::null_mut()),
+            None => std::ptr::null_mut(),
+        })
+    })
 }
 
+/// Releases a string previously returned by this library.
+///
+/// # Safety
+/// `ptr` must be null or a pointer returned by this library that has not been freed yet.
 #[no_mangle]
-pub extern "C" fn free_string(ptr: *mut c_char) {
-    if !ptr.is_null() {
-        unsafe {
+pub unsafe extern "C" fn free_string(ptr: *mut c_char) {
+    catch_panic_or((), || {
+        if !ptr.is_null() {
             let _ = CString::from_raw(ptr);
         }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The FFI layer keeps global state, so tests touching it must not interleave
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn test_lock() -> MutexGuard<'static, ()> {
+        TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn force_panic() {
+        FORCE_PANIC.with(|force| force.set(true));
+    }
+
+    fn assert_panicked() {
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_PANIC);
+        assert!(take_message().unwrap().contains("forced panic"));
+    }
+
+    fn take_message() -> Option<String> {
+        let ptr = freelist_last_error_message();
+        if ptr.is_null() {
+            return None;
+        }
+        let message = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned();
+        unsafe { free_string(ptr) };
+        Some(message)
+    }
+
+    #[test]
+    fn test_not_initialised_error() {
+        let _lock = test_lock();
+        *lock_task_list() = None;
+
+        assert_eq!(clear_all_tasks(), -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NOT_INITIALISED);
+        assert!(take_message().unwrap().contains("not initialised"));
+        assert!(get_all_tags_json().is_null());
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NOT_INITIALISED);
+    }
+
+    #[test]
+    fn test_argument_errors() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+
+        assert_eq!(unsafe { add_task(std::ptr::null(), std::ptr::null(), std::ptr::null()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NULL_ARGUMENT);
+
+        let bad = CString::new(vec![0xff, 0xfe]).unwrap();
+        assert!(unsafe { get_tasks_by_tag_json(bad.as_ptr()) }.is_null());
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_INVALID_UTF8);
+        assert!(take_message().unwrap().contains("tag"));
+    }
+
+    #[test]
+    fn test_not_found_and_success_clears_error() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+
+        assert_eq!(mark_task_done(42, 1), -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NOT_FOUND);
+        assert_eq!(delete_task(42), -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NOT_FOUND);
+
+        let title = CString::new("Write tests").unwrap();
+        let id = unsafe { add_task(title.as_ptr(), std::ptr::null(), std::ptr::null()) };
+        assert!(id > 0);
+        assert_eq!(freelist_last_error_code(), FREELIST_OK);
+        assert!(take_message().is_none());
+        assert_eq!(mark_task_done(id, 1), 0);
+    }
+
+    #[test]
+    fn test_storage_error() {
+        let _lock = test_lock();
+        let path = CString::new("/nonexistent-dir/freelist.db").unwrap();
+        assert_eq!(unsafe { init_freelist(path.as_ptr()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_STORAGE);
+    }
+
+    #[test]
+    fn test_panics_are_caught_at_every_entry_point() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let text = CString::new("x").unwrap();
+
+        force_panic();
+        assert_eq!(unsafe { init_freelist(text.as_ptr()) }, -1);
+        assert_panicked();
+        force_panic();
+        assert_eq!(init_freelist_memory(), -1);
+        assert_panicked();
+        force_panic();
+        assert_eq!(unsafe { add_task(text.as_ptr(), std::ptr::null(), std::ptr::null()) }, -1);
+        assert_panicked();
+        force_panic();
+        assert_eq!(unsafe { add_task_quick(text.as_ptr()) }, -1);
+        assert_panicked();
+        force_panic();
+        assert!(unsafe { get_tasks_json(std::ptr::null()) }.is_null());
+        assert_panicked();
+        force_panic();
+        assert!(unsafe { get_tasks_by_tag_json(text.as_ptr()) }.is_null());
+        assert_panicked();
+        force_panic();
+        assert_eq!(mark_task_done(1, 1), -1);
+        assert_panicked();
+        force_panic();
+        assert_eq!(delete_task(1), -1);
+        assert_panicked();
+        force_panic();
+        assert!(get_all_tags_json().is_null());
+        assert_panicked();
+        force_panic();
+        assert_eq!(clear_all_tasks(), -1);
+        assert_panicked();
+        force_panic();
+        assert!(unsafe { freelist_call(text.as_ptr()) }.is_null());
+        force_panic();
+        assert_eq!(unsafe { freelist_subscribe(Some(record_change), std::ptr::null_mut()) }, -1);
+        assert_panicked();
+        force_panic();
+        assert_eq!(freelist_unsubscribe(1), -1);
+        assert_panicked();
+        force_panic();
+        assert_eq!(freelist_poll_changes(), -1);
+        assert_panicked();
+
+        force_panic();
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_PANIC);
+        force_panic();
+        assert!(freelist_last_error_message().is_null());
+        force_panic();
+        unsafe { free_string(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_poisoned_lock_is_recovered() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+
+        let result: Result<(), FfiError> = catch_panic(|| with_task_list(|_| panic!("poison")));
+        assert!(matches!(result, Err(FfiError::Panic(_))));
+        assert!(TASK_LIST.is_poisoned());
+
+        let title = CString::new("Still works").unwrap();
+        let id = unsafe { add_task(title.as_ptr(), std::ptr::null(), std::ptr::null()) };
+        assert!(id > 0);
+        assert_eq!(mark_task_done(id, 1), 0);
+        TASK_LIST.clear_poison();
+    }
+
+    fn call(request: &str) -> Value {
+        let request = CString::new(request).unwrap();
+        let ptr = unsafe { freelist_call(request.as_ptr()) };
+        assert!(!ptr.is_null());
+        let response = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe { free_string(ptr) };
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn test_call_dispatches_task_list_operations() {
+        let _lock = test_lock();
+        let response = call(r#"{"version": 1, "method": "init_memory"}"#);
+        assert_eq!(response["ok"], true);
+        assert_eq!(response["version"], FREELIST_API_VERSION);
+
+        let response = call(
+            r#"{"version": 1, "method": "add", "params": {
+                "title": "Buy milk", "tag": "groceries",
+                "due_date": "2026-11-01T17:00:00Z", "estimated_duration": 15
+            }}"#,
+        );
+        let id = response["result"]["id"].as_i64().unwrap();
+        call(&format!(
+            r#"{{"version": 1, "method": "add", "params": {{"title": "Whole milk", "parent_id": {}}}}}"#,
+            id
+        ));
+
+        let task = &call(&format!(r#"{{"version": 1, "method": "get_by_id", "params": {{"id": {}}}}}"#, id))["result"];
+        assert_eq!(task["title"], "Buy milk");
+        assert_eq!(task["estimated_duration"], 15);
+        assert_eq!(task["due_date"], "2026-11-01T17:00:00Z");
+
+        let subtasks = call(&format!(
+            r#"{{"version": 1, "method": "get_subtasks", "params": {{"parent_id": {}}}}}"#,
+            id
+        ));
+        assert_eq!(subtasks["result"].as_array().unwrap().len(), 1);
+
+        call(&format!(r#"{{"version": 1, "method": "mark_done", "params": {{"id": {}}}}}"#, id));
+        assert_eq!(call(r#"{"version": 1, "method": "get_completed"}"#)["result"].as_array().unwrap().len(), 1);
+        assert_eq!(call(r#"{"version": 1, "method": "get_todo"}"#)["result"].as_array().unwrap().len(), 1);
+        assert_eq!(call(r#"{"version": 1, "method": "get_all_tags"}"#)["result"], serde_json::json!(["groceries"]));
+        assert_eq!(
+            call(r#"{"version": 1, "method": "get_by_tag", "params": {"tag": "groceries"}}"#)["result"][0]["id"],
+            id
+        );
+
+        call(&format!(r#"{{"version": 1, "method": "delete", "params": {{"id": {}}}}}"#, id));
+        assert!(call(r#"{"version": 1, "method": "all"}"#)["result"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_call_reports_errors_in_response() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+
+        let response = call(r#"{"version": 2, "method": "all"}"#);
+        assert_eq!(response["ok"], false);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_UNSUPPORTED_VERSION);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_UNSUPPORTED_VERSION);
+        // 2^32 + 1 must not wrap around to the supported version
+        let response = call(r#"{"version": 4294967297, "method": "all"}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_UNSUPPORTED_VERSION);
+        assert_eq!(response["error"]["message"], "unsupported request version 4294967295 (expected 1)");
+
+        let response = call(r#"{"version": 1, "method": "teleport"}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_REQUEST);
+
+        let response = call("not json");
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_REQUEST);
+
+        let response = call(r#"{"version": 1, "method": "mark_done", "params": {"id": 404}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_NOT_FOUND);
+        assert!(response.get("result").is_none());
+
+        call(r#"{"version": 1, "method": "all"}"#);
+        assert_eq!(freelist_last_error_code(), FREELIST_OK);
+    }
+
+    static RECEIVED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    extern "C" fn record_change(_ctx: *mut c_void, change_set_json: *const c_char) {
+        let json = unsafe { CStr::from_ptr(change_set_json) }.to_str().unwrap().to_string();
+        RECEIVED.lock().unwrap().push(json);
+        // Re-entering the library from a callback must not deadlock
+        let tags = get_all_tags_json();
+        assert!(!tags.is_null());
+        unsafe { free_string(tags) };
+    }
+
+    #[test]
+    fn test_subscribe_receives_changes() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        RECEIVED.lock().unwrap().clear();
+
+        let subscription = unsafe { freelist_subscribe(Some(record_change), std::ptr::null_mut()) };
+        assert!(subscription > 0);
+
+        let title = CString::new("Observed").unwrap();
+        let id = unsafe { add_task(title.as_ptr(), std::ptr::null(), std::ptr::null()) };
+        assert_eq!(mark_task_done(id, 1), 0);
+
+        let received: Vec<ChangeSet> = RECEIVED
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|json| serde_json::from_str(json).unwrap())
+            .collect();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].changes[0].id, id);
+        assert_eq!(received[0].changes[0].kind, crate::ChangeKind::Insert);
+        assert_eq!(received[1].changes[0].kind, crate::ChangeKind::Update);
+
+        assert_eq!(freelist_unsubscribe(subscription), 0);
+        assert_eq!(clear_all_tasks(), 0);
+        assert_eq!(RECEIVED.lock().unwrap().len(), 2);
+
+        assert_eq!(freelist_unsubscribe(subscription), -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NOT_FOUND);
+        assert_eq!(unsafe { freelist_subscribe(None, std::ptr::null_mut()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NULL_ARGUMENT);
+    }
+
+    #[test]
+    fn test_due_dates_accept_natural_language() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let title = CString::new("Call mum").unwrap();
+
+        let due = CString::new("tomorrow 5pm").unwrap();
+        let id = unsafe { add_task(title.as_ptr(), std::ptr::null(), due.as_ptr()) };
+        assert!(id > 0);
+        let stored = with_task_list(|task_list| Ok(task_list.get_by_id(id).unwrap().unwrap())).unwrap();
+        assert!(stored.due_date.unwrap() > chrono::Utc::now());
+
+        let bad = CString::new("someday maybe").unwrap();
+        assert_eq!(unsafe { add_task(title.as_ptr(), std::ptr::null(), bad.as_ptr()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_INVALID_DATE);
+
+        let response = call(r#"{"version": 1, "method": "parse_due_date", "params": {"input": "2026-11-01 09:00"}}"#);
+        assert_eq!(response["ok"], true);
+        let response = call(r#"{"version": 1, "method": "add", "params": {"title": "x", "due_date": "nope"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_DATE);
+    }
+
+    #[test]
+    fn test_quick_add() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+
+        let input = CString::new("Buy milk #groceries !high due:tomorrow ~15m").unwrap();
+        let id = unsafe { add_task_quick(input.as_ptr()) };
+        assert!(id > 0);
+        let stored = with_task_list(|task_list| Ok(task_list.get_by_id(id).unwrap().unwrap())).unwrap();
+        assert_eq!(stored.title, "Buy milk");
+        assert_eq!(stored.tag.as_deref(), Some("groceries"));
+        assert_eq!(stored.priority, Some(Priority::High));
+        assert_eq!(stored.estimated_duration, Some(15));
+        assert!(stored.due_date.is_some());
+
+        let orphan = CString::new("Child ^999").unwrap();
+        assert_eq!(unsafe { add_task_quick(orphan.as_ptr()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NOT_FOUND);
+        let untitled = CString::new("#groceries").unwrap();
+        assert_eq!(unsafe { add_task_quick(untitled.as_ptr()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_INVALID_INPUT);
+        assert_eq!(unsafe { add_task_quick(std::ptr::null()) }, -1);
+        assert_eq!(freelist_last_error_code(), FREELIST_ERR_NULL_ARGUMENT);
+
+        let response = call(&format!(
+            r#"{{"version": 1, "method": "add_quick", "params": {{"input": "Pour ~5m ^{}"}}}}"#,
+            id
+        ));
+        assert_eq!(response["ok"], true);
+        let preview = call(r#"{"version": 1, "method": "parse_quick_add", "params": {"input": "Stretch !1 due:bogus"}}"#);
+        assert_eq!(preview["error"]["code"], FREELIST_ERR_INVALID_DATE);
+        let preview = call(r#"{"version": 1, "method": "parse_quick_add", "params": {"input": "Stretch !1"}}"#);
+        assert_eq!(preview["result"]["priority"], "urgent");
+        assert_eq!(preview["result"]["id"], Value::Null);
+    }
+
+    #[test]
+    fn test_nutrition_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+
+        let response = call(
+            r#"{"version": 1, "method": "add_food", "params": {
+                "name": "Greek yogurt", "serving_g": 170,
+                "per_100g": {"energy_kcal": 97, "protein_g": 9, "calcium_mg": 100}
+            }}"#,
+        );
+        let food_id = response["result"]["id"].as_i64().unwrap();
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "log_meal",
+            "params": { "food_id": food_id, "quantity": "2x", "meal": "breakfast" },
+        });
+        assert_eq!(call(&request.to_string())["ok"], true);
+        call(r#"{"version": 1, "method": "set_nutrition_target", "params": {"nutrient": "protein", "amount": 120}}"#);
+
+        let response = call(r#"{"version": 1, "method": "day_nutrition", "params": {}}"#);
+        let day = &response["result"];
+        assert_eq!(day["entries"][0]["food"], "Greek yogurt");
+        assert_eq!(day["entries"][0]["grams"], 340.0);
+        assert_eq!(day["totals"]["calcium_mg"], 340.0);
+        assert_eq!(day["remaining"]["protein_g"], 120.0 - 30.6);
+
+        let response = call(r#"{"version": 1, "method": "day_nutrition", "params": {"date": "yesterday"}}"#);
+        assert_eq!(response["result"]["entries"], Value::Array(vec![]));
+        let response = call(r#"{"version": 1, "method": "foods"}"#);
+        assert_eq!(response["result"][0]["name"], "Greek yogurt");
+
+        let response = call(r#"{"version": 1, "method": "log_meal", "params": {"food_id": 99, "quantity": "1x"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_NOT_FOUND);
+        assert_eq!(response["error"]["message"], "food 99 not found");
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "log_meal",
+            "params": { "food_id": food_id, "quantity": "a spoonful" },
+        });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let response = call(r#"{"version": 1, "method": "set_nutrition_target", "params": {"nutrient": "joy", "amount": 1}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+    }
+
+    #[test]
+    fn test_pantry_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let response = call(r#"{"version": 1, "method": "add_food", "params": {"name": "Banana", "serving_g": 120}}"#);
+        let banana = response["result"]["id"].as_i64().unwrap();
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "add_pantry_item",
+            "params": { "name": "Bananas", "amount": "5", "food_id": banana },
+        });
+        let item = call(&request.to_string())["result"]["id"].as_i64().unwrap();
+        call(r#"{"version": 1, "method": "add_pantry_item", "params": {"name": "Flour", "amount": "1kg"}}"#);
+
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "log_meal",
+            "params": { "food_id": banana, "quantity": "2x", "from_pantry": true },
+        });
+        let response = call(&request.to_string());
+        assert!(response["result"]["id"].is_i64());
+        let remaining = &response["result"]["pantry"]["used"][0]["remaining"];
+        assert_eq!(remaining, &serde_json::json!({"quantity": 3.0, "unit": "piece"}));
+
+        let response = call(
+            r#"{"version": 1, "method": "add_recipe", "params": {
+                "name": "Banana bread", "servings": 8, "ingredients": ["250g flour", "3 bananas", "2 eggs"]
+            }}"#,
+        );
+        let recipe = response["result"]["id"].as_i64().unwrap();
+        let request = serde_json::json!({ "version": 1, "method": "cook_recipe", "params": { "id": recipe } });
+        let report = &call(&request.to_string())["result"];
+        assert_eq!(report["used"].as_array().unwrap().len(), 2);
+        assert_eq!(report["unmatched"][0]["name"], "eggs");
+        assert_eq!(report["unmatched"][0]["reason"], "not_in_pantry");
+
+        let pantry = &call(r#"{"version": 1, "method": "pantry"}"#)["result"];
+        assert_eq!(pantry[0]["name"], "Bananas");
+        assert_eq!(pantry[0]["quantity"], 0.0);
+        assert_eq!(pantry[1]["quantity"], 0.75);
+
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "set_pantry_amount",
+            "params": { "id": item, "amount": "a bunch" },
+        });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let response = call(r#"{"version": 1, "method": "cook_recipe", "params": {"id": 99}}"#);
+        assert_eq!(response["error"]["message"], "recipe 99 not found");
+        let response = call(r#"{"version": 1, "method": "delete_pantry_item", "params": {"id": 99}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_food_search_and_barcodes() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        call(r#"{"version": 1, "method": "add_food", "params": {"name": "Oat milk", "barcode": "7394376616037"}}"#);
+        call(r#"{"version": 1, "method": "add_food", "params": {"name": "Oatcakes", "barcode": "not a barcode"}}"#);
+
+        let response = call(r#"{"version": 1, "method": "search_foods", "params": {"query": "oat", "limit": 1}}"#);
+        assert_eq!(response["result"].as_array().unwrap().len(), 1);
+        let response = call(r#"{"version": 1, "method": "search_foods", "params": {"query": "oat MILK"}}"#);
+        assert_eq!(response["result"][0]["name"], "Oat milk");
+        assert_eq!(response["result"].as_array().unwrap().len(), 1);
+
+        let response = call(r#"{"version": 1, "method": "lookup_barcode", "params": {"barcode": "7394376616037"}}"#);
+        assert_eq!(response["result"]["name"], "Oat milk");
+        let response = call(r#"{"version": 1, "method": "lookup_barcode", "params": {"barcode": "0000000000017"}}"#);
+        assert_eq!(response["ok"], true);
+        assert!(response["result"].is_null());
+        let response = call(r#"{"version": 1, "method": "lookup_barcode", "params": {}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_workout_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let response = call(r#"{"version": 1, "method": "add_exercise", "params": {"name": "Deadlift"}}"#);
+        let deadlift = response["result"]["id"].as_i64().unwrap();
+        let response = call(r#"{"version": 1, "method": "start_workout", "params": {"title": "Pull day"}}"#);
+        let workout = response["result"]["id"].as_i64().unwrap();
+        assert_eq!(call(r#"{"version": 1, "method": "current_workout"}"#)["result"]["id"], workout);
+
+        for set in [r#""set": "5x140kg""#, r#""reps": 3, "weight_kg": 150"#] {
+            let request = format!(
+                r#"{{"version": 1, "method": "log_set", "params": {{"workout_id": {}, "exercise_id": {}, {}}}}}"#,
+                workout, deadlift, set
+            );
+            let response = call(&request);
+            assert!(response["result"]["id"].as_i64().unwrap() > 0);
+            if set.contains("150") {
+                assert_eq!(response["result"]["records"][0]["kind"], "max_weight");
+                assert_eq!(response["result"]["records"][0]["previous"], 140.0);
+            }
+        }
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "log_set",
+            "params": { "workout_id": workout, "exercise_id": deadlift, "set": "heavy" },
+        });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "log_set",
+            "params": { "workout_id": 99, "exercise_id": deadlift, "set": "5x100kg" },
+        });
+        assert_eq!(call(&request.to_string())["error"]["message"], "workout 99 not found");
+
+        let request = serde_json::json!({ "version": 1, "method": "finish_workout", "params": { "id": workout } });
+        assert!(call(&request.to_string())["ok"].as_bool().unwrap());
+        assert!(call(r#"{"version": 1, "method": "current_workout"}"#)["result"].is_null());
+
+        let request = serde_json::json!({ "version": 1, "method": "get_workout", "params": { "id": workout } });
+        let response = call(&request.to_string());
+        assert_eq!(response["result"]["sets"][1]["exercise"], "Deadlift");
+        assert!(response["result"]["ended_at"].is_string());
+        let response = call(r#"{"version": 1, "method": "get_workout", "params": {"id": 99}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_NOT_FOUND);
+
+        let response = call(r#"{"version": 1, "method": "personal_records", "params": {}}"#);
+        assert_eq!(response["result"].as_array().unwrap().len(), 3);
+
+        let response = call(r#"{"version": 1, "method": "weekly_volume", "params": {"weeks": 2}}"#);
+        let weeks = &response["result"];
+        assert_eq!(weeks[1]["workouts"], 1);
+        assert_eq!(weeks[1]["volume_kg"], 1150.0);
+        assert_eq!(weeks[1]["by_exercise"]["Deadlift"]["sets"], 2);
+        let response = call(r#"{"version": 1, "method": "weekly_volume", "params": {"weeks": 0}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+
+        let response = call(r#"{"version": 1, "method": "weekly_volume", "params": {}}"#);
+        assert_eq!(response["result"].as_array().unwrap().len(), 4);
+        let response = call(r#"{"version": 1, "method": "weekly_volume", "params": {"weeks": 4000000000}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let request = serde_json::json!({ "version": 1, "method": "delete_workout", "params": { "id": workout } });
+        assert!(call(&request.to_string())["ok"].as_bool().unwrap());
+        let response = call(r#"{"version": 1, "method": "personal_records", "params": {}}"#);
+        assert_eq!(response["result"], Value::Array(vec![]));
+    }
+
+    #[test]
+    fn test_plan_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "add_plan",
+            "params": { "name": "Every day", "tag": "gym", "sessions": ["daily 6am Mobility"] },
+        });
+        let plan = call(&request.to_string())["result"]["id"].as_i64().unwrap();
+        let request =
+            serde_json::json!({ "version": 1, "method": "schedule_plan", "params": { "id": plan, "weeks": 1 } });
+        let task_ids = call(&request.to_string())["result"].as_array().unwrap().clone();
+        assert_eq!(task_ids.len(), 7);
+        let tasks = call(r#"{"version": 1, "method": "get_by_tag", "params": {"tag": "gym"}}"#);
+        assert_eq!(tasks["result"][0]["title"], "Mobility");
+
+        let response = call(r#"{"version": 1, "method": "start_workout", "params": {}}"#);
+        let workout = response["result"]["id"].as_i64().unwrap();
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "complete_planned_task",
+            "params": { "task_id": task_ids[0] },
+        });
+        let planned = call(&request.to_string());
+        assert_eq!(planned["result"]["workout_id"], workout);
+        assert_eq!(planned["result"]["scheduled_for"], Local::now().date_naive().to_string());
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "link_workout",
+            "params": { "task_id": task_ids[1], "workout_id": workout },
+        });
+        assert!(call(&request.to_string())["ok"].as_bool().unwrap());
+
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "add_plan",
+            "params": { "name": "Broken", "sessions": ["someday Run"] },
+        });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let response = call(r#"{"version": 1, "method": "schedule_plan", "params": {"id": 99}}"#);
+        assert_eq!(response["error"]["message"], "training plan 99 not found");
+        let request =
+            serde_json::json!({ "version": 1, "method": "schedule_plan", "params": { "id": plan, "weeks": 53 } });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+
+        let request = serde_json::json!({ "version": 1, "method": "delete_plan", "params": { "id": plan } });
+        assert!(call(&request.to_string())["ok"].as_bool().unwrap());
+        assert_eq!(call(r#"{"version": 1, "method": "plans"}"#)["result"], Value::Array(vec![]));
+        let tasks = call(r#"{"version": 1, "method": "get_by_tag", "params": {"tag": "gym"}}"#);
+        assert_eq!(tasks["result"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_journal_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        for (recorded_at, mood) in [("3 days ago", 2), ("2 days ago", 3), ("yesterday", 5)] {
+            let request = serde_json::json!({
+                "version": 1,
+                "method": "add_journal_entry",
+                "params": { "recorded_at": recorded_at, "mood": mood, "sleep_hours": 7.5 },
+            });
+            assert!(call(&request.to_string())["result"]["id"].as_i64().is_some());
+        }
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "add_journal_entry",
+            "params": { "mood": 3, "energy": 9, "notes": "Tired" },
+        });
+        let response = call(&request.to_string());
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        assert_eq!(response["error"]["message"], "energy must be from 1 to 5, not 9");
+        let empty = call(r#"{"version": 1, "method": "add_journal_entry", "params": {"notes": " "}}"#);
+        assert_eq!(empty["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+
+        let entries = call(r#"{"version": 1, "method": "journal_entries", "params": {"from": "7 days ago"}}"#);
+        assert_eq!(entries["result"].as_array().unwrap().len(), 3);
+        let days = call(r#"{"version": 1, "method": "journal_days", "params": {"days": 4}}"#);
+        assert_eq!(days["result"][2]["mood"], 5.0);
+        assert!(days["result"][3]["mood"].is_null());
+        let response = call(r#"{"version": 1, "method": "journal_days", "params": {"days": 4000000000}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let response = call(r#"{"version": 1, "method": "journal_correlations", "params": {}}"#);
+        assert_eq!(response["result"].as_array().unwrap().len(), 12);
+        assert_eq!(response["result"][0]["wellbeing"], "mood");
+        assert_eq!(response["result"][0]["activity"], "tasks_completed");
+        assert_eq!(response["result"][0]["days"], 3);
+
+        let response = call(r#"{"version": 1, "method": "journal_correlations", "params": {"days": 7}}"#);
+        assert!(response["result"][0]["coefficient"].is_null(), "no task was completed");
+        let response = call(r#"{"version": 1, "method": "journal_correlations", "params": {"days": 0}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+
+        let id = entries["result"][0]["id"].as_i64().unwrap();
+        let request = serde_json::json!({ "version": 1, "method": "delete_journal_entry", "params": { "id": id } });
+        assert!(call(&request.to_string())["ok"].as_bool().unwrap());
+        let response = call(&request.to_string());
+        assert_eq!(response["error"]["message"], "journal entry 1 not found");
+    }
+
+    #[test]
+    fn test_review_call() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let input = CString::new("Water plants #home").unwrap();
+        let id = unsafe { add_task_quick(input.as_ptr()) };
+        assert_eq!(mark_task_done(id, 1), 0);
+
+        let review = call(r#"{"version": 1, "method": "review", "params": {"from": "6 days ago"}}"#);
+        assert_eq!(review["result"]["completed"], 1);
+        assert_eq!(review["result"]["by_tag"][0]["tag"], "home");
+        let markdown = call(r#"{"version": 1, "method": "review", "params": {"format": "markdown"}}"#);
+        assert!(markdown["result"].as_str().unwrap().contains("| #home | 1 |"));
+        let response = call(r#"{"version": 1, "method": "review", "params": {"format": "pdf"}}"#);
+        assert_eq!(response["error"]["message"], "unknown review format 'pdf'");
+        let response = call(r#"{"version": 1, "method": "review", "params": {"from": "today", "to": "yesterday"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+    }
+
+    #[test]
+    fn test_stats_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let input = CString::new("Water plants #home").unwrap();
+        let id = unsafe { add_task_quick(input.as_ptr()) };
+        assert_eq!(mark_task_done(id, 1), 0);
+
+        let stats = call(r#"{"version": 1, "method": "stats", "params": {}}"#);
+        assert_eq!(stats["result"]["completions"].as_array().unwrap().len(), 28);
+        assert_eq!(stats["result"]["completions"][27]["completed"], 1);
+        assert_eq!(stats["result"]["group_by"], "day");
+        assert_eq!(stats["result"]["tags"][0]["tag"], "home");
+        assert!(stats["result"]["on_time"]["rate"].is_null());
+        let request = r#"{"version": 1, "method": "stats", "params": {"from": "2 weeks ago", "group_by": "week"}}"#;
+        assert_eq!(call(request)["result"]["completed"], 1);
+        let response = call(r#"{"version": 1, "method": "stats", "params": {"group_by": "month"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_REQUEST);
+
+        let stats = call(r#"{"version": 1, "method": "stats", "params": {"from": "6 days ago", "group_by": "week"}}"#);
+        assert_eq!(stats["result"]["group_by"], "week");
+        assert!(stats["result"]["average_latency_hours"].as_f64().unwrap() < 1.0);
+        let response = call(r#"{"version": 1, "method": "stats", "params": {"from": "today", "to": "yesterday"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+    }
+
+    #[test]
+    fn test_move_task_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let ids: Vec<i64> = ["Wash", "Dry", "Fold"]
+            .iter()
+            .map(|title| {
+                let input = CString::new(*title).unwrap();
+                unsafe { add_task_quick(input.as_ptr()) }
+            })
+            .collect();
+        let titles = || -> Vec<String> {
+            let tasks = call(r#"{"version": 1, "method": "all"}"#);
+            tasks["result"].as_array().unwrap().iter().map(|task| task["title"].as_str().unwrap().to_string()).collect()
+        };
+        assert_eq!(titles(), ["Fold", "Dry", "Wash"]);
+
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "move_task",
+            "params": { "id": ids[0], "after": ids[2] },
+        });
+        assert_eq!(call(&request.to_string())["ok"], true);
+        assert_eq!(titles(), ["Wash", "Fold", "Dry"]);
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "move_task",
+            "params": { "id": ids[2], "before": ids[1] },
+        });
+        assert!(call(&request.to_string())["result"].is_null());
+        assert_eq!(titles(), ["Wash", "Dry", "Fold"]);
+
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "move_task",
+            "params": { "id": ids[0], "before": 99 },
+        });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_NOT_FOUND);
+        let request = serde_json::json!({ "version": 1, "method": "move_task", "params": { "id": ids[0] } });
+        assert_eq!(call(&request.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "move_task",
+            "params": { "id": ids[0], "before": ids[2], "after": ids[1] },
+        });
+        let response = call(&request.to_string());
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        assert_eq!(response["error"]["message"], format!("task {} does not come before task {}", ids[2], ids[1]));
+        let rebalanced = call(r#"{"version": 1, "method": "rebalance_positions"}"#);
+        assert_eq!(rebalanced["result"]["changed"], 3);
+        assert_eq!(titles(), ["Wash", "Dry", "Fold"]);
+    }
+
+    #[test]
+    fn test_list_calls() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let request = r##"{"version": 1, "method": "add_list", "params": {"name": "Groceries", "colour": "#2E8B57"}}"##;
+        let groceries = call(request)["result"]["id"].as_i64().unwrap();
+        let response = call(r#"{"version": 1, "method": "add_list", "params": {"name": "groceries"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+        assert_eq!(response["error"]["message"], "there is already a list called groceries");
+        let response = call(r#"{"version": 1, "method": "add_list", "params": {"name": "Home", "colour": "red"}}"#);
+        assert_eq!(response["error"]["code"], FREELIST_ERR_INVALID_INPUT);
+
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "add",
+            "params": { "title": "Milk", "list_id": groceries },
+        });
+        let milk = call(&request.to_string())["result"]["id"].as_i64().unwrap();
+        let input = CString::new("Paint fence #home").unwrap();
+        let fence = unsafe { add_task_quick(input.as_ptr()) };
+        let request = serde_json::json!({ "version": 1, "method": "get_by_list", "params": { "list_id": groceries } });
+        assert_eq!(call(&request.to_string())["result"][0]["id"], milk);
+
+        let migration = call(r#"{"version": 1, "method": "lists_from_tags"}"#);
+        assert_eq!(migration["result"]["lists_created"], 1);
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "set_task_list",
+            "params": { "task_id": fence, "list_id": 99 },
+        });
+        assert_eq!(call(&request.to_string())["error"]["message"], "list 99 not found");
+
+        let request = format!(
+            r#"{{"version": 1, "method": "update_list", "params": {{"id": {}, "colour": "", "archived": true}}}}"#,
+            groceries
+        );
+        let updated = call(&request);
+        assert!(updated["result"]["colour"].is_null());
+        let lists = call(r#"{"version": 1, "method": "lists", "params": {}}"#);
+        assert_eq!(lists["result"].as_array().unwrap().len(), 1);
+        assert_eq!(lists["result"][0]["name"], "home");
+        let lists = call(r#"{"version": 1, "method": "lists", "params": {"include_archived": true}}"#);
+        assert_eq!(lists["result"].as_array().unwrap().len(), 2);
+
+        let delete = format!(r#"{{"version": 1, "method": "delete_list", "params": {{"id": {}}}}}"#, groceries);
+        assert!(call(&delete)["result"].is_null());
+        assert_eq!(call(&delete)["error"]["code"], FREELIST_ERR_NOT_FOUND);
+        let request = serde_json::json!({ "version": 1, "method": "get_by_id", "params": { "id": milk } });
+        assert!(call(&request.to_string())["result"]["list_id"].is_null());
+    }
+
+    #[test]
+    fn test_archive_export_and_import() {
+        let _lock = test_lock();
+        assert_eq!(init_freelist_memory(), 0);
+        let input = CString::new("Pack bags #travel").unwrap();
+        assert!(unsafe { add_task_quick(input.as_ptr()) } > 0);
+
+        let exported = call(r#"{"version": 1, "method": "export_archive"}"#);
+        assert_eq!(exported["result"]["tasks"][0]["title"], "Pack bags");
+        let request = serde_json::json!({
+            "version": 1,
+            "method": "import_archive",
+            "params": { "archive": exported["result"] },
+        });
+
+        assert_eq!(init_freelist_memory(), 0);
+        for expected_added in [1, 0] {
+            let imported = call(&request.to_string());
+            assert_eq!(imported["result"]["tasks_added"], expected_added);
+        }
+
+        let newer = serde_json::json!({
+            "version": 1,
+            "method": "import_archive",
+            "params": { "archive": { "version": 2, "exported_at": "2026-10-18T00:00:00Z", "tasks": [] } },
+        });
+        assert_eq!(call(&newer.to_string())["error"]["code"], FREELIST_ERR_INVALID_INPUT);
     }
 }
@@ -0,0 +1,840 @@
+// core/src/fitness/mod.rs
+// OpenFitness: exercises, workouts made of sets, personal records and weekly training volume.
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::database::Database;
+use crate::dates;
+use crate::task_list::TaskList;
+
+mod plan;
+
+pub use plan::{PlanSession, PlannedTask, TrainingPlan, MAX_SCHEDULE_WEEKS, PLAN_TAG};
+
+const POUND_KG: f64 = 0.45359237;
+const MILE_M: f64 = 1609.344;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FitnessError {
+    UnknownKind(String),
+    InvalidSet(String),
+    InvalidSession(String),
+}
+
+impl std::fmt::Display for FitnessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FitnessError::UnknownKind(kind) => write!(f, "unknown exercise kind '{}'. Use strength or cardio", kind),
+            FitnessError::InvalidSet(set) => write!(
+                f,
+                "cannot understand set '{}'. Use reps and weight (5x100kg, 8 @ 135lb), reps (12 reps), \
+                 distance (5km) or time (25:30, 90s)",
+                set
+            ),
+            FitnessError::InvalidSession(session) => write!(
+                f,
+                "cannot understand session '{}'. Use days, an optional time and a title, \
+                 e.g. \"mon,wed,fri 7am Strength\"",
+                session
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FitnessError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExerciseKind {
+    /// Sets of reps, with or without weight
+    Strength,
+    /// Distance and time
+    Cardio,
+}
+
+impl ExerciseKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExerciseKind::Strength => "strength",
+            ExerciseKind::Cardio => "cardio",
+        }
+    }
+}
+
+impl std::str::FromStr for ExerciseKind {
+    type Err = FitnessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "strength" | "weights" | "lifting" => Ok(ExerciseKind::Strength),
+            "cardio" | "endurance" => Ok(ExerciseKind::Cardio),
+            _ => Err(FitnessError::UnknownKind(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exercise {
+    pub id: Option<i64>,
+    pub name: String,
+    pub kind: ExerciseKind,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Exercise {
+    pub fn new(name: &str, kind: ExerciseKind) -> Self {
+        Exercise { id: None, name: name.trim().to_string(), kind, created_at: Utc::now() }
+    }
+}
+
+/// What was done in one set. Anything not measured is None.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SetMeasures {
+    pub reps: Option<u32>,
+    pub weight_kg: Option<f64>,
+    pub distance_m: Option<f64>,
+    pub duration_s: Option<u32>,
+}
+
+impl SetMeasures {
+    pub fn is_empty(&self) -> bool {
+        *self == SetMeasures::default()
+    }
+
+    /// Reps times weight; 0 for sets without both.
+    pub fn volume_kg(&self) -> f64 {
+        match (self.reps, self.weight_kg) {
+            (Some(reps), Some(weight)) => reps as f64 * weight,
+            _ => 0.0,
+        }
+    }
+
+    /// The Epley estimate of the most that could be lifted once; None above
+    /// 12 reps, where it stops being useful.
+    pub fn estimated_one_rep_max(&self) -> Option<f64> {
+        match (self.reps?, self.weight_kg?) {
+            (0, _) => None,
+            (1, weight) => Some(weight),
+            (reps, weight) if reps <= 12 => Some(weight * (1.0 + reps as f64 / 30.0)),
+            _ => None,
+        }
+    }
+
+    /// Seconds per kilometre.
+    pub fn pace_s_per_km(&self) -> Option<f64> {
+        let distance = self.distance_m.filter(|metres| *metres > 0.0)?;
+        let duration = self.duration_s.filter(|seconds| *seconds > 0)?;
+        Some(duration as f64 / (distance / 1000.0))
+    }
+}
+
+impl std::str::FromStr for SetMeasures {
+    type Err = FitnessError;
+
+    /// Accepts reps and weight (`5x100kg`, `100kg x 5`, `8 @ 135lb`), reps
+    /// alone (`12`, `12 reps`), distance (`5km`, `400m`, `3mi`) and time
+    /// (`25:30`, `1:02:00`, `90s`, `20min`), in any combination: `5km in 25:30`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || FitnessError::InvalidSet(s.to_string());
+        let input = s.trim().to_lowercase().replace('×', "x").replace('@', " x ");
+        let mut measures = SetMeasures::default();
+        let mut tokens: Vec<String> = Vec::new();
+        for token in input.split_whitespace().filter(|token| !matches!(*token, "x" | "in" | "for")) {
+            match tokens.last_mut() {
+                // "100 kg" is one measure
+                Some(last)
+                    if token.starts_with(|c: char| c.is_alphabetic()) && last.ends_with(|c: char| c.is_ascii_digit()) =>
+                {
+                    last.push_str(token)
+                }
+                _ => tokens.extend(split_reps(token).unwrap_or_else(|| vec![token.to_string()])),
+            }
+        }
+
+        for token in &tokens {
+            let digits = token.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(token.len());
+            let (number, unit) = token.split_at(digits);
+            if unit.starts_with(':') {
+                measures.duration_s = Some(clock_seconds(token).ok_or_else(error)?);
+                continue;
+            }
+            let value: f64 = number.parse().map_err(|_| error())?;
+            if !value.is_finite() || value < 0.0 {
+                return Err(error());
+            }
+            match unit {
+                "" | "reps" | "rep" | "r" => {
+                    if value.fract() != 0.0 || measures.reps.is_some() {
+                        return Err(error());
+                    }
+                    measures.reps = Some(value as u32);
+                }
+                "kg" | "kgs" => measures.weight_kg = Some(value),
+                "lb" | "lbs" => measures.weight_kg = Some(value * POUND_KG),
+                "km" => measures.distance_m = Some(value * 1000.0),
+                "m" => measures.distance_m = Some(value),
+                "mi" | "mile" | "miles" => measures.distance_m = Some(value * MILE_M),
+                "s" | "sec" | "secs" => measures.duration_s = Some(value.round() as u32),
+                "min" | "mins" => measures.duration_s = Some((value * 60.0).round() as u32),
+                "h" | "hr" | "hrs" => measures.duration_s = Some((value * 3600.0).round() as u32),
+                _ => return Err(error()),
+            }
+        }
+        if measures.is_empty() {
+            return Err(error());
+        }
+        Ok(measures)
+    }
+}
+
+// "5x100kg" -> ["5", "100kg"]; None for tokens without an x between two parts
+fn split_reps(token: &str) -> Option<Vec<String>> {
+    let (left, right) = token.split_once('x')?;
+    if left.is_empty() || right.is_empty() {
+        return None;
+    }
+    Some(vec![left.to_string(), right.to_string()])
+}
+
+// "25:30" or "1:02:00" in seconds
+fn clock_seconds(token: &str) -> Option<u32> {
+    let parts: Vec<u32> = token.split(':').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [minutes, seconds] if *seconds < 60 => Some(minutes * 60 + seconds),
+        [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => Some(hours * 3600 + minutes * 60 + seconds),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkoutSet {
+    pub id: Option<i64>,
+    pub workout_id: i64,
+    pub exercise_id: i64,
+    #[serde(flatten)]
+    pub measures: SetMeasures,
+    pub performed_at: DateTime<Utc>,
+}
+
+impl WorkoutSet {
+    pub fn new(workout_id: i64, exercise_id: i64, measures: SetMeasures, performed_at: DateTime<Utc>) -> Self {
+        WorkoutSet { id: None, workout_id, exercise_id, measures, performed_at }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workout {
+    pub id: Option<i64>,
+    pub title: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// None while the workout is going on
+    pub ended_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    /// In the order they were done, with exercise names for display
+    pub sets: Vec<LoggedSet>,
+}
+
+impl Workout {
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Workout { id: None, title: None, started_at, ended_at: None, notes: None, sets: Vec::new() }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.trim().to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedSet {
+    #[serde(flatten)]
+    pub set: WorkoutSet,
+    pub exercise: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    MaxWeight,
+    EstimatedOneRepMax,
+    /// Reps in one set without added weight
+    MaxReps,
+    /// Reps times weight in one set
+    MaxVolume,
+    LongestDistance,
+    /// For timed sets without a distance, such as planks
+    LongestDuration,
+    /// Seconds per kilometre; lower is better
+    FastestPace,
+}
+
+impl RecordKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecordKind::MaxWeight => "max weight",
+            RecordKind::EstimatedOneRepMax => "estimated 1RM",
+            RecordKind::MaxReps => "max reps",
+            RecordKind::MaxVolume => "max set volume",
+            RecordKind::LongestDistance => "longest distance",
+            RecordKind::LongestDuration => "longest time",
+            RecordKind::FastestPace => "fastest pace",
+        }
+    }
+
+    // The values a set scores for each kind of record
+    fn scores(measures: &SetMeasures) -> Vec<(RecordKind, f64)> {
+        let mut scores = Vec::new();
+        if let Some(weight) = measures.weight_kg.filter(|weight| *weight > 0.0) {
+            scores.push((RecordKind::MaxWeight, weight));
+        }
+        if let Some(one_rep_max) = measures.estimated_one_rep_max().filter(|weight| *weight > 0.0) {
+            scores.push((RecordKind::EstimatedOneRepMax, one_rep_max));
+        }
+        if let (Some(reps), None) = (measures.reps, measures.weight_kg) {
+            scores.push((RecordKind::MaxReps, reps as f64));
+        }
+        if measures.volume_kg() > 0.0 {
+            scores.push((RecordKind::MaxVolume, measures.volume_kg()));
+        }
+        if let Some(distance) = measures.distance_m.filter(|metres| *metres > 0.0) {
+            scores.push((RecordKind::LongestDistance, distance));
+        }
+        if let (Some(duration), None) = (measures.duration_s, measures.distance_m) {
+            scores.push((RecordKind::LongestDuration, duration as f64));
+        }
+        if let Some(pace) = measures.pace_s_per_km() {
+            scores.push((RecordKind::FastestPace, pace));
+        }
+        scores
+    }
+
+    fn beats(self, value: f64, best: f64) -> bool {
+        match self {
+            RecordKind::FastestPace => value < best,
+            _ => value > best,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersonalRecord {
+    pub exercise_id: i64,
+    pub exercise: String,
+    pub kind: RecordKind,
+    pub value: f64,
+    /// The record this one beat; None for the first set of its kind
+    pub previous: Option<f64>,
+    pub set_id: i64,
+    pub achieved_at: DateTime<Utc>,
+}
+
+/// Training done in a stretch of time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Volume {
+    pub sets: u32,
+    pub reps: u32,
+    pub volume_kg: f64,
+    pub distance_m: f64,
+    pub duration_s: u32,
+}
+
+impl Volume {
+    fn add(&mut self, measures: &SetMeasures) {
+        self.sets += 1;
+        self.reps += measures.reps.unwrap_or(0);
+        self.volume_kg += measures.volume_kg();
+        self.distance_m += measures.distance_m.unwrap_or(0.0);
+        self.duration_s += measures.duration_s.unwrap_or(0);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyVolume {
+    /// The Monday the week starts on
+    pub week_start: NaiveDate,
+    pub workouts: u32,
+    #[serde(flatten)]
+    pub total: Volume,
+    pub by_exercise: BTreeMap<String, Volume>,
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    plan::init_schema(db)?;
+    db.connection().execute_batch(
+        "CREATE TABLE IF NOT EXISTS exercises (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_exercises_name ON exercises(name COLLATE NOCASE);
+
+        CREATE TABLE IF NOT EXISTS workouts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            notes TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_workouts_started_at ON workouts(started_at);
+
+        CREATE TABLE IF NOT EXISTS workout_sets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            workout_id INTEGER NOT NULL,
+            exercise_id INTEGER NOT NULL,
+            reps INTEGER,
+            weight_kg REAL,
+            distance_m REAL,
+            duration_s INTEGER,
+            performed_at TEXT NOT NULL,
+            FOREIGN KEY(workout_id) REFERENCES workouts(id),
+            FOREIGN KEY(exercise_id) REFERENCES exercises(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_workout_sets_workout_id ON workout_sets(workout_id);
+        CREATE INDEX IF NOT EXISTS idx_workout_sets_exercise_id ON workout_sets(exercise_id);",
+    )
+}
+
+fn parse_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn exercise_from_row(row: &Row) -> rusqlite::Result<Exercise> {
+    Ok(Exercise {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        // Kinds added by a newer version count as strength
+        kind: row.get::<_, String>(2)?.parse().unwrap_or(ExerciseKind::Strength),
+        created_at: parse_time(&row.get::<_, String>(3)?),
+    })
+}
+
+// Column order read by `set_from_row`
+const SET_COLUMNS: &str = "workout_sets.id, workout_sets.workout_id, workout_sets.exercise_id,
+    workout_sets.reps, workout_sets.weight_kg, workout_sets.distance_m, workout_sets.duration_s,
+    workout_sets.performed_at, exercises.name";
+
+fn set_from_row(row: &Row) -> rusqlite::Result<LoggedSet> {
+    Ok(LoggedSet {
+        set: WorkoutSet {
+            id: Some(row.get(0)?),
+            workout_id: row.get(1)?,
+            exercise_id: row.get(2)?,
+            measures: SetMeasures {
+                reps: row.get(3)?,
+                weight_kg: row.get(4)?,
+                distance_m: row.get(5)?,
+                duration_s: row.get(6)?,
+            },
+            performed_at: parse_time(&row.get::<_, String>(7)?),
+        },
+        exercise: row.get(8)?,
+    })
+}
+
+/// Every time a set beat the best before it, oldest first. `sets` must be in
+/// the order they were done.
+fn record_history(sets: &[LoggedSet]) -> Vec<PersonalRecord> {
+    let mut best: BTreeMap<(i64, RecordKind), f64> = BTreeMap::new();
+    let mut history = Vec::new();
+    for logged in sets {
+        for (kind, value) in RecordKind::scores(&logged.set.measures) {
+            let previous = best.get(&(logged.set.exercise_id, kind)).copied();
+            if previous.is_some_and(|previous| !kind.beats(value, previous)) {
+                continue;
+            }
+            best.insert((logged.set.exercise_id, kind), value);
+            history.push(PersonalRecord {
+                exercise_id: logged.set.exercise_id,
+                exercise: logged.exercise.clone(),
+                kind,
+                value,
+                previous,
+                set_id: logged.set.id.unwrap_or_default(),
+                achieved_at: logged.set.performed_at,
+            });
+        }
+    }
+    history
+}
+
+impl TaskList {
+    pub fn add_exercise(&mut self, exercise: &Exercise) -> Result<i64, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        conn.execute(
+            "INSERT INTO exercises (name, kind, created_at) VALUES (?1, ?2, ?3)",
+            params![exercise.name, exercise.kind.as_str(), exercise.created_at.to_rfc3339()],
+        )?;
+        let id = conn.last_insert_rowid();
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_exercise(&self, id: i64) -> Result<Option<Exercise>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare("SELECT id, name, kind, created_at FROM exercises WHERE id = ?1")?;
+        let mut rows = stmt.query_map([id], exercise_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// The exercise with this name, ignoring case.
+    pub fn find_exercise(&self, name: &str) -> Result<Option<Exercise>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt =
+            conn.prepare("SELECT id, name, kind, created_at FROM exercises WHERE name = ?1 COLLATE NOCASE")?;
+        let mut rows = stmt.query_map([name.trim()], exercise_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Every exercise, by name.
+    pub fn exercises(&self) -> Result<Vec<Exercise>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare("SELECT id, name, kind, created_at FROM exercises ORDER BY name COLLATE NOCASE")?;
+        let exercises = stmt.query_map([], exercise_from_row)?;
+        Ok(exercises.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Adds a workout; its `sets` are ignored, use `log_set`.
+    pub fn start_workout(&mut self, workout: &Workout) -> Result<i64, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        conn.execute(
+            "INSERT INTO workouts (title, started_at, ended_at, notes) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                workout.title,
+                workout.started_at.to_rfc3339(),
+                workout.ended_at.map(|at| at.to_rfc3339()),
+                workout.notes
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    /// Marks a workout as over. Returns whether it exists.
+    pub fn finish_workout(&mut self, id: i64, ended_at: DateTime<Utc>) -> Result<bool, Box<dyn std::error::Error>> {
+        let updated = self
+            .database()
+            .connection()
+            .execute("UPDATE workouts SET ended_at = ?2 WHERE id = ?1", params![id, ended_at.to_rfc3339()])?;
+        self.poll_changes()?;
+        Ok(updated > 0)
+    }
+
+    /// The most recently started workout that has not been finished.
+    pub fn current_workout(&self) -> Result<Option<Workout>, Box<dyn std::error::Error>> {
+        Ok(self.query_workouts("WHERE ended_at IS NULL ORDER BY started_at DESC, id DESC LIMIT 1", [])?.pop())
+    }
+
+    pub fn get_workout(&self, id: i64) -> Result<Option<Workout>, Box<dyn std::error::Error>> {
+        Ok(self.query_workouts("WHERE id = ?1", [id])?.pop())
+    }
+
+    /// Workouts started from `start` up to but not including `end`, oldest first.
+    pub fn workouts_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Workout>, Box<dyn std::error::Error>> {
+        self.query_workouts(
+            "WHERE started_at >= ?1 AND started_at < ?2 ORDER BY started_at, id",
+            params![start.to_rfc3339(), end.to_rfc3339()],
+        )
+    }
+
+    /// Deletes a workout and its sets. Returns whether it existed.
+    pub fn delete_workout(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().with_transaction(|db| {
+            db.connection().execute("DELETE FROM workout_sets WHERE workout_id = ?1", [id])?;
+            db.connection().execute("UPDATE plan_tasks SET workout_id = NULL WHERE workout_id = ?1", [id])?;
+            db.connection().execute("DELETE FROM workouts WHERE id = ?1", [id])
+        })?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    fn query_workouts(
+        &self,
+        filter: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Workout>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt =
+            conn.prepare(&format!("SELECT id, title, started_at, ended_at, notes FROM workouts {}", filter))?;
+        let mut workouts = stmt
+            .query_map(params, |row| {
+                Ok(Workout {
+                    id: Some(row.get(0)?),
+                    title: row.get(1)?,
+                    started_at: parse_time(&row.get::<_, String>(2)?),
+                    ended_at: row.get::<_, Option<String>>(3)?.map(|at| parse_time(&at)),
+                    notes: row.get(4)?,
+                    sets: Vec::new(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM workout_sets JOIN exercises ON exercises.id = workout_sets.exercise_id
+             WHERE workout_sets.workout_id = ?1 ORDER BY workout_sets.performed_at, workout_sets.id",
+            SET_COLUMNS
+        ))?;
+        for workout in &mut workouts {
+            let sets = stmt.query_map([workout.id], set_from_row)?;
+            workout.sets = sets.collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(workouts)
+    }
+
+    /// Adds a set to a workout. Returns its id and the personal records it beat.
+    pub fn log_set(&mut self, set: &WorkoutSet) -> Result<(i64, Vec<PersonalRecord>), Box<dyn std::error::Error>> {
+        if set.measures.is_empty() {
+            return Err("a set needs reps, weight, distance or time".into());
+        }
+        if self.get_workout(set.workout_id)?.is_none() {
+            return Err(format!("workout {} not found", set.workout_id).into());
+        }
+        if self.get_exercise(set.exercise_id)?.is_none() {
+            return Err(format!("exercise {} not found", set.exercise_id).into());
+        }
+
+        let conn = self.database().connection();
+        conn.execute(
+            "INSERT INTO workout_sets (workout_id, exercise_id, reps, weight_kg, distance_m, duration_s, performed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                set.workout_id,
+                set.exercise_id,
+                set.measures.reps,
+                set.measures.weight_kg,
+                set.measures.distance_m,
+                set.measures.duration_s,
+                set.performed_at.to_rfc3339()
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+        self.poll_changes()?;
+
+        let records = record_history(&self.exercise_sets(Some(set.exercise_id))?)
+            .into_iter()
+            .filter(|record| record.set_id == id && record.previous.is_some())
+            .collect();
+        Ok((id, records))
+    }
+
+    pub fn delete_set(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().connection().execute("DELETE FROM workout_sets WHERE id = ?1", [id])?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    // Sets of one exercise, or of all, in the order they were done
+    fn exercise_sets(&self, exercise_id: Option<i64>) -> Result<Vec<LoggedSet>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM workout_sets JOIN exercises ON exercises.id = workout_sets.exercise_id
+             WHERE ?1 IS NULL OR workout_sets.exercise_id = ?1
+             ORDER BY workout_sets.performed_at, workout_sets.id",
+            SET_COLUMNS
+        ))?;
+        let sets = stmt.query_map([exercise_id], set_from_row)?;
+        Ok(sets.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// The current best of each kind for one exercise, or for every exercise,
+    /// ordered by exercise name then kind.
+    pub fn personal_records(
+        &self,
+        exercise_id: Option<i64>,
+    ) -> Result<Vec<PersonalRecord>, Box<dyn std::error::Error>> {
+        let mut best: BTreeMap<(String, i64, RecordKind), PersonalRecord> = BTreeMap::new();
+        for record in record_history(&self.exercise_sets(exercise_id)?) {
+            best.insert((record.exercise.to_lowercase(), record.exercise_id, record.kind), record);
+        }
+        Ok(best.into_values().collect())
+    }
+
+    /// Training volume for each week, Monday to Sunday in `tz`, from the week
+    /// containing `first` to the week containing `last`. Sets count towards the
+    /// week their workout started in; weeks without training are zero.
+    pub fn weekly_volume<Tz: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Tz,
+    ) -> Result<Vec<WeeklyVolume>, Box<dyn std::error::Error>> {
+        let monday = |date: NaiveDate| date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let (first, last) = (monday(first), monday(last));
+        let start = dates::start_of_day(first, tz).ok_or_else(|| format!("{} is out of range", first))?;
+        let end = dates::start_of_day(last + Duration::days(7), tz).ok_or_else(|| format!("{} is out of range", last))?;
+
+        let mut weeks: BTreeMap<NaiveDate, WeeklyVolume> = BTreeMap::new();
+        let mut week = first;
+        while week <= last {
+            let by_exercise = BTreeMap::new();
+            weeks.insert(week, WeeklyVolume { week_start: week, workouts: 0, total: Volume::default(), by_exercise });
+            week += Duration::days(7);
+        }
+        for workout in self.workouts_between(start, end)? {
+            let Some(week) = weeks.get_mut(&monday(workout.started_at.with_timezone(tz).date_naive())) else {
+                continue;
+            };
+            week.workouts += 1;
+            for logged in &workout.sets {
+                week.total.add(&logged.set.measures);
+                week.by_exercise.entry(logged.exercise.clone()).or_default().add(&logged.set.measures);
+            }
+        }
+        Ok(weeks.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    fn set(text: &str) -> SetMeasures {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_sets() {
+        let squat = SetMeasures { reps: Some(5), weight_kg: Some(100.0), ..Default::default() };
+        assert_eq!(set("5x100kg"), squat);
+        assert_eq!(set("100kg x 5"), squat);
+        assert_eq!(set("5 @ 100 kg"), squat);
+        assert_eq!(set("5×100kg"), squat);
+        assert!((set("8 @ 135lb").weight_kg.unwrap() - 61.235).abs() < 1e-3);
+        assert_eq!(set("12 reps"), SetMeasures { reps: Some(12), ..Default::default() });
+        let run = SetMeasures { distance_m: Some(5000.0), duration_s: Some(1530), ..Default::default() };
+        assert_eq!(set("5km in 25:30"), run);
+        assert_eq!(set("1:02:03").duration_s, Some(3723));
+        assert_eq!(set("90s").duration_s, Some(90));
+        assert_eq!(set("20min").duration_s, Some(1200));
+        assert!((set("3mi").distance_m.unwrap() - 4828.032).abs() < 1e-9);
+        for invalid in ["", "heavy", "5x", "2.5 reps", "5 5", "25:75", "-5kg"] {
+            assert!(invalid.parse::<SetMeasures>().is_err(), "{}", invalid);
+        }
+
+        assert_eq!(squat.volume_kg(), 500.0);
+        assert!((squat.estimated_one_rep_max().unwrap() - 116.667).abs() < 1e-3);
+        assert_eq!(set("20 x 60kg").estimated_one_rep_max(), None);
+        assert_eq!(set("5km in 25:00").pace_s_per_km(), Some(300.0));
+    }
+
+    #[test]
+    fn test_records_and_weekly_volume() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let squat = task_list.add_exercise(&Exercise::new("Squat", ExerciseKind::Strength))?;
+        let run = task_list.add_exercise(&Exercise::new("Run", ExerciseKind::Cardio))?;
+        assert!(task_list.add_exercise(&Exercise::new("squat", ExerciseKind::Strength)).is_err());
+        assert_eq!(task_list.find_exercise("SQUAT")?.and_then(|e| e.id), Some(squat));
+
+        // Monday 12 and Thursday 15 October, then Monday 19th
+        let monday = task_list.start_workout(&Workout::new(at("2026-10-12T07:00:00Z")).with_title("Legs"))?;
+        let first = WorkoutSet::new(monday, squat, set("5x100kg"), at("2026-10-12T07:10:00Z"));
+        let (_, records) = task_list.log_set(&first)?;
+        assert!(records.is_empty(), "the first set is a baseline, not a record");
+        let heavier = WorkoutSet::new(monday, squat, set("3x110kg"), at("2026-10-12T07:15:00Z"));
+        let (heavy, records) = task_list.log_set(&heavier)?;
+        let kinds: Vec<RecordKind> = records.iter().map(|record| record.kind).collect();
+        // 3 x 110 beats 5 x 100 on weight and estimated 1RM (121 > 116.7), not volume (330 < 500)
+        assert_eq!(kinds, [RecordKind::MaxWeight, RecordKind::EstimatedOneRepMax]);
+        assert_eq!(records[0].previous, Some(100.0));
+        assert_eq!(records[0].set_id, heavy);
+        assert!(task_list.finish_workout(monday, at("2026-10-12T08:00:00Z"))?);
+        assert!(task_list.current_workout()?.is_none());
+
+        let thursday = task_list.start_workout(&Workout::new(at("2026-10-15T18:00:00Z")))?;
+        task_list.log_set(&WorkoutSet::new(thursday, run, set("5km in 27:30"), at("2026-10-15T18:30:00Z")))?;
+        let faster = WorkoutSet::new(thursday, run, set("1km in 4:30"), at("2026-10-15T18:40:00Z"));
+        let (_, records) = task_list.log_set(&faster)?;
+        assert_eq!(records.iter().map(|record| record.kind).collect::<Vec<_>>(), [RecordKind::FastestPace]);
+        assert_eq!(task_list.current_workout()?.and_then(|workout| workout.id), Some(thursday));
+
+        let next_week = task_list.start_workout(&Workout::new(at("2026-10-19T07:00:00Z")))?;
+        task_list.log_set(&WorkoutSet::new(next_week, squat, set("5x105kg"), at("2026-10-19T07:10:00Z")))?;
+        assert!(task_list.log_set(&WorkoutSet::new(99, squat, set("5x105kg"), Utc::now())).is_err());
+        assert!(task_list.log_set(&WorkoutSet::new(next_week, 99, set("5x105kg"), Utc::now())).is_err());
+
+        let records = task_list.personal_records(Some(squat))?;
+        let best: Vec<(RecordKind, f64)> =
+            records.iter().map(|record| (record.kind, (record.value * 10.0).round() / 10.0)).collect();
+        assert_eq!(
+            best,
+            [(RecordKind::MaxWeight, 110.0), (RecordKind::EstimatedOneRepMax, 122.5), (RecordKind::MaxVolume, 525.0)]
+        );
+        assert_eq!(task_list.personal_records(None)?.len(), 5);
+
+        let weeks = task_list.weekly_volume(
+            NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 27).unwrap(),
+            &Utc,
+        )?;
+        assert_eq!(weeks.iter().map(|week| week.week_start.day()).collect::<Vec<_>>(), [12, 19, 26]);
+        assert_eq!((weeks[0].workouts, weeks[0].total.sets, weeks[0].total.reps), (2, 4, 8));
+        assert_eq!(weeks[0].total.volume_kg, 830.0);
+        assert_eq!(weeks[0].total.distance_m, 6000.0);
+        assert_eq!(weeks[0].by_exercise["Run"].duration_s, 1920);
+        assert_eq!(weeks[1].total.volume_kg, 525.0);
+        assert_eq!(weeks[2].workouts, 0);
+
+        // 07:00 UTC on a Monday is still Sunday in Honolulu, so the workout on the 12th
+        // belongs to the week before and the one on the 19th to this week
+        let honolulu = FixedOffset::west_opt(10 * 3600).unwrap();
+        let weeks = task_list.weekly_volume(
+            NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+            &honolulu,
+        )?;
+        assert_eq!(weeks[0].workouts, 2);
+
+        assert!(task_list.delete_workout(monday)?);
+        assert!(task_list.get_workout(monday)?.is_none());
+        assert_eq!(task_list.personal_records(Some(squat))?[0].value, 105.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_ties_and_units() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let bench = task_list.add_exercise(&Exercise::new("Bench", ExerciseKind::Strength))?;
+        let workout = task_list.start_workout(&Workout::new(at("2026-10-12T07:00:00Z")))?;
+        let mut log = |text: &str, minute: u32| {
+            let performed_at = at(&format!("2026-10-12T07:{:02}:00Z", minute));
+            task_list.log_set(&WorkoutSet::new(workout, bench, set(text), performed_at)).map(|(_, records)| records)
+        };
+
+        log("5x100kg", 10)?;
+        assert!(log("5x100kg", 15)?.is_empty(), "matching a record does not beat it");
+        // 220lb is 99.8kg, just short of 100kg, and 225lb is 102.1kg
+        assert!(log("5 @ 220lb", 20)?.is_empty());
+        let records = log("5 @ 225lb", 25)?;
+        let kinds: Vec<RecordKind> = records.iter().map(|record| record.kind).collect();
+        assert_eq!(kinds, [RecordKind::MaxWeight, RecordKind::EstimatedOneRepMax, RecordKind::MaxVolume]);
+        assert_eq!(records[0].previous, Some(100.0));
+        assert!((records[0].value - 102.058).abs() < 1e-3);
+
+        // A tie in pace is not a record either, whichever unit the distance was in
+        let run = task_list.add_exercise(&Exercise::new("Run", ExerciseKind::Cardio))?;
+        let mut log_run = |text: &str, minute: u32| {
+            let performed_at = at(&format!("2026-10-12T08:{:02}:00Z", minute));
+            task_list.log_set(&WorkoutSet::new(workout, run, set(text), performed_at)).map(|(_, records)| records)
+        };
+        log_run("5km in 25:00", 0)?;
+        assert!(log_run("2km in 10:00", 30)?.is_empty());
+        let records = log_run("1mi in 7:30", 40)?;
+        assert_eq!(records.iter().map(|record| record.kind).collect::<Vec<_>>(), [RecordKind::FastestPace]);
+        assert!((records[0].value - 279.6).abs() < 0.1);
+        Ok(())
+    }
+}
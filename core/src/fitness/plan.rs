@@ -0,0 +1,534 @@
+// core/src/fitness/plan.rs
+// Training plans: weekly sessions scheduled as tasks, linked to the workouts that complete them.
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use crate::database::Database;
+use crate::dates;
+use crate::task::Task;
+use crate::task_list::TaskList;
+use super::{parse_time, FitnessError};
+
+/// Tag given to scheduled sessions unless a plan has its own.
+pub const PLAN_TAG: &str = "training";
+
+/// Furthest ahead a plan is scheduled, so a typo cannot add thousands of tasks.
+pub const MAX_SCHEDULE_WEEKS: u32 = 52;
+
+/// Something to do on some days of every week, e.g. "mon,wed,fri 7am Strength".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanSession {
+    pub id: Option<i64>,
+    pub days: Vec<Weekday>,
+    /// The task is due at this time, or at the end of the day
+    pub time: Option<NaiveTime>,
+    pub title: String,
+}
+
+impl std::str::FromStr for PlanSession {
+    type Err = FitnessError;
+
+    /// Days (`mon`, `tue,thu`, `weekdays`, `weekends`, `daily`), then an
+    /// optional time (`7am`, `18:30`), then the title.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || FitnessError::InvalidSession(s.to_string());
+        let lowered = s.to_lowercase();
+        let mut words = lowered.split_whitespace().zip(s.split_whitespace()).peekable();
+        let mut days = Vec::new();
+        while let Some(more) = words.peek().and_then(|(word, _)| parse_days(word)) {
+            days.extend(more);
+            words.next();
+        }
+        let time = words.peek().and_then(|(word, _)| dates::parse_time(word));
+        if time.is_some() {
+            words.next();
+        }
+        let title = words.map(|(_, word)| word).collect::<Vec<_>>().join(" ");
+        if days.is_empty() || title.is_empty() {
+            return Err(error());
+        }
+        days.sort_by_key(Weekday::num_days_from_monday);
+        days.dedup();
+        Ok(PlanSession { id: None, days, time, title })
+    }
+}
+
+impl std::fmt::Display for PlanSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", days_text(&self.days))?;
+        if let Some(time) = self.time {
+            write!(f, " {}", time.format("%H:%M"))?;
+        }
+        write!(f, " {}", self.title)
+    }
+}
+
+// "mon,wed" or one of the day groups
+fn parse_days(word: &str) -> Option<Vec<Weekday>> {
+    const WEEK: [Weekday; 7] =
+        [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun];
+    match word {
+        "daily" => Some(WEEK.to_vec()),
+        "weekdays" => Some(WEEK[..5].to_vec()),
+        "weekends" => Some(WEEK[5..].to_vec()),
+        _ => word.split(',').filter(|day| !day.is_empty()).map(dates::parse_weekday).collect(),
+    }
+}
+
+// As stored: "mon,wed,fri"
+fn days_text(days: &[Weekday]) -> String {
+    days.iter().map(|day| day.to_string().to_lowercase()).collect::<Vec<_>>().join(",")
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingPlan {
+    pub id: Option<i64>,
+    pub name: String,
+    /// Tag of the tasks the plan schedules
+    pub tag: String,
+    pub starts_on: NaiveDate,
+    /// Nothing is scheduled after this day
+    pub ends_on: Option<NaiveDate>,
+    pub sessions: Vec<PlanSession>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TrainingPlan {
+    pub fn new(name: &str, starts_on: NaiveDate, sessions: Vec<PlanSession>) -> Self {
+        TrainingPlan {
+            id: None,
+            name: name.trim().to_string(),
+            tag: PLAN_TAG.to_string(),
+            starts_on,
+            ends_on: None,
+            sessions,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tag = tag.trim().trim_start_matches('#').to_string();
+        self
+    }
+}
+
+/// One session on one day, and the task and workout for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedTask {
+    pub id: i64,
+    pub plan_id: i64,
+    pub session_id: i64,
+    pub scheduled_for: NaiveDate,
+    /// None once the task has been deleted; the day is then skipped
+    pub task_id: Option<i64>,
+    /// The workout that did this session
+    pub workout_id: Option<i64>,
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    db.connection().execute_batch(
+        "CREATE TABLE IF NOT EXISTS training_plans (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            starts_on TEXT NOT NULL,
+            ends_on TEXT,
+            created_at TEXT NOT NULL
+        );
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_training_plans_name ON training_plans(name COLLATE NOCASE);
+
+        CREATE TABLE IF NOT EXISTS plan_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_id INTEGER NOT NULL,
+            days TEXT NOT NULL,
+            time TEXT,
+            title TEXT NOT NULL,
+            FOREIGN KEY(plan_id) REFERENCES training_plans(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_plan_sessions_plan_id ON plan_sessions(plan_id);
+
+        CREATE TABLE IF NOT EXISTS plan_tasks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_id INTEGER NOT NULL,
+            session_id INTEGER NOT NULL,
+            scheduled_for TEXT NOT NULL,
+            task_id INTEGER UNIQUE,
+            workout_id INTEGER,
+            UNIQUE(session_id, scheduled_for),
+            FOREIGN KEY(plan_id) REFERENCES training_plans(id),
+            FOREIGN KEY(session_id) REFERENCES plan_sessions(id),
+            FOREIGN KEY(task_id) REFERENCES tasks(id),
+            FOREIGN KEY(workout_id) REFERENCES workouts(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_plan_tasks_plan_id ON plan_tasks(plan_id);
+        -- The day stays in plan_tasks so scheduling again does not bring a deleted task back
+        CREATE TRIGGER IF NOT EXISTS plan_tasks_task_deleted AFTER DELETE ON tasks
+        BEGIN
+            UPDATE plan_tasks SET task_id = NULL WHERE task_id = OLD.id;
+        END;",
+    )
+}
+
+fn parse_date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap_or_default()
+}
+
+fn session_from_row(row: &Row) -> rusqlite::Result<PlanSession> {
+    Ok(PlanSession {
+        id: Some(row.get(0)?),
+        days: parse_days(&row.get::<_, String>(1)?).unwrap_or_default(),
+        time: row.get::<_, Option<String>>(2)?.and_then(|time| NaiveTime::parse_from_str(&time, "%H:%M").ok()),
+        title: row.get(3)?,
+    })
+}
+
+fn planned_task_from_row(row: &Row) -> rusqlite::Result<PlannedTask> {
+    Ok(PlannedTask {
+        id: row.get(0)?,
+        plan_id: row.get(1)?,
+        session_id: row.get(2)?,
+        scheduled_for: parse_date(&row.get::<_, String>(3)?),
+        task_id: row.get(4)?,
+        workout_id: row.get(5)?,
+    })
+}
+
+const PLANNED_TASK_COLUMNS: &str = "id, plan_id, session_id, scheduled_for, task_id, workout_id";
+
+impl TaskList {
+    pub fn add_plan(&mut self, plan: &TrainingPlan) -> Result<i64, Box<dyn std::error::Error>> {
+        if plan.sessions.is_empty() {
+            return Err("a training plan needs at least one session".into());
+        }
+        let id = self.database().with_transaction(|db| {
+            let conn = db.connection();
+            conn.execute(
+                "INSERT INTO training_plans (name, tag, starts_on, ends_on, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    plan.name,
+                    plan.tag,
+                    plan.starts_on.to_string(),
+                    plan.ends_on.map(|day| day.to_string()),
+                    plan.created_at.to_rfc3339()
+                ],
+            )?;
+            let id = conn.last_insert_rowid();
+            for session in &plan.sessions {
+                conn.execute(
+                    "INSERT INTO plan_sessions (plan_id, days, time, title) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        id,
+                        days_text(&session.days),
+                        session.time.map(|time| time.format("%H:%M").to_string()),
+                        session.title
+                    ],
+                )?;
+            }
+            Ok::<_, rusqlite::Error>(id)
+        })?;
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_plan(&self, id: i64) -> Result<Option<TrainingPlan>, Box<dyn std::error::Error>> {
+        Ok(self.query_plans("WHERE id = ?1", [id])?.pop())
+    }
+
+    /// The plan with this name, ignoring case.
+    pub fn find_plan(&self, name: &str) -> Result<Option<TrainingPlan>, Box<dyn std::error::Error>> {
+        Ok(self.query_plans("WHERE name = ?1 COLLATE NOCASE", [name.trim()])?.pop())
+    }
+
+    /// Every plan, by name.
+    pub fn plans(&self) -> Result<Vec<TrainingPlan>, Box<dyn std::error::Error>> {
+        self.query_plans("ORDER BY name COLLATE NOCASE", [])
+    }
+
+    fn query_plans(
+        &self,
+        filter: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<TrainingPlan>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, name, tag, starts_on, ends_on, created_at FROM training_plans {}",
+            filter
+        ))?;
+        let mut plans = stmt
+            .query_map(params, |row| {
+                Ok(TrainingPlan {
+                    id: Some(row.get(0)?),
+                    name: row.get(1)?,
+                    tag: row.get(2)?,
+                    starts_on: parse_date(&row.get::<_, String>(3)?),
+                    ends_on: row.get::<_, Option<String>>(4)?.map(|day| parse_date(&day)),
+                    sessions: Vec::new(),
+                    created_at: parse_time(&row.get::<_, String>(5)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare("SELECT id, days, time, title FROM plan_sessions WHERE plan_id = ?1 ORDER BY id")?;
+        for plan in &mut plans {
+            let sessions = stmt.query_map([plan.id], session_from_row)?;
+            plan.sessions = sessions.collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(plans)
+    }
+
+    /// Deletes a plan and the tasks it scheduled that are not done yet. Done
+    /// tasks are kept. Returns whether the plan existed.
+    pub fn delete_plan(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().with_transaction(|db| {
+            let conn = db.connection();
+            conn.execute(
+                "DELETE FROM tasks WHERE done = 0 AND id IN (SELECT task_id FROM plan_tasks WHERE plan_id = ?1)",
+                [id],
+            )?;
+            conn.execute("DELETE FROM plan_tasks WHERE plan_id = ?1", [id])?;
+            conn.execute("DELETE FROM plan_sessions WHERE plan_id = ?1", [id])?;
+            conn.execute("DELETE FROM training_plans WHERE id = ?1", [id])
+        })?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    /// Adds a task for every session of the plan from today (or the day it
+    /// starts) through `through`, in `now`'s timezone. Days already scheduled are
+    /// skipped, so this can be run as often as wanted. `through` may be at most
+    /// `MAX_SCHEDULE_WEEKS` ahead. Returns the new task ids.
+    pub fn schedule_plan<Tz: TimeZone>(
+        &mut self,
+        id: i64,
+        through: NaiveDate,
+        now: &DateTime<Tz>,
+    ) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
+        let plan = self.get_plan(id)?.ok_or_else(|| format!("training plan {} not found", id))?;
+        let furthest = now.date_naive().checked_add_days(Days::new(7 * u64::from(MAX_SCHEDULE_WEEKS)));
+        if furthest.is_none_or(|furthest| through >= furthest) {
+            return Err(format!("cannot schedule more than {} weeks ahead", MAX_SCHEDULE_WEEKS).into());
+        }
+        let first = plan.starts_on.max(now.date_naive());
+        let last = plan.ends_on.map_or(through, |ends_on| ends_on.min(through));
+        let tz = now.timezone();
+
+        let task_ids = self.database().with_transaction(|db| {
+            let conn = db.connection();
+            let mut task_ids = Vec::new();
+            let mut day = first;
+            while day <= last {
+                for session in plan.sessions.iter().filter(|session| session.days.contains(&day.weekday())) {
+                    let session_id = session.id.unwrap_or_default();
+                    let scheduled: bool = conn.query_row(
+                        "SELECT EXISTS (SELECT 1 FROM plan_tasks WHERE session_id = ?1 AND scheduled_for = ?2)",
+                        params![session_id, day.to_string()],
+                        |row| row.get(0),
+                    )?;
+                    if scheduled {
+                        continue;
+                    }
+                    let due = dates::to_utc(&tz, day.and_time(session.time.unwrap_or_else(dates::end_of_day)));
+                    let mut task = Task::new(&session.title)
+                        .with_tag(&plan.tag)
+                        .with_details(&format!("Training plan: {}", plan.name));
+                    task.due_date = due;
+                    let task_id = db.insert_task(&task)?;
+                    conn.execute(
+                        "INSERT INTO plan_tasks (plan_id, session_id, scheduled_for, task_id) VALUES (?1, ?2, ?3, ?4)",
+                        params![id, session_id, day.to_string(), task_id],
+                    )?;
+                    task_ids.push(task_id);
+                }
+                day += Duration::days(1);
+            }
+            Ok::<_, rusqlite::Error>(task_ids)
+        })?;
+        self.poll_changes()?;
+        Ok(task_ids)
+    }
+
+    /// The plan session a task was scheduled for, if it was.
+    pub fn planned_task(&self, task_id: i64) -> Result<Option<PlannedTask>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM plan_tasks WHERE task_id = ?1", PLANNED_TASK_COLUMNS))?;
+        let mut rows = stmt.query_map([task_id], planned_task_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Every day a plan has scheduled, in order.
+    pub fn planned_tasks(&self, plan_id: i64) -> Result<Vec<PlannedTask>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM plan_tasks WHERE plan_id = ?1 ORDER BY scheduled_for, session_id",
+            PLANNED_TASK_COLUMNS
+        ))?;
+        let planned = stmt.query_map([plan_id], planned_task_from_row)?;
+        Ok(planned.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Marks a task done. For a task scheduled by a plan, also links the first
+    /// workout started between the day it was scheduled for and today that no
+    /// other session has claimed. Returns the session, whose `workout_id` is
+    /// None when there is no such workout and the workout is still to be logged.
+    pub fn complete_planned_task<Tz: TimeZone>(
+        &mut self,
+        task_id: i64,
+        now: &DateTime<Tz>,
+    ) -> Result<Option<PlannedTask>, Box<dyn std::error::Error>> {
+        if self.get_by_id(task_id)?.is_none() {
+            return Err(format!("task {} not found", task_id).into());
+        }
+        let Some(mut planned) = self.planned_task(task_id)? else {
+            self.mark_done(task_id)?;
+            return Ok(None);
+        };
+        if planned.workout_id.is_none() {
+            let today = now.date_naive();
+            let tz = now.timezone();
+            let start = dates::start_of_day(planned.scheduled_for.min(today), &tz);
+            let end = dates::start_of_day(planned.scheduled_for.max(today) + Duration::days(1), &tz);
+            if let (Some(start), Some(end)) = (start, end) {
+                let conn = self.database().connection();
+                let mut stmt = conn.prepare(
+                    "SELECT id FROM workouts WHERE started_at >= ?1 AND started_at < ?2
+                     AND id NOT IN (SELECT workout_id FROM plan_tasks WHERE workout_id IS NOT NULL)
+                     ORDER BY started_at, id LIMIT 1",
+                )?;
+                let mut rows = stmt.query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| row.get(0))?;
+                planned.workout_id = rows.next().transpose()?;
+            }
+        }
+        self.database().with_transaction(|db| {
+            db.update_task_status(task_id, true)?;
+            db.connection()
+                .execute("UPDATE plan_tasks SET workout_id = ?2 WHERE id = ?1", params![planned.id, planned.workout_id])
+        })?;
+        self.poll_changes()?;
+        Ok(Some(planned))
+    }
+
+    /// Records that a workout did a scheduled session and marks its task done.
+    /// Returns false if the task was not scheduled by a plan.
+    pub fn link_workout(&mut self, task_id: i64, workout_id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        if self.get_workout(workout_id)?.is_none() {
+            return Err(format!("workout {} not found", workout_id).into());
+        }
+        let linked = self.database().with_transaction(|db| {
+            let linked = db
+                .connection()
+                .execute("UPDATE plan_tasks SET workout_id = ?2 WHERE task_id = ?1", [task_id, workout_id])?;
+            if linked > 0 {
+                db.update_task_status(task_id, true)?;
+            }
+            Ok::<_, rusqlite::Error>(linked > 0)
+        })?;
+        self.poll_changes()?;
+        Ok(linked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fitness::Workout;
+    use chrono::FixedOffset;
+
+    fn day(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_sessions() {
+        let session: PlanSession = "Mon,Fri wed 7am Strength A".parse().unwrap();
+        assert_eq!(session.days, [Weekday::Mon, Weekday::Wed, Weekday::Fri]);
+        assert_eq!(session.time, NaiveTime::from_hms_opt(7, 0, 0));
+        assert_eq!(session.title, "Strength A");
+        assert_eq!(session.to_string(), "mon,wed,fri 07:00 Strength A");
+
+        let session: PlanSession = "sunday Long run".parse().unwrap();
+        assert_eq!((session.days.as_slice(), session.time), ([Weekday::Sun].as_slice(), None));
+        assert_eq!("weekends 18:30 Yoga".parse::<PlanSession>().unwrap().days, [Weekday::Sat, Weekday::Sun]);
+        assert_eq!("daily Stretch".parse::<PlanSession>().unwrap().days.len(), 7);
+        for invalid in ["", "Long run", "mon", "mon 7am", "mon,funday Run"] {
+            assert!(invalid.parse::<PlanSession>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_plans_schedule_tasks_linked_to_workouts() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let sessions = vec!["mon,wed,fri 7am Strength".parse()?, "sun Long run".parse()?];
+        let mut plan = TrainingPlan::new("Base", day("2026-10-14"), sessions).with_tag("#fitness");
+        plan.ends_on = Some(day("2026-10-25"));
+        let id = task_list.add_plan(&plan)?;
+        assert!(task_list.add_plan(&TrainingPlan::new("base", day("2026-10-14"), plan.sessions.clone())).is_err());
+        assert!(task_list.add_plan(&TrainingPlan::new("Empty", day("2026-10-14"), Vec::new())).is_err());
+        assert_eq!(task_list.find_plan("BASE")?.unwrap().sessions.len(), 2);
+
+        // Monday the 12th, but the plan starts on Wednesday and ends on Sunday the 25th
+        let paris = FixedOffset::east_opt(2 * 3600).unwrap();
+        let now = paris.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap();
+        let task_ids = task_list.schedule_plan(id, day("2026-11-30"), &now)?;
+        let titles: Vec<(String, String)> = task_ids
+            .iter()
+            .map(|task_id| {
+                let task = task_list.get_by_id(*task_id).unwrap().unwrap();
+                (task.title, task.due_date.unwrap().with_timezone(&paris).format("%a %d %H:%M").to_string())
+            })
+            .collect();
+        let expected = [
+            ("Strength", "Wed 14 07:00"),
+            ("Strength", "Fri 16 07:00"),
+            ("Long run", "Sun 18 23:59"),
+            ("Strength", "Mon 19 07:00"),
+            ("Strength", "Wed 21 07:00"),
+            ("Strength", "Fri 23 07:00"),
+            ("Long run", "Sun 25 23:59"),
+        ];
+        assert_eq!(titles, expected.map(|(title, due)| (title.to_string(), due.to_string())));
+        assert_eq!(task_list.get_by_tag("fitness")?.len(), 7);
+        assert!(task_list.schedule_plan(id, day("2026-11-30"), &now)?.is_empty());
+        assert!(task_list.schedule_plan(id, day("2027-10-10"), &now).is_ok());
+        assert!(task_list.schedule_plan(id, day("2027-10-11"), &now).is_err());
+        assert!(task_list.schedule_plan(id, NaiveDate::MAX, &now).is_err());
+
+        // A deleted session is not scheduled again
+        task_list.delete(task_ids[1])?;
+        assert!(task_list.schedule_plan(id, day("2026-11-30"), &now)?.is_empty());
+        assert_eq!(task_list.planned_tasks(id)?[1].task_id, None);
+
+        // Done a day late, after a workout that morning
+        let workout = task_list.start_workout(&Workout::new(at(&paris, 2026, 10, 15, 7).with_timezone(&Utc)))?;
+        let thursday = at(&paris, 2026, 10, 15, 20);
+        let planned = task_list.complete_planned_task(task_ids[0], &thursday)?.unwrap();
+        assert_eq!((planned.scheduled_for, planned.workout_id), (day("2026-10-14"), Some(workout)));
+        assert!(task_list.get_by_id(task_ids[0])?.unwrap().done);
+
+        // The workout is taken, so the next session waits for one to be logged
+        let planned = task_list.complete_planned_task(task_ids[2], &at(&paris, 2026, 10, 18, 20))?.unwrap();
+        assert_eq!(planned.workout_id, None);
+        let run = task_list.start_workout(&Workout::new(at(&paris, 2026, 10, 18, 21).with_timezone(&Utc)))?;
+        assert!(task_list.link_workout(task_ids[2], run)?);
+        assert_eq!(task_list.planned_task(task_ids[2])?.unwrap().workout_id, Some(run));
+        assert!(task_list.link_workout(task_ids[2], 99).is_err());
+
+        let chore = task_list.add(Task::new("Laundry"))?;
+        assert_eq!(task_list.complete_planned_task(chore, &thursday)?, None);
+        assert!(task_list.get_by_id(chore)?.unwrap().done);
+        assert!(!task_list.link_workout(chore, run)?);
+
+        task_list.delete_workout(run)?;
+        assert_eq!(task_list.planned_task(task_ids[2])?.unwrap().workout_id, None);
+
+        // Done tasks outlive the plan
+        assert!(task_list.delete_plan(id)?);
+        assert!(task_list.get_plan(id)?.is_none());
+        assert_eq!(task_list.get_by_tag("fitness")?.len(), 2);
+        assert!(task_list.planned_task(task_ids[0])?.is_none());
+        Ok(())
+    }
+
+    fn at(tz: &FixedOffset, year: i32, month: u32, day: u32, hour: u32) -> DateTime<FixedOffset> {
+        tz.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+}
@@ -0,0 +1,517 @@
+// core/src/ics.rs
+// iCalendar (RFC 5545): tasks are written as VTODOs; VTODOs and VEVENTs can be imported.
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use crate::dates::{end_of_day, to_utc};
+use crate::importers::{Builder, ImportError};
+use crate::task::{Priority, Task};
+use crate::task_list::TaskList;
+
+pub const PRODID: &str = "-//FreeList//FreeList//EN";
+
+const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// The UID a stored task is exported under.
+pub fn task_uid(id: i64) -> String {
+    format!("freelist-task-{}", id)
+}
+
+/// A VCALENDAR with one VTODO per task.
+///
+/// Tags become CATEGORIES and `parent_id` a RELATED-TO the parent's UID.
+/// FreeList does not store how often a task repeats, so recurring tasks get
+/// a daily RRULE starting when the task was created.
+pub fn to_ics(tasks: &[Task], now: DateTime<Utc>) -> String {
+    to_ics_with(tasks, now, task_uid)
+}
+
+/// `to_ics` with `uid` giving the UID of each task id.
+pub fn to_ics_with(tasks: &[Task], now: DateTime<Utc>, uid: impl Fn(i64) -> String) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{}", PRODID));
+    for task in tasks {
+        write_vtodo(&mut out, task, now, &uid);
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn write_vtodo(out: &mut String, task: &Task, now: DateTime<Utc>, uid: &impl Fn(i64) -> String) {
+    let timestamp = |date_time: DateTime<Utc>| date_time.format(UTC_FORMAT).to_string();
+
+    push_line(out, "BEGIN:VTODO");
+    push_line(out, &format!("UID:{}", uid(task.id.unwrap_or(0))));
+    push_line(out, &format!("DTSTAMP:{}", timestamp(now)));
+    push_line(out, &format!("CREATED:{}", timestamp(task.created_at)));
+    push_line(out, &format!("LAST-MODIFIED:{}", timestamp(task.updated_at)));
+    push_line(out, &format!("SUMMARY:{}", escape(&task.title)));
+    if let Some(details) = &task.details {
+        push_line(out, &format!("DESCRIPTION:{}", escape(details)));
+    }
+    if task.is_recurring {
+        push_line(out, &format!("DTSTART:{}", timestamp(task.created_at)));
+    }
+    if let Some(due) = task.due_date {
+        push_line(out, &format!("DUE:{}", timestamp(due)));
+    }
+    if task.is_recurring {
+        push_line(out, "RRULE:FREQ=DAILY");
+    }
+    if task.done {
+        push_line(out, "STATUS:COMPLETED");
+        push_line(out, &format!("COMPLETED:{}", timestamp(task.updated_at)));
+    } else {
+        push_line(out, "STATUS:NEEDS-ACTION");
+    }
+    if let Some(priority) = task.priority {
+        push_line(out, &format!("PRIORITY:{}", ical_priority(priority)));
+    }
+    if let Some(tag) = &task.tag {
+        push_line(out, &format!("CATEGORIES:{}", escape(tag)));
+    }
+    if let Some(parent_id) = task.parent_id {
+        push_line(out, &format!("RELATED-TO;RELTYPE=PARENT:{}", uid(parent_id)));
+    }
+    push_line(out, "END:VTODO");
+}
+
+/// iCalendar priorities run from 1 (highest) to 9; 0 means none.
+fn ical_priority(priority: Priority) -> u8 {
+    match priority {
+        Priority::Urgent => 1,
+        Priority::High => 3,
+        Priority::Medium => 5,
+        Priority::Low => 9,
+    }
+}
+
+fn from_ical_priority(value: &str) -> Option<Priority> {
+    match value.trim().parse::<u8>().ok()? {
+        1 => Some(Priority::Urgent),
+        2..=4 => Some(Priority::High),
+        5 => Some(Priority::Medium),
+        6..=9 => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// Appends a content line, folding it at 75 octets.
+fn push_line(out: &mut String, line: &str) {
+    let mut rest = line;
+    let mut limit = 75;
+    while rest.len() > limit {
+        let mut cut = limit;
+        while !rest.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        out.push_str(&rest[..cut]);
+        out.push_str("\r\n ");
+        rest = &rest[cut..];
+        // Continuation lines start with a space
+        limit = 74;
+    }
+    out.push_str(rest);
+    out.push_str("\r\n");
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// Splits a list value such as CATEGORIES on commas that are not escaped.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = vec![String::new()];
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            ',' if !escaped => items.push(String::new()),
+            _ => items.last_mut().unwrap().push(c),
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    items.iter().map(|item| unescape(item.trim())).filter(|item| !item.is_empty()).collect()
+}
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parses `NAME;PARAM=value:VALUE`; colons and semicolons inside quoted parameters are kept.
+fn parse_property(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let mut split = None;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                split = Some(index);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let (head, value) = line.split_at(split?);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+    Some(Property { name, params, value: value[1..].to_string() })
+}
+
+/// Joins folded lines back together.
+fn unfold(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in input.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// A DATE-TIME in UTC, a floating or TZID time (read in `now`'s timezone), or
+/// a DATE, which means the end of that day.
+fn parse_time<Tz: TimeZone>(property: &Property, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let value = property.value.trim();
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return to_utc(&now.timezone(), date.and_time(end_of_day()));
+    }
+    if let Ok(naive) = NaiveDateTime::parse_from_str(value, UTC_FORMAT) {
+        return Some(naive.and_utc());
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    to_utc(&now.timezone(), naive)
+}
+
+/// A VTODO or VEVENT read from a calendar.
+#[derive(Debug, Clone)]
+pub struct CalendarItem {
+    pub uid: Option<String>,
+    /// `parent_id` is never set; see `parent_uid`
+    pub task: Task,
+    /// When a completed to-do was completed, if the calendar says
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The UID named by RELATED-TO, if the relation is to a parent
+    pub parent_uid: Option<String>,
+}
+
+/// Reads the VTODOs and VEVENTs in `input`, along with warnings about the
+/// ones that were skipped. An event's start becomes the due date. Cancelled
+/// components are skipped and nested components such as VALARM are ignored.
+pub fn parse_calendar<Tz: TimeZone>(
+    input: &str,
+    now: &DateTime<Tz>,
+) -> Result<(Vec<CalendarItem>, Vec<String>), ImportError> {
+    let lines = unfold(input);
+    if !lines.first().is_some_and(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+        return Err(ImportError("invalid iCalendar file: missing BEGIN:VCALENDAR".to_string()));
+    }
+
+    let mut items = Vec::new();
+    let mut warnings = Vec::new();
+    let mut component: Option<(String, Vec<Property>)> = None;
+    let mut nested = 0;
+
+    for line in &lines {
+        let Some(property) = parse_property(line) else { continue };
+        let value = property.value.trim().to_uppercase();
+        match (property.name.as_str(), component.as_mut()) {
+            ("BEGIN", None) if value == "VTODO" || value == "VEVENT" => component = Some((value, Vec::new())),
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some((kind, _))) if *kind == value => {
+                let (kind, properties) = component.take().unwrap();
+                match parse_item(&kind, &properties, now) {
+                    Ok(item) => items.push(item),
+                    Err(warning) => warnings.push(warning),
+                }
+            }
+            (_, Some((_, properties))) if nested == 0 => properties.push(property),
+            _ => {}
+        }
+    }
+    Ok((items, warnings))
+}
+
+fn parse_item<Tz: TimeZone>(kind: &str, properties: &[Property], now: &DateTime<Tz>) -> Result<CalendarItem, String> {
+    let get = |name: &str| properties.iter().find(|p| p.name == name);
+    let text = |name: &str| get(name).map(|p| unescape(&p.value)).filter(|text| !text.trim().is_empty());
+    let time = |name: &str| get(name).and_then(|p| parse_time(p, now));
+
+    let title = text("SUMMARY").ok_or_else(|| format!("skipped a {} without a summary", kind))?;
+    let status = get("STATUS").map(|p| p.value.trim().to_uppercase());
+    if status.as_deref() == Some("CANCELLED") {
+        return Err(format!("skipped cancelled '{}'", title));
+    }
+
+    let mut task = Task::new(title.trim());
+    task.created_at = time("CREATED").unwrap_or_else(|| now.with_timezone(&Utc));
+    task.updated_at = time("LAST-MODIFIED").unwrap_or(task.created_at);
+    task.details = text("DESCRIPTION");
+    task.due_date = if kind == "VEVENT" { time("DTSTART") } else { time("DUE") };
+    task.is_recurring = get("RRULE").is_some();
+    task.priority = get("PRIORITY").and_then(|p| from_ical_priority(&p.value));
+    task.tag = properties
+        .iter()
+        .filter(|p| p.name == "CATEGORIES")
+        .flat_map(|p| split_list(&p.value))
+        .next();
+    task.done = kind == "VTODO" && status.as_deref() == Some("COMPLETED");
+
+    let parent = properties
+        .iter()
+        .find(|p| p.name == "RELATED-TO" && p.param("RELTYPE").is_none_or(|reltype| reltype.eq_ignore_ascii_case("PARENT")));
+    Ok(CalendarItem {
+        uid: get("UID").map(|p| p.value.trim().to_string()),
+        completed_at: if task.done { time("COMPLETED") } else { None },
+        parent_uid: parent.map(|p| p.value.trim().to_string()),
+        task,
+    })
+}
+
+/// `parse_calendar` for `importers::convert`, linking subtasks by UID.
+pub(crate) fn convert<Tz: TimeZone>(input: &str, now: &DateTime<Tz>, builder: &mut Builder) -> Result<(), ImportError> {
+    let (items, warnings) = parse_calendar(input, now)?;
+    for warning in warnings {
+        builder.warn(warning);
+    }
+
+    let mut uids: HashMap<String, i64> = HashMap::new();
+    let mut parents: Vec<(i64, String)> = Vec::new();
+    for item in items {
+        let done = item.task.done;
+        let id = builder.push(item.task);
+        if done {
+            builder.complete(id, item.completed_at);
+        }
+        if let Some(uid) = item.uid {
+            uids.insert(uid, id);
+        }
+        if let Some(parent_uid) = item.parent_uid {
+            parents.push((id, parent_uid));
+        }
+    }
+
+    for (id, parent_uid) in parents {
+        match uids.get(&parent_uid) {
+            Some(&parent_id) if !builder.is_ancestor(id, parent_id) => builder.task_mut(id).parent_id = Some(parent_id),
+            _ => {
+                let title = builder.task_mut(id).title.clone();
+                builder.warn(format!("'{}': parent {} not found, imported as a top-level task", title, parent_uid));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl TaskList {
+    /// The UID a calendar client gave a task, or `task_uid(id)` if it came from FreeList.
+    pub fn uid(&self, id: i64) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(self.database().uids()?.remove(&id).unwrap_or_else(|| task_uid(id)))
+    }
+
+    /// Remembers the UID a calendar client created a task under.
+    pub fn set_uid(&mut self, id: i64, uid: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.database().set_uid(id, uid)?;
+        self.poll_changes()
+    }
+
+    pub fn find_by_uid(&self, uid: &str) -> Result<Option<Task>, Box<dyn std::error::Error>> {
+        let id = match self.database().find_uid(uid)? {
+            Some(id) => Some(id),
+            None => uid.strip_prefix("freelist-task-").and_then(|id| id.parse().ok()),
+        };
+        match id {
+            Some(id) => self.get_by_id(id),
+            None => Ok(None),
+        }
+    }
+
+    /// `to_ics` using the UIDs calendar clients gave tasks.
+    pub fn export_ics(&self, tasks: &[Task], now: DateTime<Utc>) -> Result<String, Box<dyn std::error::Error>> {
+        let uids = self.database().uids()?;
+        Ok(to_ics_with(tasks, now, |id| uids.get(&id).cloned().unwrap_or_else(|| task_uid(id))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::{convert as convert_source, Conversion, SourceFormat};
+    use crate::ImportSummary;
+
+    const CALENDAR: &str = include_str!("../tests/fixtures/calendar.ics");
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    fn now() -> DateTime<Utc> {
+        at("2026-10-18T12:00:00Z")
+    }
+
+    fn parse(input: &str) -> Conversion {
+        convert_source(SourceFormat::Ics, input, None, &now()).unwrap()
+    }
+
+    #[test]
+    fn test_export_writes_vtodos() {
+        let mut task_list = TaskList::new_in_memory().unwrap();
+        let trip = task_list.add(Task::new("Plan trip; book, pack").with_tag("travel").with_priority(Priority::High)).unwrap();
+        let mut flights = Task::new("Book flights").with_parent(trip).with_due_date(at("2026-11-01T17:00:00Z"));
+        flights.is_recurring = true;
+        task_list.add(flights).unwrap();
+
+        let ics = to_ics(&task_list.all().unwrap(), now());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//FreeList//FreeList//EN\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Plan trip\\; book\\, pack\r\n"));
+        assert!(ics.contains("\r\nCATEGORIES:travel\r\nEND:VTODO\r\n"));
+        assert!(ics.contains("\r\nPRIORITY:3\r\n"));
+        assert!(ics.contains("\r\nDUE:20261101T170000Z\r\nRRULE:FREQ=DAILY\r\nSTATUS:NEEDS-ACTION\r\n"));
+        assert!(ics.contains("\r\nRELATED-TO;RELTYPE=PARENT:freelist-task-1\r\n"));
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let title = "Ünïcödé ".repeat(20);
+        let ics = to_ics(&[Task::new(&title).with_details("line one\nline two")], now());
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        let task = &parse(&ics).archive.tasks[0];
+        assert_eq!(task.title, title.trim());
+        assert_eq!(task.details.as_deref(), Some("line one\nline two"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut source = TaskList::new_in_memory().unwrap();
+        let trip = source.add(Task::new("Plan trip, soon").with_tag("travel").with_priority(Priority::Urgent)).unwrap();
+        let flights = source
+            .add(Task::new("Book flights").with_parent(trip).with_details("aisle\\window").with_due_date(at("2026-11-01T17:00:00Z")))
+            .unwrap();
+        let mut water = Task::new("Water plants").with_priority(Priority::Low);
+        water.is_recurring = true;
+        source.add(water).unwrap();
+        source.mark_done(flights).unwrap();
+
+        let conversion = parse(&to_ics(&source.all().unwrap(), now()));
+        assert!(conversion.warnings.is_empty());
+        let mut target = TaskList::new_in_memory().unwrap();
+        let summary = target.import_archive(&conversion.archive).unwrap();
+        assert_eq!(summary, ImportSummary { tasks_added: 3, tasks_skipped: 0, history_added: 1 });
+
+        let comparable = |task_list: &TaskList| {
+            let tasks = task_list.all().unwrap();
+            let title = |id: i64| tasks.iter().find(|t| t.id == Some(id)).map(|t| t.title.clone());
+            let mut rows: Vec<_> = tasks
+                .iter()
+                .map(|t| {
+                    let parent = t.parent_id.and_then(title);
+                    (t.title.clone(), t.details.clone(), t.done, t.due_date, t.is_recurring, t.tag.clone(), t.priority, parent, t.created_at.timestamp())
+                })
+                .collect();
+            rows.sort_by(|a, b| a.0.cmp(&b.0));
+            rows
+        };
+        assert_eq!(comparable(&target), comparable(&source));
+
+        // Exported tasks keep their creation time, so importing again adds nothing
+        let again = target.import_archive(&parse(&to_ics(&source.all().unwrap(), now())).archive).unwrap();
+        assert_eq!(again.tasks_added, 0);
+    }
+
+    #[test]
+    fn test_client_uids() {
+        let mut task_list = TaskList::new_in_memory().unwrap();
+        let ours = task_list.add(Task::new("Ours")).unwrap();
+        let theirs = task_list.add(Task::new("Theirs").with_parent(ours)).unwrap();
+        task_list.set_uid(theirs, "0F1C7A2E@client").unwrap();
+
+        assert_eq!(task_list.uid(ours).unwrap(), "freelist-task-1");
+        assert_eq!(task_list.uid(theirs).unwrap(), "0F1C7A2E@client");
+        assert_eq!(task_list.find_by_uid("0F1C7A2E@client").unwrap().unwrap().title, "Theirs");
+        assert_eq!(task_list.find_by_uid("freelist-task-1").unwrap().unwrap().title, "Ours");
+        assert!(task_list.find_by_uid("unknown").unwrap().is_none());
+
+        let ics = task_list.export_ics(&task_list.all().unwrap(), now()).unwrap();
+        assert!(ics.contains("\r\nUID:0F1C7A2E@client\r\n"));
+        assert!(ics.contains("\r\nRELATED-TO;RELTYPE=PARENT:freelist-task-1\r\n"));
+
+        task_list.delete(theirs).unwrap();
+        assert!(task_list.find_by_uid("0F1C7A2E@client").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_import_other_calendars() {
+        let conversion = parse(CALENDAR);
+        let titles: Vec<&str> = conversion.archive.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec![
+            "Quarterly report",
+            "Make the charts for the quarterly report so that they can be dropped straight into the slides",
+            "Team standup",
+        ]);
+
+        let report = &conversion.archive.tasks[0];
+        assert_eq!(report.details.as_deref(), Some("Numbers from finance, then\na draft for review"));
+        assert_eq!(report.due_date, Some(at("2026-10-30T23:59:59Z")));
+        assert_eq!((report.tag.as_deref(), report.priority), (Some("Work,Finance"), Some(Priority::High)));
+
+        let charts = &conversion.archive.tasks[1];
+        assert!(charts.done);
+        assert_eq!(charts.parent_id, report.id);
+        assert_eq!(conversion.archive.history[0].at, at("2026-10-05T16:00:00Z"));
+
+        // TZID times are read in the importing timezone
+        let standup = &conversion.archive.tasks[2];
+        assert!(standup.is_recurring);
+        assert_eq!(standup.due_date, Some(at("2026-10-19T09:30:00Z")));
+
+        assert_eq!(conversion.warnings, vec!["skipped cancelled 'Offsite'".to_string()]);
+        assert!(convert_source(SourceFormat::Ics, "SUMMARY:nope", None, &now()).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// core/src/importers/mod.rs
+// Converts other tools' exports into an `Archive` so they go through `TaskList::import_archive`.
+mod taskwarrior;
+mod todo_txt;
+mod todoist;
+
+use chrono::{DateTime, TimeZone, Utc};
+use crate::database::{HistoryEntry, TaskEvent};
+use crate::export::{Archive, ARCHIVE_VERSION};
+use crate::task::Task;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// One task per line in the todo.txt format
+    TodoTxt,
+    /// The JSON written by `task export`
+    Taskwarrior,
+    /// A Todoist project backup (CSV)
+    Todoist,
+    /// VTODO and VEVENT components of an iCalendar file
+    Ics,
+}
+
+impl std::str::FromStr for SourceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "todotxt" | "todo.txt" => Ok(SourceFormat::TodoTxt),
+            "taskwarrior" => Ok(SourceFormat::Taskwarrior),
+            "todoist" => Ok(SourceFormat::Todoist),
+            "ics" | "ical" | "icalendar" => Ok(SourceFormat::Ics),
+            _ => Err(format!("unknown import format '{}'", s)),
+        }
+    }
+}
+
+/// Input that could not be read at all. Individual entries that cannot be
+/// converted are reported as warnings instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError(pub String);
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// The converted tasks, numbered from 1 with `parent_id` referring to those
+/// numbers, plus anything that was dropped or guessed along the way.
+#[derive(Debug, Clone)]
+pub struct Conversion {
+    pub archive: Archive,
+    pub warnings: Vec<String>,
+}
+
+/// Converts `input` into tasks. Dates without a timezone are read in `now`'s
+/// timezone, and `now` is the creation time of tasks whose source has none.
+///
+/// `default_tag` is given to tasks that did not get a tag from the source,
+/// e.g. the project name of a Todoist backup, which is only in its file name.
+pub fn convert<Tz: TimeZone>(
+    format: SourceFormat,
+    input: &str,
+    default_tag: Option<&str>,
+    now: &DateTime<Tz>,
+) -> Result<Conversion, ImportError> {
+    let mut builder = Builder::default();
+    match format {
+        SourceFormat::TodoTxt => todo_txt::convert(input, now, &mut builder),
+        SourceFormat::Taskwarrior => taskwarrior::convert(input, &mut builder)?,
+        SourceFormat::Todoist => todoist::convert(input, now, &mut builder)?,
+        SourceFormat::Ics => crate::ics::convert(input, now, &mut builder)?,
+    }
+
+    if let Some(tag) = default_tag.map(str::trim).filter(|tag| !tag.is_empty()) {
+        for task in builder.tasks.iter_mut().filter(|task| task.tag.is_none()) {
+            task.tag = Some(tag.to_string());
+        }
+    }
+
+    Ok(Conversion {
+        archive: Archive {
+            version: ARCHIVE_VERSION,
+            exported_at: now.with_timezone(&Utc),
+            tasks: builder.tasks,
+            tags: Vec::new(),
+            history: builder.history,
+            lists: Vec::new(),
+        },
+        warnings: builder.warnings,
+    })
+}
+
+#[derive(Default)]
+pub(crate) struct Builder {
+    tasks: Vec<Task>,
+    history: Vec<HistoryEntry>,
+    warnings: Vec<String>,
+}
+
+impl Builder {
+    /// Adds a task, giving it the next archive id.
+    pub(crate) fn push(&mut self, mut task: Task) -> i64 {
+        let id = self.tasks.len() as i64 + 1;
+        task.id = Some(id);
+        self.tasks.push(task);
+        id
+    }
+
+    pub(crate) fn task_mut(&mut self, id: i64) -> &mut Task {
+        &mut self.tasks[(id - 1) as usize]
+    }
+
+    /// Whether `ancestor` is `id` or one of its parents, i.e. making `ancestor`
+    /// a subtask of `id` would create a cycle.
+    pub(crate) fn is_ancestor(&self, ancestor: i64, id: i64) -> bool {
+        let mut current = Some(id);
+        while let Some(current_id) = current {
+            if current_id == ancestor {
+                return true;
+            }
+            current = self.tasks[(current_id - 1) as usize].parent_id;
+        }
+        false
+    }
+
+    /// Marks a task done, recording when if the source knows.
+    pub(crate) fn complete(&mut self, id: i64, at: Option<DateTime<Utc>>) {
+        let task = self.task_mut(id);
+        task.done = true;
+        if let Some(at) = at {
+            task.updated_at = at;
+            self.history.push(HistoryEntry { task_id: id, event: TaskEvent::Completed, at });
+        }
+    }
+
+    pub(crate) fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImportSummary, Priority, TaskList};
+
+    const TODO_TXT: &str = include_str!("../../tests/fixtures/todo.txt");
+    const TASKWARRIOR: &str = include_str!("../../tests/fixtures/taskwarrior.json");
+    const TODOIST: &str = include_str!("../../tests/fixtures/todoist.csv");
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-18T12:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    fn find<'a>(conversion: &'a Conversion, title: &str) -> &'a Task {
+        conversion.archive.tasks.iter().find(|t| t.title == title).unwrap()
+    }
+
+    #[test]
+    fn test_todo_txt() {
+        let conversion = convert(SourceFormat::TodoTxt, TODO_TXT, None, &now()).unwrap();
+        assert_eq!(conversion.archive.tasks.len(), 7);
+
+        let call = find(&conversion, "Call the landlord about the heater @phone");
+        assert_eq!(call.priority, Some(Priority::Urgent));
+        assert_eq!(call.tag.as_deref(), Some("Home"));
+        assert_eq!(call.created_at, at("2026-10-01T00:00:00Z"));
+        assert_eq!(call.due_date, Some(at("2026-10-20T23:59:59Z")));
+
+        let garden = find(&conversion, "Plan the garden").id;
+        let seeds = find(&conversion, "Buy seeds");
+        assert_eq!((seeds.parent_id, seeds.tag.as_deref(), seeds.priority), (garden, Some("shop"), Some(Priority::Medium)));
+        assert_eq!(seeds.created_at, now());
+        let water = find(&conversion, "Water plants");
+        assert!(water.is_recurring);
+        assert_eq!(water.parent_id, garden);
+
+        let taxes = find(&conversion, "File taxes");
+        assert!(taxes.done);
+        assert_eq!(taxes.priority, Some(Priority::High));
+        assert_eq!(conversion.archive.history.len(), 1);
+        assert_eq!(conversion.archive.history[0].at, at("2026-10-05T00:00:00Z"));
+
+        let read = find(&conversion, "Read https://example.com/article");
+        assert_eq!((read.priority, read.due_date), (Some(Priority::Low), None));
+        assert_eq!(conversion.warnings, vec![
+            "line 7: cannot understand date 'someday', due date dropped".to_string(),
+            "line 8: parent 'p:missing' not found, imported as a top-level task".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_taskwarrior() {
+        let conversion = convert(SourceFormat::Taskwarrior, TASKWARRIOR, None, &now()).unwrap();
+        let titles: Vec<&str> = conversion.archive.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Prepare release", "Write changelog", "Tag the build", "Take out trash"]);
+
+        let release = find(&conversion, "Prepare release");
+        assert_eq!((release.tag.as_deref(), release.priority), (Some("Work"), Some(Priority::High)));
+        assert_eq!(release.details.as_deref(), Some("Coordinate with QA"));
+        assert_eq!(release.created_at, at("2026-10-01T09:00:00Z"));
+        assert_eq!(release.due_date, Some(at("2026-10-25T17:00:00Z")));
+
+        let changelog = find(&conversion, "Write changelog");
+        assert!(changelog.done);
+        assert_eq!(changelog.parent_id, release.id);
+        let build = find(&conversion, "Tag the build");
+        assert_eq!((build.parent_id, build.tag.as_deref()), (release.id, Some("ops")));
+        assert!(find(&conversion, "Take out trash").is_recurring);
+
+        assert_eq!(conversion.archive.history.len(), 1);
+        assert_eq!(conversion.warnings, vec!["skipped deleted task 'Old idea'".to_string()]);
+
+        // Line-per-object exports and unreadable input
+        let lines: Vec<&str> = TASKWARRIOR.lines().filter(|l| l.starts_with('{')).collect();
+        let ndjson = convert(SourceFormat::Taskwarrior, &lines.join("\n"), None, &now()).unwrap();
+        assert_eq!(ndjson.archive.tasks.len(), 4);
+        assert!(convert(SourceFormat::Taskwarrior, "not json", None, &now()).is_err());
+    }
+
+    #[test]
+    fn test_taskwarrior_circular_dependencies() {
+        let input = r#"[
+            {"uuid":"a","description":"A","status":"pending","depends":"b"},
+            {"uuid":"b","description":"B","status":"pending","depends":"a"}
+        ]"#;
+        let conversion = convert(SourceFormat::Taskwarrior, input, None, &now()).unwrap();
+        assert_eq!(find(&conversion, "B").parent_id, Some(1));
+        assert_eq!(find(&conversion, "A").parent_id, None);
+        assert_eq!(conversion.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_todoist() {
+        let conversion = convert(SourceFormat::Todoist, TODOIST, Some("Offsite"), &now()).unwrap();
+        let titles: Vec<&str> = conversion.archive.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Plan the offsite", "Book venue", "Compare quotes", "Send invites", "Write summary"]);
+
+        let plan = find(&conversion, "Plan the offsite");
+        assert_eq!((plan.tag.as_deref(), plan.priority), (Some("work"), Some(Priority::Urgent)));
+        assert_eq!(plan.details.as_deref(), Some("Budget is in the shared drive"));
+        assert_eq!(plan.due_date, Some(at("2026-11-03T23:59:59Z")));
+
+        let venue = find(&conversion, "Book venue");
+        assert_eq!((venue.parent_id, venue.tag.as_deref()), (plan.id, Some("Offsite")));
+        assert_eq!(venue.details.as_deref(), Some("Call them before Friday, ask about catering"));
+        assert_eq!(find(&conversion, "Compare quotes").parent_id, venue.id);
+        assert_eq!(find(&conversion, "Compare quotes").priority, None);
+
+        let invites = find(&conversion, "Send invites");
+        assert_eq!(invites.parent_id, plan.id);
+        assert!(invites.is_recurring);
+        // The coming Monday
+        assert_eq!(invites.due_date, Some(at("2026-10-19T23:59:59Z")));
+
+        assert_eq!(find(&conversion, "Write summary").parent_id, None);
+        assert_eq!(conversion.warnings, vec!["line 11: cannot understand date 'whenever', due date dropped".to_string()]);
+
+        assert!(convert(SourceFormat::Todoist, "NAME,VALUE\nx,y\n", None, &now()).is_err());
+    }
+
+    #[test]
+    fn test_conversions_import_and_preview() {
+        let conversion = convert(SourceFormat::TodoTxt, TODO_TXT, Some("inbox"), &now()).unwrap();
+        let mut task_list = TaskList::new_in_memory().unwrap();
+
+        let preview = task_list.preview_import(&conversion.archive).unwrap();
+        assert_eq!(preview, ImportSummary { tasks_added: 7, tasks_skipped: 0, history_added: 1 });
+        assert!(task_list.all().unwrap().is_empty());
+
+        assert_eq!(task_list.import_archive(&conversion.archive).unwrap(), preview);
+        let orphan = task_list.all().unwrap().into_iter().find(|t| t.title == "Orphan subtask").unwrap();
+        assert_eq!(orphan.tag.as_deref(), Some("inbox"));
+        let seeds = task_list.all().unwrap().into_iter().find(|t| t.title == "Buy seeds").unwrap();
+        assert_eq!(task_list.get_by_id(seeds.parent_id.unwrap()).unwrap().unwrap().title, "Plan the garden");
+
+        // Lines with creation dates are recognised on a second import
+        let again = task_list.preview_import(&conversion.archive).unwrap();
+        assert_eq!(again.tasks_skipped, 7);
+    }
+
+    #[test]
+    fn test_source_format_from_str() {
+        assert_eq!("todo.txt".parse(), Ok(SourceFormat::TodoTxt));
+        assert_eq!("Todoist".parse(), Ok(SourceFormat::Todoist));
+        assert!("trello".parse::<SourceFormat>().is_err());
+    }
+}
@@ -0,0 +1,135 @@
+// core/src/importers/taskwarrior.rs
+// Taskwarrior's `task export`: a JSON array, or one object per line from older versions.
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use super::{Builder, ImportError};
+use crate::task::{Priority, Task};
+
+#[derive(Deserialize)]
+struct Entry {
+    uuid: String,
+    description: String,
+    status: String,
+    entry: Option<String>,
+    modified: Option<String>,
+    end: Option<String>,
+    due: Option<String>,
+    project: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    priority: Option<String>,
+    /// An array, or a comma-separated string before Taskwarrior 2.6
+    depends: Option<serde_json::Value>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    recur: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Annotation {
+    description: String,
+}
+
+/// The project (or the first tag) becomes the tag and annotations become the
+/// details. Tasks a task depends on are imported as its subtasks. Deleted
+/// tasks and recurrence templates are skipped; their instances are imported
+/// as recurring tasks.
+pub(super) fn convert(input: &str, builder: &mut Builder) -> Result<(), ImportError> {
+    let entries = parse_entries(input)?;
+    let mut ids: HashMap<&str, i64> = HashMap::new();
+
+    for entry in &entries {
+        match entry.status.as_str() {
+            "deleted" => {
+                builder.warn(format!("skipped deleted task '{}'", entry.description));
+                continue;
+            }
+            // The template that generates recurring instances
+            "recurring" => continue,
+            _ => {}
+        }
+
+        let mut task = Task::new(&entry.description);
+        if let Some(created_at) = timestamp(builder, entry, "entry", entry.entry.as_deref()) {
+            task.created_at = created_at;
+        }
+        task.updated_at = timestamp(builder, entry, "modified", entry.modified.as_deref()).unwrap_or(task.created_at);
+        task.due_date = timestamp(builder, entry, "due", entry.due.as_deref());
+        task.tag = entry.project.clone().or_else(|| entry.tags.first().cloned());
+        task.priority = match entry.priority.as_deref() {
+            Some("H") => Some(Priority::High),
+            Some("M") => Some(Priority::Medium),
+            Some("L") => Some(Priority::Low),
+            _ => None,
+        };
+        task.is_recurring = entry.recur.is_some();
+        if !entry.annotations.is_empty() {
+            let notes: Vec<&str> = entry.annotations.iter().map(|a| a.description.as_str()).collect();
+            task.details = Some(notes.join("\n"));
+        }
+
+        let id = builder.push(task);
+        ids.insert(&entry.uuid, id);
+        if entry.status == "completed" {
+            let end = timestamp(builder, entry, "end", entry.end.as_deref());
+            builder.complete(id, end);
+        }
+    }
+
+    for entry in &entries {
+        let Some(&id) = ids.get(entry.uuid.as_str()) else { continue };
+        for dependency in dependencies(entry.depends.as_ref()) {
+            let Some(&child) = ids.get(dependency.as_str()) else { continue };
+            if builder.is_ancestor(child, id) {
+                let title = builder.task_mut(child).title.clone();
+                builder.warn(format!("'{}' has circular dependencies, some were not linked", title));
+                continue;
+            }
+            let task = builder.task_mut(child);
+            if task.parent_id.is_none() {
+                task.parent_id = Some(id);
+            } else {
+                let title = task.title.clone();
+                builder.warn(format!("'{}' is a dependency of several tasks, kept under the first", title));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_entries(input: &str) -> Result<Vec<Entry>, ImportError> {
+    let invalid = |err: serde_json::Error| ImportError(format!("invalid Taskwarrior export: {}", err));
+    if input.trim_start().starts_with('[') {
+        return serde_json::from_str(input).map_err(invalid);
+    }
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line.trim_end_matches(',')).map_err(invalid))
+        .collect()
+}
+
+fn dependencies(depends: Option<&serde_json::Value>) -> Vec<String> {
+    match depends {
+        Some(serde_json::Value::Array(uuids)) => {
+            uuids.iter().filter_map(|uuid| uuid.as_str().map(str::to_string)).collect()
+        }
+        Some(serde_json::Value::String(uuids)) => {
+            uuids.split(',').map(|uuid| uuid.trim().to_string()).filter(|uuid| !uuid.is_empty()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Taskwarrior timestamps are UTC in the form `20261018T120000Z`.
+fn timestamp(builder: &mut Builder, entry: &Entry, field: &str, value: Option<&str>) -> Option<DateTime<Utc>> {
+    let value = value?;
+    match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        Ok(naive) => Some(naive.and_utc()),
+        Err(_) => {
+            builder.warn(format!("'{}': cannot read {} '{}'", entry.description, field, value));
+            None
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// core/src/importers/todo_txt.rs
+// todo.txt: `x (A) 2026-10-02 2026-10-01 Call mom +Family @phone due:2026-10-05`
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use std::collections::HashMap;
+use super::Builder;
+use crate::dates::{parse_due_date, start_of_day};
+use crate::task::{Priority, Task};
+
+/// The first `+project` (or, failing that, `@context`) becomes the tag; any
+/// others stay in the title. `due:` sets the due date, `rec:` marks the task
+/// recurring, and the `id:`/`p:` convention links subtasks to their parent.
+pub(super) fn convert<Tz: TimeZone>(input: &str, now: &DateTime<Tz>, builder: &mut Builder) {
+    let mut keys: HashMap<String, i64> = HashMap::new();
+    let mut parents: Vec<(i64, String, usize)> = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parsed = parse_line(line, now, builder, line_number);
+        if parsed.title.is_empty() {
+            builder.warn(format!("line {}: skipped, no task text", line_number));
+            continue;
+        }
+
+        let mut task = Task::new(&parsed.title);
+        task.created_at = parsed.created_at.unwrap_or_else(|| now.with_timezone(&Utc));
+        task.updated_at = task.created_at;
+        task.priority = parsed.priority;
+        task.tag = parsed.tag;
+        task.due_date = parsed.due_date;
+        task.is_recurring = parsed.recurring;
+        let id = builder.push(task);
+        if parsed.done {
+            builder.complete(id, parsed.completed_at.or(parsed.created_at));
+        }
+
+        if let Some(key) = parsed.key {
+            keys.insert(key, id);
+        }
+        if let Some(parent) = parsed.parent {
+            parents.push((id, parent, line_number));
+        }
+    }
+
+    for (id, parent, line_number) in parents {
+        match keys.get(&parent) {
+            Some(&parent_id) if !builder.is_ancestor(id, parent_id) => builder.task_mut(id).parent_id = Some(parent_id),
+            Some(_) => builder.warn(format!("line {}: 'p:{}' would make a cycle, not linked", line_number, parent)),
+            None => builder.warn(format!("line {}: parent 'p:{}' not found, imported as a top-level task", line_number, parent)),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Line {
+    title: String,
+    done: bool,
+    priority: Option<Priority>,
+    completed_at: Option<DateTime<Utc>>,
+    created_at: Option<DateTime<Utc>>,
+    tag: Option<String>,
+    due_date: Option<DateTime<Utc>>,
+    recurring: bool,
+    key: Option<String>,
+    parent: Option<String>,
+}
+
+fn parse_line<Tz: TimeZone>(line: &str, now: &DateTime<Tz>, builder: &mut Builder, line_number: usize) -> Line {
+    let mut parsed = Line::default();
+    let mut tokens = line.split_whitespace().peekable();
+    let tz = now.timezone();
+
+    if tokens.next_if_eq(&"x").is_some() {
+        parsed.done = true;
+    }
+    if let Some(priority) = tokens.peek().and_then(|token| parse_priority(token)) {
+        parsed.priority = Some(priority);
+        tokens.next();
+    }
+    // A completed task has its completion date first, then the creation date
+    let mut dates = Vec::new();
+    while dates.len() < 1 + usize::from(parsed.done) {
+        let Some(date) = tokens.peek().and_then(|token| NaiveDate::parse_from_str(token, "%Y-%m-%d").ok()) else {
+            break;
+        };
+        dates.push(date);
+        tokens.next();
+    }
+    match (parsed.done, dates.as_slice()) {
+        (true, [completed]) => parsed.completed_at = start_of_day(*completed, &tz),
+        (true, [completed, created]) => {
+            parsed.completed_at = start_of_day(*completed, &tz);
+            parsed.created_at = start_of_day(*created, &tz);
+        }
+        (false, [created]) => parsed.created_at = start_of_day(*created, &tz),
+        _ => {}
+    }
+
+    let mut words: Vec<&str> = Vec::new();
+    let mut project = None;
+    let mut context = None;
+    for token in tokens {
+        if let Some(name) = token.strip_prefix('+').filter(|name| !name.is_empty()) {
+            project = project.or(Some((words.len(), name)));
+        } else if let Some(name) = token.strip_prefix('@').filter(|name| !name.is_empty()) {
+            context = context.or(Some((words.len(), name)));
+        } else if let Some((key, value)) = token.split_once(':').filter(|(_, value)| !value.is_empty()) {
+            match key {
+                "due" => match parse_due_date(value, now) {
+                    Ok(due) => parsed.due_date = Some(due),
+                    Err(err) => builder.warn(format!("line {}: {}, due date dropped", line_number, err)),
+                },
+                "rec" => parsed.recurring = true,
+                "id" => parsed.key = Some(value.to_string()),
+                "p" => parsed.parent = Some(value.to_string()),
+                // Completed tasks often keep their priority as `pri:A`
+                "pri" => parsed.priority = parse_priority(&format!("({})", value)).or(parsed.priority),
+                _ => words.push(token),
+            }
+            continue;
+        }
+        words.push(token);
+    }
+
+    // The tag is taken out of the title; other projects and contexts are kept
+    if let Some((position, name)) = project.or(context) {
+        parsed.tag = Some(name.to_string());
+        words.remove(position);
+    }
+    parsed.title = words.join(" ");
+    parsed
+}
+
+/// `(A)` is urgent, `(B)` high, `(C)` medium and anything lower is low.
+fn parse_priority(token: &str) -> Option<Priority> {
+    let letter = token.strip_prefix('(')?.strip_suffix(')')?;
+    let [letter] = letter.as_bytes() else { return None };
+    match letter {
+        b'A' => Some(Priority::Urgent),
+        b'B' => Some(Priority::High),
+        b'C' => Some(Priority::Medium),
+        b'D'..=b'Z' => Some(Priority::Low),
+        _ => None,
+    }
+}
@@ -0,0 +1,104 @@
+// core/src/importers/todoist.rs
+// Todoist project backups: a CSV with TYPE, CONTENT, DESCRIPTION, PRIORITY, INDENT, ..., DATE columns.
+use chrono::{DateTime, TimeZone};
+use super::{Builder, ImportError};
+use crate::dates::parse_due_date;
+use crate::task::{Priority, Task};
+
+/// Each `task` row becomes a task and `note` rows are added to the details of
+/// the task above them. INDENT nests subtasks under the closest shallower task.
+/// The first `@label` in the content becomes the tag; backups do not name their
+/// project, so pass it to `convert` as the default tag. Todoist has no creation
+/// times in backups, so every task is created "now" and importing the same
+/// backup twice adds its tasks twice.
+pub(super) fn convert<Tz: TimeZone>(input: &str, now: &DateTime<Tz>, builder: &mut Builder) -> Result<(), ImportError> {
+    let invalid = |err: csv::Error| ImportError(format!("invalid Todoist backup: {}", err));
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(input.as_bytes());
+    let headers: Vec<String> = reader.headers().map_err(invalid)?.iter().map(|h| h.trim().to_uppercase()).collect();
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let (Some(kind), Some(content)) = (column("TYPE"), column("CONTENT")) else {
+        return Err(ImportError("invalid Todoist backup: missing TYPE or CONTENT column".to_string()));
+    };
+    let (description, priority, indent, date) = (column("DESCRIPTION"), column("PRIORITY"), column("INDENT"), column("DATE"));
+
+    // Tasks that can still take subtasks, with their indent
+    let mut open: Vec<(u32, i64)> = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(invalid)?;
+        // The header is line 1
+        let line_number = index + 2;
+        let field = |column: Option<usize>| column.and_then(|c| record.get(c)).map(str::trim).unwrap_or("");
+
+        match field(Some(kind)) {
+            "task" => {}
+            "note" => {
+                let Some(&(_, id)) = open.last() else { continue };
+                let task = builder.task_mut(id);
+                let note = field(Some(content));
+                task.details = Some(match task.details.take() {
+                    Some(details) => format!("{}\n{}", details, note),
+                    None => note.to_string(),
+                });
+                continue;
+            }
+            // Sections, view settings and blank separator rows
+            _ => continue,
+        }
+
+        let (title, label) = take_label(field(Some(content)));
+        if title.is_empty() {
+            builder.warn(format!("line {}: skipped, no task text", line_number));
+            continue;
+        }
+        let mut task = Task::new(&title);
+        task.created_at = now.with_timezone(&chrono::Utc);
+        task.updated_at = task.created_at;
+        task.tag = label;
+        task.details = Some(field(description).to_string()).filter(|d| !d.is_empty());
+        // Backups number priorities as the app shows them: 1 is p1, the highest
+        task.priority = match field(priority) {
+            "1" => Some(Priority::Urgent),
+            "2" => Some(Priority::High),
+            "3" => Some(Priority::Medium),
+            _ => None,
+        };
+
+        let date = field(date);
+        if !date.is_empty() {
+            let (recurring, when) = match date.strip_prefix("every ") {
+                Some(rest) => (true, rest),
+                None => (false, date),
+            };
+            task.is_recurring = recurring;
+            match parse_due_date(when, now) {
+                Ok(due) => task.due_date = Some(due),
+                Err(_) => builder.warn(format!("line {}: cannot understand date '{}', due date dropped", line_number, date)),
+            }
+        }
+
+        let indent: u32 = field(indent).parse().unwrap_or(1);
+        while open.last().is_some_and(|&(open_indent, _)| open_indent >= indent) {
+            open.pop();
+        }
+        task.parent_id = open.last().map(|&(_, id)| id);
+        let id = builder.push(task);
+        open.push((indent, id));
+    }
+    Ok(())
+}
+
+/// Removes the first `@label` from the content, returning it as the tag.
+fn take_label(content: &str) -> (String, Option<String>) {
+    let mut label = None;
+    let words: Vec<&str> = content
+        .split_whitespace()
+        .filter(|word| match word.strip_prefix('@').filter(|name| !name.is_empty()) {
+            Some(name) if label.is_none() => {
+                label = Some(name.to_string());
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    (words.join(" "), label)
+}
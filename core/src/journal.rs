@@ -0,0 +1,452 @@
+// core/src/journal.rs
+// How you feel: mood, energy and sleep ratings with notes, and how they line up
+// with tasks completed and meals logged each day.
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::database::Database;
+use crate::dates;
+use crate::task_list::TaskList;
+
+/// Ratings go from 1 (worst) to 5 (best).
+pub const RATING_SCALE: std::ops::RangeInclusive<u8> = 1..=5;
+
+// Fewer days than this say nothing about a correlation
+const MIN_CORRELATION_DAYS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalError {
+    InvalidRating(&'static str, u8),
+    InvalidSleepHours(f64),
+    Empty,
+}
+
+impl std::fmt::Display for JournalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JournalError::InvalidRating(field, value) => write!(
+                f,
+                "{} must be from {} to {}, not {}",
+                field,
+                RATING_SCALE.start(),
+                RATING_SCALE.end(),
+                value
+            ),
+            JournalError::InvalidSleepHours(hours) => write!(f, "cannot have slept {} hours in a day", hours),
+            JournalError::Empty => write!(f, "a journal entry needs a rating or a note"),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub id: Option<i64>,
+    pub recorded_at: DateTime<Utc>,
+    pub mood: Option<u8>,
+    pub energy: Option<u8>,
+    /// How well you slept
+    pub sleep: Option<u8>,
+    pub sleep_hours: Option<f64>,
+    pub notes: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(recorded_at: DateTime<Utc>) -> Self {
+        JournalEntry { id: None, recorded_at, mood: None, energy: None, sleep: None, sleep_hours: None, notes: None }
+    }
+
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.trim().to_string()).filter(|notes| !notes.is_empty());
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), JournalError> {
+        for (field, rating) in [("mood", self.mood), ("energy", self.energy), ("sleep", self.sleep)] {
+            if let Some(rating) = rating.filter(|rating| !RATING_SCALE.contains(rating)) {
+                return Err(JournalError::InvalidRating(field, rating));
+            }
+        }
+        if let Some(hours) = self.sleep_hours.filter(|hours| !(0.0..=24.0).contains(hours)) {
+            return Err(JournalError::InvalidSleepHours(hours));
+        }
+        let rated = self.mood.is_some() || self.energy.is_some() || self.sleep.is_some() || self.sleep_hours.is_some();
+        if !rated && self.notes.is_none() {
+            return Err(JournalError::Empty);
+        }
+        Ok(())
+    }
+}
+
+/// A day's journal averages next to what else happened that day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalDay {
+    pub date: NaiveDate,
+    pub entries: u32,
+    pub mood: Option<f64>,
+    pub energy: Option<f64>,
+    pub sleep: Option<f64>,
+    pub sleep_hours: Option<f64>,
+    pub tasks_completed: u32,
+    pub meals_logged: u32,
+    pub energy_kcal: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wellbeing {
+    Mood,
+    Energy,
+    Sleep,
+    SleepHours,
+}
+
+impl Wellbeing {
+    pub const ALL: [Wellbeing; 4] = [Wellbeing::Mood, Wellbeing::Energy, Wellbeing::Sleep, Wellbeing::SleepHours];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Wellbeing::Mood => "mood",
+            Wellbeing::Energy => "energy",
+            Wellbeing::Sleep => "sleep",
+            Wellbeing::SleepHours => "sleep hours",
+        }
+    }
+
+    fn of(self, day: &JournalDay) -> Option<f64> {
+        match self {
+            Wellbeing::Mood => day.mood,
+            Wellbeing::Energy => day.energy,
+            Wellbeing::Sleep => day.sleep,
+            Wellbeing::SleepHours => day.sleep_hours,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activity {
+    TasksCompleted,
+    MealsLogged,
+    EnergyKcal,
+}
+
+impl Activity {
+    pub const ALL: [Activity; 3] = [Activity::TasksCompleted, Activity::MealsLogged, Activity::EnergyKcal];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Activity::TasksCompleted => "tasks completed",
+            Activity::MealsLogged => "meals logged",
+            Activity::EnergyKcal => "kcal eaten",
+        }
+    }
+
+    fn of(self, day: &JournalDay) -> f64 {
+        match self {
+            Activity::TasksCompleted => day.tasks_completed as f64,
+            Activity::MealsLogged => day.meals_logged as f64,
+            Activity::EnergyKcal => day.energy_kcal,
+        }
+    }
+}
+
+/// How closely a journal rating moves with a daily activity, over the days that have the rating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Correlation {
+    pub wellbeing: Wellbeing,
+    pub activity: Activity,
+    /// Pearson's r from -1 to 1; None with fewer than 3 days or when either side never changes
+    pub coefficient: Option<f64>,
+    pub days: u32,
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    db.connection().execute_batch(
+        "CREATE TABLE IF NOT EXISTS journal_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            recorded_at TEXT NOT NULL,
+            mood INTEGER,
+            energy INTEGER,
+            sleep INTEGER,
+            sleep_hours REAL,
+            notes TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_journal_entries_recorded_at ON journal_entries(recorded_at);",
+    )
+}
+
+fn entry_from_row(row: &Row) -> rusqlite::Result<JournalEntry> {
+    let recorded_at: String = row.get(1)?;
+    Ok(JournalEntry {
+        id: Some(row.get(0)?),
+        recorded_at: DateTime::parse_from_rfc3339(&recorded_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+        mood: row.get(2)?,
+        energy: row.get(3)?,
+        sleep: row.get(4)?,
+        sleep_hours: row.get(5)?,
+        notes: row.get(6)?,
+    })
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+fn pearson(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < MIN_CORRELATION_DAYS {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+        variance_y += (y - mean_y).powi(2);
+    }
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+    Some(covariance / (variance_x * variance_y).sqrt())
+}
+
+impl TaskList {
+    pub fn add_journal_entry(&mut self, entry: &JournalEntry) -> Result<i64, Box<dyn std::error::Error>> {
+        entry.validate()?;
+        let conn = self.database().connection();
+        conn.execute(
+            "INSERT INTO journal_entries (recorded_at, mood, energy, sleep, sleep_hours, notes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.recorded_at.to_rfc3339(),
+                entry.mood,
+                entry.energy,
+                entry.sleep,
+                entry.sleep_hours,
+                entry.notes
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_journal_entry(&self, id: i64) -> Result<Option<JournalEntry>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(
+            "SELECT id, recorded_at, mood, energy, sleep, sleep_hours, notes FROM journal_entries WHERE id = ?1",
+        )?;
+        let mut rows = stmt.query_map([id], entry_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Entries recorded from `start` up to but not including `end`, oldest first.
+    pub fn journal_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<JournalEntry>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(
+            "SELECT id, recorded_at, mood, energy, sleep, sleep_hours, notes FROM journal_entries
+             WHERE recorded_at >= ?1 AND recorded_at < ?2 ORDER BY recorded_at, id",
+        )?;
+        let entries = stmt.query_map(params![start.to_rfc3339(), end.to_rfc3339()], entry_from_row)?;
+        Ok(entries.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn delete_journal_entry(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().connection().execute("DELETE FROM journal_entries WHERE id = ?1", [id])?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    /// Each day from `first` to `last` inclusive in `tz`: journal averages, tasks
+    /// completed and meals logged. Days without entries have no ratings.
+    pub fn journal_days<Tz: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Tz,
+    ) -> Result<Vec<JournalDay>, Box<dyn std::error::Error>> {
+        let start = dates::start_of_day(first, tz).ok_or_else(|| format!("{} is out of range", first))?;
+        let end = last
+            .succ_opt()
+            .and_then(|next| dates::start_of_day(next, tz))
+            .ok_or_else(|| format!("{} is out of range", last))?;
+
+        #[derive(Default)]
+        struct Ratings {
+            entries: u32,
+            mood: Vec<f64>,
+            energy: Vec<f64>,
+            sleep: Vec<f64>,
+            sleep_hours: Vec<f64>,
+            tasks_completed: u32,
+            meals_logged: u32,
+            energy_kcal: f64,
+        }
+        let mut days: BTreeMap<NaiveDate, Ratings> =
+            first.iter_days().take_while(|day| *day <= last).map(|day| (day, Ratings::default())).collect();
+        let local_day = |at: DateTime<Utc>| at.with_timezone(tz).date_naive();
+
+        for entry in self.journal_between(start, end)? {
+            let Some(day) = days.get_mut(&local_day(entry.recorded_at)) else { continue };
+            day.entries += 1;
+            day.mood.extend(entry.mood.map(f64::from));
+            day.energy.extend(entry.energy.map(f64::from));
+            day.sleep.extend(entry.sleep.map(f64::from));
+            day.sleep_hours.extend(entry.sleep_hours);
+        }
+
+        let conn = self.database().connection();
+        let mut stmt =
+            conn.prepare("SELECT at FROM task_history WHERE event = 'completed' AND at >= ?1 AND at < ?2")?;
+        let completions = stmt.query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| row.get::<_, String>(0))?;
+        for at in completions {
+            let Ok(at) = DateTime::parse_from_rfc3339(&at?) else { continue };
+            if let Some(day) = days.get_mut(&local_day(at.with_timezone(&Utc))) {
+                day.tasks_completed += 1;
+            }
+        }
+
+        for logged in self.meals_between(start, end)? {
+            if let Some(day) = days.get_mut(&local_day(logged.entry.eaten_at)) {
+                day.meals_logged += 1;
+                day.energy_kcal += logged.nutrients.energy_kcal;
+            }
+        }
+
+        Ok(days
+            .into_iter()
+            .map(|(date, ratings)| JournalDay {
+                date,
+                entries: ratings.entries,
+                mood: average(&ratings.mood),
+                energy: average(&ratings.energy),
+                sleep: average(&ratings.sleep),
+                sleep_hours: average(&ratings.sleep_hours),
+                tasks_completed: ratings.tasks_completed,
+                meals_logged: ratings.meals_logged,
+                energy_kcal: ratings.energy_kcal,
+            })
+            .collect())
+    }
+
+    /// Every journal rating against every activity over the days from `first`
+    /// to `last`, in `tz`.
+    pub fn journal_correlations<Tz: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Tz,
+    ) -> Result<Vec<Correlation>, Box<dyn std::error::Error>> {
+        let days = self.journal_days(first, last, tz)?;
+        let mut correlations = Vec::new();
+        for wellbeing in Wellbeing::ALL {
+            for activity in Activity::ALL {
+                let pairs: Vec<(f64, f64)> = days
+                    .iter()
+                    .filter_map(|day| Some((wellbeing.of(day)?, activity.of(day))))
+                    .collect();
+                correlations.push(Correlation {
+                    wellbeing,
+                    activity,
+                    coefficient: pearson(&pairs),
+                    days: pairs.len() as u32,
+                });
+            }
+        }
+        Ok(correlations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nutrition::{Food, Meal, MealEntry, Nutrients};
+    use crate::Task;
+    use chrono::FixedOffset;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_entries_are_validated() {
+        let mut entry = JournalEntry::new(Utc::now());
+        assert_eq!(entry.validate(), Err(JournalError::Empty));
+        entry.mood = Some(6);
+        assert_eq!(entry.validate(), Err(JournalError::InvalidRating("mood", 6)));
+        entry.mood = Some(5);
+        entry.sleep_hours = Some(25.0);
+        assert_eq!(entry.validate(), Err(JournalError::InvalidSleepHours(25.0)));
+        assert!(JournalEntry::new(Utc::now()).with_notes("Rainy, but fine").validate().is_ok());
+        assert_eq!(JournalEntry::new(Utc::now()).with_notes("  ").notes, None);
+        assert_eq!(pearson(&[(1.0, 2.0), (2.0, 4.0), (3.0, 6.0)]), Some(1.0));
+        assert_eq!(pearson(&[(1.0, 2.0), (2.0, 2.0), (3.0, 2.0)]), None);
+        assert_eq!(pearson(&[(1.0, 2.0), (2.0, 4.0)]), None);
+    }
+
+    #[test]
+    fn test_days_line_up_with_tasks_and_meals() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let rice = task_list.add_food(&Food::new("Rice", Nutrients { energy_kcal: 130.0, ..Default::default() }))?;
+
+        // Mood rises with tasks done over three days in Tokyo
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        for (day, mood, tasks) in [(12, 2, 0), (13, 3, 1), (14, 5, 3)] {
+            let mut entry = JournalEntry::new(at(&format!("2026-10-{}T12:00:00Z", day)));
+            entry.mood = Some(mood);
+            entry.sleep_hours = Some(7.0);
+            task_list.add_journal_entry(&entry)?;
+            for _ in 0..tasks {
+                let id = task_list.add(Task::new("Chore"))?;
+                task_list.mark_done(id)?;
+                task_list.database().connection().execute(
+                    "UPDATE task_history SET at = ?1 WHERE event = 'completed' AND task_id = ?2",
+                    params![format!("2026-10-{}T10:00:00+00:00", day), id],
+                )?;
+            }
+        }
+        let mut evening = JournalEntry::new(at("2026-10-14T14:30:00Z")).with_notes("Good day");
+        evening.mood = Some(4);
+        let evening = task_list.add_journal_entry(&evening)?;
+        // 20:00 in Tokyo on the 13th
+        task_list.log_meal(&MealEntry::new(rice, 200.0, Meal::Dinner, at("2026-10-13T11:00:00Z")))?;
+        assert!(task_list.add_journal_entry(&JournalEntry::new(Utc::now())).is_err());
+
+        let first = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let last = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let days = task_list.journal_days(first, last, &tokyo)?;
+        assert_eq!(days.len(), 4);
+        assert_eq!((days[0].mood, days[0].tasks_completed), (Some(2.0), 0));
+        assert_eq!((days[1].meals_logged, days[1].energy_kcal), (1, 260.0));
+        assert_eq!((days[2].entries, days[2].mood, days[2].tasks_completed), (2, Some(4.5), 3));
+        assert_eq!((days[3].entries, days[3].mood), (0, None));
+
+        let correlations = task_list.journal_correlations(first, last, &tokyo)?;
+        assert_eq!(correlations.len(), 12);
+        let mood_tasks = &correlations[0];
+        assert_eq!((mood_tasks.wellbeing, mood_tasks.activity), (Wellbeing::Mood, Activity::TasksCompleted));
+        assert_eq!(mood_tasks.days, 3);
+        assert!(mood_tasks.coefficient.unwrap() > 0.9);
+        let sleep_tasks = correlations.iter().find(|c| c.wellbeing == Wellbeing::SleepHours).unwrap();
+        assert_eq!(sleep_tasks.coefficient, None, "sleep never changed");
+        assert_eq!(correlations.iter().find(|c| c.wellbeing == Wellbeing::Energy).unwrap().days, 0);
+
+        assert!(task_list.delete_journal_entry(evening)?);
+        assert!(task_list.get_journal_entry(evening)?.is_none());
+        Ok(())
+    }
+}
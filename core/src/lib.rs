@@ -1,11 +1,47 @@
 pub mod task;
 pub mod task_list;
 pub mod database;
+pub mod changes;
+pub mod dates;
+pub mod quick_add;
+pub mod export;
+pub mod importers;
+pub mod ics;
+pub mod nutrition;
+pub mod fitness;
+pub mod journal;
+pub mod lists;
+pub mod ordering;
+pub mod reports;
 pub mod ffi;
+pub mod bindings;
+mod scaffolding;
 
-pub use task::Task;
+// The generated code refers to these from the crate root
+use scaffolding::{UniFfiTag, UniffiCustomTypeConverter};
+
+pub use task::{Priority, Task};
 pub use task_list::TaskList;
-pub use database::{Database, TaskFilter, TaskStatus};
+pub use database::{Database, HistoryEntry, TaskEvent, TaskFilter, TaskStatus};
+pub use export::{Archive, ArchiveError, ImportSummary};
+pub use importers::{Conversion, ImportError, SourceFormat};
+pub use nutrition::{
+    Amount, DayNutrition, Food, FoodDump, FoodImportSummary, Ingredient, LoggedMeal, Meal, MealEntry, Nutrients,
+    NutritionError, PantryItem, PantryReport, Quantity, Recipe, Unit,
+};
+pub use fitness::{
+    Exercise, ExerciseKind, FitnessError, LoggedSet, PersonalRecord, PlanSession, PlannedTask, RecordKind, SetMeasures,
+    TrainingPlan, Volume, WeeklyVolume, Workout, WorkoutSet,
+};
+pub use journal::{Activity, Correlation, JournalDay, JournalEntry, JournalError, Wellbeing};
+pub use lists::{List, ListError, TagMigration};
+pub use ordering::OrderingError;
+pub use reports::{
+    GroupBy, Habit, OnTime, PeriodCount, ReportError, Review, SlippedTask, Stats, TagCount, TimeSpent,
+};
+pub use changes::{Change, ChangeKind, ChangeObserver, ChangeSet};
+pub use quick_add::QuickAddError;
+pub use bindings::{parse_quick_add, FreeList, FreeListError, UtcDateTime};
 pub use chrono::{DateTime, Utc};
 
 pub fn add(left: u64, right: u64) -> u64 {
@@ -52,4 +88,54 @@ mod tests {
         
         Ok(())
     }
+
+    #[test]
+    fn test_update_saves_edits() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let id = task_list.add(Task::new("Draft"))?;
+
+        let mut task = task_list.get_by_id(id)?.unwrap();
+        task.title = "Final".to_string();
+        task.record_duration(25);
+        task = task.with_tag("writing");
+        task_list.update(&task)?;
+
+        let stored = task_list.get_by_id(id)?.unwrap();
+        assert_eq!(stored.title, "Final");
+        assert_eq!(stored.tag.as_deref(), Some("writing"));
+        assert_eq!(stored.last_duration, Some(25));
+        assert_eq!(stored.created_at, task.created_at);
+        assert!(task_list.update(&Task::new("Unsaved")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_migrates_databases_without_priority() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!("freelist-migrate-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let conn = rusqlite::Connection::open(&path)?;
+            conn.execute_batch(
+                "CREATE TABLE tasks (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, details TEXT,
+                    done INTEGER NOT NULL DEFAULT 0, due_date TEXT, is_recurring INTEGER NOT NULL DEFAULT 0,
+                    estimated_duration INTEGER, last_duration INTEGER, tag TEXT, parent_id INTEGER,
+                    created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                 INSERT INTO tasks (title, created_at, updated_at)
+                    VALUES ('Old task', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');",
+            )?;
+        }
+
+        let mut task_list = TaskList::new(&path)?;
+        assert_eq!(task_list.all()?[0].priority, None);
+        assert_eq!(task_list.all()?[0].list_id, None);
+        assert_eq!(task_list.all()?[0].position.as_deref(), Some("a0"));
+        let id = task_list.add(Task::new("New task").with_priority(Priority::Urgent))?;
+        assert_eq!(task_list.get_by_id(id)?.unwrap().priority, Some(Priority::Urgent));
+        assert_eq!(task_list.all()?[0].id, Some(id));
+
+        drop(task_list);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
 }
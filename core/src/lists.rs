@@ -0,0 +1,310 @@
+// core/src/lists.rs
+// Lists such as Groceries, Work or Home that hold tasks. A task is in at most one
+// list; tags are separate and stay as they are.
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use crate::database::Database;
+use crate::task_list::TaskList;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListError {
+    EmptyName,
+    InvalidColour(String),
+    DuplicateName(String),
+}
+
+impl std::fmt::Display for ListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListError::EmptyName => write!(f, "a list needs a name"),
+            ListError::InvalidColour(colour) => {
+                write!(f, "'{}' is not a colour; use #rgb or #rrggbb, e.g. #3a7bd5", colour)
+            }
+            ListError::DuplicateName(name) => write!(f, "there is already a list called {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ListError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct List {
+    pub id: Option<i64>,
+    pub name: String,
+    /// "#rrggbb" or "#rgb"
+    pub colour: Option<String>,
+    /// An emoji or an icon name understood by the app
+    pub icon: Option<String>,
+    /// Lists are shown lowest first
+    pub sort_order: i64,
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl List {
+    pub fn new(name: &str) -> Self {
+        List {
+            id: None,
+            name: name.trim().to_string(),
+            colour: None,
+            icon: None,
+            sort_order: 0,
+            archived: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_colour(mut self, colour: &str) -> Self {
+        self.colour = Some(colour.trim().to_lowercase());
+        self
+    }
+
+    pub fn with_icon(mut self, icon: &str) -> Self {
+        self.icon = Some(icon.trim().to_string()).filter(|icon| !icon.is_empty());
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ListError> {
+        if self.name.trim().is_empty() {
+            return Err(ListError::EmptyName);
+        }
+        if let Some(colour) = &self.colour {
+            let digits = colour.strip_prefix('#').unwrap_or("");
+            if !matches!(digits.len(), 3 | 6) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ListError::InvalidColour(colour.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What `TaskList::lists_from_tags` did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagMigration {
+    pub lists_created: u32,
+    pub tasks_moved: u32,
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    db.connection().execute_batch(
+        "CREATE TABLE IF NOT EXISTS lists (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            colour TEXT,
+            icon TEXT,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            archived INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL
+        );
+        -- Tasks outlive their list
+        CREATE TRIGGER IF NOT EXISTS lists_deleted AFTER DELETE ON lists
+        BEGIN
+            UPDATE tasks SET list_id = NULL WHERE list_id = OLD.id;
+        END;",
+    )
+}
+
+const LIST_COLUMNS: &str = "id, name, colour, icon, sort_order, archived, created_at";
+
+fn list_from_row(row: &Row) -> rusqlite::Result<List> {
+    let created_at: String = row.get(6)?;
+    Ok(List {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        colour: row.get(2)?,
+        icon: row.get(3)?,
+        sort_order: row.get(4)?,
+        archived: row.get(5)?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+// Adds a list after every other one
+pub(crate) fn insert_list(db: &Database, list: &List) -> rusqlite::Result<i64> {
+    let conn = db.connection();
+    conn.execute(
+        "INSERT INTO lists (name, colour, icon, sort_order, archived, created_at)
+         SELECT ?1, ?2, ?3, COALESCE(MAX(sort_order) + 1, 0), ?4, ?5 FROM lists",
+        params![list.name, list.colour, list.icon, list.archived, list.created_at.to_rfc3339()],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub(crate) fn find_list_id(db: &Database, name: &str) -> rusqlite::Result<Option<i64>> {
+    let conn = db.connection();
+    let mut stmt = conn.prepare("SELECT id FROM lists WHERE name = ?1 COLLATE NOCASE")?;
+    let mut rows = stmt.query_map([name.trim()], |row| row.get(0))?;
+    rows.next().transpose()
+}
+
+impl TaskList {
+    /// Adds a list at the end; move it with `update_list`.
+    pub fn add_list(&mut self, list: &List) -> Result<i64, Box<dyn std::error::Error>> {
+        list.validate()?;
+        if find_list_id(self.database(), &list.name)?.is_some() {
+            return Err(ListError::DuplicateName(list.name.clone()).into());
+        }
+        let id = insert_list(self.database(), list)?;
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_list(&self, id: i64) -> Result<Option<List>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM lists WHERE id = ?1", LIST_COLUMNS))?;
+        let mut rows = stmt.query_map([id], list_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// The list with this name, ignoring case.
+    pub fn find_list(&self, name: &str) -> Result<Option<List>, Box<dyn std::error::Error>> {
+        match find_list_id(self.database(), name)? {
+            Some(id) => self.get_list(id),
+            None => Ok(None),
+        }
+    }
+
+    /// Lists in their sort order, leaving out archived ones unless asked.
+    pub fn lists(&self, include_archived: bool) -> Result<Vec<List>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM lists WHERE ?1 OR archived = 0 ORDER BY sort_order, name COLLATE NOCASE",
+            LIST_COLUMNS
+        ))?;
+        let lists = stmt.query_map([include_archived], list_from_row)?;
+        Ok(lists.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Saves every field of a stored list. Returns false if it does not exist.
+    pub fn update_list(&mut self, list: &List) -> Result<bool, Box<dyn std::error::Error>> {
+        let id = list.id.ok_or("list has no id")?;
+        list.validate()?;
+        if find_list_id(self.database(), &list.name)?.is_some_and(|other| other != id) {
+            return Err(ListError::DuplicateName(list.name.clone()).into());
+        }
+        let updated = self.database().connection().execute(
+            "UPDATE lists SET name = ?1, colour = ?2, icon = ?3, sort_order = ?4, archived = ?5 WHERE id = ?6",
+            params![list.name, list.colour, list.icon, list.sort_order, list.archived, id],
+        )?;
+        self.poll_changes()?;
+        Ok(updated > 0)
+    }
+
+    /// Deletes a list; its tasks are kept outside any list.
+    pub fn delete_list(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().connection().execute("DELETE FROM lists WHERE id = ?1", [id])?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    /// Puts a task in a list, or takes it out of its list. Returns false if the task does not exist.
+    pub fn set_task_list(&mut self, task_id: i64, list_id: Option<i64>) -> Result<bool, Box<dyn std::error::Error>> {
+        let updated = self.database().connection().execute(
+            "UPDATE tasks SET list_id = ?1, updated_at = ?2 WHERE id = ?3",
+            params![list_id, Utc::now().to_rfc3339(), task_id],
+        )?;
+        self.poll_changes()?;
+        Ok(updated > 0)
+    }
+
+    /// Makes a list for each tag (or uses the list already named like it) and
+    /// moves the tag's tasks that are in no list into it. Tags are kept.
+    pub fn lists_from_tags(&mut self) -> Result<TagMigration, Box<dyn std::error::Error>> {
+        let migration = self.database().with_transaction(|db| {
+            let mut migration = TagMigration::default();
+            let tags: Vec<String> = {
+                let mut stmt = db.connection().prepare(
+                    "SELECT DISTINCT tag FROM tasks WHERE list_id IS NULL AND tag IS NOT NULL AND tag != ''
+                     ORDER BY tag COLLATE NOCASE",
+                )?;
+                let tags = stmt.query_map([], |row| row.get(0))?;
+                tags.collect::<rusqlite::Result<_>>()?
+            };
+            for tag in tags {
+                let list_id = match find_list_id(db, &tag)? {
+                    Some(id) => id,
+                    None => {
+                        migration.lists_created += 1;
+                        insert_list(db, &List::new(&tag))?
+                    }
+                };
+                let moved = db.connection().execute(
+                    "UPDATE tasks SET list_id = ?1 WHERE tag = ?2 AND list_id IS NULL",
+                    params![list_id, tag],
+                )?;
+                migration.tasks_moved += moved as u32;
+            }
+            Ok::<_, rusqlite::Error>(migration)
+        })?;
+        self.poll_changes()?;
+        Ok(migration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Task, TaskFilter, TaskStatus};
+
+    #[test]
+    fn test_lists_hold_tasks() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let groceries = task_list.add_list(&List::new("Groceries").with_colour("#3A7BD5").with_icon("🛒"))?;
+        let work = task_list.add_list(&List::new(" Work "))?;
+        assert_eq!(task_list.get_list(groceries)?.unwrap().colour.as_deref(), Some("#3a7bd5"));
+        assert_eq!(task_list.find_list("work")?.unwrap().sort_order, 1);
+        assert!(task_list.add_list(&List::new("GROCERIES")).is_err());
+        assert_eq!(List::new("Home").with_colour("blue").validate(), Err(ListError::InvalidColour("blue".into())));
+        assert_eq!(List::new("  ").validate(), Err(ListError::EmptyName));
+
+        let milk = task_list.add(Task::new("Milk").with_list(groceries))?;
+        let eggs = task_list.add(Task::new("Eggs").with_list(groceries))?;
+        let report = task_list.add(Task::new("Report"))?;
+        assert!(task_list.set_task_list(report, Some(work))?);
+        task_list.mark_done(eggs)?;
+        let titles = |tasks: Vec<Task>| tasks.into_iter().map(|task| task.title).collect::<Vec<_>>();
+        assert_eq!(titles(task_list.get_by_list(groceries)?), vec!["Eggs", "Milk"]);
+        let open = TaskFilter { status: Some(TaskStatus::Todo), list_id: Some(groceries), ..Default::default() };
+        assert_eq!(titles(task_list.fetch(open)?), vec!["Milk"]);
+
+        // Moved to the front and archived
+        let mut list = task_list.get_list(work)?.unwrap();
+        list.sort_order = -1;
+        list.archived = true;
+        assert!(task_list.update_list(&list)?);
+        assert!(task_list.lists(false)?.iter().all(|list| list.name != "Work"));
+        assert_eq!(task_list.lists(true)?[0].name, "Work");
+        list.name = "groceries".to_string();
+        assert!(task_list.update_list(&list).is_err());
+
+        assert!(task_list.delete_list(groceries)?);
+        assert!(!task_list.delete_list(groceries)?);
+        assert_eq!(task_list.get_by_id(milk)?.unwrap().list_id, None);
+        assert_eq!(task_list.get_by_id(report)?.unwrap().list_id, Some(work));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lists_from_tags() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let home = task_list.add_list(&List::new("Home"))?;
+        task_list.add(Task::new("Laundry").with_tag("home"))?;
+        task_list.add(Task::new("Flights").with_tag("travel"))?;
+        task_list.add(Task::new("Hotel").with_tag("travel"))?;
+        let elsewhere = task_list.add(Task::new("Passport").with_tag("travel").with_list(home))?;
+        task_list.add(Task::new("Untagged"))?;
+
+        let migration = task_list.lists_from_tags()?;
+        assert_eq!(migration, TagMigration { lists_created: 1, tasks_moved: 3 });
+        let travel = task_list.find_list("travel")?.unwrap();
+        assert_eq!((travel.name.as_str(), travel.sort_order), ("travel", 1));
+        assert_eq!(task_list.get_by_list(travel.id.unwrap())?.len(), 2);
+        assert_eq!(task_list.get_by_list(home)?.len(), 2);
+        assert_eq!(task_list.get_by_id(elsewhere)?.unwrap().list_id, Some(home));
+        assert_eq!(task_list.lists_from_tags()?, TagMigration::default());
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+// core/src/nutrition/catalogue.rs
+// Food databases imported from Open Food Facts and USDA FoodData Central dumps,
+// found by barcode or by full-text search on name and brand.
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use rusqlite::params_from_iter;
+use serde::{Deserialize, Serialize};
+use crate::database::Database;
+use crate::task_list::TaskList;
+use super::{food_columns, food_from_row, food_insert_columns, food_values, insert_food, open_food_facts, usda, Food};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodDump {
+    /// A CSV (tab- or comma-separated) or JSONL export of Open Food Facts products
+    OpenFoodFacts,
+    /// A directory of FoodData Central CSV files: food.csv, food_nutrient.csv
+    /// and, for branded foods, branded_food.csv
+    Usda,
+}
+
+impl FoodDump {
+    /// FoodData Central dumps are directories; anything else is taken to be Open Food Facts.
+    pub fn detect(path: &Path) -> FoodDump {
+        if path.is_dir() {
+            FoodDump::Usda
+        } else {
+            FoodDump::OpenFoodFacts
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FoodImportError {
+    Io(String),
+    Csv(String),
+    MissingColumn { file: String, column: &'static str },
+    Storage(String),
+}
+
+impl std::fmt::Display for FoodImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FoodImportError::Io(msg) => write!(f, "{}", msg),
+            FoodImportError::Csv(msg) => write!(f, "invalid CSV: {}", msg),
+            FoodImportError::MissingColumn { file, column } => write!(f, "{} has no '{}' column", file, column),
+            FoodImportError::Storage(msg) => write!(f, "storage error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FoodImportError {}
+
+impl From<rusqlite::Error> for FoodImportError {
+    fn from(err: rusqlite::Error) -> Self {
+        FoodImportError::Storage(err.to_string())
+    }
+}
+
+impl From<csv::Error> for FoodImportError {
+    fn from(err: csv::Error) -> Self {
+        FoodImportError::Csv(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for FoodImportError {
+    fn from(err: std::io::Error) -> Self {
+        FoodImportError::Io(err.to_string())
+    }
+}
+
+/// What `TaskList::import_food_dump` did.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FoodImportSummary {
+    pub foods_added: u32,
+    /// Foods imported before from the same source, matched by `source_id`
+    pub foods_updated: u32,
+    /// Records without a name or any nutrition facts
+    pub records_skipped: u32,
+}
+
+/// Receives the foods read from a dump, one at a time so whole dumps never sit in memory.
+pub(crate) trait FoodSink {
+    fn add(&mut self, food: Food) -> Result<(), FoodImportError>;
+    fn skip(&mut self);
+}
+
+struct Upserter<'a> {
+    db: &'a Database,
+    summary: FoodImportSummary,
+}
+
+impl FoodSink for Upserter<'_> {
+    fn add(&mut self, food: Food) -> Result<(), FoodImportError> {
+        let conn = self.db.connection();
+        let existing = match &food.source_id {
+            Some(source_id) => {
+                let mut stmt = conn.prepare_cached("SELECT id FROM foods WHERE source_id = ?1")?;
+                let mut rows = stmt.query_map([source_id], |row| row.get::<_, i64>(0))?;
+                rows.next().transpose()?
+            }
+            None => None,
+        };
+
+        match existing {
+            Some(id) => {
+                // Every column but created_at (?4) is overwritten
+                let mut values = food_values(&food);
+                let assignments: Vec<String> = food_insert_columns()
+                    .split(", ")
+                    .enumerate()
+                    .filter(|(_, column)| *column != "created_at")
+                    .map(|(index, column)| format!("{} = ?{}", column, index + 1))
+                    .collect();
+                values.push(id.into());
+                conn.prepare_cached(&format!(
+                    "UPDATE foods SET {} WHERE id = ?{}",
+                    assignments.join(", "),
+                    values.len()
+                ))?
+                .execute(params_from_iter(values))?;
+                self.summary.foods_updated += 1;
+            }
+            None => {
+                insert_food(self.db, &food)?;
+                self.summary.foods_added += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn skip(&mut self) {
+        self.summary.records_skipped += 1;
+    }
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    let conn = db.connection();
+    let indexed: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'foods_fts'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_foods_barcode ON foods(barcode);
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_foods_source_id ON foods(source_id);
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS foods_fts USING fts5(
+            name, brand, content = 'foods', content_rowid = 'id', tokenize = 'unicode61 remove_diacritics 2'
+        );
+        CREATE TRIGGER IF NOT EXISTS foods_fts_insert AFTER INSERT ON foods
+        BEGIN
+            INSERT INTO foods_fts (rowid, name, brand) VALUES (NEW.id, NEW.name, NEW.brand);
+        END;
+        CREATE TRIGGER IF NOT EXISTS foods_fts_delete AFTER DELETE ON foods
+        BEGIN
+            INSERT INTO foods_fts (foods_fts, rowid, name, brand) VALUES ('delete', OLD.id, OLD.name, OLD.brand);
+        END;
+        CREATE TRIGGER IF NOT EXISTS foods_fts_update AFTER UPDATE OF name, brand ON foods
+        BEGIN
+            INSERT INTO foods_fts (foods_fts, rowid, name, brand) VALUES ('delete', OLD.id, OLD.name, OLD.brand);
+            INSERT INTO foods_fts (rowid, name, brand) VALUES (NEW.id, NEW.name, NEW.brand);
+        END;",
+    )?;
+
+    if !indexed {
+        // Foods added before the search index existed
+        conn.execute("INSERT INTO foods_fts (foods_fts) VALUES ('rebuild')", [])?;
+    }
+    Ok(())
+}
+
+/// The digits of an EAN-8, EAN-13, UPC-A or GTIN-14 code in one canonical form:
+/// padding zeros are dropped and codes shorter than 13 digits are padded back to
+/// 13, so `012345678905` (UPC-A) and `0012345678905` (GTIN-14) give the same
+/// EAN-13. Spaces and dashes are ignored; anything else that is not a digit
+/// makes the code invalid.
+pub fn normalise_barcode(code: &str) -> Option<String> {
+    let digits: String = code.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    if !(8..=14).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let significant = digits.trim_start_matches('0');
+    if significant.is_empty() {
+        return None;
+    }
+    Some(format!("{:0>13}", significant))
+}
+
+impl TaskList {
+    /// Adds the foods in an Open Food Facts or FoodData Central dump in one
+    /// transaction. Foods imported before from the same source are updated,
+    /// so a newer dump can be imported over an older one.
+    pub fn import_food_dump(
+        &mut self,
+        dump: FoodDump,
+        path: &Path,
+    ) -> Result<FoodImportSummary, Box<dyn std::error::Error>> {
+        let summary = self.database().with_transaction(|db| {
+            let mut upserter = Upserter { db, summary: FoodImportSummary::default() };
+            match dump {
+                FoodDump::OpenFoodFacts => {
+                    let file = File::open(path)
+                        .map_err(|e| FoodImportError::Io(format!("{}: {}", path.display(), e)))?;
+                    open_food_facts::read(BufReader::new(file), &mut upserter)?
+                }
+                FoodDump::Usda => usda::read(path, &mut upserter)?,
+            }
+            Ok::<_, FoodImportError>(upserter.summary)
+        })?;
+        self.poll_changes()?;
+        Ok(summary)
+    }
+
+    /// The food with this barcode, in any of the forms `normalise_barcode` accepts.
+    pub fn lookup_barcode(&self, code: &str) -> Result<Option<Food>, Box<dyn std::error::Error>> {
+        let Some(barcode) = normalise_barcode(code) else { return Ok(None) };
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM foods WHERE barcode = ?1 ORDER BY id LIMIT 1",
+            food_columns("foods")
+        ))?;
+        let mut rows = stmt.query_map([barcode], |row| food_from_row(row, 0))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Foods whose name or brand contain words starting with every word of
+    /// `query`, best matches first. Accents and case are ignored.
+    pub fn search_foods(&self, query: &str, limit: usize) -> Result<Vec<Food>, Box<dyn std::error::Error>> {
+        let terms: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{}\"*", word))
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.database().connection();
+        // Matches in the name count twice as much as matches in the brand
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM foods_fts JOIN foods ON foods.id = foods_fts.rowid
+             WHERE foods_fts MATCH ?1
+             ORDER BY bm25(foods_fts, 2.0, 1.0), length(foods.name), foods.id
+             LIMIT ?2",
+            food_columns("foods")
+        ))?;
+        let foods = stmt.query_map(rusqlite::params![terms.join(" "), limit as i64], |row| food_from_row(row, 0))?;
+        Ok(foods.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nutrition::Nutrients;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+    }
+
+    #[test]
+    fn test_barcodes() {
+        assert_eq!(normalise_barcode("3017620422003").as_deref(), Some("3017620422003"));
+        assert_eq!(normalise_barcode("012345678905").as_deref(), Some("0012345678905"));
+        assert_eq!(normalise_barcode("00012345678905").as_deref(), Some("0012345678905"));
+        assert_eq!(normalise_barcode("9638-5074").as_deref(), Some("0000096385074"));
+        assert_eq!(normalise_barcode("12345"), None);
+        assert_eq!(normalise_barcode("30176204220AB"), None);
+        assert_eq!(normalise_barcode("00000000"), None);
+    }
+
+    #[test]
+    fn test_import_open_food_facts_and_search() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let own = Food::new("Homemade granola", Nutrients { energy_kcal: 471.0, ..Default::default() });
+        task_list.add_food(&own)?;
+
+        let summary = task_list.import_food_dump(FoodDump::OpenFoodFacts, &fixture("openfoodfacts.tsv"))?;
+        assert_eq!(summary, FoodImportSummary { foods_added: 3, foods_updated: 0, records_skipped: 2 });
+        let summary = task_list.import_food_dump(FoodDump::OpenFoodFacts, &fixture("openfoodfacts.jsonl"))?;
+        assert_eq!(summary, FoodImportSummary { foods_added: 1, foods_updated: 1, records_skipped: 1 });
+
+        let spread = task_list.lookup_barcode("3017620422003")?.unwrap();
+        assert_eq!(spread.name, "Nutella");
+        assert_eq!(spread.brand.as_deref(), Some("Ferrero"));
+        assert_eq!(spread.source_id.as_deref(), Some("off:3017620422003"));
+        assert_eq!(spread.serving_g, Some(15.0));
+        assert_eq!(spread.per_100g.energy_kcal, 539.0);
+        // Updated from the JSONL dump, with minerals converted from grams
+        assert_eq!(spread.per_100g.sugar_g, 56.0);
+        assert!((spread.per_100g.sodium_mg - 42.8).abs() < 1e-9);
+
+        // UPC-A, stored as EAN-13
+        let soup = task_list.lookup_barcode("051000012616")?.unwrap();
+        assert_eq!(soup.barcode.as_deref(), Some("0051000012616"));
+        // Energy given only in kJ, sodium only as salt
+        assert!((soup.per_100g.energy_kcal - 100.0).abs() < 0.1);
+        assert!((soup.per_100g.sodium_mg - 400.0).abs() < 1e-9);
+        assert_eq!(task_list.lookup_barcode("0000000000000")?, None);
+
+        let names = |query: &str| -> Vec<String> {
+            task_list.search_foods(query, 10).unwrap().into_iter().map(|food| food.name).collect()
+        };
+        assert_eq!(names("granola"), ["Homemade granola", "Crunchy Granola Clusters"]);
+        assert_eq!(names("gran ferr"), Vec::<String>::new());
+        assert_eq!(names("ferrero"), ["Nutella"]);
+        assert_eq!(names("creme"), ["Crème fraîche"]);
+        assert_eq!(names("TOMATO so"), ["Tomato soup"]);
+        assert!(names("\"*").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_usda() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        assert_eq!(FoodDump::detect(&fixture("usda")), FoodDump::Usda);
+        let summary = task_list.import_food_dump(FoodDump::Usda, &fixture("usda"))?;
+        assert_eq!(summary, FoodImportSummary { foods_added: 3, foods_updated: 0, records_skipped: 2 });
+        let again = task_list.import_food_dump(FoodDump::Usda, &fixture("usda"))?;
+        assert_eq!(again.foods_updated, 3);
+        assert_eq!(task_list.foods()?.len(), 3);
+
+        let oats = task_list.search_foods("oats", 5)?;
+        assert_eq!(oats.len(), 2);
+        let legacy = oats.iter().find(|food| food.source_id.as_deref() == Some("usda:173904")).unwrap();
+        assert_eq!(legacy.per_100g.energy_kcal, 379.0);
+        assert_eq!(legacy.per_100g.fiber_g, 10.1);
+        assert_eq!(legacy.barcode, None);
+
+        let branded = task_list.lookup_barcode("00030000010204")?.unwrap();
+        assert_eq!(branded.name, "OLD FASHIONED OATS");
+        assert_eq!(branded.brand.as_deref(), Some("Quaker"));
+        assert_eq!(branded.serving_g, Some(40.0));
+        // Atwater energy stands in for a missing kcal value
+        assert_eq!(branded.per_100g.energy_kcal, 375.0);
+
+        let apple = task_list.search_foods("apple", 5)?;
+        assert_eq!(apple[0].per_100g.energy_kcal, 52.0);
+        assert!((apple[0].per_100g.vitamin_c_mg - 4.6).abs() < 1e-9);
+        Ok(())
+    }
+}
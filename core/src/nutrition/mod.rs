@@ -0,0 +1,732 @@
+// core/src/nutrition/mod.rs
+// OpenNutrition: foods with per-100 g nutrients, a meal log, and daily totals against targets.
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::database::Database;
+use crate::dates;
+use crate::task_list::TaskList;
+
+mod catalogue;
+mod open_food_facts;
+mod pantry;
+mod usda;
+
+pub use catalogue::{normalise_barcode, FoodDump, FoodImportError, FoodImportSummary};
+pub use pantry::{Amount, Ingredient, PantryItem, PantryReport, PantryUse, Recipe, Unit, Unmatched, UnmatchedReason};
+
+/// Field names of `Nutrients`, in order. They are also the nutrient columns of
+/// the `foods` table and the keys of nutrition targets.
+pub const NUTRIENTS: [&str; 12] = [
+    "energy_kcal",
+    "protein_g",
+    "carbs_g",
+    "fat_g",
+    "saturated_fat_g",
+    "sugar_g",
+    "fiber_g",
+    "sodium_mg",
+    "potassium_mg",
+    "calcium_mg",
+    "iron_mg",
+    "vitamin_c_mg",
+];
+
+/// Most that can be logged as eaten at once; more is a typo rather than a meal
+const MAX_GRAMS: f64 = 10_000.0;
+const MAX_SERVINGS: f64 = 100.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NutritionError {
+    UnknownNutrient(String),
+    UnknownMeal(String),
+    InvalidQuantity(String),
+    /// A quantity over `MAX_GRAMS` or `MAX_SERVINGS`
+    QuantityTooLarge(String),
+    /// A quantity in servings of a food without a serving size
+    NoServingSize(String),
+    /// A pantry or recipe amount, see `Amount`
+    InvalidAmount(String),
+}
+
+impl std::fmt::Display for NutritionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NutritionError::UnknownNutrient(name) => {
+                write!(f, "unknown nutrient '{}'. Use one of: {}", name, NUTRIENTS.join(", "))
+            }
+            NutritionError::UnknownMeal(meal) => {
+                write!(f, "unknown meal '{}'. Use breakfast, lunch, dinner or snack", meal)
+            }
+            NutritionError::InvalidQuantity(quantity) => write!(
+                f,
+                "cannot understand quantity '{}'. Use grams (150g), kg, oz, lb or servings (2x)",
+                quantity
+            ),
+            NutritionError::QuantityTooLarge(quantity) => {
+                write!(f, "'{}' is too much to log; the most is {} g or {} servings", quantity, MAX_GRAMS, MAX_SERVINGS)
+            }
+            NutritionError::NoServingSize(food) => {
+                write!(f, "{} has no serving size; give the quantity in grams", food)
+            }
+            NutritionError::InvalidAmount(amount) => write!(
+                f,
+                "cannot understand amount '{}'. Use a number and a unit: 500g, 1.5 l, 2 cups, 3 (pieces)",
+                amount
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NutritionError {}
+
+/// Amounts of each nutrient. Foods store them per 100 g; anything a source
+/// does not list is 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Nutrients {
+    pub energy_kcal: f64,
+    pub protein_g: f64,
+    pub carbs_g: f64,
+    pub fat_g: f64,
+    pub saturated_fat_g: f64,
+    pub sugar_g: f64,
+    pub fiber_g: f64,
+    pub sodium_mg: f64,
+    pub potassium_mg: f64,
+    pub calcium_mg: f64,
+    pub iron_mg: f64,
+    pub vitamin_c_mg: f64,
+}
+
+impl Nutrients {
+    /// Values in the order of `NUTRIENTS`.
+    pub fn values(&self) -> [f64; 12] {
+        [
+            self.energy_kcal,
+            self.protein_g,
+            self.carbs_g,
+            self.fat_g,
+            self.saturated_fat_g,
+            self.sugar_g,
+            self.fiber_g,
+            self.sodium_mg,
+            self.potassium_mg,
+            self.calcium_mg,
+            self.iron_mg,
+            self.vitamin_c_mg,
+        ]
+    }
+
+    pub fn from_values(values: [f64; 12]) -> Self {
+        let [
+            energy_kcal, protein_g, carbs_g, fat_g, saturated_fat_g, sugar_g,
+            fiber_g, sodium_mg, potassium_mg, calcium_mg, iron_mg, vitamin_c_mg,
+        ] = values;
+        Nutrients {
+            energy_kcal,
+            protein_g,
+            carbs_g,
+            fat_g,
+            saturated_fat_g,
+            sugar_g,
+            fiber_g,
+            sodium_mg,
+            potassium_mg,
+            calcium_mg,
+            iron_mg,
+            vitamin_c_mg,
+        }
+    }
+
+    pub fn get(&self, nutrient: &str) -> Option<f64> {
+        NUTRIENTS.iter().position(|name| *name == nutrient).map(|index| self.values()[index])
+    }
+
+    pub fn set(&mut self, nutrient: &str, amount: f64) -> Result<(), NutritionError> {
+        let index = NUTRIENTS
+            .iter()
+            .position(|name| *name == nutrient)
+            .ok_or_else(|| NutritionError::UnknownNutrient(nutrient.to_string()))?;
+        let mut values = self.values();
+        values[index] = amount;
+        *self = Nutrients::from_values(values);
+        Ok(())
+    }
+
+    /// What `grams` of a food with these amounts per 100 g contain.
+    pub fn for_grams(&self, grams: f64) -> Nutrients {
+        Nutrients::from_values(self.values().map(|value| value * grams / 100.0))
+    }
+}
+
+impl std::ops::AddAssign for Nutrients {
+    fn add_assign(&mut self, other: Nutrients) {
+        let mut values = self.values();
+        for (value, other) in values.iter_mut().zip(other.values()) {
+            *value += other;
+        }
+        *self = Nutrients::from_values(values);
+    }
+}
+
+/// The entry of `NUTRIENTS` meant by `input`, which may leave off the unit or
+/// use a common name: "protein", "calories", "carbohydrates", "fibre".
+pub fn nutrient_name(input: &str) -> Result<&'static str, NutritionError> {
+    let normalised = input.trim().to_lowercase().replace([' ', '-'], "_");
+    let alias = match normalised.as_str() {
+        "calories" | "kcal" | "energy" => "energy_kcal",
+        "carbohydrates" | "carbohydrate" => "carbs_g",
+        "saturated" | "saturates" => "saturated_fat_g",
+        "sugars" => "sugar_g",
+        "fibre" => "fiber_g",
+        other => other,
+    };
+    NUTRIENTS
+        .iter()
+        .copied()
+        .find(|name| *name == alias || name.rsplit_once('_').is_some_and(|(base, _)| base == alias))
+        .ok_or_else(|| NutritionError::UnknownNutrient(input.trim().to_string()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Meal {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+impl Meal {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Meal::Breakfast => "breakfast",
+            Meal::Lunch => "lunch",
+            Meal::Dinner => "dinner",
+            Meal::Snack => "snack",
+        }
+    }
+
+    /// The meal usually eaten at this local time, for entries logged without one.
+    pub fn at<Tz: TimeZone>(time: &DateTime<Tz>) -> Meal {
+        match time.hour() {
+            5..=10 => Meal::Breakfast,
+            11..=15 => Meal::Lunch,
+            17..=21 => Meal::Dinner,
+            _ => Meal::Snack,
+        }
+    }
+}
+
+impl std::str::FromStr for Meal {
+    type Err = NutritionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "breakfast" => Ok(Meal::Breakfast),
+            "lunch" => Ok(Meal::Lunch),
+            "dinner" | "supper" => Ok(Meal::Dinner),
+            "snack" | "snacks" => Ok(Meal::Snack),
+            _ => Err(NutritionError::UnknownMeal(s.to_string())),
+        }
+    }
+}
+
+/// How much of a food was eaten.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+    Grams(f64),
+    Servings(f64),
+}
+
+impl std::str::FromStr for Quantity {
+    type Err = NutritionError;
+
+    /// Accepts `150`, `150g`, `0.2 kg`, `5oz`, `1lb`, `2x` and `1.5 servings`; a bare number is grams.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || NutritionError::InvalidQuantity(s.to_string());
+        let input = s.trim().to_lowercase();
+        let split = input.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(input.len());
+        let (number, unit) = input.split_at(split);
+        let amount: f64 = number.parse().map_err(|_| error())?;
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(error());
+        }
+        let quantity = match unit.trim() {
+            "" | "g" | "gram" | "grams" => Quantity::Grams(amount),
+            "kg" => Quantity::Grams(amount * 1000.0),
+            "oz" => Quantity::Grams(amount * 28.349523125),
+            "lb" | "lbs" => Quantity::Grams(amount * 453.59237),
+            "x" | "serving" | "servings" => Quantity::Servings(amount),
+            _ => return Err(error()),
+        };
+        match quantity {
+            Quantity::Grams(grams) if grams > MAX_GRAMS => Err(NutritionError::QuantityTooLarge(s.to_string())),
+            Quantity::Servings(servings) if servings > MAX_SERVINGS => {
+                Err(NutritionError::QuantityTooLarge(s.to_string()))
+            }
+            quantity => Ok(quantity),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Food {
+    pub id: Option<i64>,
+    pub name: String,
+    pub brand: Option<String>,
+    /// Grams in one serving, for quantities given in servings
+    pub serving_g: Option<f64>,
+    /// EAN-13 (UPC-A codes gain a leading zero), see `normalise_barcode`
+    pub barcode: Option<String>,
+    /// Where an imported food came from, e.g. `off:3017620422003` or `usda:171287`;
+    /// importing it again updates the same food
+    pub source_id: Option<String>,
+    pub per_100g: Nutrients,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Food {
+    pub fn new(name: &str, per_100g: Nutrients) -> Self {
+        Food {
+            id: None,
+            name: name.to_string(),
+            brand: None,
+            serving_g: None,
+            barcode: None,
+            source_id: None,
+            per_100g,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_brand(mut self, brand: &str) -> Self {
+        self.brand = Some(brand.to_string());
+        self
+    }
+
+    pub fn with_serving(mut self, grams: f64) -> Self {
+        self.serving_g = Some(grams);
+        self
+    }
+
+    /// Sets the barcode if `code` is a valid EAN/UPC, see `normalise_barcode`.
+    pub fn with_barcode(mut self, code: &str) -> Self {
+        self.barcode = normalise_barcode(code);
+        self
+    }
+
+    pub fn grams(&self, quantity: Quantity) -> Result<f64, NutritionError> {
+        match (quantity, self.serving_g) {
+            (Quantity::Grams(grams), _) => Ok(grams),
+            (Quantity::Servings(servings), Some(serving_g)) => Ok(servings * serving_g),
+            (Quantity::Servings(_), None) => Err(NutritionError::NoServingSize(self.name.clone())),
+        }
+    }
+}
+
+/// One food eaten at one time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MealEntry {
+    pub id: Option<i64>,
+    pub food_id: i64,
+    pub meal: Meal,
+    pub grams: f64,
+    pub eaten_at: DateTime<Utc>,
+}
+
+impl MealEntry {
+    pub fn new(food_id: i64, grams: f64, meal: Meal, eaten_at: DateTime<Utc>) -> Self {
+        MealEntry { id: None, food_id, meal, grams, eaten_at }
+    }
+}
+
+/// A meal log entry with the name of its food and what it contained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedMeal {
+    #[serde(flatten)]
+    pub entry: MealEntry,
+    pub food: String,
+    pub nutrients: Nutrients,
+}
+
+/// Everything eaten on one day, with totals against the current targets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayNutrition {
+    pub date: NaiveDate,
+    pub entries: Vec<LoggedMeal>,
+    pub totals: Nutrients,
+    /// Daily targets by nutrient name; nutrients without a target are left out
+    pub targets: BTreeMap<String, f64>,
+    /// Target minus total for each target; negative once a target is exceeded
+    pub remaining: BTreeMap<String, f64>,
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    let nutrient_columns: String = NUTRIENTS
+        .iter()
+        .map(|nutrient| format!("{} REAL NOT NULL DEFAULT 0,\n", nutrient))
+        .collect();
+    db.connection().execute_batch(&format!(
+        "CREATE TABLE IF NOT EXISTS foods (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            brand TEXT,
+            serving_g REAL,
+            {}created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_foods_name ON foods(name COLLATE NOCASE);
+
+        CREATE TABLE IF NOT EXISTS meal_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            food_id INTEGER NOT NULL,
+            meal TEXT NOT NULL,
+            grams REAL NOT NULL,
+            eaten_at TEXT NOT NULL,
+            FOREIGN KEY(food_id) REFERENCES foods(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_meal_log_eaten_at ON meal_log(eaten_at);
+
+        CREATE TABLE IF NOT EXISTS nutrition_targets (
+            nutrient TEXT PRIMARY KEY,
+            amount REAL NOT NULL
+        );",
+        nutrient_columns
+    ))?;
+
+    // Columns added for imported food databases
+    db.add_column_if_missing("foods", "barcode", "TEXT")?;
+    db.add_column_if_missing("foods", "source_id", "TEXT")?;
+    catalogue::init_schema(db)?;
+    pantry::init_schema(db)
+}
+
+// Column order read by `food_from_row`
+fn food_columns(table: &str) -> String {
+    let columns: Vec<String> = ["id", "name", "brand", "serving_g", "created_at", "barcode", "source_id"]
+        .iter()
+        .chain(NUTRIENTS.iter())
+        .map(|column| format!("{}.{}", table, column))
+        .collect();
+    columns.join(", ")
+}
+
+fn food_from_row(row: &Row, offset: usize) -> rusqlite::Result<Food> {
+    let mut values = [0.0; 12];
+    for (index, value) in values.iter_mut().enumerate() {
+        *value = row.get(offset + 7 + index)?;
+    }
+    Ok(Food {
+        id: Some(row.get(offset)?),
+        name: row.get(offset + 1)?,
+        brand: row.get(offset + 2)?,
+        serving_g: row.get(offset + 3)?,
+        created_at: parse_time(&row.get::<_, String>(offset + 4)?),
+        barcode: row.get(offset + 5)?,
+        source_id: row.get(offset + 6)?,
+        per_100g: Nutrients::from_values(values),
+    })
+}
+
+// Column order of `food_values`
+fn food_insert_columns() -> String {
+    format!("name, brand, serving_g, created_at, barcode, source_id, {}", NUTRIENTS.join(", "))
+}
+
+fn food_values(food: &Food) -> Vec<Value> {
+    let mut values: Vec<Value> = vec![
+        food.name.clone().into(),
+        food.brand.clone().into(),
+        food.serving_g.into(),
+        food.created_at.to_rfc3339().into(),
+        food.barcode.clone().into(),
+        food.source_id.clone().into(),
+    ];
+    values.extend(food.per_100g.values().map(Value::from));
+    values
+}
+
+fn insert_food(db: &Database, food: &Food) -> rusqlite::Result<i64> {
+    let values = food_values(food);
+    let placeholders: Vec<String> = (1..=values.len()).map(|i| format!("?{}", i)).collect();
+    let conn = db.connection();
+    conn.prepare_cached(&format!(
+        "INSERT INTO foods ({}) VALUES ({})",
+        food_insert_columns(),
+        placeholders.join(", ")
+    ))?
+    .execute(params_from_iter(values))?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn insert_meal_entry(db: &Database, entry: &MealEntry) -> rusqlite::Result<i64> {
+    let conn = db.connection();
+    conn.execute(
+        "INSERT INTO meal_log (food_id, meal, grams, eaten_at) VALUES (?1, ?2, ?3, ?4)",
+        params![entry.food_id, entry.meal.as_str(), entry.grams, entry.eaten_at.to_rfc3339()],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn parse_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+impl TaskList {
+    pub fn add_food(&mut self, food: &Food) -> Result<i64, Box<dyn std::error::Error>> {
+        let id = insert_food(self.database(), food)?;
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_food(&self, id: i64) -> Result<Option<Food>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM foods WHERE id = ?1", food_columns("foods")))?;
+        let mut rows = stmt.query_map([id], |row| food_from_row(row, 0))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// The first food with exactly this name, ignoring case.
+    pub fn find_food(&self, name: &str) -> Result<Option<Food>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM foods WHERE name = ?1 COLLATE NOCASE ORDER BY id LIMIT 1",
+            food_columns("foods")
+        ))?;
+        let mut rows = stmt.query_map([name.trim()], |row| food_from_row(row, 0))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Every food, by name.
+    pub fn foods(&self) -> Result<Vec<Food>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM foods ORDER BY name COLLATE NOCASE, id",
+            food_columns("foods")
+        ))?;
+        let foods = stmt.query_map([], |row| food_from_row(row, 0))?;
+        Ok(foods.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn log_meal(&mut self, entry: &MealEntry) -> Result<i64, Box<dyn std::error::Error>> {
+        if self.get_food(entry.food_id)?.is_none() {
+            return Err(format!("food {} not found", entry.food_id).into());
+        }
+        let id = insert_meal_entry(self.database(), entry)?;
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    /// Removes a meal log entry. Returns whether it existed.
+    pub fn delete_meal_entry(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().connection().execute("DELETE FROM meal_log WHERE id = ?1", [id])?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    /// Entries eaten from `start` up to but not including `end`, oldest first.
+    pub fn meals_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LoggedMeal>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT meal_log.id, meal_log.meal, meal_log.grams, meal_log.eaten_at, {}
+             FROM meal_log JOIN foods ON foods.id = meal_log.food_id
+             WHERE meal_log.eaten_at >= ?1 AND meal_log.eaten_at < ?2
+             ORDER BY meal_log.eaten_at, meal_log.id",
+            food_columns("foods")
+        ))?;
+        let rows = stmt.query_map(params![start.to_rfc3339(), end.to_rfc3339()], |row| {
+            let meal: String = row.get(1)?;
+            let eaten_at: String = row.get(3)?;
+            Ok((row.get(0)?, meal, row.get(2)?, eaten_at, food_from_row(row, 4)?))
+        })?;
+
+        let mut meals = Vec::new();
+        for row in rows {
+            let (id, meal, grams, eaten_at, food): (i64, String, f64, String, Food) = row?;
+            meals.push(LoggedMeal {
+                entry: MealEntry {
+                    id: Some(id),
+                    food_id: food.id.unwrap_or_default(),
+                    // Meals added by a newer version count as snacks
+                    meal: meal.parse().unwrap_or(Meal::Snack),
+                    grams,
+                    eaten_at: parse_time(&eaten_at),
+                },
+                nutrients: food.per_100g.for_grams(grams),
+                food: food.name,
+            });
+        }
+        Ok(meals)
+    }
+
+    /// What was eaten on `date`, a calendar day in `tz`.
+    pub fn day_nutrition<Tz: TimeZone>(
+        &self,
+        date: NaiveDate,
+        tz: &Tz,
+    ) -> Result<DayNutrition, Box<dyn std::error::Error>> {
+        let (start, end) = day_bounds(date, tz)?;
+        let entries = self.meals_between(start, end)?;
+        let mut totals = Nutrients::default();
+        for logged in &entries {
+            totals += logged.nutrients;
+        }
+        let targets = self.nutrition_targets()?;
+        let remaining = targets
+            .iter()
+            .map(|(nutrient, target)| (nutrient.clone(), target - totals.get(nutrient).unwrap_or(0.0)))
+            .collect();
+        Ok(DayNutrition { date, entries, totals, targets, remaining })
+    }
+
+    /// Totals for each day from `first` to `last` inclusive, in `tz`; days with nothing logged are zero.
+    pub fn daily_totals<Tz: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Tz,
+    ) -> Result<Vec<(NaiveDate, Nutrients)>, Box<dyn std::error::Error>> {
+        let (start, _) = day_bounds(first, tz)?;
+        let (_, end) = day_bounds(last, tz)?;
+        let mut days: BTreeMap<NaiveDate, Nutrients> =
+            first.iter_days().take_while(|day| *day <= last).map(|day| (day, Nutrients::default())).collect();
+        for logged in self.meals_between(start, end)? {
+            let day = logged.entry.eaten_at.with_timezone(tz).date_naive();
+            if let Some(totals) = days.get_mut(&day) {
+                *totals += logged.nutrients;
+            }
+        }
+        Ok(days.into_iter().collect())
+    }
+
+    /// Sets the daily target for a nutrient (see `nutrient_name`), or clears it with None.
+    pub fn set_nutrition_target(
+        &mut self,
+        nutrient: &str,
+        amount: Option<f64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let nutrient = nutrient_name(nutrient)?;
+        let conn = self.database().connection();
+        match amount {
+            Some(amount) => conn.execute(
+                "INSERT OR REPLACE INTO nutrition_targets (nutrient, amount) VALUES (?1, ?2)",
+                params![nutrient, amount],
+            )?,
+            None => conn.execute("DELETE FROM nutrition_targets WHERE nutrient = ?1", [nutrient])?,
+        };
+        self.poll_changes()
+    }
+
+    pub fn nutrition_targets(&self) -> Result<BTreeMap<String, f64>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare("SELECT nutrient, amount FROM nutrition_targets")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+/// UTC bounds of a calendar day in `tz`, which is not always 24 hours long.
+fn day_bounds<Tz: TimeZone>(date: NaiveDate, tz: &Tz) -> Result<(DateTime<Utc>, DateTime<Utc>), String> {
+    let start = dates::start_of_day(date, tz);
+    let end = date.succ_opt().and_then(|next| dates::start_of_day(next, tz));
+    match (start, end) {
+        (Some(start), Some(end)) => Ok((start, end)),
+        _ => Err(format!("{} is out of range", date)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn oats() -> Food {
+        let mut per_100g = Nutrients { energy_kcal: 379.0, protein_g: 13.2, carbs_g: 67.7, fat_g: 6.5, ..Default::default() };
+        per_100g.set("fiber_g", 10.1).unwrap();
+        Food::new("Rolled oats", per_100g).with_serving(40.0)
+    }
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_quantities_and_names() {
+        assert_eq!("150".parse(), Ok(Quantity::Grams(150.0)));
+        assert_eq!("0.2 kg".parse(), Ok(Quantity::Grams(200.0)));
+        assert_eq!("2x".parse(), Ok(Quantity::Servings(2.0)));
+        assert_eq!("1.5 servings".parse(), Ok(Quantity::Servings(1.5)));
+        assert!("a handful".parse::<Quantity>().is_err());
+        assert!("0g".parse::<Quantity>().is_err());
+        assert!("1e41g".parse::<Quantity>().is_err());
+        let huge = format!("1{}g", "0".repeat(41));
+        assert_eq!(huge.parse::<Quantity>(), Err(NutritionError::QuantityTooLarge(huge.clone())));
+        assert!("11 kg".parse::<Quantity>().is_err());
+        assert!("101x".parse::<Quantity>().is_err());
+        assert_eq!("10 kg".parse(), Ok(Quantity::Grams(10_000.0)));
+
+        assert_eq!(oats().grams(Quantity::Servings(2.0)), Ok(80.0));
+        assert!(Food::new("Apple", Nutrients::default()).grams(Quantity::Servings(1.0)).is_err());
+
+        assert_eq!(nutrient_name("Protein"), Ok("protein_g"));
+        assert_eq!(nutrient_name("calories"), Ok("energy_kcal"));
+        assert_eq!(nutrient_name("vitamin c"), Ok("vitamin_c_mg"));
+        assert_eq!(nutrient_name("sodium_mg"), Ok("sodium_mg"));
+        assert!(nutrient_name("vibes").is_err());
+
+        let evening = FixedOffset::east_opt(3600).unwrap().with_ymd_and_hms(2026, 10, 18, 19, 30, 0).unwrap();
+        assert_eq!(Meal::at(&evening), Meal::Dinner);
+        assert_eq!("Supper".parse(), Ok(Meal::Dinner));
+    }
+
+    #[test]
+    fn test_day_totals_and_targets() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let oats_id = task_list.add_food(&oats())?;
+        let milk = Food::new("Milk", Nutrients { energy_kcal: 64.0, protein_g: 3.3, calcium_mg: 120.0, ..Default::default() });
+        let milk_id = task_list.add_food(&milk)?;
+        assert_eq!(task_list.find_food("rolled OATS")?.unwrap().id, Some(oats_id));
+        assert_eq!(task_list.get_food(milk_id)?.unwrap().per_100g, milk.per_100g);
+
+        // 23:30 on the 17th in UTC is already the 18th at UTC+2
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        task_list.log_meal(&MealEntry::new(oats_id, 80.0, Meal::Breakfast, at("2026-10-17T23:30:00Z")))?;
+        task_list.log_meal(&MealEntry::new(milk_id, 250.0, Meal::Breakfast, at("2026-10-18T06:00:00Z")))?;
+        let late = task_list.log_meal(&MealEntry::new(milk_id, 100.0, Meal::Snack, at("2026-10-18T22:30:00Z")))?;
+        assert!(task_list.log_meal(&MealEntry::new(99, 1.0, Meal::Snack, Utc::now())).is_err());
+
+        task_list.set_nutrition_target("calories", Some(2000.0))?;
+        task_list.set_nutrition_target("protein", Some(120.0))?;
+        task_list.set_nutrition_target("protein", None)?;
+
+        let day = task_list.day_nutrition(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(), &tz)?;
+        let foods: Vec<_> = day.entries.iter().map(|logged| logged.food.as_str()).collect();
+        assert_eq!(foods, ["Rolled oats", "Milk"]);
+        assert!((day.totals.energy_kcal - (303.2 + 160.0)).abs() < 1e-9);
+        assert!((day.totals.calcium_mg - 300.0).abs() < 1e-9);
+        assert!((day.totals.fiber_g - 8.08).abs() < 1e-9);
+        assert_eq!(day.targets.keys().collect::<Vec<_>>(), ["energy_kcal"]);
+        assert!((day.remaining["energy_kcal"] - (2000.0 - 463.2)).abs() < 1e-9);
+
+        let totals = task_list.daily_totals(
+            NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+            &tz,
+        )?;
+        let energy: Vec<_> = totals.iter().map(|(_, totals)| totals.energy_kcal.round()).collect();
+        assert_eq!(energy, [0.0, 463.0, 64.0]);
+
+        assert!(task_list.delete_meal_entry(late)?);
+        assert!(!task_list.delete_meal_entry(late)?);
+        Ok(())
+    }
+}
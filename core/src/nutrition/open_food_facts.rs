@@ -0,0 +1,125 @@
+// core/src/nutrition/open_food_facts.rs
+// Reads Open Food Facts product exports: the tab-separated CSV dump, a comma-separated
+// extract, or the JSONL dump with one product per line.
+use std::collections::HashMap;
+use std::io::{BufRead, Cursor, Read};
+use serde_json::Value;
+use super::catalogue::{normalise_barcode, FoodImportError, FoodSink};
+use super::{Food, Nutrients};
+
+/// `_100g` fields read into `Nutrients`, with the factor that converts them.
+/// Minerals and vitamins are given in grams.
+const FIELDS: [(&str, &str, f64); 11] = [
+    ("proteins_100g", "protein_g", 1.0),
+    ("carbohydrates_100g", "carbs_g", 1.0),
+    ("fat_100g", "fat_g", 1.0),
+    ("saturated-fat_100g", "saturated_fat_g", 1.0),
+    ("sugars_100g", "sugar_g", 1.0),
+    ("fiber_100g", "fiber_g", 1.0),
+    ("sodium_100g", "sodium_mg", 1000.0),
+    ("potassium_100g", "potassium_mg", 1000.0),
+    ("calcium_100g", "calcium_mg", 1000.0),
+    ("iron_100g", "iron_mg", 1000.0),
+    ("vitamin-c_100g", "vitamin_c_mg", 1000.0),
+];
+
+pub(crate) fn read(mut input: impl BufRead, sink: &mut dyn FoodSink) -> Result<(), FoodImportError> {
+    let mut header = String::new();
+    input.read_line(&mut header)?;
+    if header.trim_start().starts_with('{') {
+        return read_jsonl(Cursor::new(header).chain(input), sink);
+    }
+
+    // The full dump is tab-separated and does not quote fields
+    let tabs = header.contains('\t');
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(if tabs { b'\t' } else { b',' })
+        .quoting(!tabs)
+        .flexible(true)
+        .from_reader(Cursor::new(header).chain(input));
+    let columns: HashMap<String, usize> =
+        reader.headers()?.iter().enumerate().map(|(index, name)| (name.to_string(), index)).collect();
+    if !columns.contains_key("product_name") {
+        return Err(FoodImportError::MissingColumn { file: "Open Food Facts dump".to_string(), column: "product_name" });
+    }
+
+    for record in reader.records() {
+        let record = record?;
+        let field = |name: &str| record.get(*columns.get(name)?).map(str::to_string);
+        match product(field) {
+            Some(food) => sink.add(food)?,
+            None => sink.skip(),
+        }
+    }
+    Ok(())
+}
+
+fn read_jsonl(input: impl BufRead, sink: &mut dyn FoodSink) -> Result<(), FoodImportError> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            sink.skip();
+            continue;
+        };
+        // Nutrition facts are nested under `nutriments`, everything else is top level
+        let field = |name: &str| {
+            let value = value.get(name).or_else(|| value.get("nutriments")?.get(name))?;
+            match value {
+                Value::String(text) => Some(text.clone()),
+                Value::Number(number) => Some(number.to_string()),
+                _ => None,
+            }
+        };
+        match product(field) {
+            Some(food) => sink.add(food)?,
+            None => sink.skip(),
+        }
+    }
+    Ok(())
+}
+
+/// A product with a name and at least one nutrition fact, from a lookup of its fields.
+fn product(field: impl Fn(&str) -> Option<String>) -> Option<Food> {
+    let text = |name: &str| field(name).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    let number = |name: &str| text(name)?.parse::<f64>().ok().filter(|value| value.is_finite() && *value >= 0.0);
+
+    let name = text("product_name").or_else(|| text("product_name_en")).or_else(|| text("generic_name"))?;
+    let mut per_100g = Nutrients::default();
+    let mut found = false;
+
+    // energy_100g is in kJ
+    if let Some(kcal) = number("energy-kcal_100g").or_else(|| number("energy_100g").map(|kj| kj / 4.184)) {
+        per_100g.energy_kcal = kcal;
+        found = true;
+    }
+    for (field, nutrient, factor) in FIELDS {
+        if let Some(amount) = number(field) {
+            per_100g.set(nutrient, amount * factor).ok()?;
+            found = true;
+        }
+    }
+    if number("sodium_100g").is_none() {
+        if let Some(salt) = number("salt_100g") {
+            // Salt is 40% sodium
+            per_100g.sodium_mg = salt * 400.0;
+            found = true;
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    let mut food = Food::new(&name, per_100g);
+    food.brand = text("brands").and_then(|brands| {
+        brands.split(',').map(str::trim).find(|brand| !brand.is_empty()).map(str::to_string)
+    });
+    food.serving_g = number("serving_quantity").filter(|grams| *grams > 0.0);
+    if let Some(code) = text("code") {
+        food.barcode = normalise_barcode(&code);
+        food.source_id = Some(format!("off:{}", code));
+    }
+    Some(food)
+}
@@ -0,0 +1,750 @@
+// core/src/nutrition/pantry.rs
+// What is in the kitchen: pantry items, recipes, and taking what was eaten or cooked
+// out of the pantry with a report of anything that could not be found.
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use crate::database::Database;
+use crate::task_list::TaskList;
+use super::{insert_meal_entry, parse_time, Food, MealEntry, NutritionError};
+
+// Amounts left over from floating point arithmetic, not food
+const TRACE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unit {
+    #[serde(rename = "g")]
+    Gram,
+    #[serde(rename = "kg")]
+    Kilogram,
+    #[serde(rename = "oz")]
+    Ounce,
+    #[serde(rename = "lb")]
+    Pound,
+    #[serde(rename = "ml")]
+    Millilitre,
+    #[serde(rename = "l")]
+    Litre,
+    #[serde(rename = "tsp")]
+    Teaspoon,
+    #[serde(rename = "tbsp")]
+    Tablespoon,
+    #[serde(rename = "cup")]
+    Cup,
+    #[serde(rename = "fl_oz")]
+    FluidOunce,
+    /// Eggs, apples, tins: anything counted
+    #[serde(rename = "piece")]
+    Piece,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Mass,
+    Volume,
+    Count,
+}
+
+impl Unit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Ounce => "oz",
+            Unit::Pound => "lb",
+            Unit::Millilitre => "ml",
+            Unit::Litre => "l",
+            Unit::Teaspoon => "tsp",
+            Unit::Tablespoon => "tbsp",
+            Unit::Cup => "cup",
+            Unit::FluidOunce => "fl_oz",
+            Unit::Piece => "piece",
+        }
+    }
+
+    // What the unit measures, and its size in grams, millilitres or pieces
+    fn size(self) -> (Dimension, f64) {
+        match self {
+            Unit::Gram => (Dimension::Mass, 1.0),
+            Unit::Kilogram => (Dimension::Mass, 1000.0),
+            Unit::Ounce => (Dimension::Mass, 28.349523125),
+            Unit::Pound => (Dimension::Mass, 453.59237),
+            Unit::Millilitre => (Dimension::Volume, 1.0),
+            Unit::Litre => (Dimension::Volume, 1000.0),
+            Unit::Teaspoon => (Dimension::Volume, 5.0),
+            Unit::Tablespoon => (Dimension::Volume, 15.0),
+            Unit::Cup => (Dimension::Volume, 240.0),
+            Unit::FluidOunce => (Dimension::Volume, 29.5735295625),
+            Unit::Piece => (Dimension::Count, 1.0),
+        }
+    }
+}
+
+impl std::str::FromStr for Unit {
+    type Err = NutritionError;
+
+    /// Abbreviations and names, singular or plural; no unit at all means pieces.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "g" | "gram" | "grams" => Ok(Unit::Gram),
+            "kg" | "kilogram" | "kilograms" => Ok(Unit::Kilogram),
+            "oz" | "ounce" | "ounces" => Ok(Unit::Ounce),
+            "lb" | "lbs" | "pound" | "pounds" => Ok(Unit::Pound),
+            "ml" | "millilitre" | "millilitres" | "milliliter" | "milliliters" => Ok(Unit::Millilitre),
+            "l" | "litre" | "litres" | "liter" | "liters" => Ok(Unit::Litre),
+            "tsp" | "teaspoon" | "teaspoons" => Ok(Unit::Teaspoon),
+            "tbsp" | "tablespoon" | "tablespoons" => Ok(Unit::Tablespoon),
+            "cup" | "cups" => Ok(Unit::Cup),
+            "fl_oz" | "fl oz" | "floz" => Ok(Unit::FluidOunce),
+            "" | "x" | "piece" | "pieces" | "pc" | "pcs" => Ok(Unit::Piece),
+            _ => Err(NutritionError::InvalidAmount(s.to_string())),
+        }
+    }
+}
+
+/// A quantity and its unit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Amount {
+    pub quantity: f64,
+    pub unit: Unit,
+}
+
+impl Amount {
+    pub fn new(quantity: f64, unit: Unit) -> Self {
+        Amount { quantity, unit }
+    }
+
+    /// This amount in `unit`, if it can be converted. Masses and pieces convert
+    /// through `serving_g`, the grams in one piece; volumes only convert to
+    /// volumes, since densities are not known.
+    pub fn to(self, unit: Unit, serving_g: Option<f64>) -> Option<Amount> {
+        let (from, from_size) = self.unit.size();
+        let (to, to_size) = unit.size();
+        let serving_g = serving_g.filter(|grams| *grams > 0.0);
+        let base = self.quantity * from_size;
+        let base = match (from, to) {
+            _ if from == to => base,
+            (Dimension::Mass, Dimension::Count) => base / serving_g?,
+            (Dimension::Count, Dimension::Mass) => base * serving_g?,
+            _ => return None,
+        };
+        Some(Amount::new(base / to_size, unit))
+    }
+}
+
+impl std::fmt::Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let quantity = (self.quantity * 100.0).round() / 100.0;
+        match self.unit {
+            Unit::Piece => write!(f, "{}", quantity),
+            unit => write!(f, "{} {}", quantity, unit.as_str()),
+        }
+    }
+}
+
+impl std::str::FromStr for Amount {
+    type Err = NutritionError;
+
+    /// Accepts `500g`, `1.5 l`, `2 cups` and `3` (pieces). Zero is allowed, for things that ran out.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || NutritionError::InvalidAmount(s.to_string());
+        let input = s.trim();
+        let split = input.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(input.len());
+        let (number, unit) = input.split_at(split);
+        let quantity: f64 = number.parse().map_err(|_| error())?;
+        if !quantity.is_finite() || quantity < 0.0 {
+            return Err(error());
+        }
+        Ok(Amount::new(quantity, unit.parse().map_err(|_| error())?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PantryItem {
+    pub id: Option<i64>,
+    pub name: String,
+    /// The food this is, so eating it is taken out of the pantry even under another name
+    pub food_id: Option<i64>,
+    #[serde(flatten)]
+    pub amount: Amount,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PantryItem {
+    pub fn new(name: &str, amount: Amount) -> Self {
+        PantryItem { id: None, name: name.trim().to_string(), food_id: None, amount, updated_at: Utc::now() }
+    }
+
+    pub fn with_food(mut self, food_id: i64) -> Self {
+        self.food_id = Some(food_id);
+        self
+    }
+}
+
+/// One line of a recipe, for the whole recipe rather than per serving.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ingredient {
+    pub name: String,
+    pub food_id: Option<i64>,
+    #[serde(flatten)]
+    pub amount: Amount,
+}
+
+impl std::str::FromStr for Ingredient {
+    type Err = NutritionError;
+
+    /// Accepts an amount followed by a name: `200g rolled oats`, `2 cups of milk`, `3 eggs`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || NutritionError::InvalidAmount(s.to_string());
+        let input = s.trim();
+        let split = input.find(|c: char| !(c.is_ascii_digit() || c == '.')).ok_or_else(error)?;
+        let (number, rest) = input.split_at(split);
+        let rest = rest.trim_start();
+        let (unit, name) = match rest.split_once(char::is_whitespace) {
+            _ if rest.to_lowercase().starts_with("fl oz ") => (Unit::FluidOunce, &rest[6..]),
+            Some((word, name)) => match word.parse::<Unit>() {
+                Ok(unit) => (unit, name),
+                Err(_) => (Unit::Piece, rest),
+            },
+            None => (Unit::Piece, rest),
+        };
+        let name = name.trim_start();
+        let name = name.strip_prefix("of ").unwrap_or(name).trim();
+        if name.is_empty() {
+            return Err(error());
+        }
+        let quantity: f64 = number.parse().map_err(|_| error())?;
+        if !quantity.is_finite() || quantity <= 0.0 {
+            return Err(error());
+        }
+        Ok(Ingredient { name: name.to_string(), food_id: None, amount: Amount::new(quantity, unit) })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recipe {
+    pub id: Option<i64>,
+    pub name: String,
+    /// Servings one batch makes
+    pub servings: f64,
+    pub ingredients: Vec<Ingredient>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Recipe {
+    pub fn new(name: &str, servings: f64, ingredients: Vec<Ingredient>) -> Self {
+        Recipe { id: None, name: name.trim().to_string(), servings, ingredients, created_at: Utc::now() }
+    }
+}
+
+/// How much was taken from one pantry item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PantryUse {
+    pub item_id: i64,
+    pub name: String,
+    pub taken: Amount,
+    pub remaining: Amount,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnmatchedReason {
+    /// No pantry item has this name or food
+    NotInPantry,
+    /// The pantry ran out before the whole amount was taken
+    NotEnough,
+    /// Only items kept in units this amount cannot be converted to, e.g. grams of milk kept in litres
+    IncompatibleUnits,
+}
+
+/// Food that was eaten or cooked but could not be taken out of the pantry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Unmatched {
+    pub name: String,
+    pub missing: Amount,
+    pub reason: UnmatchedReason,
+}
+
+/// What `log_meal_from_pantry` and `cook_recipe` took out of the pantry, and
+/// what they could not. Anything unmatched means the pantry was out of date.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PantryReport {
+    /// The meal log entry, when a meal was logged
+    pub meal_entry_id: Option<i64>,
+    pub used: Vec<PantryUse>,
+    pub unmatched: Vec<Unmatched>,
+}
+
+impl PantryReport {
+    pub fn is_reconciled(&self) -> bool {
+        self.unmatched.is_empty()
+    }
+}
+
+pub(crate) fn init_schema(db: &Database) -> rusqlite::Result<()> {
+    db.connection().execute_batch(
+        "CREATE TABLE IF NOT EXISTS pantry_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            food_id INTEGER,
+            quantity REAL NOT NULL,
+            unit TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(food_id) REFERENCES foods(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_pantry_items_name ON pantry_items(name COLLATE NOCASE);
+        CREATE INDEX IF NOT EXISTS idx_pantry_items_food_id ON pantry_items(food_id);
+
+        CREATE TABLE IF NOT EXISTS recipes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            servings REAL NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS recipe_ingredients (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            recipe_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            food_id INTEGER,
+            quantity REAL NOT NULL,
+            unit TEXT NOT NULL,
+            FOREIGN KEY(recipe_id) REFERENCES recipes(id),
+            FOREIGN KEY(food_id) REFERENCES foods(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_recipe_ingredients_recipe_id ON recipe_ingredients(recipe_id);",
+    )
+}
+
+// Units written by a newer version are read as pieces
+fn unit_from_row(row: &Row, index: usize) -> rusqlite::Result<Unit> {
+    Ok(row.get::<_, String>(index)?.parse().unwrap_or(Unit::Piece))
+}
+
+const ITEM_COLUMNS: &str = "id, name, food_id, quantity, unit, updated_at";
+
+fn item_from_row(row: &Row) -> rusqlite::Result<PantryItem> {
+    Ok(PantryItem {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        food_id: row.get(2)?,
+        amount: Amount::new(row.get(3)?, unit_from_row(row, 4)?),
+        updated_at: parse_time(&row.get::<_, String>(5)?),
+    })
+}
+
+/// Takes `need` of `name` (or of `food`) out of the pantry: items of that food
+/// first, then items with that name, oldest first. Returns what was taken and
+/// any part that could not be.
+fn take(
+    db: &Database,
+    name: &str,
+    food: Option<&Food>,
+    need: Amount,
+) -> rusqlite::Result<(Vec<PantryUse>, Option<Unmatched>)> {
+    let conn = db.connection();
+    let food_id = food.and_then(|food| food.id);
+    let mut stmt = conn.prepare_cached(
+        "SELECT pantry_items.id, pantry_items.name, pantry_items.quantity, pantry_items.unit, foods.serving_g
+         FROM pantry_items LEFT JOIN foods ON foods.id = pantry_items.food_id
+         WHERE pantry_items.food_id = ?1 OR pantry_items.name = ?2 COLLATE NOCASE
+         ORDER BY pantry_items.food_id = ?1 DESC, pantry_items.id",
+    )?;
+    let candidates = stmt
+        .query_map(params![food_id, name.trim()], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, unit_from_row(row, 3)?, row.get(4)?))
+        })?
+        .collect::<rusqlite::Result<Vec<(i64, String, f64, Unit, Option<f64>)>>>()?;
+
+    let mut left = need;
+    let mut used = Vec::new();
+    let mut incompatible = false;
+    for (id, item_name, quantity, unit, item_serving_g) in &candidates {
+        if left.quantity <= TRACE {
+            break;
+        }
+        let serving_g = food.and_then(|food| food.serving_g).or(*item_serving_g);
+        let Some(wanted) = left.to(*unit, serving_g) else {
+            incompatible = true;
+            continue;
+        };
+        if *quantity <= 0.0 {
+            continue;
+        }
+        let taken = wanted.quantity.min(*quantity);
+        let remaining = if quantity - taken <= TRACE { 0.0 } else { quantity - taken };
+        conn.prepare_cached("UPDATE pantry_items SET quantity = ?2, updated_at = ?3 WHERE id = ?1")?
+            .execute(params![id, remaining, Utc::now().to_rfc3339()])?;
+        used.push(PantryUse {
+            item_id: *id,
+            name: item_name.clone(),
+            taken: Amount::new(taken, *unit),
+            remaining: Amount::new(remaining, *unit),
+        });
+        left.quantity *= 1.0 - taken / wanted.quantity;
+    }
+
+    let unmatched = (left.quantity > TRACE).then(|| Unmatched {
+        name: name.trim().to_string(),
+        missing: left,
+        reason: if candidates.is_empty() {
+            UnmatchedReason::NotInPantry
+        } else if used.is_empty() && incompatible {
+            UnmatchedReason::IncompatibleUnits
+        } else {
+            UnmatchedReason::NotEnough
+        },
+    });
+    Ok((used, unmatched))
+}
+
+impl TaskList {
+    pub fn add_pantry_item(&mut self, item: &PantryItem) -> Result<i64, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        conn.execute(
+            "INSERT INTO pantry_items (name, food_id, quantity, unit, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                item.name,
+                item.food_id,
+                item.amount.quantity,
+                item.amount.unit.as_str(),
+                item.updated_at.to_rfc3339()
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_pantry_item(&self, id: i64) -> Result<Option<PantryItem>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM pantry_items WHERE id = ?1", ITEM_COLUMNS))?;
+        let mut rows = stmt.query_map([id], item_from_row)?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Everything in the pantry, by name, including items that ran out.
+    pub fn pantry(&self) -> Result<Vec<PantryItem>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt =
+            conn.prepare(&format!("SELECT {} FROM pantry_items ORDER BY name COLLATE NOCASE, id", ITEM_COLUMNS))?;
+        let items = stmt.query_map([], item_from_row)?;
+        Ok(items.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Sets how much of an item there is, after shopping or a stock check.
+    /// Returns whether the item exists.
+    pub fn set_pantry_amount(&mut self, id: i64, amount: Amount) -> Result<bool, Box<dyn std::error::Error>> {
+        let updated = self.database().connection().execute(
+            "UPDATE pantry_items SET quantity = ?2, unit = ?3, updated_at = ?4 WHERE id = ?1",
+            params![id, amount.quantity, amount.unit.as_str(), Utc::now().to_rfc3339()],
+        )?;
+        self.poll_changes()?;
+        Ok(updated > 0)
+    }
+
+    /// Returns whether the item existed.
+    pub fn delete_pantry_item(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().connection().execute("DELETE FROM pantry_items WHERE id = ?1", [id])?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    pub fn add_recipe(&mut self, recipe: &Recipe) -> Result<i64, Box<dyn std::error::Error>> {
+        let id = self.database().with_transaction(|db| {
+            let conn = db.connection();
+            conn.execute(
+                "INSERT INTO recipes (name, servings, created_at) VALUES (?1, ?2, ?3)",
+                params![recipe.name, recipe.servings, recipe.created_at.to_rfc3339()],
+            )?;
+            let id = conn.last_insert_rowid();
+            for (position, ingredient) in recipe.ingredients.iter().enumerate() {
+                conn.execute(
+                    "INSERT INTO recipe_ingredients (recipe_id, position, name, food_id, quantity, unit)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        id,
+                        position as i64,
+                        ingredient.name,
+                        ingredient.food_id,
+                        ingredient.amount.quantity,
+                        ingredient.amount.unit.as_str()
+                    ],
+                )?;
+            }
+            Ok::<_, rusqlite::Error>(id)
+        })?;
+        self.poll_changes()?;
+        Ok(id)
+    }
+
+    pub fn get_recipe(&self, id: i64) -> Result<Option<Recipe>, Box<dyn std::error::Error>> {
+        Ok(self.query_recipes("WHERE id = ?1", [id])?.pop())
+    }
+
+    /// The first recipe with exactly this name, ignoring case.
+    pub fn find_recipe(&self, name: &str) -> Result<Option<Recipe>, Box<dyn std::error::Error>> {
+        Ok(self.query_recipes("WHERE name = ?1 COLLATE NOCASE", [name.trim()])?.into_iter().next())
+    }
+
+    /// Every recipe, by name.
+    pub fn recipes(&self) -> Result<Vec<Recipe>, Box<dyn std::error::Error>> {
+        self.query_recipes("", [])
+    }
+
+    /// Returns whether the recipe existed.
+    pub fn delete_recipe(&mut self, id: i64) -> Result<bool, Box<dyn std::error::Error>> {
+        let deleted = self.database().with_transaction(|db| {
+            db.connection().execute("DELETE FROM recipe_ingredients WHERE recipe_id = ?1", [id])?;
+            db.connection().execute("DELETE FROM recipes WHERE id = ?1", [id])
+        })?;
+        self.poll_changes()?;
+        Ok(deleted > 0)
+    }
+
+    fn query_recipes(
+        &self,
+        filter: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Recipe>, Box<dyn std::error::Error>> {
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, name, servings, created_at FROM recipes {} ORDER BY name COLLATE NOCASE, id",
+            filter
+        ))?;
+        let mut recipes = stmt
+            .query_map(params, |row| {
+                Ok(Recipe {
+                    id: Some(row.get(0)?),
+                    name: row.get(1)?,
+                    servings: row.get(2)?,
+                    ingredients: Vec::new(),
+                    created_at: parse_time(&row.get::<_, String>(3)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT name, food_id, quantity, unit FROM recipe_ingredients WHERE recipe_id = ?1 ORDER BY position",
+        )?;
+        for recipe in &mut recipes {
+            let ingredients = stmt.query_map([recipe.id], |row| {
+                Ok(Ingredient {
+                    name: row.get(0)?,
+                    food_id: row.get(1)?,
+                    amount: Amount::new(row.get(2)?, unit_from_row(row, 3)?),
+                })
+            })?;
+            recipe.ingredients = ingredients.collect::<rusqlite::Result<_>>()?;
+        }
+        Ok(recipes)
+    }
+
+    /// Logs a meal and takes what was eaten out of the pantry, in one transaction.
+    pub fn log_meal_from_pantry(&mut self, entry: &MealEntry) -> Result<PantryReport, Box<dyn std::error::Error>> {
+        let food = self.get_food(entry.food_id)?.ok_or_else(|| format!("food {} not found", entry.food_id))?;
+        let report = self.database().with_transaction(|db| {
+            let meal_entry_id = insert_meal_entry(db, entry)?;
+            let (used, unmatched) = take(db, &food.name, Some(&food), Amount::new(entry.grams, Unit::Gram))?;
+            Ok::<_, rusqlite::Error>(PantryReport {
+                meal_entry_id: Some(meal_entry_id),
+                used,
+                unmatched: unmatched.into_iter().collect(),
+            })
+        })?;
+        self.poll_changes()?;
+        Ok(report)
+    }
+
+    /// Takes the ingredients of `batches` of a recipe out of the pantry, in one transaction.
+    pub fn cook_recipe(&mut self, recipe_id: i64, batches: f64) -> Result<PantryReport, Box<dyn std::error::Error>> {
+        if !batches.is_finite() || batches <= 0.0 {
+            return Err(format!("cannot cook {} batches", batches).into());
+        }
+        let recipe = self.get_recipe(recipe_id)?.ok_or_else(|| format!("recipe {} not found", recipe_id))?;
+        let mut foods = Vec::new();
+        for ingredient in &recipe.ingredients {
+            foods.push(match ingredient.food_id {
+                Some(food_id) => self.get_food(food_id)?,
+                None => None,
+            });
+        }
+
+        let report = self.database().with_transaction(|db| {
+            let mut report = PantryReport::default();
+            for (ingredient, food) in recipe.ingredients.iter().zip(&foods) {
+                let need = Amount::new(ingredient.amount.quantity * batches, ingredient.amount.unit);
+                let (used, unmatched) = take(db, &ingredient.name, food.as_ref(), need)?;
+                report.used.extend(used);
+                report.unmatched.extend(unmatched);
+            }
+            Ok::<_, rusqlite::Error>(report)
+        })?;
+        self.poll_changes()?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nutrition::{Meal, Nutrients};
+
+    fn amount(text: &str) -> Amount {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_amounts_and_ingredients() {
+        assert_eq!(amount("500g"), Amount::new(500.0, Unit::Gram));
+        assert_eq!(amount("1.5 Litres"), Amount::new(1.5, Unit::Litre));
+        assert_eq!(amount("6"), Amount::new(6.0, Unit::Piece));
+        assert_eq!(amount("0 cups"), Amount::new(0.0, Unit::Cup));
+        assert!("some".parse::<Amount>().is_err());
+        assert!("2 handfuls".parse::<Amount>().is_err());
+
+        assert_eq!(amount("1 kg").to(Unit::Pound, None).map(|a| (a.quantity * 1000.0).round()), Some(2205.0));
+        assert_eq!(amount("2 cups").to(Unit::Millilitre, None), Some(Amount::new(480.0, Unit::Millilitre)));
+        assert_eq!(amount("150 g").to(Unit::Piece, Some(50.0)), Some(Amount::new(3.0, Unit::Piece)));
+        assert_eq!(amount("2").to(Unit::Gram, Some(50.0)), Some(Amount::new(100.0, Unit::Gram)));
+        assert_eq!(amount("150 g").to(Unit::Piece, None), None);
+        assert_eq!(amount("1 l").to(Unit::Gram, Some(50.0)), None);
+        assert_eq!(amount("1.5 l").to_string(), "1.5 l");
+        assert_eq!(amount("3").to_string(), "3");
+
+        let ingredient: Ingredient = "2 cups of Whole milk".parse().unwrap();
+        assert_eq!((ingredient.name.as_str(), ingredient.amount), ("Whole milk", Amount::new(2.0, Unit::Cup)));
+        let ingredient: Ingredient = "200g rolled oats".parse().unwrap();
+        assert_eq!((ingredient.name.as_str(), ingredient.amount), ("rolled oats", Amount::new(200.0, Unit::Gram)));
+        let ingredient: Ingredient = "3 eggs".parse().unwrap();
+        assert_eq!((ingredient.name.as_str(), ingredient.amount), ("eggs", Amount::new(3.0, Unit::Piece)));
+        let ingredient: Ingredient = "4 fl oz cream".parse().unwrap();
+        assert_eq!(ingredient.amount.unit, Unit::FluidOunce);
+        assert!("a pinch of salt".parse::<Ingredient>().is_err());
+        assert!("2 cups".parse::<Ingredient>().is_ok_and(|i| i.name == "cups"));
+    }
+
+    #[test]
+    fn test_meals_and_recipes_take_from_pantry() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let kcal = |energy_kcal| Nutrients { energy_kcal, ..Default::default() };
+        let eggs = task_list.add_food(&Food::new("Egg", kcal(143.0)).with_serving(50.0))?;
+        let oats = task_list.add_food(&Food::new("Rolled oats", kcal(379.0)))?;
+
+        let egg_box = task_list.add_pantry_item(&PantryItem::new("Eggs", amount("6")).with_food(eggs))?;
+        let old_bag = task_list.add_pantry_item(&PantryItem::new("rolled oats", amount("0.1 kg")))?;
+        let new_bag = task_list.add_pantry_item(&PantryItem::new("Rolled oats", amount("1 lb")))?;
+        let milk = task_list.add_pantry_item(&PantryItem::new("Milk", amount("1 l")))?;
+
+        // 100 g of egg is two eggs
+        let report = task_list.log_meal_from_pantry(&MealEntry::new(eggs, 100.0, Meal::Breakfast, Utc::now()))?;
+        assert!(report.is_reconciled());
+        assert_eq!(report.used.len(), 1);
+        assert_eq!(report.used[0].remaining, Amount::new(4.0, Unit::Piece));
+        assert_eq!(task_list.day_nutrition(Utc::now().date_naive(), &Utc)?.entries.len(), 1);
+
+        // Matched by name; the older bag is used up first
+        let report = task_list.log_meal_from_pantry(&MealEntry::new(oats, 150.0, Meal::Breakfast, Utc::now()))?;
+        assert!(report.is_reconciled());
+        let taken: Vec<(i64, Amount)> = report.used.iter().map(|used| (used.item_id, used.taken)).collect();
+        assert_eq!(taken[0], (old_bag, Amount::new(0.1, Unit::Kilogram)));
+        assert_eq!(taken[1].0, new_bag);
+        assert!((taken[1].1.quantity - 50.0 / 453.59237).abs() < 1e-9);
+
+        let porridge = Recipe::new(
+            "Porridge",
+            2.0,
+            vec!["100g rolled oats".parse()?, "2 cups milk".parse()?, "1 egg".parse()?, "1 tbsp honey".parse()?],
+        );
+        let id = task_list.add_recipe(&porridge)?;
+        assert_eq!(task_list.find_recipe("porridge")?.unwrap().ingredients, porridge.ingredients);
+
+        let report = task_list.cook_recipe(id, 2.0)?;
+        let milk_left = task_list.get_pantry_item(milk)?.unwrap().amount;
+        assert!((milk_left.quantity - 0.04).abs() < 1e-9);
+        assert_eq!(milk_left.unit, Unit::Litre);
+        // "egg" is not the pantry's "Eggs" and the recipe does not say it is the Egg food
+        let unmatched: Vec<(&str, UnmatchedReason)> =
+            report.unmatched.iter().map(|unmatched| (unmatched.name.as_str(), unmatched.reason)).collect();
+        assert_eq!(unmatched, [("egg", UnmatchedReason::NotInPantry), ("honey", UnmatchedReason::NotInPantry)]);
+        assert_eq!(report.unmatched[0].missing, Amount::new(2.0, Unit::Piece));
+
+        // Out of milk, and oats are kept by weight
+        task_list.set_pantry_amount(milk, amount("1 cup"))?;
+        task_list.add_pantry_item(&PantryItem::new("Honey", amount("1")))?;
+        let report = task_list.cook_recipe(id, 1.0)?;
+        let unmatched: Vec<(&str, UnmatchedReason)> =
+            report.unmatched.iter().map(|unmatched| (unmatched.name.as_str(), unmatched.reason)).collect();
+        assert_eq!(
+            unmatched,
+            [
+                ("milk", UnmatchedReason::NotEnough),
+                ("egg", UnmatchedReason::NotInPantry),
+                ("honey", UnmatchedReason::IncompatibleUnits)
+            ]
+        );
+        assert_eq!(report.unmatched[0].missing, Amount::new(1.0, Unit::Cup));
+        assert_eq!(task_list.get_pantry_item(milk)?.unwrap().amount.quantity, 0.0);
+        assert_eq!(task_list.get_pantry_item(egg_box)?.unwrap().amount.quantity, 4.0);
+
+        assert!(task_list.cook_recipe(99, 1.0).is_err());
+        assert!(task_list.cook_recipe(id, 0.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_amounts_that_cannot_be_converted_stay_in_pantry() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let unmatched = |report: &PantryReport| -> Vec<(String, UnmatchedReason)> {
+            report.unmatched.iter().map(|unmatched| (unmatched.name.clone(), unmatched.reason)).collect()
+        };
+
+        // Without a serving size, grams of bread cannot be taken from slices
+        let bread = task_list.add_food(&Food::new("Bread", Nutrients::default()))?;
+        let loaf = task_list.add_pantry_item(&PantryItem::new("Bread", amount("10")))?;
+        let report = task_list.log_meal_from_pantry(&MealEntry::new(bread, 80.0, Meal::Lunch, Utc::now()))?;
+        assert_eq!(unmatched(&report), [("Bread".to_string(), UnmatchedReason::IncompatibleUnits)]);
+        assert_eq!(report.unmatched[0].missing, Amount::new(80.0, Unit::Gram));
+        assert!(report.meal_entry_id.is_some(), "the meal is logged even when the pantry is not updated");
+        assert_eq!(task_list.get_pantry_item(loaf)?.unwrap().amount, amount("10"));
+
+        // Grams of milk kept in litres are skipped, and the carton kept by weight is used instead
+        let milk = task_list.add_food(&Food::new("Milk", Nutrients::default()))?;
+        let carton = task_list.add_pantry_item(&PantryItem::new("Milk", amount("1 l")))?;
+        let jug = task_list.add_pantry_item(&PantryItem::new("Milk", amount("100 g")))?;
+        let report = task_list.log_meal_from_pantry(&MealEntry::new(milk, 250.0, Meal::Snack, Utc::now()))?;
+        assert_eq!(report.used.iter().map(|used| used.item_id).collect::<Vec<_>>(), [jug]);
+        // Once something was taken, the rest is missing because there was not enough
+        assert_eq!(unmatched(&report), [("Milk".to_string(), UnmatchedReason::NotEnough)]);
+        assert!((report.unmatched[0].missing.quantity - 150.0).abs() < 1e-9);
+        assert_eq!(task_list.get_pantry_item(carton)?.unwrap().amount, amount("1 l"));
+
+        // A recipe measuring flour by volume, and eggs by weight without a food to give their size
+        let flour = task_list.add_pantry_item(&PantryItem::new("Flour", amount("1 kg")))?;
+        let eggs = task_list.add_pantry_item(&PantryItem::new("Eggs", amount("6")))?;
+        let cake = Recipe::new("Cake", 8.0, vec!["2 cups flour".parse()?, "100g eggs".parse()?]);
+        let id = task_list.add_recipe(&cake)?;
+        let report = task_list.cook_recipe(id, 1.0)?;
+        assert!(report.used.is_empty());
+        assert_eq!(
+            unmatched(&report),
+            [
+                ("flour".to_string(), UnmatchedReason::IncompatibleUnits),
+                ("eggs".to_string(), UnmatchedReason::IncompatibleUnits)
+            ]
+        );
+        assert_eq!(report.unmatched[0].missing, Amount::new(2.0, Unit::Cup));
+        assert_eq!(task_list.get_pantry_item(flour)?.unwrap().amount, amount("1 kg"));
+        assert_eq!(task_list.get_pantry_item(eggs)?.unwrap().amount, amount("6"));
+
+        // Linking the ingredient to a food with a serving size lets grams of eggs come out of the box
+        let egg = task_list.add_food(&Food::new("Egg", Nutrients::default()).with_serving(50.0))?;
+        let mut ingredients = cake.ingredients.clone();
+        ingredients[1].food_id = Some(egg);
+        let id = task_list.add_recipe(&Recipe::new("Egg cake", 8.0, ingredients))?;
+        let report = task_list.cook_recipe(id, 1.0)?;
+        assert_eq!(unmatched(&report), [("flour".to_string(), UnmatchedReason::IncompatibleUnits)]);
+        assert_eq!(task_list.get_pantry_item(eggs)?.unwrap().amount, amount("4"));
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+// core/src/nutrition/usda.rs
+// Reads a USDA FoodData Central CSV download: food.csv names the foods, food_nutrient.csv
+// holds one row per nutrient amount and branded_food.csv adds brands, barcodes and servings.
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::Path;
+use super::catalogue::{normalise_barcode, FoodImportError, FoodSink};
+use super::{Food, Nutrients, NUTRIENTS};
+
+/// FoodData Central nutrient ids, the `Nutrients` field they fill, a unit factor
+/// and a rank: when a food has several ids for one field the lowest rank wins.
+const NUTRIENT_IDS: [(i64, &str, f64, u8); 18] = [
+    (1008, "energy_kcal", 1.0, 0),
+    // Atwater general and specific factors, used by foundation foods
+    (2047, "energy_kcal", 1.0, 1),
+    (2048, "energy_kcal", 1.0, 2),
+    (1062, "energy_kcal", 1.0 / 4.184, 3),
+    (1003, "protein_g", 1.0, 0),
+    (1005, "carbs_g", 1.0, 0),
+    (1050, "carbs_g", 1.0, 1),
+    (1004, "fat_g", 1.0, 0),
+    (1085, "fat_g", 1.0, 1),
+    (1258, "saturated_fat_g", 1.0, 0),
+    (2000, "sugar_g", 1.0, 0),
+    (1063, "sugar_g", 1.0, 1),
+    (1079, "fiber_g", 1.0, 0),
+    (1093, "sodium_mg", 1.0, 0),
+    (1092, "potassium_mg", 1.0, 0),
+    (1087, "calcium_mg", 1.0, 0),
+    (1089, "iron_mg", 1.0, 0),
+    (1162, "vitamin_c_mg", 1.0, 0),
+];
+
+/// Data types of foods people eat; samples, sub-samples and acquisitions
+/// are lab records behind the foundation foods.
+const DATA_TYPES: [&str; 5] =
+    ["foundation_food", "sr_legacy_food", "survey_fndds_food", "branded_food", "experimental_food"];
+
+struct Entry {
+    food: Food,
+    /// Rank of each nutrient's amount so far, see `NUTRIENT_IDS`
+    ranks: [Option<u8>; 12],
+}
+
+pub(crate) fn read(dir: &Path, sink: &mut dyn FoodSink) -> Result<(), FoodImportError> {
+    let mut foods: BTreeMap<i64, Entry> = BTreeMap::new();
+    let mut reader = require(dir, "food.csv")?;
+    let [fdc_id, data_type, description] = columns(&mut reader, "food.csv", ["fdc_id", "data_type", "description"])?;
+    for record in reader.records() {
+        let record = record.map_err(|e| csv_error("food.csv", e))?;
+        let name = record.get(description).unwrap_or("").trim();
+        let Some(id) = record.get(fdc_id).and_then(|id| id.trim().parse().ok()) else {
+            sink.skip();
+            continue;
+        };
+        if name.is_empty() || !DATA_TYPES.contains(&record.get(data_type).unwrap_or("")) {
+            sink.skip();
+            continue;
+        }
+        let mut food = Food::new(name, Nutrients::default());
+        food.source_id = Some(format!("usda:{}", id));
+        foods.insert(id, Entry { food, ranks: [None; 12] });
+    }
+
+    let mut reader = require(dir, "food_nutrient.csv")?;
+    let [fdc_id, nutrient_id, amount] =
+        columns(&mut reader, "food_nutrient.csv", ["fdc_id", "nutrient_id", "amount"])?;
+    let nutrients: HashMap<i64, (usize, f64, u8)> = NUTRIENT_IDS
+        .iter()
+        .map(|(id, field, factor, rank)| (*id, (NUTRIENTS.iter().position(|n| n == field).unwrap(), *factor, *rank)))
+        .collect();
+    for record in reader.records() {
+        let record = record.map_err(|e| csv_error("food_nutrient.csv", e))?;
+        let id = |column: usize| record.get(column).and_then(|value| value.trim().parse::<i64>().ok());
+        let (Some(entry), Some(&(index, factor, rank))) =
+            (id(fdc_id).and_then(|id| foods.get_mut(&id)), id(nutrient_id).and_then(|id| nutrients.get(&id)))
+        else {
+            continue;
+        };
+        let Some(amount) = record.get(amount).and_then(|value| value.trim().parse::<f64>().ok()) else { continue };
+        if entry.ranks[index].is_none_or(|current| rank < current) {
+            let mut values = entry.food.per_100g.values();
+            values[index] = amount * factor;
+            entry.food.per_100g = Nutrients::from_values(values);
+            entry.ranks[index] = Some(rank);
+        }
+    }
+
+    if let Some(mut reader) = open(dir, "branded_food.csv")? {
+        let [fdc_id, brand_owner, brand_name, gtin_upc, serving_size, serving_size_unit] = columns(
+            &mut reader,
+            "branded_food.csv",
+            ["fdc_id", "brand_owner", "brand_name", "gtin_upc", "serving_size", "serving_size_unit"],
+        )?;
+        for record in reader.records() {
+            let record = record.map_err(|e| csv_error("branded_food.csv", e))?;
+            let text = |column: usize| Some(record.get(column)?.trim()).filter(|value| !value.is_empty());
+            let Some(entry) = text(fdc_id).and_then(|id| id.parse().ok()).and_then(|id: i64| foods.get_mut(&id)) else {
+                continue;
+            };
+            entry.food.brand = text(brand_name).or(text(brand_owner)).map(str::to_string);
+            entry.food.barcode = text(gtin_upc).and_then(normalise_barcode);
+            if matches!(text(serving_size_unit), Some("g" | "GRM")) {
+                entry.food.serving_g =
+                    text(serving_size).and_then(|grams| grams.parse().ok()).filter(|grams: &f64| *grams > 0.0);
+            }
+        }
+    }
+
+    for entry in foods.into_values() {
+        if entry.ranks.iter().all(Option::is_none) {
+            sink.skip();
+        } else {
+            sink.add(entry.food)?;
+        }
+    }
+    Ok(())
+}
+
+fn open(dir: &Path, file: &str) -> Result<Option<csv::Reader<File>>, FoodImportError> {
+    let path = dir.join(file);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file = File::open(&path).map_err(|e| FoodImportError::Io(format!("{}: {}", path.display(), e)))?;
+    Ok(Some(csv::ReaderBuilder::new().flexible(true).from_reader(file)))
+}
+
+fn require(dir: &Path, file: &str) -> Result<csv::Reader<File>, FoodImportError> {
+    open(dir, file)?.ok_or_else(|| {
+        FoodImportError::Io(format!("{} is not a FoodData Central download: {} is missing", dir.display(), file))
+    })
+}
+
+fn columns<const N: usize>(
+    reader: &mut csv::Reader<File>,
+    file: &str,
+    names: [&'static str; N],
+) -> Result<[usize; N], FoodImportError> {
+    let headers = reader.headers().map_err(|e| csv_error(file, e))?;
+    let mut indexes = [0; N];
+    for (index, name) in indexes.iter_mut().zip(names) {
+        *index = headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| FoodImportError::MissingColumn { file: file.to_string(), column: name })?;
+    }
+    Ok(indexes)
+}
+
+fn csv_error(file: &str, err: csv::Error) -> FoodImportError {
+    FoodImportError::Csv(format!("{}: {}", file, err))
+}
@@ -0,0 +1,476 @@
+// core/src/ordering.rs
+// Manual task order. Every task has a `position` key and tasks sort by it as plain
+// text, so moving a task rewrites only that task's row, and devices that move
+// different tasks never write the same row. Two devices that move tasks into the
+// same gap can still store equal keys; the next move next to them settles those
+// rows alone. Keys use fractional indexing: an
+// integer part whose first character gives its length, then base-62 fraction
+// digits, so there is always a key between any two others.
+use chrono::Utc;
+use rusqlite::params;
+use rusqlite::types::Type;
+use crate::database::{Database, TASK_ORDER};
+use crate::task_list::TaskList;
+
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// Where a fresh order starts
+const FIRST_KEY: &str = "a0";
+/// The lowest integer part; a key must have a fraction after it so something can go before
+const SMALLEST_INTEGER: &str = "A00000000000000000000000000";
+/// Moving a task to a longer key than this rebalances every key first
+const MAX_KEY_LEN: usize = 24;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderingError {
+    InvalidKey(String),
+    /// The first key or task does not sort before the second
+    OutOfOrder(String, String),
+    TaskNotFound(i64),
+    /// A task cannot be moved next to itself
+    SameTask(i64),
+    /// `move_task` was given neither neighbour
+    NoNeighbour,
+    /// Only after an absurd number of moves to the very top
+    Exhausted,
+}
+
+impl std::fmt::Display for OrderingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderingError::InvalidKey(key) => write!(f, "'{}' is not a position key", key),
+            OrderingError::OutOfOrder(first, second) => write!(f, "{} does not come before {}", first, second),
+            OrderingError::TaskNotFound(id) => write!(f, "task {} not found", id),
+            OrderingError::SameTask(id) => write!(f, "task {} cannot be moved next to itself", id),
+            OrderingError::NoNeighbour => write!(f, "give the task to move it before, after, or both"),
+            OrderingError::Exhausted => write!(f, "no position key is left before the first task"),
+        }
+    }
+}
+
+impl std::error::Error for OrderingError {}
+
+fn value(digit: u8) -> usize {
+    DIGITS.iter().position(|&d| d == digit).unwrap_or(0)
+}
+
+fn integer_len(head: u8) -> Option<usize> {
+    match head {
+        b'a'..=b'z' => Some(usize::from(head - b'a') + 2),
+        b'A'..=b'Z' => Some(usize::from(b'Z' - head) + 2),
+        _ => None,
+    }
+}
+
+// Splits a key into its integer part and its fraction
+fn split_key(key: &str) -> Result<(&str, &str), OrderingError> {
+    let invalid = || OrderingError::InvalidKey(key.to_string());
+    let len = key.bytes().next().and_then(integer_len).ok_or_else(invalid)?;
+    if key.len() < len || !key.bytes().skip(1).all(|d| DIGITS.contains(&d)) {
+        return Err(invalid());
+    }
+    let (integer, fraction) = key.split_at(len);
+    if fraction.ends_with('0') || (integer == SMALLEST_INTEGER && fraction.is_empty()) {
+        return Err(invalid());
+    }
+    Ok((integer, fraction))
+}
+
+pub fn is_valid_key(key: &str) -> bool {
+    split_key(key).is_ok()
+}
+
+// A fraction between `low` and `high` (or 1 when None), where `low` < `high`
+// and neither ends in 0
+fn midpoint(low: &[u8], high: Option<&[u8]>) -> Vec<u8> {
+    if let Some(high) = high {
+        // Keep the digits both share, reading missing digits of `low` as 0
+        let shared = high
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &digit)| low.get(i).copied().unwrap_or(b'0') == digit)
+            .count();
+        if shared > 0 {
+            let mut key = high[..shared].to_vec();
+            key.extend(midpoint(low.get(shared..).unwrap_or_default(), Some(&high[shared..])));
+            return key;
+        }
+    }
+    let low_digit = low.first().map_or(0, |&digit| value(digit));
+    let high_digit = high.map_or(DIGITS.len(), |high| value(high[0]));
+    if high_digit - low_digit > 1 {
+        vec![DIGITS[(low_digit + high_digit).div_ceil(2)]]
+    } else if let Some(high) = high.filter(|high| high.len() > 1) {
+        vec![high[0]]
+    } else {
+        let mut key = vec![DIGITS[low_digit]];
+        key.extend(midpoint(low.get(1..).unwrap_or_default(), None));
+        key
+    }
+}
+
+fn increment_integer(integer: &str) -> Option<String> {
+    let (head, digits) = integer.as_bytes().split_first()?;
+    let mut digits = digits.to_vec();
+    for digit in digits.iter_mut().rev() {
+        let next = value(*digit) + 1;
+        if next < DIGITS.len() {
+            *digit = DIGITS[next];
+            return String::from_utf8([vec![*head], digits].concat()).ok();
+        }
+        *digit = b'0';
+    }
+    // Carried out of every digit: the next length up
+    let head = match head {
+        b'Z' => return Some("a0".to_string()),
+        b'z' => return None,
+        head => head + 1,
+    };
+    if head > b'a' {
+        digits.push(b'0');
+    } else {
+        digits.pop();
+    }
+    String::from_utf8([vec![head], digits].concat()).ok()
+}
+
+fn decrement_integer(integer: &str) -> Option<String> {
+    let (head, digits) = integer.as_bytes().split_first()?;
+    let mut digits = digits.to_vec();
+    for digit in digits.iter_mut().rev() {
+        let current = value(*digit);
+        if current > 0 {
+            *digit = DIGITS[current - 1];
+            return String::from_utf8([vec![*head], digits].concat()).ok();
+        }
+        *digit = b'z';
+    }
+    let head = match head {
+        b'a' => return Some("Zz".to_string()),
+        b'A' => return None,
+        head => head - 1,
+    };
+    if head < b'Z' {
+        digits.push(b'z');
+    } else {
+        digits.pop();
+    }
+    String::from_utf8([vec![head], digits].concat()).ok()
+}
+
+fn join(integer: &str, fraction: Vec<u8>) -> String {
+    // Every byte comes from DIGITS
+    format!("{}{}", integer, String::from_utf8(fraction).unwrap_or_default())
+}
+
+/// A key that sorts after `low` and before `high`; None means the start or end of the order.
+/// Keys stay short when tasks are added at either end, and grow by about one
+/// character for every six moves into the same gap.
+pub fn key_between(low: Option<&str>, high: Option<&str>) -> Result<String, OrderingError> {
+    match (low, high) {
+        (None, None) => Ok(FIRST_KEY.to_string()),
+        (None, Some(high)) => {
+            let (integer, fraction) = split_key(high)?;
+            if integer == SMALLEST_INTEGER {
+                Ok(join(integer, midpoint(b"", Some(fraction.as_bytes()))))
+            } else if !fraction.is_empty() {
+                Ok(integer.to_string())
+            } else {
+                decrement_integer(integer).ok_or(OrderingError::Exhausted)
+            }
+        }
+        (Some(low), None) => {
+            let (integer, fraction) = split_key(low)?;
+            Ok(increment_integer(integer).unwrap_or_else(|| join(integer, midpoint(fraction.as_bytes(), None))))
+        }
+        (Some(low), Some(high)) => {
+            let (low_integer, low_fraction) = split_key(low)?;
+            let (high_integer, high_fraction) = split_key(high)?;
+            if low >= high {
+                return Err(OrderingError::OutOfOrder(low.to_string(), high.to_string()));
+            }
+            if low_integer == high_integer {
+                return Ok(join(low_integer, midpoint(low_fraction.as_bytes(), Some(high_fraction.as_bytes()))));
+            }
+            match increment_integer(low_integer) {
+                Some(next) if next.as_str() < high => Ok(next),
+                _ => Ok(join(low_integer, midpoint(low_fraction.as_bytes(), None))),
+            }
+        }
+    }
+}
+
+fn stored_key_error(err: OrderingError) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(0, Type::Text, Box::new(err))
+}
+
+/// A key before every stored task, for a new task that has none.
+pub(crate) fn top_key(db: &Database) -> rusqlite::Result<String> {
+    let first: Option<String> = db.connection().query_row("SELECT MIN(position) FROM tasks", [], |row| row.get(0))?;
+    key_between(None, first.as_deref()).map_err(stored_key_error)
+}
+
+/// Gives tasks stored before positions existed a key, keeping them newest first.
+pub(crate) fn place_unordered(db: &Database) -> rusqlite::Result<()> {
+    let ids: Vec<i64> = {
+        let mut stmt =
+            db.connection().prepare("SELECT id FROM tasks WHERE position IS NULL ORDER BY created_at, id")?;
+        let ids = stmt.query_map([], |row| row.get(0))?;
+        ids.collect::<rusqlite::Result<_>>()?
+    };
+    if ids.is_empty() {
+        return Ok(());
+    }
+    db.with_transaction(|db| {
+        for id in ids {
+            db.connection().execute("UPDATE tasks SET position = ?1 WHERE id = ?2", params![top_key(db)?, id])?;
+        }
+        Ok(())
+    })
+}
+
+fn position_of(db: &Database, id: i64) -> Result<String, Box<dyn std::error::Error>> {
+    let mut stmt = db.connection().prepare("SELECT position FROM tasks WHERE id = ?1")?;
+    let mut rows = stmt.query_map([id], |row| row.get::<_, Option<String>>(0))?;
+    Ok(rows.next().transpose()?.flatten().ok_or(OrderingError::TaskNotFound(id))?)
+}
+
+// When other tasks share task `id`'s key, the first of them in list order (ties
+// go newest first, then by id) keeps it and the rest get keys of their own before
+// the next key along. Every device settles a tie the same way, and no other row
+// changes. Like a rebalance, this leaves `updated_at` alone.
+fn settle_shared_key(db: &Database, id: i64) -> Result<(), Box<dyn std::error::Error>> {
+    let key = position_of(db, id)?;
+    let ids: Vec<i64> = {
+        let query = format!("SELECT id FROM tasks WHERE position = ?1 ORDER BY {}", TASK_ORDER);
+        let mut stmt = db.connection().prepare(&query)?;
+        let ids = stmt.query_map([&key], |row| row.get(0))?;
+        ids.collect::<rusqlite::Result<_>>()?
+    };
+    if ids.len() < 2 {
+        return Ok(());
+    }
+    let next: Option<String> =
+        db.connection().query_row("SELECT MIN(position) FROM tasks WHERE position > ?1", [&key], |row| row.get(0))?;
+    db.with_transaction(|db| {
+        let mut low = key;
+        for id in &ids[1..] {
+            let settled = key_between(Some(&low), next.as_deref()).map_err(stored_key_error)?;
+            db.connection().execute("UPDATE tasks SET position = ?1 WHERE id = ?2", params![settled, id])?;
+            low = settled;
+        }
+        Ok::<_, rusqlite::Error>(())
+    })?;
+    Ok(())
+}
+
+impl TaskList {
+    /// Moves task `id` so it comes right after `before` and right before `after`.
+    /// Either may be None, e.g. when a task is dropped at the top or bottom of a
+    /// filtered view, and the other then decides where it goes. Only the moved
+    /// task's row changes unless the keys need rebalancing first.
+    pub fn move_task(
+        &mut self,
+        id: i64,
+        before: Option<i64>,
+        after: Option<i64>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if before.is_none() && after.is_none() {
+            return Err(OrderingError::NoNeighbour.into());
+        }
+        if before == Some(id) || after == Some(id) {
+            return Err(OrderingError::SameTask(id).into());
+        }
+        position_of(self.database(), id)?;
+        for neighbour in before.into_iter().chain(after) {
+            settle_shared_key(self.database(), neighbour)?;
+        }
+        let mut key = self.position_between(id, before, after)?;
+        if key.len() > MAX_KEY_LEN {
+            self.rebalance_positions()?;
+            key = self.position_between(id, before, after)?;
+        }
+
+        self.database().connection().execute(
+            "UPDATE tasks SET position = ?1, updated_at = ?2 WHERE id = ?3",
+            params![key, Utc::now().to_rfc3339(), id],
+        )?;
+        self.poll_changes()?;
+        Ok(())
+    }
+
+    // A key for `id` between its new neighbours, skipping its own current key
+    fn position_between(
+        &self,
+        id: i64,
+        before: Option<i64>,
+        after: Option<i64>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let db = self.database();
+        let mut low = before.map(|before| position_of(db, before)).transpose()?;
+        let mut high = after.map(|after| position_of(db, after)).transpose()?;
+        match (&low, &high) {
+            (Some(low), Some(high)) if low >= high => {
+                let (before, after) = (before.unwrap_or_default(), after.unwrap_or_default());
+                return Err(OrderingError::OutOfOrder(format!("task {}", before), format!("task {}", after)).into());
+            }
+            (Some(key), None) => {
+                high = db.connection().query_row(
+                    "SELECT MIN(position) FROM tasks WHERE position > ?1 AND id != ?2",
+                    params![key, id],
+                    |row| row.get(0),
+                )?;
+            }
+            (None, Some(key)) => {
+                low = db.connection().query_row(
+                    "SELECT MAX(position) FROM tasks WHERE position < ?1 AND id != ?2",
+                    params![key, id],
+                    |row| row.get(0),
+                )?;
+            }
+            _ => {}
+        }
+        Ok(key_between(low.as_deref(), high.as_deref())?)
+    }
+
+    /// Gives every task a fresh, short key in the current order and returns how
+    /// many keys changed. `move_task` calls this when keys grow long. Only
+    /// `position` is rewritten, so the rewrite does not count as an edit of the task.
+    pub fn rebalance_positions(&mut self) -> Result<u32, Box<dyn std::error::Error>> {
+        let changed = self.database().with_transaction(|db| {
+            let tasks: Vec<(i64, Option<String>)> = {
+                let query = format!("SELECT id, position FROM tasks ORDER BY {}", TASK_ORDER);
+                let mut stmt = db.connection().prepare(&query)?;
+                let tasks = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                tasks.collect::<rusqlite::Result<_>>()?
+            };
+            let mut key: Option<String> = None;
+            let mut changed = 0;
+            for (id, position) in tasks {
+                let next = key_between(key.as_deref(), None).map_err(stored_key_error)?;
+                if position.as_deref() != Some(next.as_str()) {
+                    db.connection().execute("UPDATE tasks SET position = ?1 WHERE id = ?2", params![next, id])?;
+                    changed += 1;
+                }
+                key = Some(next);
+            }
+            Ok::<_, rusqlite::Error>(changed)
+        })?;
+        self.poll_changes()?;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Task;
+
+    #[test]
+    fn test_keys_sort_between_their_neighbours() {
+        assert_eq!(key_between(None, None).unwrap(), "a0");
+        assert_eq!(key_between(Some("a0"), None).unwrap(), "a1");
+        assert_eq!(key_between(Some("az"), None).unwrap(), "b00");
+        assert_eq!(key_between(None, Some("a0")).unwrap(), "Zz");
+        assert_eq!(key_between(None, Some("b00")).unwrap(), "az");
+        assert_eq!(key_between(Some("a0"), Some("a1")).unwrap(), "a0V");
+        assert_eq!(key_between(Some("a0V"), Some("a1")).unwrap(), "a0l");
+        assert_eq!(key_between(Some("a0"), Some("a0V")).unwrap(), "a0G");
+        assert_eq!(key_between(Some("a1"), Some("a5")).unwrap(), "a2");
+        assert!(key_between(Some("a1"), Some("a1")).is_err());
+        assert!(key_between(Some("a10"), None).is_err(), "trailing zero");
+        assert!(key_between(Some("a"), None).is_err());
+        assert!(key_between(None, Some(SMALLEST_INTEGER)).is_err());
+        assert!(!is_valid_key("a-"));
+
+        // Squeezing into one gap grows keys slowly, and they stay in order
+        let (low, mut high) = ("a0".to_string(), "a1".to_string());
+        for _ in 0..60 {
+            let key = key_between(Some(&low), Some(&high)).unwrap();
+            assert!(low < key && key < high, "{} < {} < {}", low, key, high);
+            high = key;
+        }
+        assert!(high.len() <= 14, "{}", high);
+
+        // So does adding at either end
+        let mut key = "a0".to_string();
+        for _ in 0..5000 {
+            let next = key_between(None, Some(&key)).unwrap();
+            assert!(next < key);
+            key = next;
+        }
+        assert!(key.len() <= 4, "{}", key);
+    }
+
+    fn titles(task_list: &TaskList) -> Vec<String> {
+        task_list.all().unwrap().into_iter().map(|task| task.title).collect()
+    }
+
+    #[test]
+    fn test_move_task() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let mut ids = Vec::new();
+        for title in ["One", "Two", "Three", "Four"] {
+            ids.push(task_list.add(Task::new(title))?);
+        }
+        // New tasks go on top
+        assert_eq!(titles(&task_list), ["Four", "Three", "Two", "One"]);
+
+        task_list.move_task(ids[0], Some(ids[3]), Some(ids[2]))?;
+        assert_eq!(titles(&task_list), ["Four", "One", "Three", "Two"]);
+        // Only the moved task's row changed
+        assert_eq!(task_list.get_by_id(ids[3])?.unwrap().position.as_deref(), Some("Zx"));
+        task_list.move_task(ids[3], Some(ids[1]), None)?;
+        assert_eq!(titles(&task_list), ["One", "Three", "Two", "Four"]);
+        task_list.move_task(ids[1], None, Some(ids[0]))?;
+        assert_eq!(titles(&task_list), ["Two", "One", "Three", "Four"]);
+
+        let err = task_list.move_task(ids[2], Some(ids[3]), Some(ids[1])).unwrap_err();
+        assert_eq!(err.to_string(), "task 4 does not come before task 2");
+        assert!(task_list.move_task(ids[2], None, None).is_err());
+        assert!(task_list.move_task(ids[2], Some(ids[2]), None).is_err());
+        assert!(task_list.move_task(99, Some(ids[2]), None).is_err());
+
+        // Bouncing a task back and forth in one gap ends in a rebalance
+        for _ in 0..100 {
+            task_list.move_task(ids[2], Some(ids[1]), Some(ids[0]))?;
+            task_list.move_task(ids[0], Some(ids[1]), Some(ids[2]))?;
+        }
+        assert_eq!(titles(&task_list), ["Two", "One", "Three", "Four"]);
+        assert!(task_list.all()?.iter().all(|task| task.position.as_ref().unwrap().len() <= MAX_KEY_LEN));
+        Ok(())
+    }
+
+    #[test]
+    fn test_moves_settle_shared_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let mut ids = Vec::new();
+        for title in ["First", "Second", "Third", "Fourth"] {
+            ids.push(task_list.add(Task::new(title))?);
+        }
+        // As if a sync brought in a task moved into the same gap elsewhere
+        let key = task_list.get_by_id(ids[1])?.unwrap().position;
+        let tie = "UPDATE tasks SET position = ?1 WHERE id = ?2";
+        task_list.database().connection().execute(tie, params![key, ids[0]])?;
+        assert_eq!(titles(&task_list), ["Fourth", "Third", "Second", "First"], "ties fall back to newest first");
+        let before = task_list.all()?;
+
+        task_list.move_task(ids[3], Some(ids[0]), None)?;
+        assert_eq!(titles(&task_list), ["Third", "Second", "First", "Fourth"]);
+        let keys: Vec<String> = task_list.all()?.into_iter().filter_map(|task| task.position).collect();
+        assert_eq!(keys, ["Zy", "Zz", "a0", "a1"]);
+        // Only the tied task and the moved one were rewritten, and settling the tie is not an edit
+        let updated_at =
+            |tasks: &[Task], id: Option<i64>| tasks.iter().find(|task| task.id == id).map(|task| task.updated_at);
+        assert_eq!(updated_at(&task_list.all()?, Some(ids[0])), updated_at(&before, Some(ids[0])));
+
+        assert_eq!(task_list.rebalance_positions()?, 4);
+        assert_eq!(task_list.rebalance_positions()?, 0);
+        let rebalanced = task_list.all()?;
+        let keys: Vec<&str> = rebalanced.iter().filter_map(|task| task.position.as_deref()).collect();
+        assert_eq!(keys, ["a0", "a1", "a2", "a3"]);
+        for task in &rebalanced[..3] {
+            assert_eq!(Some(task.updated_at), updated_at(&before, task.id), "{}", task.title);
+        }
+        Ok(())
+    }
+}
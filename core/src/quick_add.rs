@@ -0,0 +1,292 @@
+// core/src/quick_add.rs
+// One-line task entry: "Buy milk #groceries !high due:tomorrow ~15m ^12".
+use chrono::{DateTime, TimeZone};
+use crate::dates::{self, DateParseError};
+use crate::task::{Priority, Task};
+
+// Longest natural-language date tried after `due:`, in words ("next friday at 5pm")
+const MAX_DUE_WORDS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickAddError {
+    MissingTitle,
+    InvalidDate(DateParseError),
+}
+
+impl std::fmt::Display for QuickAddError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuickAddError::MissingTitle => write!(f, "quick-add text has no title"),
+            QuickAddError::InvalidDate(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for QuickAddError {}
+
+impl From<DateParseError> for QuickAddError {
+    fn from(err: DateParseError) -> Self {
+        QuickAddError::InvalidDate(err)
+    }
+}
+
+impl Task {
+    /// Builds a task from a single line of quick-add text, using the system clock for dates.
+    ///
+    /// - `#groceries` sets the tag; a task has one, so later tags stay in the title
+    /// - `!high` or `!1` sets the priority (low, medium, high, urgent, or 4 down to 1)
+    /// - `due:tomorrow` sets the due date, see `dates::parse_due_date`
+    /// - `~15m`, `~1h30m` or `~90` sets the estimated duration in minutes
+    /// - `^12` makes it a subtask of task 12
+    ///
+    /// `due:` takes the longest run of following words that reads as a date, so
+    /// `due:next friday 5pm` works unquoted; `due:"..."` sets the extent explicitly.
+    /// Everything else, including tokens that do not parse (`#1`, `!important`),
+    /// becomes the title. A leading backslash keeps a token literal: `\#1 fan`.
+    pub fn parse_quick_add(input: &str) -> Result<Task, QuickAddError> {
+        Task::parse_quick_add_at(input, &chrono::Local::now())
+    }
+
+    /// `parse_quick_add` with due dates resolved relative to `now`.
+    pub fn parse_quick_add_at<Tz: TimeZone>(input: &str, now: &DateTime<Tz>) -> Result<Task, QuickAddError> {
+        let tokens = tokenize(input);
+        let mut title: Vec<&str> = Vec::new();
+        let mut task = Task::new("");
+        let mut index = 0;
+
+        while index < tokens.len() {
+            let token = tokens[index];
+            index += 1;
+
+            if let Some(literal) = token.strip_prefix('\\') {
+                title.push(literal);
+            } else if let Some(value) = token.strip_prefix("due:") {
+                let (due_date, consumed) = parse_due(value, &tokens[index..], now)?;
+                task.due_date = Some(due_date);
+                index += consumed;
+            } else if let Some(tag) = token.strip_prefix('#').filter(|t| is_tag(t)) {
+                if task.tag.is_none() {
+                    task.tag = Some(tag.to_string());
+                } else {
+                    title.push(token);
+                }
+            } else if let Some(priority) = token.strip_prefix('!').and_then(|p| p.parse::<Priority>().ok()) {
+                task.priority = Some(priority);
+            } else if let Some(minutes) = token.strip_prefix('~').and_then(parse_minutes) {
+                task.estimated_duration = Some(minutes);
+            } else if let Some(parent_id) = token.strip_prefix('^').and_then(|id| id.parse::<i64>().ok()) {
+                task.parent_id = Some(parent_id);
+            } else {
+                title.push(token);
+            }
+        }
+
+        if title.is_empty() {
+            return Err(QuickAddError::MissingTitle);
+        }
+        task.title = title.join(" ");
+        Ok(task)
+    }
+}
+
+/// Splits on whitespace, keeping a quoted `key:"…"` value (`due:"next friday"`)
+/// in one token. Any other `"`, such as the inch mark in `12" pizza`, is plain
+/// text, and so is a value whose quote never closes.
+fn tokenize(input: &str) -> Vec<&str> {
+    let word_end = |text: &str| text.find(char::is_whitespace).unwrap_or(text.len());
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+    while !rest.is_empty() {
+        let mut end = word_end(rest);
+        let key = |open: &usize| *open > 0 && rest[..*open].chars().all(char::is_alphabetic);
+        if let Some(open) = rest[..end].find(":\"").filter(key) {
+            if let Some(close) = rest[open + 2..].find('"') {
+                let after = open + 2 + close + 1;
+                end = after + word_end(&rest[after..]);
+            }
+        }
+        tokens.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    tokens
+}
+
+fn is_special(token: &str) -> bool {
+    token.starts_with(['#', '!', '~', '^', '\\']) || token.starts_with("due:")
+}
+
+/// Parses the value of `due:`, returning the date and how many following tokens it used.
+fn parse_due<Tz: TimeZone>(
+    value: &str,
+    rest: &[&str],
+    now: &DateTime<Tz>,
+) -> Result<(DateTime<chrono::Utc>, usize), DateParseError> {
+    if let Some(quoted) = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+        return Ok((dates::parse_due_date(quoted, now)?, 0));
+    }
+    // A quote that never closed was split like any other text
+    let value = value.trim_start_matches('"');
+
+    let words = rest.iter().take_while(|token| !is_special(token)).count();
+    let max_extra = words.min(MAX_DUE_WORDS.saturating_sub(usize::from(!value.is_empty())));
+    for extra in (0..=max_extra).rev() {
+        let candidate: Vec<&str> = std::iter::once(value).chain(rest[..extra].iter().copied()).collect();
+        let candidate = candidate.join(" ");
+        if candidate.trim().is_empty() {
+            continue;
+        }
+        if let Ok(due_date) = dates::parse_due_date(&candidate, now) {
+            return Ok((due_date, extra));
+        }
+    }
+    Err(DateParseError(value.to_string()))
+}
+
+fn is_tag(tag: &str) -> bool {
+    // "#1" reads as a number ("Fix issue #42"), not a tag
+    !tag.is_empty()
+        && !tag.chars().all(|c| c.is_ascii_digit())
+        && tag.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+}
+
+/// Reads "15m", "15min", "2h", "1h30m" or a bare number of minutes.
+fn parse_minutes(text: &str) -> Option<i32> {
+    if let Ok(minutes) = text.parse::<i32>() {
+        return (minutes > 0).then_some(minutes);
+    }
+
+    let mut total = 0i32;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return None;
+        }
+        let amount: i32 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "h" | "hr" | "hrs" => 60,
+            "m" | "min" | "mins" => 1,
+            _ => return None,
+        };
+        total = total.checked_add(amount.checked_mul(factor)?)?;
+        rest = &rest[unit_len..];
+    }
+    (total > 0).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate, Utc};
+
+    // Sunday 18 October 2026, 10:00 at UTC-7
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-10-18T10:00:00-07:00").unwrap()
+    }
+
+    fn local(date: (i32, u32, u32), time: (u32, u32, u32)) -> DateTime<Utc> {
+        let naive = NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+            .unwrap()
+            .and_hms_opt(time.0, time.1, time.2)
+            .unwrap();
+        now().timezone().from_local_datetime(&naive).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_full_line_populates_task() {
+        let task = Task::parse_quick_add_at("Buy milk #groceries !high due:tomorrow ~15m", &now()).unwrap();
+        assert_eq!(task.title, "Buy milk");
+        assert_eq!(task.tag.as_deref(), Some("groceries"));
+        assert_eq!(task.priority, Some(Priority::High));
+        assert_eq!(task.due_date, Some(local((2026, 10, 19), (23, 59, 59))));
+        assert_eq!(task.estimated_duration, Some(15));
+        assert_eq!(task.parent_id, None);
+        assert!(!task.done);
+    }
+
+    #[test]
+    fn test_tokens_can_appear_anywhere() {
+        let task = Task::parse_quick_add_at("!1 ^7 Call #work the bank ~1h30m", &now()).unwrap();
+        assert_eq!(task.title, "Call the bank");
+        assert_eq!(task.tag.as_deref(), Some("work"));
+        assert_eq!(task.priority, Some(Priority::Urgent));
+        assert_eq!(task.parent_id, Some(7));
+        assert_eq!(task.estimated_duration, Some(90));
+    }
+
+    #[test]
+    fn test_due_takes_longest_date() {
+        let unquoted = Task::parse_quick_add_at("Report due:next friday 5pm for Sam", &now()).unwrap();
+        assert_eq!(unquoted.title, "Report for Sam");
+        assert_eq!(unquoted.due_date, Some(local((2026, 10, 23), (17, 0, 0))));
+
+        let quoted = Task::parse_quick_add_at("Report due:\"in 3 days\" draft", &now()).unwrap();
+        assert_eq!(quoted.title, "Report draft");
+        assert_eq!(quoted.due_date, Some(local((2026, 10, 21), (23, 59, 59))));
+
+        let spaced = Task::parse_quick_add_at("Dentist due: nov 3 #health", &now()).unwrap();
+        assert_eq!(spaced.title, "Dentist");
+        assert_eq!(spaced.due_date, Some(local((2026, 11, 3), (23, 59, 59))));
+    }
+
+    #[test]
+    fn test_quotes_in_titles() {
+        let pizza = Task::parse_quick_add_at("Buy 12\" pizza due:tomorrow #food", &now()).unwrap();
+        assert_eq!(pizza.title, "Buy 12\" pizza");
+        assert_eq!(pizza.tag.as_deref(), Some("food"));
+        assert_eq!(pizza.due_date, Some(local((2026, 10, 19), (23, 59, 59))));
+
+        let book = Task::parse_quick_add_at("Read \"War and Peace\" !low #books", &now()).unwrap();
+        assert_eq!(book.title, "Read \"War and Peace\"");
+        assert_eq!((book.tag.as_deref(), book.priority), (Some("books"), Some(Priority::Low)));
+
+        // The quote never closes, so the date is read word by word
+        let open = Task::parse_quick_add_at("Report due:\"next friday #work", &now()).unwrap();
+        assert_eq!(open.title, "Report");
+        assert_eq!(open.tag.as_deref(), Some("work"));
+        assert_eq!(open.due_date, Some(local((2026, 10, 23), (23, 59, 59))));
+        assert_eq!(tokenize("a  due:\"in 3 days\"  b"), ["a", "due:\"in 3 days\"", "b"]);
+    }
+
+    #[test]
+    fn test_unparsed_tokens_stay_in_title() {
+        let task = Task::parse_quick_add_at(
+            "Fix issue #42 !important ~soon #bugs #later \\#1 ^parent",
+            &now(),
+        )
+        .unwrap();
+        assert_eq!(task.title, "Fix issue #42 !important ~soon #later #1 ^parent");
+        assert_eq!(task.tag.as_deref(), Some("bugs"));
+        assert_eq!(task.priority, None);
+        assert_eq!(task.estimated_duration, None);
+        assert_eq!(task.parent_id, None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Task::parse_quick_add_at("#groceries !high", &now()).unwrap_err(),
+            QuickAddError::MissingTitle
+        );
+        assert_eq!(
+            Task::parse_quick_add_at("Buy milk due:someday", &now()).unwrap_err(),
+            QuickAddError::InvalidDate(DateParseError("someday".to_string()))
+        );
+        assert!(matches!(
+            Task::parse_quick_add_at("Buy milk due:", &now()),
+            Err(QuickAddError::InvalidDate(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_minutes() {
+        assert_eq!(parse_minutes("45"), Some(45));
+        assert_eq!(parse_minutes("2h"), Some(120));
+        assert_eq!(parse_minutes("1hr15min"), Some(75));
+        assert_eq!(parse_minutes("0m"), None);
+        assert_eq!(parse_minutes("m15"), None);
+        assert_eq!(parse_minutes("1d"), None);
+    }
+}
@@ -0,0 +1,520 @@
+// core/src/reports/mod.rs
+// Reviews of a day, week or any range of days: what got done, what slipped and
+// which habits held, as JSON (through serde), Markdown or plain text.
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use crate::dates;
+use crate::task_list::TaskList;
+
+mod stats;
+
+pub use stats::{GroupBy, OnTime, PeriodCount, Stats};
+
+// Tasks listed under "Slipped"
+const TOP_SLIPPED: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportError {
+    /// The last day comes before the first
+    EmptyRange(NaiveDate, NaiveDate),
+    OutOfRange(NaiveDate),
+    UnknownGrouping(String),
+}
+
+impl std::fmt::Display for ReportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportError::EmptyRange(first, last) => write!(f, "{} is before {}", last, first),
+            ReportError::OutOfRange(day) => write!(f, "{} is out of range", day),
+            ReportError::UnknownGrouping(grouping) => {
+                write!(f, "unknown grouping '{}', expected day or week", grouping)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    pub first: NaiveDate,
+    pub last: NaiveDate,
+    pub completed: u32,
+    pub created: u32,
+    /// Open at the end of the range (or now) and past their due date
+    pub overdue: u32,
+    /// Most completions first; untagged tasks have no tag
+    pub by_tag: Vec<TagCount>,
+    pub time: TimeSpent,
+    pub habits: Vec<Habit>,
+    /// Tasks whose due date was moved later most often in the range
+    pub slipped: Vec<SlippedTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: Option<String>,
+    pub completed: u32,
+}
+
+/// Timer minutes against estimates, over tasks completed in the range that have both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeSpent {
+    pub tasks: u32,
+    pub spent_minutes: i64,
+    pub estimated_minutes: i64,
+}
+
+/// A recurring task and the days it was done.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Habit {
+    pub task_id: i64,
+    pub title: String,
+    pub days_done: u32,
+    pub days: u32,
+    /// Days in a row up to the last day, or the day before while the last day is still open
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlippedTask {
+    pub task_id: i64,
+    pub title: String,
+    pub slips: u32,
+    /// The local day it is now due
+    pub due_on: Option<NaiveDate>,
+    pub done: bool,
+}
+
+impl Review {
+    fn heading(&self) -> String {
+        if self.first == self.last {
+            format!("Review {}", self.first)
+        } else {
+            format!("Review {} to {}", self.first, self.last)
+        }
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "Completed {} {}, created {}, {} overdue",
+            self.completed,
+            if self.completed == 1 { "task" } else { "tasks" },
+            self.created,
+            self.overdue
+        )
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\n{}\n", self.heading(), self.summary());
+        if !self.by_tag.is_empty() {
+            out.push_str("\nCompleted by tag\n");
+            for count in &self.by_tag {
+                let _ = writeln!(out, "  {:<20} {:>4}", tag_name(&count.tag), count.completed);
+            }
+        }
+        if self.time.tasks > 0 {
+            let _ = writeln!(out, "\nTime\n  {}", self.time);
+        }
+        if !self.habits.is_empty() {
+            out.push_str("\nHabits\n");
+            for habit in &self.habits {
+                let _ = writeln!(
+                    out,
+                    "  {:<20} {}/{} days, streak {}, best {}",
+                    habit.title, habit.days_done, habit.days, habit.streak, habit.best_streak
+                );
+            }
+        }
+        if !self.slipped.is_empty() {
+            out.push_str("\nSlipped\n");
+            for task in &self.slipped {
+                let _ = writeln!(out, "  {:<20} {}", task.title, slip_text(task));
+            }
+        }
+        out
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n{}.\n", self.heading(), self.summary());
+        if !self.by_tag.is_empty() {
+            out.push_str("\n## Completed by tag\n\n| Tag | Completed |\n| --- | ---: |\n");
+            for count in &self.by_tag {
+                let _ = writeln!(out, "| {} | {} |", cell(&tag_name(&count.tag)), count.completed);
+            }
+        }
+        if self.time.tasks > 0 {
+            let _ = writeln!(out, "\n## Time\n\n{}.", self.time);
+        }
+        if !self.habits.is_empty() {
+            out.push_str("\n## Habits\n\n| Habit | Days done | Streak | Best |\n| --- | ---: | ---: | ---: |\n");
+            for habit in &self.habits {
+                let _ = writeln!(
+                    out,
+                    "| {} | {}/{} | {} | {} |",
+                    cell(&habit.title),
+                    habit.days_done,
+                    habit.days,
+                    habit.streak,
+                    habit.best_streak
+                );
+            }
+        }
+        if !self.slipped.is_empty() {
+            out.push_str("\n## Slipped\n\n");
+            for task in &self.slipped {
+                let _ = writeln!(out, "- {}: {}", task.title, slip_text(task));
+            }
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for TimeSpent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} timed {} took {} against {} estimated",
+            self.tasks,
+            if self.tasks == 1 { "task" } else { "tasks" },
+            minutes_text(self.spent_minutes),
+            minutes_text(self.estimated_minutes)
+        )?;
+        if self.estimated_minutes > 0 && self.spent_minutes != self.estimated_minutes {
+            let off = (self.spent_minutes - self.estimated_minutes) as f64 / self.estimated_minutes as f64;
+            let direction = if off > 0.0 { "over" } else { "under" };
+            write!(f, " ({:.0}% {})", off.abs() * 100.0, direction)?;
+        }
+        Ok(())
+    }
+}
+
+fn tag_name(tag: &Option<String>) -> String {
+    match tag {
+        Some(tag) => format!("#{}", tag),
+        None => "(untagged)".to_string(),
+    }
+}
+
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn minutes_text(minutes: i64) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+fn slip_text(task: &SlippedTask) -> String {
+    let times = if task.slips == 1 { "once".to_string() } else { format!("{} times", task.slips) };
+    match (task.done, task.due_on) {
+        (true, _) => format!("moved {}, since done", times),
+        (false, Some(due_on)) => format!("moved {}, due {}", times, due_on),
+        (false, None) => format!("moved {}", times),
+    }
+}
+
+// The longest run of consecutive days in `days`
+fn longest_run(days: &BTreeSet<NaiveDate>) -> u32 {
+    let (mut best, mut run, mut previous) = (0, 0, None::<NaiveDate>);
+    for day in days {
+        run = if previous.and_then(|previous| previous.succ_opt()) == Some(*day) { run + 1 } else { 1 };
+        best = best.max(run);
+        previous = Some(*day);
+    }
+    best
+}
+
+// Where the days from `first` to `last` inclusive start and end in `tz`
+fn bounds<Tz: TimeZone>(
+    first: NaiveDate,
+    last: NaiveDate,
+    tz: &Tz,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), ReportError> {
+    if last < first {
+        return Err(ReportError::EmptyRange(first, last));
+    }
+    let start = dates::start_of_day(first, tz).ok_or(ReportError::OutOfRange(first))?;
+    let end = last
+        .succ_opt()
+        .and_then(|next| dates::start_of_day(next, tz))
+        .ok_or(ReportError::OutOfRange(last))?;
+    Ok((start, end))
+}
+
+// Completions from `start` up to `end` for each tag, most first and untagged last among equals
+fn completions_by_tag(conn: &Connection, start: &str, end: &str) -> rusqlite::Result<Vec<TagCount>> {
+    let mut stmt = conn.prepare(
+        "SELECT NULLIF(tasks.tag, ''), COUNT(*) FROM task_history JOIN tasks ON tasks.id = task_history.task_id
+         WHERE task_history.event = 'completed' AND task_history.at >= ?1 AND task_history.at < ?2
+         GROUP BY NULLIF(tasks.tag, '') ORDER BY COUNT(*) DESC, NULLIF(tasks.tag, '') IS NULL, tasks.tag",
+    )?;
+    let counts = stmt.query_map(params![start, end], |row| {
+        Ok(TagCount { tag: row.get(0)?, completed: row.get(1)? })
+    })?;
+    counts.collect()
+}
+
+impl TaskList {
+    /// Reviews the days from `first` to `last` inclusive in `tz`.
+    pub fn review<Tz: TimeZone>(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        tz: &Tz,
+    ) -> Result<Review, Box<dyn std::error::Error>> {
+        let (start, end) = bounds(first, last, tz)?;
+        let (start_text, end_text) = (start.to_rfc3339(), end.to_rfc3339());
+        let local_day = |at: &str| {
+            DateTime::parse_from_rfc3339(at).ok().map(|at| at.with_timezone(tz).date_naive())
+        };
+        let conn = self.database().connection();
+
+        let by_tag = completions_by_tag(conn, &start_text, &end_text)?;
+        let completed = by_tag.iter().map(|count| count.completed).sum();
+
+        let created: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM task_history WHERE event = 'created' AND at >= ?1 AND at < ?2",
+            params![start_text, end_text],
+            |row| row.get(0),
+        )?;
+
+        // A range that is not over yet only counts what is overdue so far
+        let cutoff = end.min(Utc::now()).to_rfc3339();
+        let overdue: u32 = conn.query_row(
+            "SELECT COUNT(*) FROM tasks
+             WHERE due_date IS NOT NULL AND due_date < ?1 AND created_at < ?1
+             AND (done = 0 OR (SELECT MAX(at) FROM task_history
+                               WHERE task_id = tasks.id AND event = 'completed') > ?1)",
+            [&cutoff],
+            |row| row.get(0),
+        )?;
+
+        let time = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(last_duration), 0), COALESCE(SUM(estimated_duration), 0) FROM tasks
+             WHERE last_duration IS NOT NULL AND estimated_duration IS NOT NULL AND id IN (
+                SELECT task_id FROM task_history WHERE event = 'completed' AND at >= ?1 AND at < ?2
+             )",
+            params![start_text, end_text],
+            |row| Ok(TimeSpent { tasks: row.get(0)?, spent_minutes: row.get(1)?, estimated_minutes: row.get(2)? }),
+        )?;
+
+        // Streaks can start before the range, so every earlier completion counts
+        let mut stmt = conn.prepare(
+            "SELECT tasks.id, tasks.title, task_history.at FROM tasks
+             LEFT JOIN task_history ON task_history.task_id = tasks.id
+                AND task_history.event = 'completed' AND task_history.at < ?1
+             WHERE tasks.is_recurring = 1 AND tasks.created_at < ?1
+             ORDER BY tasks.title COLLATE NOCASE, tasks.id",
+        )?;
+        let rows = stmt.query_map([&end_text], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+        })?;
+        let mut done_on: BTreeMap<(String, i64), BTreeSet<NaiveDate>> = BTreeMap::new();
+        for row in rows {
+            let (id, title, at) = row?;
+            let days = done_on.entry((title, id)).or_default();
+            days.extend(at.as_deref().and_then(local_day));
+        }
+        let range_days = (last - first).num_days() as u32 + 1;
+        let habits = done_on
+            .into_iter()
+            .map(|((title, task_id), days)| {
+                let in_range: BTreeSet<NaiveDate> = days.range(first..=last).copied().collect();
+                let mut day = if days.contains(&last) { Some(last) } else { last.pred_opt() };
+                let mut streak = 0;
+                while let Some(done) = day.filter(|day| days.contains(day)) {
+                    streak += 1;
+                    day = done.pred_opt();
+                }
+                Habit {
+                    task_id,
+                    title,
+                    days_done: in_range.len() as u32,
+                    days: range_days,
+                    streak,
+                    best_streak: longest_run(&in_range),
+                }
+            })
+            .collect();
+
+        let mut stmt = conn.prepare(
+            "SELECT tasks.id, tasks.title, COUNT(*), tasks.due_date, tasks.done FROM task_history
+             JOIN tasks ON tasks.id = task_history.task_id
+             WHERE task_history.event = 'rescheduled' AND task_history.at >= ?1 AND task_history.at < ?2
+             GROUP BY tasks.id ORDER BY COUNT(*) DESC, tasks.id LIMIT ?3",
+        )?;
+        let slipped = stmt
+            .query_map(params![start_text, end_text, TOP_SLIPPED as i64], |row| {
+                Ok(SlippedTask {
+                    task_id: row.get(0)?,
+                    title: row.get(1)?,
+                    slips: row.get(2)?,
+                    due_on: row.get::<_, Option<String>>(3)?.as_deref().and_then(local_day),
+                    done: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(Review { first, last, completed, created, overdue, by_tag, time, habits, slipped })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Task;
+    use chrono::{Duration, FixedOffset};
+
+    fn set_history(task_list: &TaskList, task_id: i64, event: &str, at: &str) {
+        task_list
+            .database()
+            .connection()
+            .execute(
+                "UPDATE task_history SET at = ?3 WHERE id = (
+                    SELECT MAX(id) FROM task_history WHERE task_id = ?1 AND event = ?2
+                 )",
+                params![task_id, event, at],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_review_of_a_week() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+        let first = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let last = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+
+        for (title, tag, day) in [("Report", Some("work"), 13), ("Slides", Some("work"), 14), ("Call mum", None, 15)] {
+            let mut task = Task::new(title);
+            task.tag = tag.map(str::to_string);
+            task.estimated_duration = Some(30);
+            task.last_duration = Some(if title == "Report" { 45 } else { 30 });
+            let id = task_list.add(task)?;
+            task_list.mark_done(id)?;
+            set_history(&task_list, id, "completed", &format!("2026-10-{}T09:00:00+00:00", day));
+        }
+        // Done on the 11th in UTC but the 12th in Berlin
+        let late = task_list.add(Task::new("Late night").with_tag("home"))?;
+        task_list.mark_done(late)?;
+        set_history(&task_list, late, "completed", "2026-10-11T23:30:00+00:00");
+        // Done the week before, so it is not in the review
+        let earlier = task_list.add(Task::new("Earlier").with_tag("home"))?;
+        task_list.mark_done(earlier)?;
+        set_history(&task_list, earlier, "completed", "2026-10-05T09:00:00+00:00");
+
+        // Overdue since the 14th and pushed back twice
+        let due = DateTime::parse_from_rfc3339("2026-10-14T17:00:00Z")?.with_timezone(&Utc);
+        let taxes = task_list.add(Task::new("Taxes").with_due_date(due))?;
+        for days in [1, 2] {
+            let mut task = task_list.get_by_id(taxes)?.unwrap();
+            task.due_date = Some(due + Duration::days(days));
+            task_list.update(&task)?;
+            set_history(&task_list, taxes, "rescheduled", &format!("2026-10-1{}T08:00:00+00:00", 4 + days));
+        }
+        // Moving a due date earlier is not a slip
+        let mut task = task_list.get_by_id(taxes)?.unwrap();
+        task.due_date = Some(due);
+        task_list.update(&task)?;
+
+        // Done five days, the last three in a row
+        let mut stretch = Task::new("Stretch");
+        stretch.is_recurring = true;
+        let stretch = task_list.add(stretch)?;
+        for day in [9, 12, 13, 16, 17, 18] {
+            task_list.mark_done(stretch)?;
+            set_history(&task_list, stretch, "completed", &format!("2026-10-{:02}T06:00:00+00:00", day));
+            task_list.mark_undone(stretch)?;
+        }
+
+        // Everything was added the week before, apart from the taxes
+        let db = task_list.database().connection();
+        db.execute("UPDATE tasks SET created_at = '2026-10-01T00:00:00+00:00'", [])?;
+        db.execute("UPDATE task_history SET at = '2026-10-01T00:00:00+00:00' WHERE event = 'created'", [])?;
+        set_history(&task_list, taxes, "created", "2026-10-13T12:00:00+00:00");
+
+        let review = task_list.review(first, last, &berlin)?;
+        assert_eq!(review.completed, 9);
+        assert_eq!(review.created, 1);
+        assert_eq!(review.overdue, 1);
+        let tags: Vec<(Option<&str>, u32)> =
+            review.by_tag.iter().map(|count| (count.tag.as_deref(), count.completed)).collect();
+        assert_eq!(tags, vec![(None, 6), (Some("work"), 2), (Some("home"), 1)]);
+        assert_eq!(review.time, TimeSpent { tasks: 3, spent_minutes: 105, estimated_minutes: 90 });
+        assert_eq!(review.habits.len(), 1);
+        let habit = &review.habits[0];
+        assert_eq!((habit.days_done, habit.days, habit.streak, habit.best_streak), (5, 7, 3, 3));
+        assert_eq!(review.slipped.len(), 1);
+        assert_eq!(review.slipped[0].slips, 2);
+        assert_eq!(review.slipped[0].due_on, NaiveDate::from_ymd_opt(2026, 10, 14));
+
+        let text = review.to_text();
+        assert!(text.starts_with("Review 2026-10-12 to 2026-10-18\nCompleted 9 tasks, created 1, 1 overdue\n"));
+        assert!(text.contains("  #work                   2\n"), "{}", text);
+        assert!(text.contains("3 timed tasks took 1h 45m against 1h 30m estimated (17% over)"), "{}", text);
+        assert!(text.contains("  Stretch              5/7 days, streak 3, best 3\n"), "{}", text);
+        assert!(text.contains("  Taxes                moved 2 times, due 2026-10-14\n"), "{}", text);
+        let markdown = review.to_markdown();
+        assert!(markdown.starts_with("# Review 2026-10-12 to 2026-10-18\n\n"), "{}", markdown);
+        assert!(markdown.contains("| (untagged) | 6 |\n"), "{}", markdown);
+        assert!(markdown.contains("| Stretch | 5/7 | 3 | 3 |\n"), "{}", markdown);
+
+        // The streak is still going while today is open
+        let today = task_list.review(last.succ_opt().unwrap(), last.succ_opt().unwrap(), &berlin)?;
+        assert_eq!((today.habits[0].streak, today.habits[0].days_done), (3, 0));
+        assert!(today.to_text().starts_with("Review 2026-10-19\n"));
+        let backwards = task_list.review(last, first, &berlin).unwrap_err();
+        assert_eq!(backwards.to_string(), "2026-10-12 is before 2026-10-18");
+        Ok(())
+    }
+
+    #[test]
+    fn test_slipped_tasks_within_the_range() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let berlin = FixedOffset::east_opt(2 * 3600).unwrap();
+        let first = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let last = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let due = DateTime::parse_from_rfc3339("2026-10-13T12:00:00Z")?.with_timezone(&Utc);
+        let mut slip = |title: &str, times: &[&str]| -> Result<i64, Box<dyn std::error::Error>> {
+            let id = task_list.add(Task::new(title).with_due_date(due))?;
+            for (days, at) in times.iter().enumerate() {
+                let mut task = task_list.get_by_id(id)?.unwrap();
+                task.due_date = Some(due + Duration::days(days as i64 + 1));
+                task_list.update(&task)?;
+                set_history(&task_list, id, "rescheduled", at);
+            }
+            Ok(id)
+        };
+
+        // The week runs from 22:00 UTC on Sunday the 11th to 22:00 UTC on Sunday the 18th
+        let monday = slip("Monday", &["2026-10-11T21:59:00+00:00", "2026-10-11T22:00:00+00:00"])?;
+        let sunday = slip("Sunday", &["2026-10-18T21:59:00+00:00", "2026-10-18T22:00:00+00:00"])?;
+        slip("Next week", &["2026-10-18T22:00:00+00:00"])?;
+        let mut others = Vec::new();
+        for title in ["A", "B", "C", "D", "E"] {
+            others.push(slip(title, &["2026-10-14T09:00:00+00:00", "2026-10-15T09:00:00+00:00"])?);
+        }
+
+        // Only the five that slipped most are listed, so the two that slipped once this week are left out
+        let review = task_list.review(first, last, &berlin)?;
+        let slipped: Vec<(i64, u32)> = review.slipped.iter().map(|task| (task.task_id, task.slips)).collect();
+        assert_eq!(slipped, others.iter().map(|id| (*id, 2)).collect::<Vec<_>>());
+
+        let weekend = task_list.review(last, last, &berlin)?;
+        let slipped: Vec<(i64, u32)> = weekend.slipped.iter().map(|task| (task.task_id, task.slips)).collect();
+        assert_eq!(slipped, [(sunday, 1)]);
+        assert_eq!(weekend.slipped[0].due_on, NaiveDate::from_ymd_opt(2026, 10, 15));
+
+        let review = task_list.review(first, first, &berlin)?;
+        assert_eq!(review.slipped.iter().map(|task| task.task_id).collect::<Vec<_>>(), [monday]);
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+// core/src/reports/stats.rs
+// Aggregates over completion history for charts, computed in SQL.
+use chrono::{Datelike, Duration, NaiveDate, TimeZone};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use super::{bounds, completions_by_tag, ReportError, TagCount};
+use crate::dates;
+use crate::task_list::TaskList;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    Day,
+    /// Monday to Sunday
+    Week,
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = ReportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "day" | "daily" => Ok(GroupBy::Day),
+            "week" | "weekly" => Ok(GroupBy::Week),
+            _ => Err(ReportError::UnknownGrouping(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub first: NaiveDate,
+    pub last: NaiveDate,
+    pub group_by: GroupBy,
+    /// One entry per day or week, oldest first
+    pub completions: Vec<PeriodCount>,
+    pub completed: u32,
+    /// Mean hours from creation to completion, over one-off tasks completed in the range
+    pub average_latency_hours: Option<f64>,
+    /// Most completions first
+    pub tags: Vec<TagCount>,
+    pub on_time: OnTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodCount {
+    /// The day, or the Monday of the week; only days inside the range are counted
+    pub start: NaiveDate,
+    pub completed: u32,
+}
+
+/// One-off tasks with a due date completed in the range, and how many of them by that date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OnTime {
+    pub due: u32,
+    pub on_time: u32,
+    /// From 0 to 1; None when nothing was due
+    pub rate: Option<f64>,
+}
+
+impl TaskList {
+    /// Completion statistics for the local days in `range`, with completions
+    /// grouped by day or week in `tz`.
+    pub fn stats<Tz: TimeZone>(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+        group_by: GroupBy,
+        tz: &Tz,
+    ) -> Result<Stats, Box<dyn std::error::Error>> {
+        let (first, last) = range.into_inner();
+        let (start, end) = bounds(first, last, tz)?;
+        let (start_text, end_text) = (start.to_rfc3339(), end.to_rfc3339());
+
+        // Periods go to SQLite as [start, from, to] triples, with `to` exclusive
+        let mut periods = Vec::new();
+        let mut day = first;
+        while day <= last {
+            let period = match group_by {
+                GroupBy::Day => day,
+                GroupBy::Week => day - Duration::days(i64::from(day.weekday().num_days_from_monday())),
+            };
+            let next = match group_by {
+                GroupBy::Day => day + Duration::days(1),
+                GroupBy::Week => period + Duration::weeks(1),
+            }
+            .min(last + Duration::days(1));
+            let from = dates::start_of_day(day, tz).ok_or(ReportError::OutOfRange(day))?;
+            let to = dates::start_of_day(next, tz).ok_or(ReportError::OutOfRange(next))?;
+            periods.push(serde_json::json!([period.to_string(), from.to_rfc3339(), to.to_rfc3339()]));
+            day = next;
+        }
+
+        let conn = self.database().connection();
+        let mut stmt = conn.prepare(
+            "WITH periods AS (
+                SELECT json_extract(value, '$[0]') AS start, json_extract(value, '$[1]') AS from_at,
+                       json_extract(value, '$[2]') AS to_at
+                FROM json_each(?1)
+             )
+             SELECT periods.start, COUNT(task_history.id) FROM periods
+             LEFT JOIN task_history ON task_history.event = 'completed'
+                AND task_history.at >= periods.from_at AND task_history.at < periods.to_at
+             GROUP BY periods.start ORDER BY periods.start",
+        )?;
+        let completions = stmt
+            .query_map([serde_json::Value::Array(periods).to_string()], |row| {
+                let start: String = row.get(0)?;
+                Ok((start, row.get(1)?))
+            })?
+            .map(|row| {
+                let (start, completed) = row?;
+                Ok(PeriodCount { start: start.parse()?, completed })
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+        let completed = completions.iter().map(|period| period.completed).sum();
+
+        // Recurring tasks are left out: their creation and due date say little about each completion
+        let (average_latency_hours, due, on_time) = conn.query_row(
+            "SELECT AVG((julianday(task_history.at) - julianday(tasks.created_at)) * 24),
+                    COUNT(tasks.due_date),
+                    COALESCE(SUM(julianday(task_history.at) <= julianday(tasks.due_date)), 0)
+             FROM task_history JOIN tasks ON tasks.id = task_history.task_id
+             WHERE task_history.event = 'completed' AND task_history.at >= ?1 AND task_history.at < ?2
+                AND tasks.is_recurring = 0",
+            params![start_text, end_text],
+            |row| Ok((row.get::<_, Option<f64>>(0)?, row.get::<_, u32>(1)?, row.get::<_, u32>(2)?)),
+        )?;
+        let rate = if due > 0 { Some(f64::from(on_time) / f64::from(due)) } else { None };
+
+        Ok(Stats {
+            first,
+            last,
+            group_by,
+            completions,
+            completed,
+            average_latency_hours,
+            tags: completions_by_tag(conn, &start_text, &end_text)?,
+            on_time: OnTime { due, on_time, rate },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Task;
+    use chrono::{DateTime, FixedOffset, Utc};
+
+    #[test]
+    fn test_stats_group_completions_and_measure_latency() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let new_york = FixedOffset::west_opt(4 * 3600).unwrap();
+        let at = |text: &str| DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc);
+
+        // (created, completed, due, tag)
+        let tasks = [
+            ("2026-10-01T12:00:00Z", "2026-10-01T18:00:00Z", Some("2026-10-02T00:00:00Z"), Some("work")),
+            ("2026-10-03T12:00:00Z", "2026-10-05T12:00:00Z", Some("2026-10-04T12:00:00Z"), Some("work")),
+            // Late on Monday the 5th in New York, Tuesday in UTC
+            ("2026-10-05T00:00:00Z", "2026-10-06T02:00:00Z", None, None),
+            ("2026-10-06T00:00:00Z", "2026-10-12T10:00:00Z", None, Some("home")),
+        ];
+        for (created, completed, due, tag) in tasks {
+            let mut task = Task::new("Task");
+            task.created_at = at(created);
+            task.due_date = due.map(at);
+            task.tag = tag.map(str::to_string);
+            let id = task_list.add(task)?;
+            task_list.mark_done(id)?;
+            task_list.database().connection().execute(
+                "UPDATE task_history SET at = ?2 WHERE task_id = ?1 AND event = 'completed'",
+                params![id, at(completed).to_rfc3339()],
+            )?;
+        }
+        let mut habit = Task::new("Stretch");
+        habit.is_recurring = true;
+        habit.created_at = at("2026-01-01T00:00:00Z");
+        let habit = task_list.add(habit)?;
+        task_list.mark_done(habit)?;
+        task_list.database().connection().execute(
+            "UPDATE task_history SET at = '2026-10-07T12:00:00+00:00' WHERE task_id = ?1 AND event = 'completed'",
+            [habit],
+        )?;
+
+        // Thursday the 1st to Monday the 12th
+        let first = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let last = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let weekly = task_list.stats(first..=last, GroupBy::Week, &new_york)?;
+        let weeks: Vec<(String, u32)> =
+            weekly.completions.iter().map(|period| (period.start.to_string(), period.completed)).collect();
+        assert_eq!(
+            weeks,
+            vec![("2026-09-28".to_string(), 1), ("2026-10-05".to_string(), 3), ("2026-10-12".to_string(), 1)]
+        );
+        assert_eq!(weekly.completed, 5);
+        // 6, 48, 26 and 154 hours; the recurring task is left out
+        assert_eq!(weekly.average_latency_hours, Some(58.5));
+        assert_eq!(weekly.on_time, OnTime { due: 2, on_time: 1, rate: Some(0.5) });
+        let tags: Vec<(Option<&str>, u32)> =
+            weekly.tags.iter().map(|count| (count.tag.as_deref(), count.completed)).collect();
+        assert_eq!(tags, vec![(Some("work"), 2), (None, 2), (Some("home"), 1)]);
+
+        let daily = task_list.stats(first..=last, GroupBy::Day, &new_york)?;
+        assert_eq!(daily.completions.len(), 12);
+        assert_eq!(daily.completions[4].start, NaiveDate::from_ymd_opt(2026, 10, 5).unwrap());
+        assert_eq!(daily.completions[4].completed, 2);
+        assert_eq!(daily.completions[5].completed, 0);
+
+        let empty = task_list.stats(last..=last, GroupBy::Day, &new_york)?;
+        assert_eq!((empty.completed, empty.on_time.rate), (1, None));
+        assert!(task_list.stats(last..=first, GroupBy::Day, &new_york).is_err());
+        assert_eq!("Weekly".parse::<GroupBy>(), Ok(GroupBy::Week));
+        assert!("monthly".parse::<GroupBy>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_weeks_span_sundays_and_years() -> Result<(), Box<dyn std::error::Error>> {
+        let mut task_list = TaskList::new_in_memory()?;
+        let at = |text: &str| DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc);
+        // Sunday 27 December 2026 to Monday 4 January 2027, none of them due
+        let completions = [
+            "2026-12-27T20:00:00Z",
+            "2026-12-28T00:30:00Z",
+            "2026-12-31T23:30:00Z",
+            "2027-01-01T00:30:00Z",
+            "2027-01-03T23:59:00Z",
+            "2027-01-04T00:00:00Z",
+        ];
+        for completed in completions {
+            let id = task_list.add(Task::new("Task"))?;
+            task_list.mark_done(id)?;
+            task_list.database().connection().execute(
+                "UPDATE task_history SET at = ?2 WHERE task_id = ?1 AND event = 'completed'",
+                params![id, at(completed).to_rfc3339()],
+            )?;
+        }
+        // A recurring task's due date does not count towards the on-time rate
+        let mut habit = Task::new("Stretch");
+        habit.is_recurring = true;
+        habit.due_date = Some(at("2026-12-30T00:00:00Z"));
+        let habit = task_list.add(habit)?;
+        task_list.mark_done(habit)?;
+        task_list.database().connection().execute(
+            "UPDATE task_history SET at = '2026-12-29T12:00:00+00:00' WHERE task_id = ?1 AND event = 'completed'",
+            [habit],
+        )?;
+
+        let first = NaiveDate::from_ymd_opt(2026, 12, 27).unwrap();
+        let last = NaiveDate::from_ymd_opt(2027, 1, 5).unwrap();
+        let weeks = |stats: &Stats| -> Vec<(String, u32)> {
+            stats.completions.iter().map(|period| (period.start.to_string(), period.completed)).collect()
+        };
+        let utc = task_list.stats(first..=last, GroupBy::Week, &Utc)?;
+        // The week of Monday 28 December runs into 2027
+        assert_eq!(
+            weeks(&utc),
+            vec![("2026-12-21".to_string(), 1), ("2026-12-28".to_string(), 5), ("2027-01-04".to_string(), 1)]
+        );
+        assert_eq!(utc.completed, 7);
+        assert_eq!(utc.on_time, OnTime { due: 0, on_time: 0, rate: None });
+
+        // 20:00 UTC on Sunday is already Monday in Tokyo, and 23:59 on Sunday the 3rd is Monday the 4th
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let tokyo = task_list.stats(first..=last, GroupBy::Week, &tokyo)?;
+        assert_eq!(
+            weeks(&tokyo),
+            vec![("2026-12-21".to_string(), 0), ("2026-12-28".to_string(), 5), ("2027-01-04".to_string(), 2)]
+        );
+        Ok(())
+    }
+}
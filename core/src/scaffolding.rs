@@ -0,0 +1,7 @@
+// core/src/scaffolding.rs
+// The UniFFI scaffolding for freelist.udl. It is generated code, so the lint it
+// trips is allowed for this module only.
+#![allow(clippy::empty_line_after_doc_comments)]
+use crate::*;
+
+uniffi::include_scaffolding!("freelist");
@@ -2,6 +2,64 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    /// Value stored in the `priority` column; higher is more important.
+    pub fn rank(self) -> i32 {
+        match self {
+            Priority::Low => 1,
+            Priority::Medium => 2,
+            Priority::High => 3,
+            Priority::Urgent => 4,
+        }
+    }
+
+    pub fn from_rank(rank: i32) -> Option<Self> {
+        match rank {
+            1 => Some(Priority::Low),
+            2 => Some(Priority::Medium),
+            3 => Some(Priority::High),
+            4 => Some(Priority::Urgent),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for Priority {
+    type Err = String;
+
+    /// Accepts the names (`high`, `med`, ...) and Todoist-style levels, where `1` is the most urgent.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" | "4" => Ok(Priority::Low),
+            "medium" | "med" | "3" => Ok(Priority::Medium),
+            "high" | "2" => Ok(Priority::High),
+            "urgent" | "1" => Ok(Priority::Urgent),
+            _ => Err(format!("unknown priority '{}'. Use low, medium, high, urgent or 1-4", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: Option<i64>,
@@ -13,9 +71,16 @@ pub struct Task {
     pub estimated_duration: Option<i32>, // in minutes
     pub last_duration: Option<i32>,     // in minutes
     pub tag: Option<String>,
+    pub priority: Option<Priority>,
     pub parent_id: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// The `List` it belongs to, if any
+    #[serde(default)]
+    pub list_id: Option<i64>,
+    /// Sort key for manual ordering, see `ordering`; new tasks are put on top
+    #[serde(default)]
+    pub position: Option<String>,
 }
 
 impl Task {
@@ -31,9 +96,12 @@ impl Task {
             estimated_duration: None,
             last_duration: None,
             tag: None,
+            priority: None,
             parent_id: None,
             created_at: now,
             updated_at: now,
+            list_id: None,
+            position: None,
         }
     }
 
@@ -49,6 +117,12 @@ impl Task {
         self
     }
 
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn with_due_date(mut self, due_date: DateTime<Utc>) -> Self {
         self.due_date = Some(due_date);
         self.updated_at = Utc::now();
@@ -61,6 +135,12 @@ impl Task {
         self
     }
 
+    pub fn with_list(mut self, list_id: i64) -> Self {
+        self.list_id = Some(list_id);
+        self.updated_at = Utc::now();
+        self
+    }
+
     pub fn mark_done(&mut self) {
         self.done = true;
         self.updated_at = Utc::now();
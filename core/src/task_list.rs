@@ -1,29 +1,81 @@
 // core/src/task_list.rs
 use crate::task::Task;
-use crate::database::{Database, TaskFilter, TaskStatus};
+use crate::changes::{ChangeObserver, ChangeSet};
+use crate::database::{Database, HistoryEntry, TaskFilter, TaskStatus};
 use std::path::Path;
 
 pub struct TaskList {
     db: Database,
+    observers: Vec<(u64, Box<dyn ChangeObserver>)>,
+    next_observer_id: u64,
+    data_version: i64,
 }
 
 impl TaskList {
     pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self, Box<dyn std::error::Error>> {
         let db = Database::new(db_path)?;
-        Ok(TaskList { db })
+        Self::from_database(db)
     }
 
     pub fn new_in_memory() -> Result<Self, Box<dyn std::error::Error>> {
         let db = Database::new_in_memory()?;
-        Ok(TaskList { db })
+        Self::from_database(db)
+    }
+
+    fn from_database(db: Database) -> Result<Self, Box<dyn std::error::Error>> {
+        let data_version = db.data_version()?;
+        Ok(TaskList {
+            db,
+            observers: Vec::new(),
+            next_observer_id: 1,
+            data_version,
+        })
+    }
+
+    /// Registers an observer called after each committed change. Returns an id for `unsubscribe`.
+    pub fn subscribe(&mut self, observer: impl ChangeObserver + 'static) -> u64 {
+        let id = self.next_observer_id;
+        self.next_observer_id += 1;
+        self.observers.push((id, Box::new(observer)));
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: u64) -> bool {
+        let before = self.observers.len();
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+        self.observers.len() != before
+    }
+
+    /// Notifies observers of changes committed since the last call, including
+    /// writes made by other connections to the same database file.
+    /// Mutating methods call this themselves; call it directly to pick up external writers.
+    pub fn poll_changes(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let data_version = self.db.data_version()?;
+        let change_set = ChangeSet {
+            changes: self.db.take_changes(),
+            external: data_version != self.data_version,
+        };
+        self.data_version = data_version;
+
+        if !change_set.is_empty() {
+            for (_, observer) in &self.observers {
+                observer.on_change(&change_set);
+            }
+        }
+        Ok(())
     }
 
     pub fn add(&mut self, mut task: Task) -> Result<i64, Box<dyn std::error::Error>> {
         let id = self.db.insert_task(&task)?;
         task.id = Some(id);
+        self.poll_changes()?;
         Ok(id)
     }
 
+    pub fn fetch(&self, filter: TaskFilter) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        Ok(self.db.fetch_tasks(filter)?)
+    }
+
     pub fn all(&self) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
         let filter = TaskFilter::default();
         Ok(self.db.fetch_tasks(filter)?)
@@ -53,6 +105,14 @@ impl TaskList {
         Ok(self.db.fetch_tasks(filter)?)
     }
 
+    pub fn get_by_list(&self, list_id: i64) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+        let filter = TaskFilter {
+            list_id: Some(list_id),
+            ..Default::default()
+        };
+        Ok(self.db.fetch_tasks(filter)?)
+    }
+
     pub fn get_subtasks(&self, parent_id: i64) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
         let filter = TaskFilter {
             parent_id: Some(parent_id),
@@ -67,22 +127,38 @@ impl TaskList {
 
     pub fn mark_done(&mut self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
         self.db.update_task_status(id, true)?;
-        Ok(())
+        self.poll_changes()
     }
 
     pub fn mark_undone(&mut self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
         self.db.update_task_status(id, false)?;
-        Ok(())
+        self.poll_changes()
+    }
+
+    /// Saves an edited task fetched from this list.
+    pub fn update(&mut self, task: &Task) -> Result<(), Box<dyn std::error::Error>> {
+        let id = task.id.ok_or("cannot update a task that has not been added")?;
+        self.db.update_task(id, task)?;
+        self.poll_changes()
     }
 
     pub fn delete(&mut self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
         self.db.delete_task(id)?;
-        Ok(())
+        self.poll_changes()
     }
 
     pub fn clear_all(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.db.clear_all_tasks()?;
-        Ok(())
+        self.poll_changes()
+    }
+
+    /// What happened to task `id` (or to every task), oldest first.
+    pub fn history(&self, id: Option<i64>) -> Result<Vec<HistoryEntry>, Box<dyn std::error::Error>> {
+        Ok(self.db.fetch_history(id)?)
+    }
+
+    pub(crate) fn database(&self) -> &Database {
+        &self.db
     }
 
     pub fn get_all_tags(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
@@ -0,0 +1,76 @@
+// core/tests/bindings.rs
+// Checks that the checked-in Swift/Kotlin bindings match what src/freelist.udl generates.
+// Run with UPDATE_BINDINGS=1 to regenerate them after changing the UDL.
+use camino::{Utf8Path, Utf8PathBuf};
+use std::collections::BTreeMap;
+use std::fs;
+use uniffi::{KotlinBindingGenerator, SwiftBindingGenerator};
+
+fn manifest_dir() -> Utf8PathBuf {
+    Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn read_tree(dir: &Utf8Path) -> BTreeMap<String, String> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = current.read_dir_utf8() else { continue };
+        for entry in entries {
+            let path = entry.unwrap().path().to_path_buf();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                let relative = path.strip_prefix(dir).unwrap().to_string();
+                files.insert(relative, fs::read_to_string(&path).unwrap());
+            }
+        }
+    }
+    files
+}
+
+fn generate(out_dir: &Utf8Path) {
+    let udl = manifest_dir().join("src/freelist.udl");
+    let config = manifest_dir().join("uniffi.toml");
+    for language in ["kotlin", "swift"] {
+        let dir = out_dir.join(language);
+        fs::create_dir_all(&dir).unwrap();
+        let result = match language {
+            "kotlin" => uniffi::generate_bindings(
+                &udl, Some(&config), KotlinBindingGenerator, Some(&dir), None, Some("core"), false,
+            ),
+            _ => uniffi::generate_bindings(
+                &udl, Some(&config), SwiftBindingGenerator, Some(&dir), None, Some("core"), false,
+            ),
+        };
+        result.unwrap();
+    }
+}
+
+#[test]
+fn test_generated_bindings_are_up_to_date() {
+    let checked_in = manifest_dir().join("bindings");
+    if std::env::var_os("UPDATE_BINDINGS").is_some() {
+        let _ = fs::remove_dir_all(&checked_in);
+        generate(&checked_in);
+        return;
+    }
+
+    let scratch = Utf8PathBuf::from_path_buf(std::env::temp_dir())
+        .unwrap()
+        .join(format!("freelist-bindings-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&scratch);
+    generate(&scratch.join("first"));
+    generate(&scratch.join("second"));
+
+    let first = read_tree(&scratch.join("first"));
+    let second = read_tree(&scratch.join("second"));
+    fs::remove_dir_all(&scratch).unwrap();
+
+    assert!(first.contains_key("swift/freelistFFI.h"), "C header missing: {:?}", first.keys());
+    assert_eq!(first, second, "binding generation is not deterministic");
+    assert_eq!(
+        first,
+        read_tree(&checked_in),
+        "core/bindings is stale; run `UPDATE_BINDINGS=1 cargo test --test bindings`"
+    );
+}